@@ -10,7 +10,29 @@ mod m20240130_000006_create_email_system;
 mod m20240130_000007_create_lead_system;
 mod m20240130_000008_create_metadata_engine;
 mod m20240130_000009_create_custom_object_data;
+// Superseded by m20240130_000010_add_tenant_workspace_id; kept as it shipped
+#[allow(dead_code)]
 mod m20240130_000010_add_workspace_id;
+mod m20240130_000010_add_tenant_workspace_id;
+mod m20240130_000011_create_sessions;
+mod m20240130_000012_create_workspace_roles;
+mod m20240130_000013_scope_remaining_tables;
+mod m20240130_000014_create_workspace_invitations;
+mod m20240130_000015_create_user_tokens;
+mod m20240130_000016_create_api_keys;
+mod m20240130_000017_create_sso_configs;
+mod m20240130_000018_add_record_versions;
+mod m20240130_000019_add_link_deleted_at;
+mod m20240130_000020_add_hot_path_indexes;
+mod m20240130_000021_add_workflow_run_input;
+mod m20240130_000022_add_workflow_step_name;
+mod m20240130_000023_add_workflow_run_cursor;
+mod m20240130_000024_scope_unique_keys;
+mod m20240130_000025_scope_email_templates;
+mod m20240130_000026_add_member_suspended_at;
+mod m20240130_000027_add_workflow_run_chain;
+mod m20240130_000028_exclude_trashed_from_unique_keys;
+mod m20240130_000029_use_postgres_column_types;
 
 pub struct Migrator;
 
@@ -27,7 +49,26 @@ impl MigratorTrait for Migrator {
             Box::new(m20240130_000007_create_lead_system::CreateLeadSystem),
             Box::new(m20240130_000008_create_metadata_engine::CreateMetadataEngine),
             Box::new(m20240130_000009_create_custom_object_data::Migration),
-            Box::new(m20240130_000010_add_workspace_id::Migration),
+            Box::new(m20240130_000010_add_tenant_workspace_id::Migration),
+            Box::new(m20240130_000011_create_sessions::Migration),
+            Box::new(m20240130_000012_create_workspace_roles::Migration),
+            Box::new(m20240130_000013_scope_remaining_tables::Migration),
+            Box::new(m20240130_000014_create_workspace_invitations::Migration),
+            Box::new(m20240130_000015_create_user_tokens::Migration),
+            Box::new(m20240130_000016_create_api_keys::Migration),
+            Box::new(m20240130_000017_create_sso_configs::Migration),
+            Box::new(m20240130_000018_add_record_versions::Migration),
+            Box::new(m20240130_000019_add_link_deleted_at::Migration),
+            Box::new(m20240130_000020_add_hot_path_indexes::Migration),
+            Box::new(m20240130_000021_add_workflow_run_input::Migration),
+            Box::new(m20240130_000022_add_workflow_step_name::Migration),
+            Box::new(m20240130_000023_add_workflow_run_cursor::Migration),
+            Box::new(m20240130_000024_scope_unique_keys::Migration),
+            Box::new(m20240130_000025_scope_email_templates::Migration),
+            Box::new(m20240130_000026_add_member_suspended_at::Migration),
+            Box::new(m20240130_000027_add_workflow_run_chain::Migration),
+            Box::new(m20240130_000028_exclude_trashed_from_unique_keys::Migration),
+            Box::new(m20240130_000029_use_postgres_column_types::Migration),
         ]
     }
 }
@@ -0,0 +1,103 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::DatabaseBackend;
+
+/// Adds `workspace_id` to the tenant tables in place of `m20240130_000010_add_workspace_id`,
+/// which named tables that do not exist (`leads`, `companies`, ...) and so never applied
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Define the tables we are modifying
+        let tables = vec![
+            "lead",
+            "company",
+            "email",
+            "object_metadata",
+            "custom_object_data",
+            "view",
+        ];
+
+        let is_sqlite = manager.get_database_backend() == DatabaseBackend::Sqlite;
+
+        for table in tables {
+            // SQLite only accepts a single option per ALTER TABLE, requires a default for
+            // NOT NULL columns and cannot add foreign keys to existing tables.
+            let mut column = ColumnDef::new(Alias::new("workspace_id"));
+            column.uuid().not_null(); // Enforce tenancy
+            if is_sqlite {
+                column.default("00000000-0000-0000-0000-000000000000");
+            }
+
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .add_column(&mut column)
+                        .to_owned(),
+                )
+                .await?;
+
+            if is_sqlite {
+                continue;
+            }
+
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .add_foreign_key(
+                            TableForeignKey::new()
+                                .name(format!("fk_{}_workspace_id", table))
+                                .from_tbl(Alias::new(table))
+                                .from_col(Alias::new("workspace_id"))
+                                .to_tbl(Alias::new("workspaces"))
+                                .to_col(Alias::new("id"))
+                                .on_delete(ForeignKeyAction::Cascade)
+                                .on_update(ForeignKeyAction::Cascade),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let tables = vec![
+            "lead",
+            "company",
+            "email",
+            "object_metadata",
+            "custom_object_data",
+            "view",
+        ];
+
+        let is_sqlite = manager.get_database_backend() == DatabaseBackend::Sqlite;
+
+        for table in tables {
+            if !is_sqlite {
+                manager
+                    .alter_table(
+                        Table::alter()
+                            .table(Alias::new(table))
+                            .drop_foreign_key(Alias::new(format!("fk_{}_workspace_id", table)))
+                            .to_owned(),
+                    )
+                    .await?;
+            }
+
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .drop_column(Alias::new("workspace_id"))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(WorkspaceRoles::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(WorkspaceRoles::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(WorkspaceRoles::WorkspaceId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(WorkspaceRoles::Name).string().not_null())
+                    // Object key -> granted actions
+                    .col(
+                        ColumnDef::new(WorkspaceRoles::Permissions)
+                            .json()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WorkspaceRoles::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WorkspaceRoles::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_workspace_roles_workspace_id")
+                            .from(WorkspaceRoles::Table, WorkspaceRoles::WorkspaceId)
+                            .to(Workspaces::Table, Workspaces::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Role names are unique within a workspace
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_workspace_roles_workspace_id_name")
+                    .table(WorkspaceRoles::Table)
+                    .col(WorkspaceRoles::WorkspaceId)
+                    .col(WorkspaceRoles::Name)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(WorkspaceRoles::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum WorkspaceRoles {
+    Table,
+    Id,
+    WorkspaceId,
+    Name,
+    Permissions,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Workspaces {
+    Table,
+    Id,
+}
@@ -0,0 +1,112 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::DatabaseBackend;
+
+/// Tenant tables that were left out of `m20240130_000010_add_workspace_id`
+const TABLES: [&str; 7] = [
+    "person",
+    "opportunity",
+    "task",
+    "note",
+    "workflow",
+    "calendar_event",
+    "timeline_activity",
+];
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let is_sqlite = manager.get_database_backend() == DatabaseBackend::Sqlite;
+
+        for table in TABLES {
+            // Same constraints as m20240130_000010: one option per ALTER TABLE, a default
+            // for NOT NULL columns and no foreign keys on existing tables in SQLite.
+            let mut column = ColumnDef::new(Alias::new("workspace_id"));
+            column.uuid().not_null();
+            if is_sqlite {
+                column.default("00000000-0000-0000-0000-000000000000");
+            }
+
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .add_column(&mut column)
+                        .to_owned(),
+                )
+                .await?;
+
+            manager
+                .create_index(
+                    Index::create()
+                        .name(format!("idx_{}_workspace_id", table))
+                        .table(Alias::new(table))
+                        .col(Alias::new("workspace_id"))
+                        .to_owned(),
+                )
+                .await?;
+
+            if is_sqlite {
+                continue;
+            }
+
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .add_foreign_key(
+                            TableForeignKey::new()
+                                .name(format!("fk_{}_workspace_id", table))
+                                .from_tbl(Alias::new(table))
+                                .from_col(Alias::new("workspace_id"))
+                                .to_tbl(Alias::new("workspaces"))
+                                .to_col(Alias::new("id"))
+                                .on_delete(ForeignKeyAction::Cascade)
+                                .on_update(ForeignKeyAction::Cascade),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let is_sqlite = manager.get_database_backend() == DatabaseBackend::Sqlite;
+
+        for table in TABLES {
+            if !is_sqlite {
+                manager
+                    .alter_table(
+                        Table::alter()
+                            .table(Alias::new(table))
+                            .drop_foreign_key(Alias::new(format!("fk_{}_workspace_id", table)))
+                            .to_owned(),
+                    )
+                    .await?;
+            }
+
+            manager
+                .drop_index(
+                    Index::drop()
+                        .name(format!("idx_{}_workspace_id", table))
+                        .table(Alias::new(table))
+                        .to_owned(),
+                )
+                .await?;
+
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .drop_column(Alias::new("workspace_id"))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,126 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(WorkspaceInvitations::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(WorkspaceInvitations::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(WorkspaceInvitations::WorkspaceId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WorkspaceInvitations::Email)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WorkspaceInvitations::Role)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WorkspaceInvitations::InvitedBy)
+                            .uuid()
+                            .not_null(),
+                    )
+                    // Only a SHA-256 digest of the emailed token is stored
+                    .col(
+                        ColumnDef::new(WorkspaceInvitations::TokenHash)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(WorkspaceInvitations::ExpiresAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WorkspaceInvitations::AcceptedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(WorkspaceInvitations::RevokedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(WorkspaceInvitations::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WorkspaceInvitations::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_workspace_invitations_workspace_id")
+                            .from(
+                                WorkspaceInvitations::Table,
+                                WorkspaceInvitations::WorkspaceId,
+                            )
+                            .to(Workspaces::Table, Workspaces::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Index on workspace_id for listing a workspace's invitations
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_workspace_invitations_workspace_id")
+                    .table(WorkspaceInvitations::Table)
+                    .col(WorkspaceInvitations::WorkspaceId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(WorkspaceInvitations::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum WorkspaceInvitations {
+    Table,
+    Id,
+    WorkspaceId,
+    Email,
+    Role,
+    InvitedBy,
+    TokenHash,
+    ExpiresAt,
+    AcceptedAt,
+    RevokedAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Workspaces {
+    Table,
+    Id,
+}
@@ -0,0 +1,44 @@
+use sea_orm_migration::prelude::*;
+
+/// Tables whose rows are updated with optimistic concurrency control
+const TABLES: [&str; 3] = ["company", "opportunity", "custom_object_data"];
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in TABLES {
+            // Existing rows start at the first version
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .add_column(
+                            ColumnDef::new(Alias::new("version"))
+                                .integer()
+                                .not_null()
+                                .default(1),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in TABLES {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .drop_column(Alias::new("version"))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
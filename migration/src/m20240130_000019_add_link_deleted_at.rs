@@ -0,0 +1,40 @@
+use sea_orm_migration::prelude::*;
+
+/// Link tables trashed and restored together with the records they point to
+const TABLES: [&str; 2] = ["task_target", "timeline_activity"];
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in TABLES {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .add_column(
+                            ColumnDef::new(Alias::new("deleted_at")).timestamp_with_time_zone(),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in TABLES {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .drop_column(Alias::new("deleted_at"))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
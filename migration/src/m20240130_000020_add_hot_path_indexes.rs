@@ -0,0 +1,96 @@
+use sea_orm_migration::prelude::*;
+
+/// Composite indexes for the hot list and lookup paths of the repositories. Every query on
+/// these tables is restricted to one workspace, so each index leads with `workspace_id` and
+/// continues with the filter columns and then the sort column of its query. Lookups of people
+/// and leads by email are served by their unique `(workspace_id, email)` indexes, see
+/// `m20240130_000024_scope_unique_keys`.
+const INDEXES: [(&str, &str, &[&str]); 11] = [
+    // Default order of the paginated lists, with the key of their cursors
+    (
+        "idx_person_workspace_created_at",
+        "person",
+        &["workspace_id", "created_at", "id"],
+    ),
+    (
+        "idx_company_workspace_created_at",
+        "company",
+        &["workspace_id", "created_at", "id"],
+    ),
+    (
+        "idx_email_workspace_created_at",
+        "email",
+        &["workspace_id", "created_at", "id"],
+    ),
+    // Leads are listed highest score first, which also serves `find_high_score`
+    (
+        "idx_lead_workspace_score",
+        "lead",
+        &["workspace_id", "score", "id"],
+    ),
+    // Lead queues by status and by assignee, highest score first
+    (
+        "idx_lead_workspace_status_score",
+        "lead",
+        &["workspace_id", "status", "score"],
+    ),
+    (
+        "idx_lead_workspace_assigned_to_score",
+        "lead",
+        &["workspace_id", "assigned_to_id", "score"],
+    ),
+    // Outbox of the email worker, oldest first
+    (
+        "idx_email_workspace_status_created_at",
+        "email",
+        &["workspace_id", "status", "created_at"],
+    ),
+    // Emails and timelines of a record, newest first
+    (
+        "idx_email_workspace_person_created_at",
+        "email",
+        &["workspace_id", "person_id", "created_at"],
+    ),
+    (
+        "idx_timeline_activity_workspace_person_created_at",
+        "timeline_activity",
+        &["workspace_id", "person_id", "created_at"],
+    ),
+    (
+        "idx_timeline_activity_workspace_company_created_at",
+        "timeline_activity",
+        &["workspace_id", "company_id", "created_at"],
+    ),
+    (
+        "idx_timeline_activity_workspace_opportunity_created_at",
+        "timeline_activity",
+        &["workspace_id", "opportunity_id", "created_at"],
+    ),
+];
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (name, table, columns) in INDEXES {
+            let mut index = Index::create();
+            index.name(name).table(Alias::new(table));
+            for column in columns {
+                index.col(Alias::new(*column));
+            }
+            manager.create_index(index.to_owned()).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (name, table, _) in INDEXES {
+            manager
+                .drop_index(Index::drop().name(name).table(Alias::new(table)).to_owned())
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,30 @@
+use sea_orm_migration::prelude::*;
+
+/// What started a run: the trigger and, for record triggers, the record that fired it
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("workflow_run"))
+                    .add_column(ColumnDef::new(Alias::new("input")).json_binary())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("workflow_run"))
+                    .drop_column(Alias::new("input"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
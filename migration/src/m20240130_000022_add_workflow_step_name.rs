@@ -0,0 +1,50 @@
+use sea_orm_migration::prelude::*;
+
+/// Names under which steps publish their output to the later steps of a run, unique within
+/// a version
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("workflow_version_step"))
+                    .add_column(ColumnDef::new(Alias::new("name")).string())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_workflow_version_step_version_name")
+                    .table(Alias::new("workflow_version_step"))
+                    .col(Alias::new("workflow_version_id"))
+                    .col(Alias::new("name"))
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_workflow_version_step_version_name")
+                    .table(Alias::new("workflow_version_step"))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("workflow_version_step"))
+                    .drop_column(Alias::new("name"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,61 @@
+use sea_orm_migration::prelude::*;
+
+/// Where a run continues, and when a run parked by a delay wakes up. The index serves the
+/// worker looking for pending runs that are due.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite takes one column per ALTER TABLE
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("workflow_run"))
+                    .add_column(ColumnDef::new(Alias::new("cursor")).uuid())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("workflow_run"))
+                    .add_column(ColumnDef::new(Alias::new("wake_at")).timestamp_with_time_zone())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_workflow_run_status_wake_at")
+                    .table(Alias::new("workflow_run"))
+                    .col(Alias::new("status"))
+                    .col(Alias::new("wake_at"))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_workflow_run_status_wake_at")
+                    .table(Alias::new("workflow_run"))
+                    .to_owned(),
+            )
+            .await?;
+        for column in ["wake_at", "cursor"] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new("workflow_run"))
+                        .drop_column(Alias::new(column))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
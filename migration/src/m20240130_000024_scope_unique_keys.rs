@@ -0,0 +1,70 @@
+use sea_orm_migration::prelude::*;
+
+/// Unique keys hold within a workspace: the global indexes let one workspace's records block,
+/// and reveal, the same email or domain in another one. Each replacement leads with
+/// `workspace_id`, so it also serves the lookups by email.
+const KEYS: [(&str, &str, &str, &str); 3] = [
+    (
+        "person",
+        "email",
+        "idx_person_email_unique",
+        "idx_person_workspace_email_unique",
+    ),
+    (
+        "company",
+        "domain_name",
+        "idx_company_domain_unique",
+        "idx_company_workspace_domain_unique",
+    ),
+    (
+        "lead",
+        "email",
+        "idx_lead_email_unique",
+        "idx_lead_workspace_email_unique",
+    ),
+];
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (table, column, global, scoped) in KEYS {
+            manager
+                .drop_index(Index::drop().name(global).table(Alias::new(table)).to_owned())
+                .await?;
+            manager
+                .create_index(
+                    Index::create()
+                        .name(scoped)
+                        .table(Alias::new(table))
+                        .col(Alias::new("workspace_id"))
+                        .col(Alias::new(column))
+                        .unique()
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (table, column, global, scoped) in KEYS {
+            manager
+                .drop_index(Index::drop().name(scoped).table(Alias::new(table)).to_owned())
+                .await?;
+            manager
+                .create_index(
+                    Index::create()
+                        .name(global)
+                        .table(Alias::new(table))
+                        .col(Alias::new(column))
+                        .unique()
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::DatabaseBackend;
+
+/// Email templates belong to a workspace, and their names are unique within it
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let is_sqlite = manager.get_database_backend() == DatabaseBackend::Sqlite;
+
+        // Same constraints as m20240130_000013: a default for the NOT NULL column and no
+        // foreign key on an existing table in SQLite
+        let mut column = ColumnDef::new(Alias::new("workspace_id"));
+        column.uuid().not_null();
+        if is_sqlite {
+            column.default("00000000-0000-0000-0000-000000000000");
+        }
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("email_template"))
+                    .add_column(&mut column)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_email_template_name_unique")
+                    .table(Alias::new("email_template"))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_email_template_workspace_name_unique")
+                    .table(Alias::new("email_template"))
+                    .col(Alias::new("workspace_id"))
+                    .col(Alias::new("name"))
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        if is_sqlite {
+            return Ok(());
+        }
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("email_template"))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_email_template_workspace_id")
+                            .from_tbl(Alias::new("email_template"))
+                            .from_col(Alias::new("workspace_id"))
+                            .to_tbl(Alias::new("workspaces"))
+                            .to_col(Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != DatabaseBackend::Sqlite {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new("email_template"))
+                        .drop_foreign_key(Alias::new("fk_email_template_workspace_id"))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_email_template_workspace_name_unique")
+                    .table(Alias::new("email_template"))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_email_template_name_unique")
+                    .table(Alias::new("email_template"))
+                    .col(Alias::new("name"))
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("email_template"))
+                    .drop_column(Alias::new("workspace_id"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
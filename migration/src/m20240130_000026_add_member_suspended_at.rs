@@ -0,0 +1,33 @@
+use sea_orm_migration::prelude::*;
+
+/// Suspension is per membership, so that a workspace admin blocks the member in their own
+/// workspace only
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("workspace_members"))
+                    .add_column(
+                        ColumnDef::new(Alias::new("suspended_at")).timestamp_with_time_zone(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("workspace_members"))
+                    .drop_column(Alias::new("suspended_at"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
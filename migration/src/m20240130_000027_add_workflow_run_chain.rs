@@ -0,0 +1,31 @@
+use sea_orm_migration::prelude::*;
+
+/// The workflows whose runs led to a run, kept so that a resumed run still cannot trigger
+/// them again
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("workflow_run"))
+                    .add_column(ColumnDef::new(Alias::new("workflow_chain")).json_binary())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("workflow_run"))
+                    .drop_column(Alias::new("workflow_chain"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,64 @@
+use sea_orm_migration::prelude::*;
+
+/// The unique keys hold among live records only: a trashed person, company or lead must
+/// not block a new record with the same email or domain. sea-query cannot express the
+/// index predicate, so the partial indexes are plain SQL, which SQLite and Postgres share.
+const KEYS: [(&str, &str, &str); 3] = [
+    ("person", "email", "idx_person_workspace_email_unique"),
+    (
+        "company",
+        "domain_name",
+        "idx_company_workspace_domain_unique",
+    ),
+    ("lead", "email", "idx_lead_workspace_email_unique"),
+];
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        for (table, column, index) in KEYS {
+            manager
+                .drop_index(
+                    Index::drop()
+                        .name(index)
+                        .table(Alias::new(table))
+                        .to_owned(),
+                )
+                .await?;
+            db.execute_unprepared(&format!(
+                r#"CREATE UNIQUE INDEX "{index}" ON "{table}" ("workspace_id", "{column}") WHERE "deleted_at" IS NULL"#
+            ))
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (table, column, index) in KEYS {
+            manager
+                .drop_index(
+                    Index::drop()
+                        .name(index)
+                        .table(Alias::new(table))
+                        .to_owned(),
+                )
+                .await?;
+            manager
+                .create_index(
+                    Index::create()
+                        .name(index)
+                        .table(Alias::new(table))
+                        .col(Alias::new("workspace_id"))
+                        .col(Alias::new(column))
+                        .unique()
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::DatabaseBackend;
+
+/// The JSON columns of the first migrations are `json` on Postgres, which the entities bind
+/// as `jsonb`, and a company address is plain text rather than JSON. SQLite stores all of
+/// these as text already, so only Postgres changes.
+const COLUMNS: [(&str, &str); 11] = [
+    ("workflow_version_step", "settings"),
+    ("workflow_run", "output"),
+    ("email", "cc_emails"),
+    ("email", "bcc_emails"),
+    ("email", "metadata"),
+    ("field_metadata", "settings"),
+    ("view", "filters"),
+    ("view", "sort"),
+    ("custom_object_data", "properties"),
+    ("workspace_roles", "permissions"),
+    ("api_keys", "objects"),
+];
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+impl Migration {
+    async fn retype(
+        manager: &SchemaManager<'_>,
+        table: &str,
+        column: &str,
+        column_type: ColumnType,
+    ) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new(table))
+                    .modify_column(&mut ColumnDef::new_with_type(
+                        Alias::new(column),
+                        column_type,
+                    ))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != DatabaseBackend::Postgres {
+            return Ok(());
+        }
+        for (table, column) in COLUMNS {
+            Self::retype(manager, table, column, ColumnType::JsonBinary).await?;
+        }
+        Self::retype(manager, "company", "address", ColumnType::Text).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != DatabaseBackend::Postgres {
+            return Ok(());
+        }
+        for (table, column) in COLUMNS {
+            Self::retype(manager, table, column, ColumnType::Json).await?;
+        }
+        // Text has no automatic cast to JSON; each address becomes a JSON string
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"ALTER TABLE "company" ALTER COLUMN "address" TYPE json USING to_json("address")"#,
+            )
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use crate::application::ports::identity::Identity;
+use crate::domain::entities::WorkspaceMember;
+use std::future::Future;
+use uuid::Uuid;
+
+/// Who the current task is acting on behalf of
+#[derive(Debug, Clone)]
+pub enum Actor {
+    /// An authenticated request
+    User(Box<Identity>),
+    /// Background work (event subscribers, jobs, webhooks); bypasses permission checks
+    System,
+    /// Background work for one workspace, such as triggered workflow runs; bypasses
+    /// permission checks like `System` but only sees that workspace
+    Workspace(Uuid),
+}
+
+tokio::task_local! {
+    static ACTOR: Actor;
+    static WORKFLOW_CHAIN: Vec<Uuid>;
+}
+
+/// Runs `f` with `actor` as the ambient actor. The context does not follow
+/// `tokio::spawn`, so spawned tasks must establish their own scope.
+pub async fn run_as<F: Future>(actor: Actor, f: F) -> F::Output {
+    ACTOR.scope(actor, f).await
+}
+
+pub async fn run_as_system<F: Future>(f: F) -> F::Output {
+    run_as(Actor::System, f).await
+}
+
+/// The ambient actor, `None` outside of any scope (anonymous)
+pub fn current_actor() -> Option<Actor> {
+    ACTOR.try_with(|actor| actor.clone()).ok()
+}
+
+/// Workspace membership of the ambient user, `None` for system and anonymous scopes
+pub fn current_member() -> Option<WorkspaceMember> {
+    match current_actor() {
+        Some(Actor::User(identity)) => identity.member,
+        _ => None,
+    }
+}
+
+/// Workspace of the ambient user, `None` for system and anonymous scopes
+pub fn current_workspace_id() -> Option<Uuid> {
+    current_member().map(|m| m.workspace_id)
+}
+
+/// Runs `f` as part of a chain of workflow runs, each started by a record written by the one
+/// before. Record events carry the chain, so that a workflow cannot trigger itself again.
+pub async fn run_in_workflow_chain<F: Future>(chain: Vec<Uuid>, f: F) -> F::Output {
+    WORKFLOW_CHAIN.scope(chain, f).await
+}
+
+/// Workflows whose runs led to the current task, outermost first
+pub fn workflow_chain() -> Vec<Uuid> {
+    WORKFLOW_CHAIN
+        .try_with(|chain| chain.clone())
+        .unwrap_or_default()
+}
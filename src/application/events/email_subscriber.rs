@@ -1,6 +1,8 @@
+use crate::application::context;
 use crate::application::ports::messaging::EventBus;
 use crate::application::use_cases::send_email::{SendEmail, SendEmailInput};
 use std::sync::Arc;
+use tokio::task::JoinHandle;
 
 pub struct EmailEventSubscriber {
     event_bus: Arc<dyn EventBus>,
@@ -15,34 +17,40 @@ impl EmailEventSubscriber {
         }
     }
 
-    pub async fn start(&self) -> Result<(), String> {
+    /// Subscribes and spawns the listening loop; the task ends when the subscription closes
+    pub async fn start(&self) -> Result<JoinHandle<()>, String> {
         // Subscribe to all events using wildcard
         let mut receiver = self.event_bus.subscribe("*").await?;
 
         let send_email_use_case = self.send_email_use_case.clone();
 
         // Spawn task to listen for events
-        tokio::spawn(async move {
+        let task = tokio::spawn(async move {
             tracing::info!("EmailEventSubscriber started");
 
             while let Ok(event) = receiver.recv().await {
                 tracing::debug!("EmailEventSubscriber received event: {}", event.topic);
 
-                let result = match event.topic.as_str() {
-                    "opportunity.created" => {
-                        Self::handle_opportunity_created(&send_email_use_case, &event.payload).await
+                // Events are handled on behalf of the system, outside any request scope
+                let result = context::run_as_system(async {
+                    match event.topic.as_str() {
+                        "opportunity.created" => {
+                            Self::handle_opportunity_created(&send_email_use_case, &event.payload)
+                                .await
+                        }
+                        "task.assigned" => {
+                            Self::handle_task_assigned(&send_email_use_case, &event.payload).await
+                        }
+                        "opportunity.won" => {
+                            Self::handle_opportunity_won(&send_email_use_case, &event.payload).await
+                        }
+                        _ => {
+                            // Ignore other events
+                            Ok(())
+                        }
                     }
-                    "task.assigned" => {
-                        Self::handle_task_assigned(&send_email_use_case, &event.payload).await
-                    }
-                    "opportunity.won" => {
-                        Self::handle_opportunity_won(&send_email_use_case, &event.payload).await
-                    }
-                    _ => {
-                        // Ignore other events
-                        Ok(())
-                    }
-                };
+                })
+                .await;
 
                 if let Err(e) = result {
                     tracing::error!("Error handling event {}: {}", event.topic, e);
@@ -52,7 +60,7 @@ impl EmailEventSubscriber {
             tracing::warn!("EmailEventSubscriber receiver closed");
         });
 
-        Ok(())
+        Ok(task)
     }
 
     async fn handle_opportunity_created(
@@ -1,3 +1,4 @@
+use crate::application::context;
 use crate::application::ports::messaging::EventBus;
 use crate::application::ports::output::TimelineActivityRepository;
 use crate::application::use_cases::send_email::{SendEmail, SendEmailInput};
@@ -5,6 +6,7 @@ use crate::domain::states::LeadSource;
 use crate::domain::{Lead, TimelineActivity};
 use chrono::Utc;
 use std::sync::Arc;
+use tokio::task::JoinHandle;
 use uuid::Uuid;
 
 pub struct LeadEventSubscriber {
@@ -26,7 +28,7 @@ impl LeadEventSubscriber {
         }
     }
 
-    pub async fn start(&self) -> Result<(), String> {
+    pub async fn start(&self) -> Result<JoinHandle<()>, String> {
         // Subscribe to lead events
         let mut receiver = self.event_bus.subscribe("lead.*").await?;
 
@@ -34,26 +36,30 @@ impl LeadEventSubscriber {
         let timeline_repo = self.timeline_repo.clone();
 
         // Spawn task to listen for events
-        tokio::spawn(async move {
+        let task = tokio::spawn(async move {
             tracing::info!("LeadEventSubscriber started");
 
             while let Ok(event) = receiver.recv().await {
                 tracing::debug!("LeadEventSubscriber received event: {}", event.topic);
 
-                let result = match event.topic.as_str() {
-                    "lead.created" => {
-                        Self::handle_lead_created(
-                            &send_email_use_case,
-                            &timeline_repo,
-                            &event.payload,
-                        )
-                        .await
+                // Events are handled on behalf of the system, outside any request scope
+                let result = context::run_as_system(async {
+                    match event.topic.as_str() {
+                        "lead.created" => {
+                            Self::handle_lead_created(
+                                &send_email_use_case,
+                                &timeline_repo,
+                                &event.payload,
+                            )
+                            .await
+                        }
+                        _ => {
+                            // Ignore other events
+                            Ok(())
+                        }
                     }
-                    _ => {
-                        // Ignore other events
-                        Ok(())
-                    }
-                };
+                })
+                .await;
 
                 if let Err(e) = result {
                     tracing::error!("Error handling event {}: {}", event.topic, e);
@@ -63,7 +69,7 @@ impl LeadEventSubscriber {
             tracing::warn!("LeadEventSubscriber receiver closed");
         });
 
-        Ok(())
+        Ok(task)
     }
 
     async fn handle_lead_created(
@@ -1,2 +1,4 @@
 pub mod email_subscriber;
 pub mod lead_subscriber;
+pub mod record_events;
+pub mod workflow_trigger_subscriber;
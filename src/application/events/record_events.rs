@@ -0,0 +1,141 @@
+use crate::application::context;
+use crate::application::ports::messaging::{DomainEvent, EventBus};
+use crate::domain::custom_object_data::CustomObjectData;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub const RECORD_CREATED: &str = "record.created";
+pub const RECORD_UPDATED: &str = "record.updated";
+pub const RECORD_DELETED: &str = "record.deleted";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordChange {
+    Created,
+    Updated,
+    Deleted,
+}
+
+impl RecordChange {
+    pub fn topic(self) -> &'static str {
+        match self {
+            RecordChange::Created => RECORD_CREATED,
+            RecordChange::Updated => RECORD_UPDATED,
+            RecordChange::Deleted => RECORD_DELETED,
+        }
+    }
+}
+
+/// A record of a built-in or custom object was written; the payload of the `record.*` topics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordEvent {
+    pub change: RecordChange,
+    /// Object key, as in permissions (`person`, `custom_object:<id>`, ...)
+    pub object: String,
+    pub workspace_id: Uuid,
+    /// The record after the change; as it was for deletions
+    pub record: Value,
+    /// The record before an update
+    pub previous: Option<Value>,
+    /// Workflows whose runs wrote the record, see `context::workflow_chain`
+    #[serde(default)]
+    pub workflow_chain: Vec<Uuid>,
+}
+
+impl RecordEvent {
+    pub fn created(object: &str, workspace_id: Uuid, record: &impl Serialize) -> Self {
+        Self::new(RecordChange::Created, object, workspace_id, record, None)
+    }
+
+    pub fn updated(
+        object: &str,
+        workspace_id: Uuid,
+        previous: &impl Serialize,
+        record: &impl Serialize,
+    ) -> Self {
+        let previous = serde_json::to_value(previous).ok();
+        Self::new(
+            RecordChange::Updated,
+            object,
+            workspace_id,
+            record,
+            previous,
+        )
+    }
+
+    pub fn deleted(object: &str, workspace_id: Uuid, record: &impl Serialize) -> Self {
+        Self::new(RecordChange::Deleted, object, workspace_id, record, None)
+    }
+
+    fn new(
+        change: RecordChange,
+        object: &str,
+        workspace_id: Uuid,
+        record: &impl Serialize,
+        previous: Option<Value>,
+    ) -> Self {
+        Self {
+            change,
+            object: object.to_string(),
+            workspace_id,
+            record: serde_json::to_value(record).unwrap_or(Value::Null),
+            previous,
+            workflow_chain: context::workflow_chain(),
+        }
+    }
+}
+
+/// A custom record as triggers see it: its properties next to its own columns, so that
+/// fields are addressed the same way as on built-in records
+pub fn custom_record(record: &CustomObjectData) -> Value {
+    let mut value = match &record.properties {
+        Value::Object(properties) => properties.clone(),
+        _ => serde_json::Map::new(),
+    };
+    value.insert("id".to_string(), record.id.to_string().into());
+    value.insert(
+        "object_metadata_id".to_string(),
+        record.object_metadata_id.to_string().into(),
+    );
+    value.insert(
+        "created_at".to_string(),
+        serde_json::to_value(record.created_at).unwrap_or(Value::Null),
+    );
+    value.insert(
+        "updated_at".to_string(),
+        serde_json::to_value(record.updated_at).unwrap_or(Value::Null),
+    );
+    Value::Object(value)
+}
+
+/// Publishes record events for the use cases that write records
+pub struct RecordEvents {
+    event_bus: Arc<dyn EventBus>,
+}
+
+impl RecordEvents {
+    pub fn new(event_bus: Arc<dyn EventBus>) -> Self {
+        Self { event_bus }
+    }
+
+    /// The write has already happened, so a failure to publish is only logged
+    pub async fn publish(&self, event: RecordEvent) {
+        let topic = event.change.topic();
+        let payload = match serde_json::to_string(&event) {
+            Ok(payload) => payload,
+            Err(e) => {
+                tracing::error!("Failed to serialize {} event: {}", topic, e);
+                return;
+            }
+        };
+        let event = DomainEvent {
+            topic: topic.to_string(),
+            payload,
+        };
+        if let Err(e) = self.event_bus.publish(&event).await {
+            tracing::debug!("No subscriber for {}: {}", topic, e);
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use crate::application::context;
+use crate::application::events::record_events::{
+    RecordEvent, RECORD_CREATED, RECORD_DELETED, RECORD_UPDATED,
+};
+use crate::application::ports::messaging::EventBus;
+use crate::application::workflow::launcher::WorkflowLauncher;
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinHandle;
+
+/// Starts the workflows triggered by record events
+pub struct WorkflowTriggerSubscriber {
+    event_bus: Arc<dyn EventBus>,
+    launcher: Arc<WorkflowLauncher>,
+}
+
+impl WorkflowTriggerSubscriber {
+    pub fn new(event_bus: Arc<dyn EventBus>, launcher: Arc<WorkflowLauncher>) -> Self {
+        Self {
+            event_bus,
+            launcher,
+        }
+    }
+
+    pub async fn start(&self) -> Result<JoinHandle<()>, String> {
+        let mut receiver = self.event_bus.subscribe("record.*").await?;
+        let launcher = self.launcher.clone();
+
+        let task = tokio::spawn(async move {
+            tracing::info!("WorkflowTriggerSubscriber started");
+
+            loop {
+                let event = match receiver.recv().await {
+                    Ok(event) => event,
+                    // Events missed while the bus was full cannot be replayed
+                    Err(RecvError::Lagged(missed)) => {
+                        tracing::warn!("WorkflowTriggerSubscriber missed {} events", missed);
+                        continue;
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+                if ![RECORD_CREATED, RECORD_UPDATED, RECORD_DELETED].contains(&event.topic.as_str())
+                {
+                    continue;
+                }
+
+                let record_event: RecordEvent = match serde_json::from_str(&event.payload) {
+                    Ok(record_event) => record_event,
+                    Err(e) => {
+                        tracing::error!("Failed to parse {} event: {}", event.topic, e);
+                        continue;
+                    }
+                };
+
+                // Triggered runs act on behalf of the system, outside any request scope
+                match context::run_as_system(launcher.on_record_event(&record_event)).await {
+                    Ok(runs) if !runs.is_empty() => tracing::info!(
+                        "{} on {} started {} workflow runs",
+                        event.topic,
+                        record_event.object,
+                        runs.len()
+                    ),
+                    Ok(_) => {}
+                    Err(e) => tracing::error!("Error handling event {}: {}", event.topic, e),
+                }
+            }
+
+            tracing::warn!("WorkflowTriggerSubscriber receiver closed");
+        });
+
+        Ok(task)
+    }
+}
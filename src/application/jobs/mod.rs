@@ -1 +1,3 @@
 pub mod email_worker;
+pub mod trash_worker;
+pub mod workflow_worker;
@@ -0,0 +1,64 @@
+use crate::application::ports::output::TrashRepository;
+use crate::application::ports::scheduling::Job;
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+/// Permanently deletes records that have been in the trash longer than the retention period
+pub struct TrashPurgeWorker {
+    trash_repo: Arc<dyn TrashRepository>,
+    retention: Duration,
+    job_receiver: mpsc::Receiver<Job>,
+}
+
+impl TrashPurgeWorker {
+    pub fn new(
+        trash_repo: Arc<dyn TrashRepository>,
+        retention: Duration,
+        job_receiver: mpsc::Receiver<Job>,
+    ) -> Self {
+        Self {
+            trash_repo,
+            retention,
+            job_receiver,
+        }
+    }
+
+    pub async fn start(mut self) {
+        tracing::info!(
+            "TrashPurgeWorker started, retention {} days",
+            self.retention.num_days()
+        );
+
+        while let Some(job) = self.job_receiver.recv().await {
+            tracing::debug!("TrashPurgeWorker processing job: {}", job.name);
+
+            let result = match job.name.as_str() {
+                "purge_trash" => self.purge_expired().await,
+                _ => {
+                    tracing::warn!("Unknown job type: {}", job.name);
+                    Ok(())
+                }
+            };
+
+            if let Err(e) = result {
+                tracing::error!("Error processing job {}: {}", job.name, e);
+            }
+        }
+
+        tracing::warn!("TrashPurgeWorker receiver closed");
+    }
+
+    async fn purge_expired(&self) -> Result<(), String> {
+        let cutoff = Utc::now() - self.retention;
+        let purged = self
+            .trash_repo
+            .purge_deleted_before(cutoff)
+            .await
+            .map_err(|e| format!("Failed to purge trash: {}", e))?;
+        if purged > 0 {
+            tracing::info!("Purged {} records trashed before {}", purged, cutoff);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,250 @@
+use crate::application::ports::output::WorkflowRunRepository;
+use crate::application::ports::scheduling::Job;
+use crate::application::workflow::executor::WorkflowExecutor;
+use crate::domain::WorkflowRun;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+/// Picks workflow runs up where they stopped: runs parked by a `Delay` step once they are
+/// due, and at startup the runs the previous process left running. Only one process may
+/// work the runs, since any other one's running runs would look interrupted.
+pub struct WorkflowRunWorker {
+    workflow_run_repo: Arc<dyn WorkflowRunRepository>,
+    executor: Arc<WorkflowExecutor>,
+    job_receiver: mpsc::Receiver<Job>,
+    /// When this process started; runs saved since then belong to it, not to the previous one
+    started_at: DateTime<Utc>,
+}
+
+impl WorkflowRunWorker {
+    pub fn new(
+        workflow_run_repo: Arc<dyn WorkflowRunRepository>,
+        executor: Arc<WorkflowExecutor>,
+        job_receiver: mpsc::Receiver<Job>,
+        started_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            workflow_run_repo,
+            executor,
+            job_receiver,
+            started_at,
+        }
+    }
+
+    pub async fn start(mut self) {
+        tracing::info!("WorkflowRunWorker started");
+
+        while let Some(job) = self.job_receiver.recv().await {
+            tracing::debug!("WorkflowRunWorker processing job: {}", job.name);
+
+            let result = match job.name.as_str() {
+                "resume_interrupted_workflow_runs" => self.resume_interrupted().await,
+                "resume_due_workflow_runs" => self.resume_due().await,
+                _ => {
+                    tracing::warn!("Unknown job type: {}", job.name);
+                    Ok(())
+                }
+            };
+
+            if let Err(e) = result {
+                tracing::error!("Error processing job {}: {}", job.name, e);
+            }
+        }
+
+        tracing::warn!("WorkflowRunWorker receiver closed");
+    }
+
+    async fn resume_interrupted(&self) -> Result<(), String> {
+        let runs = self
+            .workflow_run_repo
+            .find_running_before(self.started_at)
+            .await
+            .map_err(|e| format!("Failed to fetch running workflow runs: {}", e))?;
+        if !runs.is_empty() {
+            tracing::info!("Resuming {} interrupted workflow runs", runs.len());
+        }
+        self.resume(runs).await;
+        Ok(())
+    }
+
+    async fn resume_due(&self) -> Result<(), String> {
+        let runs = self
+            .workflow_run_repo
+            .find_due(Utc::now())
+            .await
+            .map_err(|e| format!("Failed to fetch due workflow runs: {}", e))?;
+        self.resume(runs).await;
+        Ok(())
+    }
+
+    /// A run that cannot resume does not hold up the others
+    async fn resume(&self, runs: Vec<WorkflowRun>) {
+        for run in runs {
+            let id = run.id;
+            match self.executor.resume(run).await {
+                Ok(run) => tracing::debug!("Workflow run {} is now {:?}", id, run.status),
+                Err(e) => tracing::error!("Failed to resume workflow run {}: {}", id, e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::context::{run_as, run_as_system};
+    use crate::application::events::record_events::{RecordEvent, RECORD_CREATED};
+    use crate::application::ports::messaging::EventBus;
+    use crate::application::use_cases::create_workflow::{CreateWorkflow, CreateWorkflowInput};
+    use crate::application::use_cases::manage_workflow_versions::AddStepInput;
+    use crate::application::use_cases::testing::{
+        acting_as, executor, executor_on, identity_provider, member, user, workflow_versions,
+        workspace,
+    };
+    use crate::domain::states::{WorkflowRunStatus, WorkflowStepType};
+    use crate::infrastructure::messaging::InMemoryEventBus;
+    use crate::infrastructure::persistence::in_memory_repo::InMemoryRepo;
+    use chrono::Duration;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_only_runs_left_by_the_previous_process_are_resumed() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let versions = workflow_versions(&repo);
+        let a = workspace(&repo).await;
+        let ada = user(&repo, "ada@example.com").await;
+        let admin = member(&repo, a, &ada, "Admin").await;
+        let started_at = Utc::now();
+
+        let (interrupted, current) = run_as(acting_as(&ada, &admin), async {
+            let workflow = CreateWorkflow::new(repo.clone(), identity_provider(&repo))
+                .execute(CreateWorkflowInput {
+                    name: "Welcome".to_string(),
+                    workspace_id: a,
+                })
+                .await
+                .unwrap();
+            let version = versions.create_version(workflow.id).await.unwrap();
+            let step = versions
+                .add_step(
+                    version.id,
+                    AddStepInput {
+                        name: None,
+                        step_type: WorkflowStepType::FindRecords,
+                        settings: json!({ "object": "person" }),
+                        position: None,
+                    },
+                )
+                .await
+                .unwrap();
+            versions.publish(version.id).await.unwrap();
+
+            let run = |updated_at| WorkflowRun {
+                id: Uuid::new_v4(),
+                created_at: updated_at,
+                updated_at,
+                workflow_version_id: version.id,
+                status: WorkflowRunStatus::Running,
+                input: None,
+                output: None,
+                error: None,
+                cursor: Some(step.id),
+                wake_at: None,
+                workflow_chain: vec![],
+            };
+            let interrupted = run(started_at - Duration::minutes(1));
+            // Started by this process, and still running its step
+            let current = run(Utc::now());
+            for run in [&interrupted, &current] {
+                repo.create(run.clone()).await.unwrap();
+            }
+            (interrupted, current)
+        })
+        .await;
+
+        let (_, job_receiver) = mpsc::channel(1);
+        let worker =
+            WorkflowRunWorker::new(repo.clone(), executor(&repo), job_receiver, started_at);
+        run_as_system(async {
+            worker.resume_interrupted().await.unwrap();
+            for (run, status) in [
+                (interrupted, WorkflowRunStatus::Completed),
+                (current, WorkflowRunStatus::Running),
+            ] {
+                let stored = repo.find_by_id(run.id).await.unwrap().unwrap();
+                assert_eq!(stored.status, status);
+            }
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_resumed_runs_keep_their_workflow_chain() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let versions = workflow_versions(&repo);
+        let event_bus = Arc::new(InMemoryEventBus::new());
+        let mut events = event_bus.subscribe(RECORD_CREATED).await.unwrap();
+        let a = workspace(&repo).await;
+        let ada = user(&repo, "ada@example.com").await;
+        let admin = member(&repo, a, &ada, "Admin").await;
+
+        let run = run_as(acting_as(&ada, &admin), async {
+            let workflow = CreateWorkflow::new(repo.clone(), identity_provider(&repo))
+                .execute(CreateWorkflowInput {
+                    name: "Welcome".to_string(),
+                    workspace_id: a,
+                })
+                .await
+                .unwrap();
+            let version = versions.create_version(workflow.id).await.unwrap();
+            let step = versions
+                .add_step(
+                    version.id,
+                    AddStepInput {
+                        name: None,
+                        step_type: WorkflowStepType::CreateRecord,
+                        settings: json!({
+                            "object": "person",
+                            "fields": { "name": "Ada", "email": "ada@example.com" },
+                        }),
+                        position: None,
+                    },
+                )
+                .await
+                .unwrap();
+            versions.publish(version.id).await.unwrap();
+
+            // Parked by a delay in a run started by another workflow's run
+            let run = WorkflowRun {
+                id: Uuid::new_v4(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                workflow_version_id: version.id,
+                status: WorkflowRunStatus::Pending,
+                input: None,
+                output: None,
+                error: None,
+                cursor: Some(step.id),
+                wake_at: Some(Utc::now() - Duration::minutes(1)),
+                workflow_chain: vec![Uuid::new_v4(), workflow.id],
+            };
+            repo.create(run.clone()).await.unwrap()
+        })
+        .await;
+
+        let (_, job_receiver) = mpsc::channel(1);
+        let worker = WorkflowRunWorker::new(
+            repo.clone(),
+            executor_on(&repo, &event_bus),
+            job_receiver,
+            Utc::now(),
+        );
+        run_as_system(worker.resume_due()).await.unwrap();
+
+        let event = events.recv().await.unwrap();
+        let event: RecordEvent = serde_json::from_str(&event.payload).unwrap();
+        assert_eq!(event.workflow_chain, run.workflow_chain);
+    }
+}
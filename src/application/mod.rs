@@ -1,3 +1,4 @@
+pub mod context;
 pub mod events;
 pub mod jobs;
 pub mod ports;
@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+
+/// A dependency the server needs in order to serve requests, checked by `/readyz`
+#[async_trait]
+pub trait HealthCheck: Send + Sync {
+    /// Name the dependency is reported under
+    fn name(&self) -> &str;
+    async fn check(&self) -> Result<(), String>;
+}
@@ -1,8 +1,117 @@
+use crate::application::context::{current_actor, Actor};
+use crate::domain::entities::{ApiKey, SsoConfig, User, WorkspaceMember};
+use crate::domain::permissions::Permission;
+use crate::domain::DomainError;
 use async_trait::async_trait;
 use uuid::Uuid;
 
+/// The authenticated principal behind a request
+#[derive(Debug, Clone)]
+pub struct Identity {
+    pub user: User,
+    /// Membership in the workspace the request operates on, if any
+    pub member: Option<WorkspaceMember>,
+    /// API key the request authenticated with; it narrows what the member may do
+    pub api_key: Option<ApiKey>,
+}
+
+/// Raw credential presented by a client
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Credential {
+    /// Value of the session cookie
+    Session(String),
+    /// Value of an `Authorization: Bearer` header: an API key or a session token
+    Bearer(String),
+}
+
 #[async_trait]
 pub trait IdentityProvider: Send + Sync {
-    async fn get_current_user_id(&self) -> Result<Option<Uuid>, String>;
-    async fn has_permission(&self, user_id: Uuid, permission: &str) -> Result<bool, String>;
+    /// Resolves a credential to an identity. `Ok(None)` means the credential is
+    /// unknown, expired or belongs to a disabled account.
+    ///
+    /// The membership is taken in `workspace_id` when the request is routed to a
+    /// workspace, and defaults to the user's oldest membership otherwise.
+    async fn authenticate(
+        &self,
+        credential: &Credential,
+        workspace_id: Option<Uuid>,
+    ) -> Result<Option<Identity>, String>;
+    async fn has_permission(
+        &self,
+        identity: &Identity,
+        permission: &Permission,
+    ) -> Result<bool, String>;
+
+    /// Checks `permission` for the ambient actor (see `application::context`)
+    async fn authorize(&self, permission: &Permission) -> Result<(), DomainError> {
+        let identity = match current_actor() {
+            Some(Actor::System | Actor::Workspace(_)) => return Ok(()),
+            Some(Actor::User(identity)) => identity,
+            None => {
+                return Err(DomainError::Permission(
+                    "Authentication required".to_string(),
+                ))
+            }
+        };
+
+        if self
+            .has_permission(&identity, permission)
+            .await
+            .map_err(DomainError::InfrastructureError)?
+        {
+            Ok(())
+        } else {
+            Err(DomainError::Permission(format!(
+                "{} is not allowed",
+                permission
+            )))
+        }
+    }
+}
+
+pub trait PasswordHasher: Send + Sync {
+    fn hash(&self, password: &str) -> Result<String, String>;
+    fn verify(&self, password: &str, password_hash: &str) -> Result<bool, String>;
+}
+
+/// Start of an authorization-code flow. Everything but `url` must be kept by the
+/// browser session until the callback.
+#[derive(Debug, Clone)]
+pub struct SsoAuthorization {
+    /// Where to send the browser
+    pub url: String,
+    /// Echoed back on the callback; guards against forged callbacks
+    pub state: String,
+    /// Expected in the ID token; guards against replayed tokens
+    pub nonce: String,
+    /// PKCE secret whose digest was sent as the code challenge
+    pub pkce_verifier: String,
+}
+
+/// Verified claims of an ID token
+#[derive(Debug, Clone)]
+pub struct SsoClaims {
+    pub subject: String,
+    pub email: String,
+    pub email_verified: bool,
+    pub name: Option<String>,
+}
+
+/// Signs users in through an external identity provider (see `SsoConfig`)
+#[async_trait]
+pub trait SsoProvider: Send + Sync {
+    async fn authorize(
+        &self,
+        config: &SsoConfig,
+        redirect_uri: &str,
+    ) -> Result<SsoAuthorization, String>;
+    /// Redeems the authorization code and validates the returned ID token
+    async fn exchange_code(
+        &self,
+        config: &SsoConfig,
+        code: &str,
+        redirect_uri: &str,
+        pkce_verifier: &str,
+        nonce: &str,
+    ) -> Result<SsoClaims, String>;
 }
@@ -8,7 +8,8 @@ pub trait RecordUseCase: Send + Sync {
         &self,
         card_id: Uuid,
         new_stage: OpportunityStage,
-    ) -> Result<(), String>;
+        expected_version: i32,
+    ) -> Result<(), crate::domain::DomainError>;
 
     async fn list_opportunities(&self) -> Result<Vec<crate::domain::Opportunity>, String>;
 }
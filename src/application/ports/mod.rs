@@ -1,11 +1,14 @@
 pub mod billing;
 pub mod email;
 pub mod external;
+pub mod health;
 pub mod identity;
 pub mod input;
 pub mod messaging;
 pub mod output;
+pub mod query;
 pub mod scheduling;
 pub mod search;
 pub mod storage;
 pub mod time;
+pub mod unit_of_work;
@@ -1,16 +1,23 @@
-use crate::domain::states::LeadStatus;
+use super::query::{ListQuery, Page};
+use crate::domain::permissions::RoleDefinition;
+use crate::domain::states::{LeadStatus, UserTokenPurpose};
 use crate::domain::{
-    CalendarEvent, CalendarEventParticipant, Company, ConnectedAccount, DomainError, Email,
-    EmailTemplate, Lead, Note, Opportunity, Person, Task, TaskTarget, TimelineActivity, User,
-    Workflow, WorkflowRun, WorkflowVersion, WorkflowVersionStep, Workspace, WorkspaceMember,
+    ApiKey, CalendarEvent, CalendarEventParticipant, Company, ConnectedAccount, DomainError, Email,
+    EmailTemplate, Invitation, Lead, Note, Opportunity, Person, Session, SsoConfig, Task,
+    TaskTarget, TimelineActivity, TrashedKind, TrashedRecord, User, UserToken, Workflow,
+    WorkflowRun, WorkflowVersion, WorkflowVersionStep, Workspace, WorkspaceMember,
 };
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
 #[async_trait]
 pub trait PersonRepository: Send + Sync {
+    async fn find_by_id(&self, id: uuid::Uuid) -> Result<Option<Person>, DomainError>;
     async fn find_by_email(&self, email: &str) -> Result<Option<Person>, DomainError>;
     async fn create(&self, person: Person) -> Result<Person, DomainError>;
+    async fn update(&self, person: Person) -> Result<Person, DomainError>;
     async fn find_all(&self) -> Result<Vec<Person>, DomainError>;
+    async fn find_page(&self, query: &ListQuery) -> Result<Page<Person>, DomainError>;
     async fn delete(&self, id: uuid::Uuid) -> Result<(), DomainError>;
 }
 
@@ -25,8 +32,53 @@ pub trait OpportunityRepository: Send + Sync {
 
 #[async_trait]
 pub trait UserRepository: Send + Sync {
+    async fn find_by_id(&self, id: uuid::Uuid) -> Result<Option<User>, DomainError>;
     async fn find_by_email(&self, email: &str) -> Result<Option<User>, DomainError>;
     async fn create(&self, user: User) -> Result<User, DomainError>;
+    async fn update(&self, user: User) -> Result<User, DomainError>;
+}
+
+#[async_trait]
+pub trait UserTokenRepository: Send + Sync {
+    async fn create(&self, token: UserToken) -> Result<UserToken, DomainError>;
+    async fn find_by_token_hash(&self, token_hash: &str) -> Result<Option<UserToken>, DomainError>;
+    /// Marks the token used unless it already is; `false` when it was, so that a token is
+    /// only ever exchanged once even by concurrent requests
+    async fn mark_used(&self, id: uuid::Uuid, used_at: DateTime<Utc>) -> Result<bool, DomainError>;
+    /// Marks every unused token issued to a user for `purpose` as used
+    async fn mark_all_used(
+        &self,
+        user_id: uuid::Uuid,
+        purpose: UserTokenPurpose,
+        used_at: DateTime<Utc>,
+    ) -> Result<(), DomainError>;
+    /// Tokens issued to a user for `purpose` since the given time, used or not
+    async fn count_since(
+        &self,
+        user_id: uuid::Uuid,
+        purpose: UserTokenPurpose,
+        since: DateTime<Utc>,
+    ) -> Result<u64, DomainError>;
+}
+
+#[async_trait]
+pub trait SessionRepository: Send + Sync {
+    async fn create(&self, session: Session) -> Result<Session, DomainError>;
+    async fn find_by_token_hash(&self, token_hash: &str) -> Result<Option<Session>, DomainError>;
+    async fn delete_by_token_hash(&self, token_hash: &str) -> Result<(), DomainError>;
+    /// Signs the user out everywhere
+    async fn delete_by_user_id(&self, user_id: uuid::Uuid) -> Result<(), DomainError>;
+}
+
+#[async_trait]
+pub trait ApiKeyRepository: Send + Sync {
+    async fn create(&self, api_key: ApiKey) -> Result<ApiKey, DomainError>;
+    async fn find_by_id(&self, id: uuid::Uuid) -> Result<Option<ApiKey>, DomainError>;
+    async fn find_by_token_hash(&self, token_hash: &str) -> Result<Option<ApiKey>, DomainError>;
+    /// Keys of a member that were not revoked
+    async fn find_by_member_id(&self, member_id: uuid::Uuid) -> Result<Vec<ApiKey>, DomainError>;
+    async fn update(&self, api_key: ApiKey) -> Result<ApiKey, DomainError>;
+    async fn record_use(&self, id: uuid::Uuid, at: DateTime<Utc>) -> Result<(), DomainError>;
 }
 
 #[async_trait]
@@ -34,11 +86,65 @@ pub trait WorkspaceRepository: Send + Sync {
     async fn create(&self, workspace: Workspace) -> Result<Workspace, DomainError>;
     async fn find_by_subdomain(&self, subdomain: &str) -> Result<Option<Workspace>, DomainError>;
     async fn add_member(&self, member: WorkspaceMember) -> Result<WorkspaceMember, DomainError>;
+    async fn find_members_by_user_id(
+        &self,
+        user_id: uuid::Uuid,
+    ) -> Result<Vec<WorkspaceMember>, DomainError>;
+    async fn find_member_by_id(
+        &self,
+        id: uuid::Uuid,
+    ) -> Result<Option<WorkspaceMember>, DomainError>;
+    async fn update_member(&self, member: WorkspaceMember) -> Result<WorkspaceMember, DomainError>;
+    async fn find_members(
+        &self,
+        workspace_id: uuid::Uuid,
+    ) -> Result<Vec<WorkspaceMember>, DomainError>;
+    async fn remove_member(&self, id: uuid::Uuid) -> Result<(), DomainError>;
+}
+
+#[async_trait]
+pub trait SsoConfigRepository: Send + Sync {
+    async fn find_by_workspace_id(
+        &self,
+        workspace_id: uuid::Uuid,
+    ) -> Result<Option<SsoConfig>, DomainError>;
+    /// Creates or replaces the workspace's configuration
+    async fn save(&self, config: SsoConfig) -> Result<SsoConfig, DomainError>;
+}
+
+#[async_trait]
+pub trait InvitationRepository: Send + Sync {
+    async fn create(&self, invitation: Invitation) -> Result<Invitation, DomainError>;
+    async fn find_by_id(&self, id: uuid::Uuid) -> Result<Option<Invitation>, DomainError>;
+    async fn find_by_token_hash(&self, token_hash: &str)
+        -> Result<Option<Invitation>, DomainError>;
+    /// Invitations of a workspace that were neither accepted nor revoked (possibly expired)
+    async fn find_open(&self, workspace_id: uuid::Uuid) -> Result<Vec<Invitation>, DomainError>;
+    async fn update(&self, invitation: Invitation) -> Result<Invitation, DomainError>;
+    /// Marks an open invitation accepted; `false` when it was already accepted or revoked,
+    /// so that an invitation is only accepted once even by concurrent requests
+    async fn mark_accepted(
+        &self,
+        id: uuid::Uuid,
+        accepted_at: DateTime<Utc>,
+    ) -> Result<bool, DomainError>;
+}
+
+#[async_trait]
+pub trait RoleRepository: Send + Sync {
+    async fn find_all(&self, workspace_id: uuid::Uuid) -> Result<Vec<RoleDefinition>, DomainError>;
+    async fn find_by_name(
+        &self,
+        workspace_id: uuid::Uuid,
+        name: &str,
+    ) -> Result<Option<RoleDefinition>, DomainError>;
+    async fn create(&self, role: RoleDefinition) -> Result<RoleDefinition, DomainError>;
 }
 
 #[async_trait]
 pub trait CompanyRepository: Send + Sync {
     async fn find_all(&self) -> Result<Vec<Company>, DomainError>;
+    async fn find_page(&self, query: &ListQuery) -> Result<Page<Company>, DomainError>;
     async fn find_by_id(&self, id: uuid::Uuid) -> Result<Option<Company>, DomainError>;
     async fn create(&self, company: Company) -> Result<Company, DomainError>;
     async fn update(&self, company: Company) -> Result<Company, DomainError>;
@@ -63,6 +169,20 @@ pub trait NoteRepository: Send + Sync {
     async fn delete(&self, id: uuid::Uuid) -> Result<(), DomainError>;
 }
 
+/// Records deleted through their repository's `delete`, which only moves them to the trash
+#[async_trait]
+pub trait TrashRepository: Send + Sync {
+    /// Trashed records of the given kinds, most recently deleted first
+    async fn find_trashed(&self, kinds: &[TrashedKind]) -> Result<Vec<TrashedRecord>, DomainError>;
+    /// Takes a record out of the trash, with the task targets and timeline activities
+    /// trashed along with it
+    async fn restore(&self, kind: TrashedKind, id: uuid::Uuid) -> Result<(), DomainError>;
+    /// Permanently deletes a trashed record
+    async fn purge(&self, kind: TrashedKind, id: uuid::Uuid) -> Result<(), DomainError>;
+    /// Permanently deletes every record trashed before `cutoff`; returns how many
+    async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<u64, DomainError>;
+}
+
 #[async_trait]
 pub trait TaskTargetRepository: Send + Sync {
     async fn find_by_task_id(&self, task_id: uuid::Uuid) -> Result<Vec<TaskTarget>, DomainError>;
@@ -77,6 +197,12 @@ pub trait WorkflowRepository: Send + Sync {
     async fn create(&self, workflow: Workflow) -> Result<Workflow, DomainError>;
     async fn update(&self, workflow: Workflow) -> Result<Workflow, DomainError>;
     async fn delete(&self, id: uuid::Uuid) -> Result<(), DomainError>;
+    /// Trigger steps of the published versions, each with its workflow; only those of
+    /// `workspace_id` when given
+    async fn find_published_triggers(
+        &self,
+        workspace_id: Option<uuid::Uuid>,
+    ) -> Result<Vec<(Workflow, WorkflowVersionStep)>, DomainError>;
 }
 
 #[async_trait]
@@ -107,6 +233,13 @@ pub trait WorkflowRunRepository: Send + Sync {
     async fn find_by_id(&self, id: uuid::Uuid) -> Result<Option<WorkflowRun>, DomainError>;
     async fn create(&self, run: WorkflowRun) -> Result<WorkflowRun, DomainError>;
     async fn update(&self, run: WorkflowRun) -> Result<WorkflowRun, DomainError>;
+    /// Pending runs whose `wake_at` has come by `now`, the earliest first
+    async fn find_due(&self, now: DateTime<Utc>) -> Result<Vec<WorkflowRun>, DomainError>;
+    /// Runs still marked as running that were last saved before `before`, the oldest first
+    async fn find_running_before(
+        &self,
+        before: DateTime<Utc>,
+    ) -> Result<Vec<WorkflowRun>, DomainError>;
 }
 
 #[async_trait]
@@ -165,7 +298,7 @@ pub trait TimelineActivityRepository: Send + Sync {
 
 #[async_trait]
 pub trait EmailRepository: Send + Sync {
-    async fn find_all(&self) -> Result<Vec<Email>, DomainError>;
+    async fn find_page(&self, query: &ListQuery) -> Result<Page<Email>, DomainError>;
     async fn find_by_id(&self, id: uuid::Uuid) -> Result<Option<Email>, DomainError>;
     async fn find_by_person_id(&self, person_id: uuid::Uuid) -> Result<Vec<Email>, DomainError>;
     async fn find_by_company_id(&self, company_id: uuid::Uuid) -> Result<Vec<Email>, DomainError>;
@@ -191,7 +324,7 @@ pub trait EmailTemplateRepository: Send + Sync {
 
 #[async_trait]
 pub trait LeadRepository: Send + Sync {
-    async fn find_all(&self) -> Result<Vec<Lead>, DomainError>;
+    async fn find_page(&self, query: &ListQuery) -> Result<Page<Lead>, DomainError>;
     async fn find_by_id(&self, id: uuid::Uuid) -> Result<Option<Lead>, DomainError>;
     async fn find_by_email(&self, email: &str) -> Result<Option<Lead>, DomainError>;
     async fn find_by_status(&self, status: LeadStatus) -> Result<Vec<Lead>, DomainError>;
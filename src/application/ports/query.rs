@@ -0,0 +1,131 @@
+//! Shared list query accepted by the repository `find_page` methods: keyset pagination,
+//! field filters and a single sort field. Field names are the domain field names; each
+//! repository decides which of them can be filtered and sorted on.
+
+use serde::Serialize;
+
+pub const DEFAULT_PAGE_SIZE: u64 = 50;
+pub const MAX_PAGE_SIZE: u64 = 200;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListQuery {
+    /// Page size, `DEFAULT_PAGE_SIZE` when omitted and capped at `MAX_PAGE_SIZE`
+    pub limit: Option<u64>,
+    /// Opaque `next_cursor` of the previous page
+    pub cursor: Option<String>,
+    /// Repository default ordering when omitted
+    pub sort: Option<Sort>,
+    /// All filters must match
+    pub filters: Vec<Filter>,
+}
+
+impl ListQuery {
+    pub fn page_size(&self) -> u64 {
+        self.limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sort {
+    pub field: String,
+    pub direction: SortDirection,
+}
+
+impl Sort {
+    pub fn asc(field: &str) -> Self {
+        Self {
+            field: field.to_string(),
+            direction: SortDirection::Asc,
+        }
+    }
+
+    pub fn desc(field: &str) -> Self {
+        Self {
+            field: field.to_string(),
+            direction: SortDirection::Desc,
+        }
+    }
+
+    /// `name` sorts ascending, `-name` descending
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.strip_prefix('-') {
+            Some(field) if !field.is_empty() => Some(Self::desc(field)),
+            Some(_) => None,
+            None if !value.is_empty() => Some(Self::asc(value)),
+            None => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterOp {
+    Eq,
+    Neq,
+    /// Substring match, text fields only
+    Contains,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+impl FilterOp {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "eq" => Some(Self::Eq),
+            "neq" => Some(Self::Neq),
+            "contains" => Some(Self::Contains),
+            "gt" => Some(Self::Gt),
+            "gte" => Some(Self::Gte),
+            "lt" => Some(Self::Lt),
+            "lte" => Some(Self::Lte),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filter {
+    pub field: String,
+    pub op: FilterOp,
+    /// Raw value, parsed by the repository according to the field's type
+    pub value: String,
+}
+
+/// One page of results; `next_cursor` is `None` on the last page
+#[derive(Debug, Clone, Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sort_parsing() {
+        assert_eq!(Sort::parse("name"), Some(Sort::asc("name")));
+        assert_eq!(Sort::parse("-created_at"), Some(Sort::desc("created_at")));
+        assert_eq!(Sort::parse("-"), None);
+        assert_eq!(Sort::parse(""), None);
+    }
+
+    #[test]
+    fn page_size_is_bounded() {
+        let mut query = ListQuery::default();
+        assert_eq!(query.page_size(), DEFAULT_PAGE_SIZE);
+        query.limit = Some(0);
+        assert_eq!(query.page_size(), 1);
+        query.limit = Some(10_000);
+        assert_eq!(query.page_size(), MAX_PAGE_SIZE);
+    }
+}
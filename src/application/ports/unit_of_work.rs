@@ -0,0 +1,37 @@
+use crate::domain::DomainError;
+use async_trait::async_trait;
+use std::future::Future;
+use std::pin::Pin;
+
+/// Work run by a `UnitOfWork`
+pub type Work<'a> = Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send + 'a>>;
+
+/// Groups repository calls into one atomic change.
+///
+/// Every repository call made while `work` runs joins the same transaction, which commits
+/// when `work` returns `Ok` and rolls back when it fails. Like the actor context, the
+/// transaction does not follow `tokio::spawn`. Nested units of work roll back on their own
+/// and only commit with the outermost one.
+#[async_trait]
+pub trait UnitOfWork: Send + Sync {
+    async fn run<'a>(&self, work: Work<'a>) -> Result<(), DomainError>;
+}
+
+impl dyn UnitOfWork {
+    /// `run` for work that produces a value
+    pub async fn atomically<T, F>(&self, work: F) -> Result<T, DomainError>
+    where
+        T: Send,
+        F: Future<Output = Result<T, DomainError>> + Send,
+    {
+        let mut output = None;
+        self.run(Box::pin(async {
+            output = Some(work.await?);
+            Ok(())
+        }))
+        .await?;
+        output.ok_or_else(|| {
+            DomainError::InfrastructureError("Unit of work finished without a result".to_string())
+        })
+    }
+}
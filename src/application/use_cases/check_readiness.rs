@@ -0,0 +1,42 @@
+use crate::application::ports::health::HealthCheck;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How long one check may take before it counts as failed
+const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Outcome of one check; `error` is `None` when the dependency is healthy
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    pub name: String,
+    pub error: Option<String>,
+}
+
+/// Whether the server can serve requests: its database is reachable and its background
+/// workers and event subscribers are still running
+pub struct CheckReadiness {
+    checks: Vec<Arc<dyn HealthCheck>>,
+}
+
+impl CheckReadiness {
+    pub fn new(checks: Vec<Arc<dyn HealthCheck>>) -> Self {
+        Self { checks }
+    }
+
+    pub async fn execute(&self) -> Vec<CheckResult> {
+        let mut results = Vec::with_capacity(self.checks.len());
+        for check in &self.checks {
+            let error = match tokio::time::timeout(CHECK_TIMEOUT, check.check()).await {
+                Ok(Ok(())) => None,
+                Ok(Err(e)) => Some(e),
+                Err(_) => Some(format!("No answer within {:?}", CHECK_TIMEOUT)),
+            };
+            results.push(CheckResult {
+                name: check.name().to_string(),
+                error,
+            });
+        }
+        results
+    }
+}
@@ -1,7 +1,11 @@
+use crate::application::events::record_events::{RecordEvent, RecordEvents};
+use crate::application::ports::identity::IdentityProvider;
 use crate::application::ports::output::{
     CompanyRepository, LeadRepository, OpportunityRepository, PersonRepository,
     TimelineActivityRepository,
 };
+use crate::application::ports::unit_of_work::UnitOfWork;
+use crate::domain::permissions::{objects, Permission};
 use crate::domain::states::LeadStatus;
 use crate::domain::{
     Company, DomainError, Lead, Opportunity, OpportunityStage, Person, TimelineActivity,
@@ -35,15 +39,22 @@ pub struct ConvertLead {
     company_repo: Arc<dyn CompanyRepository>,
     opportunity_repo: Arc<dyn OpportunityRepository>,
     timeline_repo: Arc<dyn TimelineActivityRepository>,
+    identity_provider: Arc<dyn IdentityProvider>,
+    unit_of_work: Arc<dyn UnitOfWork>,
+    record_events: Arc<RecordEvents>,
 }
 
 impl ConvertLead {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         lead_repo: Arc<dyn LeadRepository>,
         person_repo: Arc<dyn PersonRepository>,
         company_repo: Arc<dyn CompanyRepository>,
         opportunity_repo: Arc<dyn OpportunityRepository>,
         timeline_repo: Arc<dyn TimelineActivityRepository>,
+        identity_provider: Arc<dyn IdentityProvider>,
+        unit_of_work: Arc<dyn UnitOfWork>,
+        record_events: Arc<RecordEvents>,
     ) -> Self {
         Self {
             lead_repo,
@@ -51,10 +62,46 @@ impl ConvertLead {
             company_repo,
             opportunity_repo,
             timeline_repo,
+            identity_provider,
+            unit_of_work,
+            record_events,
         }
     }
 
     pub async fn execute(&self, input: ConvertLeadInput) -> Result<ConversionResult, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::update(objects::LEAD))
+            .await?;
+        if input.create_person {
+            self.identity_provider
+                .authorize(&Permission::create(objects::PERSON))
+                .await?;
+        }
+        if input.create_company {
+            self.identity_provider
+                .authorize(&Permission::create(objects::COMPANY))
+                .await?;
+        }
+        if input.create_opportunity {
+            self.identity_provider
+                .authorize(&Permission::create(objects::OPPORTUNITY))
+                .await?;
+        }
+
+        // Either every record of the conversion is written or none is
+        let (result, events) = self.unit_of_work.atomically(self.convert(input)).await?;
+        // Only once committed, so that triggered workflows see the records
+        for event in events {
+            self.record_events.publish(event).await;
+        }
+        Ok(result)
+    }
+
+    /// The conversion, with the record events it is to publish
+    async fn convert(
+        &self,
+        input: ConvertLeadInput,
+    ) -> Result<(ConversionResult, Vec<RecordEvent>), DomainError> {
         // 1. Get lead
         let mut lead = self
             .lead_repo
@@ -66,6 +113,8 @@ impl ConvertLead {
         if lead.is_converted() {
             return Err(DomainError::InvalidState("Lead already converted".into()));
         }
+        let previous = lead.clone();
+        let mut events = Vec::new();
 
         let mut person_id = None;
         let mut company_id = None;
@@ -88,6 +137,11 @@ impl ConvertLead {
             person.validate()?;
             let person = self.person_repo.create(person).await?;
             person_id = Some(person.id);
+            events.push(RecordEvent::created(
+                objects::PERSON,
+                person.workspace_id,
+                &person,
+            ));
         }
 
         // 4. Create Company if requested
@@ -104,9 +158,15 @@ impl ConvertLead {
                 employees_count: 0,
                 position: 0,
                 workspace_id: lead.workspace_id,
+                version: 1,
             };
             let company = self.company_repo.create(company).await?;
             company_id = Some(company.id);
+            events.push(RecordEvent::created(
+                objects::COMPANY,
+                company.workspace_id,
+                &company,
+            ));
 
             // Update person's company_id if person was created
             if let Some(pid) = person_id {
@@ -139,9 +199,15 @@ impl ConvertLead {
                 company_id,
                 owner_id: lead.assigned_to_id,
                 workspace_id: lead.workspace_id,
+                version: 1,
             };
             let opportunity = self.opportunity_repo.create(opportunity).await?;
             opportunity_id = Some(opportunity.id);
+            events.push(RecordEvent::created(
+                objects::OPPORTUNITY,
+                opportunity.workspace_id,
+                &opportunity,
+            ));
         }
 
         // 6. Update lead with conversion data
@@ -151,6 +217,12 @@ impl ConvertLead {
         lead.converted_opportunity_id = opportunity_id;
         lead.converted_at = Some(Utc::now());
         let lead = self.lead_repo.update(lead).await?;
+        events.push(RecordEvent::updated(
+            objects::LEAD,
+            lead.workspace_id,
+            &previous,
+            &lead,
+        ));
 
         // 7. Create timeline activity
         let activity_name = format!(
@@ -177,11 +249,12 @@ impl ConvertLead {
         };
         self.timeline_repo.create(timeline).await?;
 
-        Ok(ConversionResult {
+        let result = ConversionResult {
             lead,
             person_id,
             company_id,
             opportunity_id,
-        })
+        };
+        Ok((result, events))
     }
 }
@@ -1,4 +1,6 @@
+use crate::application::ports::identity::IdentityProvider;
 use crate::application::ports::output::CalendarEventRepository;
+use crate::domain::permissions::{objects, Permission};
 use crate::domain::{CalendarEvent, DomainError};
 use chrono::{DateTime, Utc};
 use std::sync::Arc;
@@ -15,12 +17,17 @@ pub struct CreateCalendarEventInput {
 
 pub struct CreateCalendarEvent {
     calendar_event_repo: Arc<dyn CalendarEventRepository>,
+    identity_provider: Arc<dyn IdentityProvider>,
 }
 
 impl CreateCalendarEvent {
-    pub fn new(calendar_event_repo: Arc<dyn CalendarEventRepository>) -> Self {
+    pub fn new(
+        calendar_event_repo: Arc<dyn CalendarEventRepository>,
+        identity_provider: Arc<dyn IdentityProvider>,
+    ) -> Self {
         Self {
             calendar_event_repo,
+            identity_provider,
         }
     }
 
@@ -28,6 +35,9 @@ impl CreateCalendarEvent {
         &self,
         input: CreateCalendarEventInput,
     ) -> Result<CalendarEvent, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::create(objects::CALENDAR_EVENT))
+            .await?;
         let event = CalendarEvent {
             id: Uuid::new_v4(),
             connected_account_id: input.connected_account_id,
@@ -1,4 +1,7 @@
+use crate::application::events::record_events::{RecordEvent, RecordEvents};
+use crate::application::ports::identity::IdentityProvider;
 use crate::application::ports::output::CompanyRepository;
+use crate::domain::permissions::{objects, Permission};
 use crate::domain::{Company, DomainError};
 use chrono::Utc;
 use serde::Deserialize;
@@ -16,14 +19,27 @@ pub struct CreateCompanyInput {
 
 pub struct CreateCompany {
     company_repo: Arc<dyn CompanyRepository>,
+    identity_provider: Arc<dyn IdentityProvider>,
+    record_events: Arc<RecordEvents>,
 }
 
 impl CreateCompany {
-    pub fn new(company_repo: Arc<dyn CompanyRepository>) -> Self {
-        Self { company_repo }
+    pub fn new(
+        company_repo: Arc<dyn CompanyRepository>,
+        identity_provider: Arc<dyn IdentityProvider>,
+        record_events: Arc<RecordEvents>,
+    ) -> Self {
+        Self {
+            company_repo,
+            identity_provider,
+            record_events,
+        }
     }
 
     pub async fn execute(&self, input: CreateCompanyInput) -> Result<Company, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::create(objects::COMPANY))
+            .await?;
         // Here we could add validation (e.g. check domain uniqueness explicitly if not handled by DB constraint,
         // but DB constraint is usually enough for a start, or check it here for better error msg).
         // For MVP, we'll let DB constraint handle uniqueness error translation or do a check.
@@ -46,8 +62,17 @@ impl CreateCompany {
             // So `employees` field should NOT be here.
             position: 0,
             workspace_id: input.workspace_id,
+            version: 1,
         };
 
-        self.company_repo.create(company).await
+        let company = self.company_repo.create(company).await?;
+        self.record_events
+            .publish(RecordEvent::created(
+                objects::COMPANY,
+                company.workspace_id,
+                &company,
+            ))
+            .await;
+        Ok(company)
     }
 }
@@ -1,5 +1,8 @@
+use crate::application::events::record_events::{RecordEvent, RecordEvents};
+use crate::application::ports::identity::IdentityProvider;
 use crate::application::ports::messaging::{DomainEvent, EventBus};
 use crate::application::ports::output::LeadRepository;
+use crate::domain::permissions::{objects, Permission};
 use crate::domain::states::{LeadSource, LeadStatus};
 use crate::domain::{DomainError, Lead};
 use chrono::Utc;
@@ -23,17 +26,29 @@ pub struct CreateLeadInput {
 pub struct CreateLead {
     lead_repo: Arc<dyn LeadRepository>,
     event_bus: Arc<dyn EventBus>,
+    identity_provider: Arc<dyn IdentityProvider>,
+    record_events: Arc<RecordEvents>,
 }
 
 impl CreateLead {
-    pub fn new(lead_repo: Arc<dyn LeadRepository>, event_bus: Arc<dyn EventBus>) -> Self {
+    pub fn new(
+        lead_repo: Arc<dyn LeadRepository>,
+        event_bus: Arc<dyn EventBus>,
+        identity_provider: Arc<dyn IdentityProvider>,
+        record_events: Arc<RecordEvents>,
+    ) -> Self {
         Self {
             lead_repo,
             event_bus,
+            identity_provider,
+            record_events,
         }
     }
 
     pub async fn execute(&self, input: CreateLeadInput) -> Result<Lead, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::create(objects::LEAD))
+            .await?;
         // 1. Check email uniqueness
         if (self.lead_repo.find_by_email(&input.email).await?).is_some() {
             return Err(DomainError::Validation("Email already exists".into()));
@@ -79,6 +94,13 @@ impl CreateLead {
 
         // 6. Save
         let lead = self.lead_repo.create(lead).await?;
+        self.record_events
+            .publish(RecordEvent::created(
+                objects::LEAD,
+                lead.workspace_id,
+                &lead,
+            ))
+            .await;
 
         // 7. Publish event
         self.event_bus
@@ -1,4 +1,7 @@
+use crate::application::events::record_events::{RecordEvent, RecordEvents};
+use crate::application::ports::identity::IdentityProvider;
 use crate::application::ports::output::NoteRepository;
+use crate::domain::permissions::{objects, Permission};
 use crate::domain::{DomainError, Note};
 use chrono::Utc;
 use serde::Deserialize;
@@ -14,14 +17,27 @@ pub struct CreateNoteInput {
 
 pub struct CreateNote {
     note_repo: Arc<dyn NoteRepository>,
+    identity_provider: Arc<dyn IdentityProvider>,
+    record_events: Arc<RecordEvents>,
 }
 
 impl CreateNote {
-    pub fn new(note_repo: Arc<dyn NoteRepository>) -> Self {
-        Self { note_repo }
+    pub fn new(
+        note_repo: Arc<dyn NoteRepository>,
+        identity_provider: Arc<dyn IdentityProvider>,
+        record_events: Arc<RecordEvents>,
+    ) -> Self {
+        Self {
+            note_repo,
+            identity_provider,
+            record_events,
+        }
     }
 
     pub async fn execute(&self, input: CreateNoteInput) -> Result<Note, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::create(objects::NOTE))
+            .await?;
         // Validate that title is not empty (INV-INT-002)
         if input.title.trim().is_empty() {
             return Err(DomainError::Validation("Title is required".to_string()));
@@ -38,6 +54,14 @@ impl CreateNote {
             workspace_id: input.workspace_id,
         };
 
-        self.note_repo.create(note).await
+        let note = self.note_repo.create(note).await?;
+        self.record_events
+            .publish(RecordEvent::created(
+                objects::NOTE,
+                note.workspace_id,
+                &note,
+            ))
+            .await;
+        Ok(note)
     }
 }
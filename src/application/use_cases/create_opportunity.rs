@@ -1,4 +1,7 @@
+use crate::application::events::record_events::{RecordEvent, RecordEvents};
+use crate::application::ports::identity::IdentityProvider;
 use crate::application::ports::output::OpportunityRepository;
+use crate::domain::permissions::{objects, Permission};
 use crate::domain::{DomainError, Opportunity, OpportunityStage};
 use chrono::Utc;
 use serde::Deserialize;
@@ -20,14 +23,27 @@ pub struct CreateOpportunityInput {
 
 pub struct CreateOpportunity {
     opportunity_repo: Arc<dyn OpportunityRepository>,
+    identity_provider: Arc<dyn IdentityProvider>,
+    record_events: Arc<RecordEvents>,
 }
 
 impl CreateOpportunity {
-    pub fn new(opportunity_repo: Arc<dyn OpportunityRepository>) -> Self {
-        Self { opportunity_repo }
+    pub fn new(
+        opportunity_repo: Arc<dyn OpportunityRepository>,
+        identity_provider: Arc<dyn IdentityProvider>,
+        record_events: Arc<RecordEvents>,
+    ) -> Self {
+        Self {
+            opportunity_repo,
+            identity_provider,
+            record_events,
+        }
     }
 
     pub async fn execute(&self, input: CreateOpportunityInput) -> Result<Opportunity, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::create(objects::OPPORTUNITY))
+            .await?;
         // Parse stage from string or default to Prospecting
         let stage = if let Some(stage_str) = input.stage {
             match stage_str.as_str() {
@@ -57,8 +73,17 @@ impl CreateOpportunity {
             owner_id: input.owner_id,
             position: 0,
             workspace_id: input.workspace_id,
+            version: 1,
         };
 
-        self.opportunity_repo.create(opportunity).await
+        let opportunity = self.opportunity_repo.create(opportunity).await?;
+        self.record_events
+            .publish(RecordEvent::created(
+                objects::OPPORTUNITY,
+                opportunity.workspace_id,
+                &opportunity,
+            ))
+            .await;
+        Ok(opportunity)
     }
 }
@@ -1,4 +1,7 @@
+use crate::application::events::record_events::{RecordEvent, RecordEvents};
+use crate::application::ports::identity::IdentityProvider;
 use crate::application::ports::output::PersonRepository;
+use crate::domain::permissions::{objects, Permission};
 use crate::domain::{DomainError, Person};
 use chrono::Utc;
 use std::sync::Arc;
@@ -6,11 +9,21 @@ use uuid::Uuid;
 
 pub struct CreatePerson {
     person_repo: Arc<dyn PersonRepository>,
+    identity_provider: Arc<dyn IdentityProvider>,
+    record_events: Arc<RecordEvents>,
 }
 
 impl CreatePerson {
-    pub fn new(person_repo: Arc<dyn PersonRepository>) -> Self {
-        Self { person_repo }
+    pub fn new(
+        person_repo: Arc<dyn PersonRepository>,
+        identity_provider: Arc<dyn IdentityProvider>,
+        record_events: Arc<RecordEvents>,
+    ) -> Self {
+        Self {
+            person_repo,
+            identity_provider,
+            record_events,
+        }
     }
 
     pub async fn execute(
@@ -20,6 +33,9 @@ impl CreatePerson {
         position: i32,
         workspace_id: Uuid,
     ) -> Result<Person, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::create(objects::PERSON))
+            .await?;
         // Enforce invariants first (uniqueness check via find_by_email)
         if (self.person_repo.find_by_email(&email).await?).is_some() {
             return Err(DomainError::Validation("Email already exists".to_string()));
@@ -41,6 +57,14 @@ impl CreatePerson {
         use crate::domain::HardGuard;
         new_person.validate()?;
 
-        self.person_repo.create(new_person).await
+        let person = self.person_repo.create(new_person).await?;
+        self.record_events
+            .publish(RecordEvent::created(
+                objects::PERSON,
+                person.workspace_id,
+                &person,
+            ))
+            .await;
+        Ok(person)
     }
 }
@@ -1,4 +1,7 @@
+use crate::application::events::record_events::{RecordEvent, RecordEvents};
+use crate::application::ports::identity::IdentityProvider;
 use crate::application::ports::output::TaskRepository;
+use crate::domain::permissions::{objects, Permission};
 use crate::domain::{DomainError, Task, TaskStatus};
 use chrono::Utc;
 use serde::Deserialize;
@@ -17,14 +20,27 @@ pub struct CreateTaskInput {
 
 pub struct CreateTask {
     task_repo: Arc<dyn TaskRepository>,
+    identity_provider: Arc<dyn IdentityProvider>,
+    record_events: Arc<RecordEvents>,
 }
 
 impl CreateTask {
-    pub fn new(task_repo: Arc<dyn TaskRepository>) -> Self {
-        Self { task_repo }
+    pub fn new(
+        task_repo: Arc<dyn TaskRepository>,
+        identity_provider: Arc<dyn IdentityProvider>,
+        record_events: Arc<RecordEvents>,
+    ) -> Self {
+        Self {
+            task_repo,
+            identity_provider,
+            record_events,
+        }
     }
 
     pub async fn execute(&self, input: CreateTaskInput) -> Result<Task, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::create(objects::TASK))
+            .await?;
         // Parse status from string or default to Todo
         let status = if let Some(status_str) = input.status {
             match status_str.as_str() {
@@ -51,6 +67,14 @@ impl CreateTask {
             workspace_id: input.workspace_id,
         };
 
-        self.task_repo.create(task).await
+        let task = self.task_repo.create(task).await?;
+        self.record_events
+            .publish(RecordEvent::created(
+                objects::TASK,
+                task.workspace_id,
+                &task,
+            ))
+            .await;
+        Ok(task)
     }
 }
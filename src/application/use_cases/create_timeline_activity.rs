@@ -1,4 +1,6 @@
+use crate::application::ports::identity::IdentityProvider;
 use crate::application::ports::output::TimelineActivityRepository;
+use crate::domain::permissions::{objects, Permission};
 use crate::domain::{DomainError, TimelineActivity};
 use chrono::Utc;
 use serde::Deserialize;
@@ -21,17 +23,27 @@ pub struct CreateTimelineActivityInput {
 
 pub struct CreateTimelineActivity {
     activity_repo: Arc<dyn TimelineActivityRepository>,
+    identity_provider: Arc<dyn IdentityProvider>,
 }
 
 impl CreateTimelineActivity {
-    pub fn new(activity_repo: Arc<dyn TimelineActivityRepository>) -> Self {
-        Self { activity_repo }
+    pub fn new(
+        activity_repo: Arc<dyn TimelineActivityRepository>,
+        identity_provider: Arc<dyn IdentityProvider>,
+    ) -> Self {
+        Self {
+            activity_repo,
+            identity_provider,
+        }
     }
 
     pub async fn execute(
         &self,
         input: CreateTimelineActivityInput,
     ) -> Result<TimelineActivity, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::create(objects::TIMELINE_ACTIVITY))
+            .await?;
         let activity = TimelineActivity {
             id: Uuid::new_v4(),
             created_at: Utc::now(),
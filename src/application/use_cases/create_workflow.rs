@@ -1,4 +1,6 @@
+use crate::application::ports::identity::IdentityProvider;
 use crate::application::ports::output::WorkflowRepository;
+use crate::domain::permissions::{objects, Permission};
 use crate::domain::{DomainError, Workflow};
 use chrono::Utc;
 use std::sync::Arc;
@@ -11,14 +13,24 @@ pub struct CreateWorkflowInput {
 
 pub struct CreateWorkflow {
     workflow_repo: Arc<dyn WorkflowRepository>,
+    identity_provider: Arc<dyn IdentityProvider>,
 }
 
 impl CreateWorkflow {
-    pub fn new(workflow_repo: Arc<dyn WorkflowRepository>) -> Self {
-        Self { workflow_repo }
+    pub fn new(
+        workflow_repo: Arc<dyn WorkflowRepository>,
+        identity_provider: Arc<dyn IdentityProvider>,
+    ) -> Self {
+        Self {
+            workflow_repo,
+            identity_provider,
+        }
     }
 
     pub async fn execute(&self, input: CreateWorkflowInput) -> Result<Workflow, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::create(objects::WORKFLOW))
+            .await?;
         let workflow = Workflow {
             id: Uuid::new_v4(),
             name: input.name,
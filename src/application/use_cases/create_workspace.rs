@@ -1,6 +1,8 @@
+use crate::application::context::{current_actor, Actor};
 use crate::application::ports::input::CreateWorkspaceUseCase;
 use crate::application::ports::output::WorkspaceRepository;
 use crate::domain::entities::{Workspace, WorkspaceMember};
+use crate::domain::permissions::Role;
 use crate::domain::states::WorkspaceState;
 use crate::domain::DomainError;
 use async_trait::async_trait;
@@ -21,6 +23,25 @@ impl CreateWorkspace {
 #[async_trait]
 impl CreateWorkspaceUseCase for CreateWorkspace {
     async fn execute(&self, user_id: Uuid, subdomain: String) -> Result<Workspace, DomainError> {
+        // Any signed-in user may create a workspace, but only for themselves
+        match current_actor() {
+            Some(Actor::System) => {}
+            Some(Actor::User(identity)) if identity.user.id == user_id => {}
+            _ => {
+                return Err(DomainError::Permission(
+                    "Cannot create a workspace for another user".to_string(),
+                ))
+            }
+        }
+
+        // Subdomains route requests by host, so they must be valid DNS labels
+        let subdomain = subdomain.trim().to_ascii_lowercase();
+        if !is_valid_subdomain(&subdomain) {
+            return Err(DomainError::Validation(
+                "Subdomain must be 1-63 lowercase letters, digits or hyphens".to_string(),
+            ));
+        }
+
         // 1. Check if subdomain exists
         if let Some(_) = self.workspace_repo.find_by_subdomain(&subdomain).await? {
             return Err(DomainError::Validation(
@@ -44,8 +65,9 @@ impl CreateWorkspaceUseCase for CreateWorkspace {
             id: Uuid::new_v4(),
             user_id,
             workspace_id: saved_workspace.id,
-            role: "Admin".to_string(),
+            role: Role::Admin.name().to_string(),
             name: "Admin".to_string(), // Placeholder name until we have user profile
+            suspended_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -55,3 +77,12 @@ impl CreateWorkspaceUseCase for CreateWorkspace {
         Ok(saved_workspace)
     }
 }
+
+fn is_valid_subdomain(subdomain: &str) -> bool {
+    (1..=63).contains(&subdomain.len())
+        && subdomain
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && !subdomain.starts_with('-')
+        && !subdomain.ends_with('-')
+}
@@ -0,0 +1,103 @@
+use crate::application::context::{current_actor, Actor};
+use crate::application::ports::output::ApiKeyRepository;
+use crate::domain::entities::{ApiKey, WorkspaceMember};
+use crate::domain::permissions::ApiKeyScope;
+use crate::domain::DomainError;
+use crate::shared::token;
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Characters of a token kept in `ApiKey.token_prefix` (the `oxi_` marker plus 8 more)
+const DISPLAY_PREFIX_LEN: usize = 12;
+
+/// Members' personal API keys. Each member manages only their own keys.
+pub struct ManageApiKeys {
+    api_key_repo: Arc<dyn ApiKeyRepository>,
+}
+
+impl ManageApiKeys {
+    pub fn new(api_key_repo: Arc<dyn ApiKeyRepository>) -> Self {
+        Self { api_key_repo }
+    }
+
+    /// Keys are managed from a signed-in session only, so a key can never mint or
+    /// widen another key
+    fn member() -> Result<WorkspaceMember, DomainError> {
+        match current_actor() {
+            Some(Actor::User(identity)) if identity.api_key.is_some() => Err(
+                DomainError::Permission("API keys cannot manage API keys".to_string()),
+            ),
+            Some(Actor::User(identity)) => identity.member.ok_or_else(|| {
+                DomainError::Permission("Not a member of any workspace".to_string())
+            }),
+            _ => Err(DomainError::Permission(
+                "Authentication required".to_string(),
+            )),
+        }
+    }
+
+    /// Creates a key for the current member. Returns the key together with the raw
+    /// token, which is shown once; only its hash is persisted.
+    pub async fn create(
+        &self,
+        name: String,
+        scope: ApiKeyScope,
+        objects: Option<Vec<String>>,
+    ) -> Result<(ApiKey, String), DomainError> {
+        let member = Self::member()?;
+
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(DomainError::Validation(
+                "API key name cannot be empty".to_string(),
+            ));
+        }
+        if let Some(objects) = &objects {
+            if objects.is_empty() || objects.iter().any(|o| o.trim().is_empty()) {
+                return Err(DomainError::Validation(
+                    "Object restrictions must name at least one object".to_string(),
+                ));
+            }
+        }
+
+        let raw_token = format!("{}{}", ApiKey::TOKEN_PREFIX, token::generate());
+        let now = Utc::now();
+        let api_key = ApiKey {
+            id: Uuid::new_v4(),
+            created_at: now,
+            updated_at: now,
+            workspace_id: member.workspace_id,
+            member_id: member.id,
+            name,
+            token_prefix: raw_token[..DISPLAY_PREFIX_LEN].to_string(),
+            token_hash: token::hash(&raw_token),
+            scope,
+            objects,
+            last_used_at: None,
+            revoked_at: None,
+        };
+        let api_key = self.api_key_repo.create(api_key).await?;
+
+        Ok((api_key, raw_token))
+    }
+
+    pub async fn list(&self) -> Result<Vec<ApiKey>, DomainError> {
+        let member = Self::member()?;
+        self.api_key_repo.find_by_member_id(member.id).await
+    }
+
+    pub async fn revoke(&self, id: Uuid) -> Result<ApiKey, DomainError> {
+        let member = Self::member()?;
+        let mut api_key = self
+            .api_key_repo
+            .find_by_id(id)
+            .await?
+            .filter(|k| k.member_id == member.id && k.revoked_at.is_none())
+            .ok_or(DomainError::NotFound)?;
+
+        api_key.revoked_at = Some(Utc::now());
+        api_key.updated_at = Utc::now();
+        self.api_key_repo.update(api_key).await
+    }
+}
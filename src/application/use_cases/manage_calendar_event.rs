@@ -1,26 +1,44 @@
+use crate::application::ports::identity::IdentityProvider;
 use crate::application::ports::output::CalendarEventRepository;
 use crate::domain::{CalendarEvent, DomainError};
+use crate::domain::permissions::{objects, Permission};
 use std::sync::Arc;
 use uuid::Uuid;
 
 pub struct ManageCalendarEvent {
     calendar_event_repo: Arc<dyn CalendarEventRepository>,
+    identity_provider: Arc<dyn IdentityProvider>,
 }
 
 impl ManageCalendarEvent {
-    pub fn new(calendar_event_repo: Arc<dyn CalendarEventRepository>) -> Self {
-        Self { calendar_event_repo }
+    pub fn new(
+        calendar_event_repo: Arc<dyn CalendarEventRepository>,
+        identity_provider: Arc<dyn IdentityProvider>,
+    ) -> Self {
+        Self {
+            calendar_event_repo,
+            identity_provider,
+        }
     }
 
     pub async fn get_all(&self) -> Result<Vec<CalendarEvent>, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::CALENDAR_EVENT))
+            .await?;
         self.calendar_event_repo.find_all().await
     }
 
     pub async fn get_by_id(&self, id: Uuid) -> Result<Option<CalendarEvent>, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::CALENDAR_EVENT))
+            .await?;
         self.calendar_event_repo.find_by_id(id).await
     }
 
     pub async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        self.identity_provider
+            .authorize(&Permission::delete(objects::CALENDAR_EVENT))
+            .await?;
         self.calendar_event_repo.delete(id).await
     }
 }
@@ -1,4 +1,8 @@
+use crate::application::events::record_events::{RecordEvent, RecordEvents};
+use crate::application::ports::identity::IdentityProvider;
 use crate::application::ports::output::CompanyRepository;
+use crate::application::ports::query::{ListQuery, Page};
+use crate::domain::permissions::{objects, Permission};
 use crate::domain::{Company, DomainError};
 use serde::Deserialize;
 use std::sync::Arc;
@@ -10,15 +14,43 @@ pub struct UpdateCompanyInput {
     pub domain_name: String,
     pub address: Option<String>,
     pub employees_count: Option<i32>,
+    /// Version the change is based on; a newer stored version is a conflict.
+    /// Without it the update is checked against the version just read.
+    pub expected_version: Option<i32>,
 }
 
 pub struct ManageCompany {
     company_repo: Arc<dyn CompanyRepository>,
+    identity_provider: Arc<dyn IdentityProvider>,
+    record_events: Arc<RecordEvents>,
 }
 
 impl ManageCompany {
-    pub fn new(company_repo: Arc<dyn CompanyRepository>) -> Self {
-        Self { company_repo }
+    pub fn new(
+        company_repo: Arc<dyn CompanyRepository>,
+        identity_provider: Arc<dyn IdentityProvider>,
+        record_events: Arc<RecordEvents>,
+    ) -> Self {
+        Self {
+            company_repo,
+            identity_provider,
+            record_events,
+        }
+    }
+
+    /// A page of the workspace's companies, see `ListQuery`
+    pub async fn list(&self, query: &ListQuery) -> Result<Page<Company>, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::COMPANY))
+            .await?;
+        self.company_repo.find_page(query).await
+    }
+
+    pub async fn get_all(&self) -> Result<Vec<Company>, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::COMPANY))
+            .await?;
+        self.company_repo.find_all().await
     }
 
     pub async fn update(
@@ -26,32 +58,71 @@ impl ManageCompany {
         id: Uuid,
         input: UpdateCompanyInput,
     ) -> Result<Company, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::update(objects::COMPANY))
+            .await?;
         let current = self.company_repo.find_by_id(id).await?;
         if let Some(mut company) = current {
+            let previous = company.clone();
             company.name = input.name;
             company.domain_name = input.domain_name;
             company.address = input.address;
             if let Some(count) = input.employees_count {
                 company.employees_count = count;
             }
-            self.company_repo.update(company).await
+            if let Some(version) = input.expected_version {
+                company.version = version;
+            }
+            let company = self.company_repo.update(company).await?;
+            self.record_events
+                .publish(RecordEvent::updated(
+                    objects::COMPANY,
+                    company.workspace_id,
+                    &previous,
+                    &company,
+                ))
+                .await;
+            Ok(company)
         } else {
             Err(DomainError::Validation("Company not found".to_string()))
         }
     }
 
     pub async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        self.company_repo.delete(id).await
+        self.identity_provider
+            .authorize(&Permission::delete(objects::COMPANY))
+            .await?;
+        let company = self
+            .company_repo
+            .find_by_id(id)
+            .await?
+            .ok_or(DomainError::NotFound)?;
+        self.company_repo.delete(id).await?;
+        self.record_events
+            .publish(RecordEvent::deleted(
+                objects::COMPANY,
+                company.workspace_id,
+                &company,
+            ))
+            .await;
+        Ok(())
     }
 }
 
 pub struct UpdateCompany {
     company_repo: Arc<dyn CompanyRepository>,
+    identity_provider: Arc<dyn IdentityProvider>,
 }
 
 impl UpdateCompany {
-    pub fn new(company_repo: Arc<dyn CompanyRepository>) -> Self {
-        Self { company_repo }
+    pub fn new(
+        company_repo: Arc<dyn CompanyRepository>,
+        identity_provider: Arc<dyn IdentityProvider>,
+    ) -> Self {
+        Self {
+            company_repo,
+            identity_provider,
+        }
     }
 
     pub async fn execute(
@@ -59,6 +130,9 @@ impl UpdateCompany {
         id: Uuid,
         input: UpdateCompanyInput,
     ) -> Result<Company, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::update(objects::COMPANY))
+            .await?;
         let current = self.company_repo.find_by_id(id).await?;
         if let Some(mut company) = current {
             company.name = input.name;
@@ -67,6 +141,9 @@ impl UpdateCompany {
             if let Some(count) = input.employees_count {
                 company.employees_count = count;
             }
+            if let Some(version) = input.expected_version {
+                company.version = version;
+            }
             // UpdatedAt is handled by Repo usually or Domain logic. Repo has logic to set updated_at.
 
             self.company_repo.update(company).await
@@ -78,14 +155,24 @@ impl UpdateCompany {
 
 pub struct DeleteCompany {
     company_repo: Arc<dyn CompanyRepository>,
+    identity_provider: Arc<dyn IdentityProvider>,
 }
 
 impl DeleteCompany {
-    pub fn new(company_repo: Arc<dyn CompanyRepository>) -> Self {
-        Self { company_repo }
+    pub fn new(
+        company_repo: Arc<dyn CompanyRepository>,
+        identity_provider: Arc<dyn IdentityProvider>,
+    ) -> Self {
+        Self {
+            company_repo,
+            identity_provider,
+        }
     }
 
     pub async fn execute(&self, id: Uuid) -> Result<(), DomainError> {
+        self.identity_provider
+            .authorize(&Permission::delete(objects::COMPANY))
+            .await?;
         self.company_repo.delete(id).await
     }
 }
@@ -1,5 +1,8 @@
+use crate::application::events::record_events::{custom_record, RecordEvent, RecordEvents};
+use crate::application::ports::identity::IdentityProvider;
 use crate::application::ports::output::{CustomObjectDataRepository, MetadataRepository};
 use crate::domain::custom_object_data::CustomObjectData;
+use crate::domain::permissions::{objects, Permission};
 use crate::domain::DomainError;
 use chrono::Utc;
 use std::sync::Arc;
@@ -8,16 +11,22 @@ use uuid::Uuid;
 pub struct ManageCustomObjectData {
     repo: Arc<dyn CustomObjectDataRepository>,
     metadata_repo: Arc<dyn MetadataRepository>,
+    identity_provider: Arc<dyn IdentityProvider>,
+    record_events: Arc<RecordEvents>,
 }
 
 impl ManageCustomObjectData {
     pub fn new(
         repo: Arc<dyn CustomObjectDataRepository>,
         metadata_repo: Arc<dyn MetadataRepository>,
+        identity_provider: Arc<dyn IdentityProvider>,
+        record_events: Arc<RecordEvents>,
     ) -> Self {
         Self {
             repo,
             metadata_repo,
+            identity_provider,
+            record_events,
         }
     }
 
@@ -27,6 +36,12 @@ impl ManageCustomObjectData {
         properties: serde_json::Value,
         workspace_id: Uuid,
     ) -> Result<CustomObjectData, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::create(objects::custom_object(
+                object_metadata_id,
+            )))
+            .await?;
+
         // Verify object exists
         let object = self
             .metadata_repo
@@ -44,39 +59,100 @@ impl ManageCustomObjectData {
             object_metadata_id,
             properties,
             workspace_id,
+            version: 1,
         };
 
-        self.repo.create(record).await
+        let record = self.repo.create(record).await?;
+        self.record_events
+            .publish(RecordEvent::created(
+                &objects::custom_object(object_metadata_id),
+                record.workspace_id,
+                &custom_record(&record),
+            ))
+            .await;
+        Ok(record)
     }
 
+    /// `expected_version` is the version the change is based on, see `CustomObjectData::version`
     pub async fn update_record(
         &self,
         id: Uuid,
         properties: serde_json::Value,
+        expected_version: Option<i32>,
     ) -> Result<CustomObjectData, DomainError> {
         let existing = self.repo.find_by_id(id).await?;
 
         if let Some(mut record) = existing {
+            self.identity_provider
+                .authorize(&Permission::update(objects::custom_object(
+                    record.object_metadata_id,
+                )))
+                .await?;
+            let previous = custom_record(&record);
             record.properties = properties;
             record.updated_at = Utc::now();
-            self.repo.update(record).await
+            if let Some(version) = expected_version {
+                record.version = version;
+            }
+            let record = self.repo.update(record).await?;
+            self.record_events
+                .publish(RecordEvent::updated(
+                    &objects::custom_object(record.object_metadata_id),
+                    record.workspace_id,
+                    &previous,
+                    &custom_record(&record),
+                ))
+                .await;
+            Ok(record)
         } else {
             Err(DomainError::Validation("Record not found".to_string()))
         }
     }
 
     pub async fn delete_record(&self, id: Uuid) -> Result<(), DomainError> {
-        self.repo.delete(id).await
+        // Permissions are per custom object, so the record is loaded first
+        let record = self
+            .repo
+            .find_by_id(id)
+            .await?
+            .ok_or(DomainError::NotFound)?;
+        self.identity_provider
+            .authorize(&Permission::delete(objects::custom_object(
+                record.object_metadata_id,
+            )))
+            .await?;
+        self.repo.delete(id).await?;
+        self.record_events
+            .publish(RecordEvent::deleted(
+                &objects::custom_object(record.object_metadata_id),
+                record.workspace_id,
+                &custom_record(&record),
+            ))
+            .await;
+        Ok(())
     }
 
     pub async fn get_record(&self, id: Uuid) -> Result<Option<CustomObjectData>, DomainError> {
-        self.repo.find_by_id(id).await
+        let record = self.repo.find_by_id(id).await?;
+        if let Some(record) = &record {
+            self.identity_provider
+                .authorize(&Permission::read(objects::custom_object(
+                    record.object_metadata_id,
+                )))
+                .await?;
+        }
+        Ok(record)
     }
 
     pub async fn list_records(
         &self,
         object_metadata_id: Uuid,
     ) -> Result<Vec<CustomObjectData>, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::custom_object(
+                object_metadata_id,
+            )))
+            .await?;
         self.repo
             .find_by_object_metadata_id(object_metadata_id)
             .await
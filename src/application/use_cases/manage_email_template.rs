@@ -1,5 +1,7 @@
+use crate::application::ports::identity::IdentityProvider;
 use crate::application::ports::output::EmailTemplateRepository;
 use crate::domain::{DomainError, EmailTemplate, HardGuard};
+use crate::domain::permissions::{objects, Permission};
 use chrono::Utc;
 use serde::Deserialize;
 use std::sync::Arc;
@@ -25,14 +27,28 @@ pub struct UpdateEmailTemplateInput {
 
 pub struct ManageEmailTemplate {
     email_template_repo: Arc<dyn EmailTemplateRepository>,
+    identity_provider: Arc<dyn IdentityProvider>,
 }
 
 impl ManageEmailTemplate {
-    pub fn new(email_template_repo: Arc<dyn EmailTemplateRepository>) -> Self {
-        Self { email_template_repo }
+    pub fn new(
+        email_template_repo: Arc<dyn EmailTemplateRepository>,
+        identity_provider: Arc<dyn IdentityProvider>,
+    ) -> Self {
+        Self {
+            email_template_repo,
+            identity_provider,
+        }
     }
 
-    pub async fn create(&self, input: CreateEmailTemplateInput) -> Result<EmailTemplate, DomainError> {
+    pub async fn create(
+        &self,
+        input: CreateEmailTemplateInput,
+        workspace_id: Uuid,
+    ) -> Result<EmailTemplate, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::create(objects::EMAIL_TEMPLATE))
+            .await?;
         let template = EmailTemplate {
             id: Uuid::new_v4(),
             created_at: Utc::now(),
@@ -42,6 +58,7 @@ impl ManageEmailTemplate {
             body_text: input.body_text,
             body_html: input.body_html,
             category: input.category.unwrap_or_else(|| "manual".to_string()),
+            workspace_id,
         };
 
         // Validate template
@@ -51,10 +68,16 @@ impl ManageEmailTemplate {
     }
 
     pub async fn list(&self) -> Result<Vec<EmailTemplate>, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::EMAIL_TEMPLATE))
+            .await?;
         self.email_template_repo.find_all().await
     }
 
     pub async fn get(&self, id: Uuid) -> Result<EmailTemplate, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::EMAIL_TEMPLATE))
+            .await?;
         self.email_template_repo
             .find_by_id(id)
             .await?
@@ -62,6 +85,9 @@ impl ManageEmailTemplate {
     }
 
     pub async fn get_by_name(&self, name: &str) -> Result<EmailTemplate, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::EMAIL_TEMPLATE))
+            .await?;
         self.email_template_repo
             .find_by_name(name)
             .await?
@@ -69,6 +95,9 @@ impl ManageEmailTemplate {
     }
 
     pub async fn update(&self, id: Uuid, input: UpdateEmailTemplateInput) -> Result<EmailTemplate, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::update(objects::EMAIL_TEMPLATE))
+            .await?;
         let mut template = self.get(id).await?;
 
         if let Some(name) = input.name {
@@ -96,6 +125,9 @@ impl ManageEmailTemplate {
     }
 
     pub async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        self.identity_provider
+            .authorize(&Permission::delete(objects::EMAIL_TEMPLATE))
+            .await?;
         self.email_template_repo.delete(id).await
     }
 }
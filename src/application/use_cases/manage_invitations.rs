@@ -0,0 +1,503 @@
+use crate::application::context::{self, current_actor, current_member, Actor};
+use crate::application::ports::identity::IdentityProvider;
+use crate::application::ports::output::{
+    InvitationRepository, RoleRepository, UserRepository, WorkspaceRepository,
+};
+use crate::application::ports::unit_of_work::UnitOfWork;
+use crate::application::use_cases::manage_roles::{ensure_can_grant, resolve_role};
+use crate::application::use_cases::register_user::RegisterUser;
+use crate::application::use_cases::send_email::{SendEmail, SendEmailInput};
+use crate::domain::permissions::{objects, Permission};
+use crate::domain::{DomainError, Invitation, WorkspaceMember};
+use crate::shared::token;
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub const INVITATION_TTL_DAYS: i64 = 7;
+
+pub struct ManageInvitations {
+    invitation_repo: Arc<dyn InvitationRepository>,
+    workspace_repo: Arc<dyn WorkspaceRepository>,
+    role_repo: Arc<dyn RoleRepository>,
+    user_repo: Arc<dyn UserRepository>,
+    register_user: Arc<RegisterUser>,
+    send_email: Arc<SendEmail>,
+    identity_provider: Arc<dyn IdentityProvider>,
+    unit_of_work: Arc<dyn UnitOfWork>,
+    /// Where the app is served, e.g. `https://oxicrm.app`; invitation links point there
+    app_url: String,
+}
+
+impl ManageInvitations {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        invitation_repo: Arc<dyn InvitationRepository>,
+        workspace_repo: Arc<dyn WorkspaceRepository>,
+        role_repo: Arc<dyn RoleRepository>,
+        user_repo: Arc<dyn UserRepository>,
+        register_user: Arc<RegisterUser>,
+        send_email: Arc<SendEmail>,
+        identity_provider: Arc<dyn IdentityProvider>,
+        unit_of_work: Arc<dyn UnitOfWork>,
+        app_url: String,
+    ) -> Self {
+        Self {
+            invitation_repo,
+            workspace_repo,
+            role_repo,
+            user_repo,
+            register_user,
+            send_email,
+            identity_provider,
+            unit_of_work,
+            app_url,
+        }
+    }
+
+    fn member() -> Result<WorkspaceMember, DomainError> {
+        current_member()
+            .ok_or_else(|| DomainError::Permission("Not a member of any workspace".to_string()))
+    }
+
+    /// Invites `email` to the current workspace and emails them the invitation token.
+    /// Re-inviting an address replaces its previous open invitation.
+    pub async fn invite(&self, email: String, role: String) -> Result<Invitation, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::create(objects::WORKSPACE_MEMBER))
+            .await?;
+        let inviter = Self::member()?;
+        let workspace_id = inviter.workspace_id;
+
+        let email = email.trim().to_string();
+        if !email.contains('@') {
+            return Err(DomainError::Validation(format!("Invalid email: {}", email)));
+        }
+        let role = resolve_role(self.role_repo.as_ref(), workspace_id, &role).await?;
+        ensure_can_grant(&role)?;
+
+        if let Some(user) = self.user_repo.find_by_email(&email).await? {
+            let memberships = self.workspace_repo.find_members_by_user_id(user.id).await?;
+            if memberships.iter().any(|m| m.workspace_id == workspace_id) {
+                return Err(DomainError::Validation(
+                    "User is already a member".to_string(),
+                ));
+            }
+        }
+
+        let now = Utc::now();
+        for mut previous in self.invitation_repo.find_open(workspace_id).await? {
+            if previous.email.eq_ignore_ascii_case(&email) {
+                previous.revoked_at = Some(now);
+                previous.updated_at = now;
+                self.invitation_repo.update(previous).await?;
+            }
+        }
+
+        let raw_token = token::generate();
+        let invitation = Invitation {
+            id: Uuid::new_v4(),
+            created_at: now,
+            updated_at: now,
+            workspace_id,
+            email,
+            role: role.name().to_string(),
+            invited_by: inviter.id,
+            token_hash: token::hash(&raw_token),
+            expires_at: now + Duration::days(INVITATION_TTL_DAYS),
+            accepted_at: None,
+            revoked_at: None,
+        };
+        let invitation = self.invitation_repo.create(invitation).await?;
+
+        // The invitation email is a system notification, not mail sent by the inviter
+        let email_input = SendEmailInput {
+            from_email: "noreply@oxicrm.com".to_string(),
+            to_email: invitation.email.clone(),
+            cc_emails: None,
+            bcc_emails: None,
+            subject: "You have been invited to join a workspace on OxiCRM".to_string(),
+            body_text: format!(
+                "{} invited you to join their workspace as {}.\n\n\
+                 Accept the invitation at {}/invitations/{}\n\n\
+                 This invitation expires on {}.",
+                inviter.name,
+                invitation.role,
+                self.app_url,
+                raw_token,
+                invitation.expires_at.format("%Y-%m-%d")
+            ),
+            body_html: None,
+            template_id: None,
+            template_variables: None,
+            person_id: None,
+            company_id: None,
+            opportunity_id: None,
+            task_id: None,
+            workflow_id: None,
+            workflow_run_id: None,
+            workspace_id,
+        };
+        context::run_as_system(self.send_email.execute(email_input)).await?;
+
+        Ok(invitation)
+    }
+
+    /// Invitations of the current workspace that can still be accepted
+    pub async fn list(&self) -> Result<Vec<Invitation>, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::WORKSPACE_MEMBER))
+            .await?;
+        let workspace_id = Self::member()?.workspace_id;
+
+        let now = Utc::now();
+        Ok(self
+            .invitation_repo
+            .find_open(workspace_id)
+            .await?
+            .into_iter()
+            .filter(|i| i.is_pending(now))
+            .collect())
+    }
+
+    pub async fn revoke(&self, id: Uuid) -> Result<Invitation, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::delete(objects::WORKSPACE_MEMBER))
+            .await?;
+        let workspace_id = Self::member()?.workspace_id;
+
+        let mut invitation = self
+            .invitation_repo
+            .find_by_id(id)
+            .await?
+            .filter(|i| i.workspace_id == workspace_id)
+            .ok_or(DomainError::NotFound)?;
+        if !invitation.is_pending(Utc::now()) {
+            return Err(DomainError::InvalidState(
+                "Invitation is no longer pending".to_string(),
+            ));
+        }
+
+        invitation.revoked_at = Some(Utc::now());
+        invitation.updated_at = Utc::now();
+        self.invitation_repo.update(invitation).await
+    }
+
+    /// The pending invitation behind an emailed token. Holding the token is the
+    /// authorization, so no permission is checked.
+    pub async fn find_pending(&self, raw_token: &str) -> Result<Invitation, DomainError> {
+        let invitation = self
+            .invitation_repo
+            .find_by_token_hash(&token::hash(raw_token))
+            .await?
+            .ok_or(DomainError::NotFound)?;
+        if invitation.accepted_at.is_some() || invitation.revoked_at.is_some() {
+            return Err(DomainError::NotFound);
+        }
+        if !invitation.is_pending(Utc::now()) {
+            return Err(DomainError::InvalidState(
+                "Invitation has expired".to_string(),
+            ));
+        }
+        Ok(invitation)
+    }
+
+    /// Joins the invited workspace. An existing account must be signed in to accept;
+    /// otherwise a new account is registered with `password`.
+    pub async fn accept(
+        &self,
+        raw_token: &str,
+        password: Option<String>,
+    ) -> Result<WorkspaceMember, DomainError> {
+        let invitation = self.find_pending(raw_token).await?;
+
+        let user = match self.user_repo.find_by_email(&invitation.email).await? {
+            Some(user) => match current_actor() {
+                Some(Actor::User(identity)) if identity.user.id == user.id => user,
+                _ => {
+                    return Err(DomainError::Permission(format!(
+                        "Log in as {} to accept this invitation",
+                        invitation.email
+                    )))
+                }
+            },
+            None => {
+                let password = password.filter(|p| !p.is_empty()).ok_or_else(|| {
+                    DomainError::Validation("Choose a password to create your account".to_string())
+                })?;
+                self.register_user
+                    .execute(invitation.email.clone(), password)
+                    .await?
+            }
+        };
+
+        let memberships = self.workspace_repo.find_members_by_user_id(user.id).await?;
+        if memberships
+            .iter()
+            .any(|m| m.workspace_id == invitation.workspace_id)
+        {
+            return Err(DomainError::Validation(
+                "You are already a member of this workspace".to_string(),
+            ));
+        }
+
+        let member = WorkspaceMember {
+            id: Uuid::new_v4(),
+            user_id: user.id,
+            workspace_id: invitation.workspace_id,
+            role: invitation.role.clone(),
+            name: user.email.clone(),
+            suspended_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        // Marking the invitation first lets only one of two concurrent accepts through
+        self.unit_of_work
+            .atomically(async {
+                if !self
+                    .invitation_repo
+                    .mark_accepted(invitation.id, Utc::now())
+                    .await?
+                {
+                    return Err(DomainError::InvalidState(
+                        "Invitation is no longer pending".to_string(),
+                    ));
+                }
+                self.workspace_repo.add_member(member).await
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::identity::Identity;
+    use crate::application::use_cases::testing::{
+        acting_as, custom_role, identity_provider, member, send_email, user, workspace,
+    };
+    use crate::application::use_cases::verify_email::VerifyEmail;
+    use crate::domain::permissions::Action;
+    use crate::infrastructure::email::MockEmailProvider;
+    use crate::infrastructure::identity::Argon2PasswordHasher;
+    use crate::infrastructure::persistence::in_memory_repo::InMemoryRepo;
+
+    fn invitations(
+        repo: &Arc<InMemoryRepo>,
+        email_provider: &MockEmailProvider,
+    ) -> ManageInvitations {
+        let register_user = RegisterUser {
+            user_repo: repo.clone(),
+            password_hasher: Arc::new(Argon2PasswordHasher::new()),
+            verify_email: Arc::new(VerifyEmail::new(
+                repo.clone(),
+                repo.clone(),
+                Arc::new(email_provider.clone()),
+                "https://oxicrm.test".to_string(),
+            )),
+        };
+        ManageInvitations::new(
+            repo.clone(),
+            repo.clone(),
+            repo.clone(),
+            repo.clone(),
+            Arc::new(register_user),
+            send_email(repo, email_provider),
+            identity_provider(repo),
+            repo.clone(),
+            "https://oxicrm.test".to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_only_admins_invite_admins() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let email_provider = MockEmailProvider::new();
+        let manage_invitations = invitations(&repo, &email_provider);
+        let a = workspace(&repo).await;
+        custom_role(
+            &repo,
+            a,
+            "Recruiter",
+            &[(objects::WORKSPACE_MEMBER, &[Action::Read, Action::Create])],
+        )
+        .await;
+        let grace = user(&repo, "grace@example.com").await;
+        let recruiter = member(&repo, a, &grace, "Recruiter").await;
+        let ada = user(&repo, "ada@example.com").await;
+        let admin = member(&repo, a, &ada, "Admin").await;
+
+        let invited = context::run_as(acting_as(&grace, &recruiter), async {
+            assert!(matches!(
+                manage_invitations
+                    .invite("alan@example.com".to_string(), "Admin".to_string())
+                    .await,
+                Err(DomainError::Permission(_))
+            ));
+            manage_invitations
+                .invite("alan@example.com".to_string(), "Member".to_string())
+                .await
+        })
+        .await;
+        assert_eq!(invited.unwrap().role, "Member");
+
+        let invited = context::run_as(
+            acting_as(&ada, &admin),
+            manage_invitations.invite("alan@example.com".to_string(), "Admin".to_string()),
+        )
+        .await;
+        assert_eq!(invited.unwrap().role, "Admin");
+        assert_eq!(email_provider.get_sent_emails().await.len(), 2);
+    }
+
+    /// The raw token of the last invitation link sent
+    async fn sent_token(email_provider: &MockEmailProvider) -> String {
+        let link = "https://oxicrm.test/invitations/";
+        let emails = email_provider.get_sent_emails().await;
+        let body = emails
+            .iter()
+            .rev()
+            .map(|email| email.body_text.as_str())
+            .find(|body| body.contains(link))
+            .unwrap();
+        body.split(link)
+            .nth(1)
+            .unwrap()
+            .split_whitespace()
+            .next()
+            .unwrap()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_invitees_join_with_the_invited_role() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let email_provider = MockEmailProvider::new();
+        let manage_invitations = invitations(&repo, &email_provider);
+        let a = workspace(&repo).await;
+        let ada = user(&repo, "ada@example.com").await;
+        let admin = member(&repo, a, &ada, "Admin").await;
+
+        let invitation = context::run_as(
+            acting_as(&ada, &admin),
+            manage_invitations.invite(" alan@example.com ".to_string(), "Member".to_string()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(invitation.email, "alan@example.com");
+        let raw_token = sent_token(&email_provider).await;
+        assert_eq!(
+            manage_invitations
+                .find_pending(&raw_token)
+                .await
+                .unwrap()
+                .id,
+            invitation.id
+        );
+
+        // New accounts need a password
+        assert!(matches!(
+            manage_invitations.accept(&raw_token, None).await,
+            Err(DomainError::Validation(_))
+        ));
+        let joined = manage_invitations
+            .accept(&raw_token, Some("a long password".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(joined.workspace_id, a);
+        assert_eq!(joined.role, "Member");
+        assert_eq!(joined.name, "alan@example.com");
+
+        let open = context::run_as(acting_as(&ada, &admin), manage_invitations.list()).await;
+        assert!(open.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_invitations_are_accepted_once() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let email_provider = MockEmailProvider::new();
+        let manage_invitations = invitations(&repo, &email_provider);
+        let a = workspace(&repo).await;
+        let ada = user(&repo, "ada@example.com").await;
+        let admin = member(&repo, a, &ada, "Admin").await;
+        let alan = user(&repo, "alan@example.com").await;
+
+        context::run_as(
+            acting_as(&ada, &admin),
+            manage_invitations.invite("alan@example.com".to_string(), "Member".to_string()),
+        )
+        .await
+        .unwrap();
+        let raw_token = sent_token(&email_provider).await;
+
+        // An existing account accepts signed in
+        assert!(matches!(
+            manage_invitations.accept(&raw_token, None).await,
+            Err(DomainError::Permission(_))
+        ));
+        let signed_in = Actor::User(Box::new(Identity {
+            user: alan.clone(),
+            member: None,
+            api_key: None,
+        }));
+        context::run_as(signed_in, async {
+            manage_invitations.accept(&raw_token, None).await.unwrap();
+            assert!(matches!(
+                manage_invitations.accept(&raw_token, None).await,
+                Err(DomainError::NotFound)
+            ));
+        })
+        .await;
+        let memberships = repo.find_members_by_user_id(alan.id).await.unwrap();
+        assert_eq!(memberships.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_expired_and_revoked_invitations_cannot_be_accepted() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let email_provider = MockEmailProvider::new();
+        let manage_invitations = invitations(&repo, &email_provider);
+        let a = workspace(&repo).await;
+        let ada = user(&repo, "ada@example.com").await;
+        let admin = member(&repo, a, &ada, "Admin").await;
+        let invite = |email: &str| {
+            context::run_as(
+                acting_as(&ada, &admin),
+                manage_invitations.invite(email.to_string(), "Member".to_string()),
+            )
+        };
+        let password = || Some("a long password".to_string());
+
+        let mut expired = invite("alan@example.com").await.unwrap();
+        let expired_token = sent_token(&email_provider).await;
+        expired.expires_at = Utc::now() - Duration::minutes(1);
+        InvitationRepository::update(repo.as_ref(), expired)
+            .await
+            .unwrap();
+        assert!(matches!(
+            manage_invitations.accept(&expired_token, password()).await,
+            Err(DomainError::InvalidState(_))
+        ));
+
+        let revoked = invite("grace@example.com").await.unwrap();
+        let revoked_token = sent_token(&email_provider).await;
+        context::run_as(
+            acting_as(&ada, &admin),
+            manage_invitations.revoke(revoked.id),
+        )
+        .await
+        .unwrap();
+        assert!(matches!(
+            manage_invitations.accept(&revoked_token, password()).await,
+            Err(DomainError::NotFound)
+        ));
+
+        // Neither invitee got an account or a membership
+        for email in ["alan@example.com", "grace@example.com"] {
+            assert!(UserRepository::find_by_email(repo.as_ref(), email)
+                .await
+                .unwrap()
+                .is_none());
+        }
+    }
+}
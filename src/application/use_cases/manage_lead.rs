@@ -1,4 +1,8 @@
+use crate::application::events::record_events::{RecordEvent, RecordEvents};
+use crate::application::ports::identity::IdentityProvider;
 use crate::application::ports::output::{LeadRepository, TimelineActivityRepository};
+use crate::application::ports::query::{ListQuery, Page};
+use crate::domain::permissions::{objects, Permission};
 use crate::domain::states::LeadStatus;
 use crate::domain::{DomainError, Lead, TimelineActivity};
 use chrono::Utc;
@@ -8,24 +12,36 @@ use uuid::Uuid;
 pub struct ManageLead {
     lead_repo: Arc<dyn LeadRepository>,
     timeline_repo: Arc<dyn TimelineActivityRepository>,
+    identity_provider: Arc<dyn IdentityProvider>,
+    record_events: Arc<RecordEvents>,
 }
 
 impl ManageLead {
     pub fn new(
         lead_repo: Arc<dyn LeadRepository>,
         timeline_repo: Arc<dyn TimelineActivityRepository>,
+        identity_provider: Arc<dyn IdentityProvider>,
+        record_events: Arc<RecordEvents>,
     ) -> Self {
         Self {
             lead_repo,
             timeline_repo,
+            identity_provider,
+            record_events,
         }
     }
 
-    pub async fn list(&self) -> Result<Vec<Lead>, DomainError> {
-        self.lead_repo.find_all().await
+    pub async fn list(&self, query: &ListQuery) -> Result<Page<Lead>, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::LEAD))
+            .await?;
+        self.lead_repo.find_page(query).await
     }
 
     pub async fn get(&self, id: Uuid) -> Result<Lead, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::LEAD))
+            .await?;
         self.lead_repo
             .find_by_id(id)
             .await?
@@ -33,7 +49,11 @@ impl ManageLead {
     }
 
     pub async fn update_status(&self, id: Uuid, status: LeadStatus) -> Result<Lead, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::update(objects::LEAD))
+            .await?;
         let mut lead = self.get(id).await?;
+        let previous = lead.clone();
 
         // Don't allow status change if converted
         if lead.is_converted() && status != LeadStatus::Converted {
@@ -50,6 +70,14 @@ impl ManageLead {
         }
 
         let lead = self.lead_repo.update(lead).await?;
+        self.record_events
+            .publish(RecordEvent::updated(
+                objects::LEAD,
+                lead.workspace_id,
+                &previous,
+                &lead,
+            ))
+            .await;
 
         // Create timeline activity
         let activity = TimelineActivity {
@@ -72,13 +100,42 @@ impl ManageLead {
     }
 
     pub async fn assign(&self, id: Uuid, assigned_to_id: Uuid) -> Result<Lead, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::update(objects::LEAD))
+            .await?;
         let mut lead = self.get(id).await?;
+        let previous = lead.clone();
         lead.assigned_to_id = Some(assigned_to_id);
         lead.updated_at = Utc::now();
-        self.lead_repo.update(lead).await
+        let lead = self.lead_repo.update(lead).await?;
+        self.record_events
+            .publish(RecordEvent::updated(
+                objects::LEAD,
+                lead.workspace_id,
+                &previous,
+                &lead,
+            ))
+            .await;
+        Ok(lead)
     }
 
     pub async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        self.lead_repo.delete(id).await
+        self.identity_provider
+            .authorize(&Permission::delete(objects::LEAD))
+            .await?;
+        let lead = self
+            .lead_repo
+            .find_by_id(id)
+            .await?
+            .ok_or(DomainError::NotFound)?;
+        self.lead_repo.delete(id).await?;
+        self.record_events
+            .publish(RecordEvent::deleted(
+                objects::LEAD,
+                lead.workspace_id,
+                &lead,
+            ))
+            .await;
+        Ok(())
     }
 }
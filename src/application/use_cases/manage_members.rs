@@ -0,0 +1,208 @@
+use crate::application::context::current_member;
+use crate::application::ports::identity::IdentityProvider;
+use crate::application::ports::output::WorkspaceRepository;
+use crate::domain::permissions::{objects, Permission, Role};
+use crate::domain::{DomainError, WorkspaceMember};
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct ManageMembers {
+    workspace_repo: Arc<dyn WorkspaceRepository>,
+    identity_provider: Arc<dyn IdentityProvider>,
+}
+
+impl ManageMembers {
+    pub fn new(
+        workspace_repo: Arc<dyn WorkspaceRepository>,
+        identity_provider: Arc<dyn IdentityProvider>,
+    ) -> Self {
+        Self {
+            workspace_repo,
+            identity_provider,
+        }
+    }
+
+    fn workspace_id() -> Result<Uuid, DomainError> {
+        current_member()
+            .map(|m| m.workspace_id)
+            .ok_or_else(|| DomainError::Permission("Not a member of any workspace".to_string()))
+    }
+
+    pub async fn list(&self) -> Result<Vec<WorkspaceMember>, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::WORKSPACE_MEMBER))
+            .await?;
+        self.workspace_repo
+            .find_members(Self::workspace_id()?)
+            .await
+    }
+
+    /// Removes a member from the current workspace. The last admin cannot be removed,
+    /// so a workspace always keeps someone able to manage it.
+    pub async fn remove(&self, member_id: Uuid) -> Result<(), DomainError> {
+        self.identity_provider
+            .authorize(&Permission::delete(objects::WORKSPACE_MEMBER))
+            .await?;
+        let members = self
+            .workspace_repo
+            .find_members(Self::workspace_id()?)
+            .await?;
+        let member = members
+            .iter()
+            .find(|m| m.id == member_id)
+            .ok_or(DomainError::NotFound)?;
+        if is_last_admin(&members, member) {
+            return Err(DomainError::InvalidState(
+                "Cannot remove the last admin of a workspace".to_string(),
+            ));
+        }
+
+        self.workspace_repo.remove_member(member_id).await
+    }
+
+    /// Blocks a member from the current workspace until reactivated. The member keeps
+    /// their account and any other workspace.
+    pub async fn suspend(&self, member_id: Uuid) -> Result<WorkspaceMember, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::update(objects::WORKSPACE_MEMBER))
+            .await?;
+        let members = self
+            .workspace_repo
+            .find_members(Self::workspace_id()?)
+            .await?;
+        let member = members
+            .iter()
+            .find(|m| m.id == member_id)
+            .ok_or(DomainError::NotFound)?;
+        if current_member().is_some_and(|m| m.id == member.id) {
+            return Err(DomainError::Validation(
+                "You cannot suspend yourself".to_string(),
+            ));
+        }
+        if member.suspended_at.is_some() {
+            return Err(DomainError::InvalidState(
+                "Member is already suspended".to_string(),
+            ));
+        }
+        if is_last_admin(&members, member) {
+            return Err(DomainError::InvalidState(
+                "Cannot suspend the last admin of a workspace".to_string(),
+            ));
+        }
+
+        let now = Utc::now();
+        let member = WorkspaceMember {
+            suspended_at: Some(now),
+            updated_at: now,
+            ..member.clone()
+        };
+        self.workspace_repo.update_member(member).await
+    }
+
+    pub async fn reactivate(&self, member_id: Uuid) -> Result<WorkspaceMember, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::update(objects::WORKSPACE_MEMBER))
+            .await?;
+        let workspace_id = Self::workspace_id()?;
+        let mut member = self
+            .workspace_repo
+            .find_member_by_id(member_id)
+            .await?
+            .filter(|m| m.workspace_id == workspace_id)
+            .ok_or(DomainError::NotFound)?;
+        if member.suspended_at.is_none() {
+            return Err(DomainError::InvalidState(
+                "Member is not suspended".to_string(),
+            ));
+        }
+
+        member.suspended_at = None;
+        member.updated_at = Utc::now();
+        self.workspace_repo.update_member(member).await
+    }
+}
+
+/// Whether `member` is the only admin of `members` who is not suspended, i.e. the only one
+/// left able to manage the workspace
+pub fn is_last_admin(members: &[WorkspaceMember], member: &WorkspaceMember) -> bool {
+    let is_active_admin =
+        |m: &WorkspaceMember| m.suspended_at.is_none() && Role::parse(&m.role) == Role::Admin;
+    is_active_admin(member)
+        && !members
+            .iter()
+            .any(|m| m.id != member.id && is_active_admin(m))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::context::run_as;
+    use crate::application::ports::identity::Credential;
+    use crate::application::use_cases::testing::{
+        acting_as, identity_provider, member, session, user, workspace,
+    };
+    use crate::infrastructure::persistence::in_memory_repo::InMemoryRepo;
+
+    #[tokio::test]
+    async fn test_suspension_only_applies_to_its_workspace() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let identity_provider = identity_provider(&repo);
+        let manage_members = ManageMembers::new(repo.clone(), identity_provider.clone());
+        let (a, b) = (workspace(&repo).await, workspace(&repo).await);
+
+        let (admin_a, admin_b) = (
+            user(&repo, "a@example.com").await,
+            user(&repo, "b@example.com").await,
+        );
+        let admin_of_a = member(&repo, a, &admin_a, "Admin").await;
+        let admin_of_b = member(&repo, b, &admin_b, "Admin").await;
+        let ada = user(&repo, "ada@example.com").await;
+        let ada_in_a = member(&repo, a, &ada, "Member").await;
+        member(&repo, b, &ada, "Member").await;
+        let credential = Credential::Session(session(&repo, &ada).await);
+
+        let suspended = run_as(
+            acting_as(&admin_a, &admin_of_a),
+            manage_members.suspend(ada_in_a.id),
+        )
+        .await
+        .unwrap();
+        assert!(suspended.suspended_at.is_some());
+
+        let in_a = identity_provider
+            .authenticate(&credential, Some(a))
+            .await
+            .unwrap();
+        assert!(in_a.unwrap().member.is_none());
+        let in_b = identity_provider
+            .authenticate(&credential, Some(b))
+            .await
+            .unwrap();
+        assert_eq!(in_b.unwrap().member.unwrap().workspace_id, b);
+
+        // Admins of another workspace cannot lift the suspension
+        assert!(matches!(
+            run_as(
+                acting_as(&admin_b, &admin_of_b),
+                manage_members.reactivate(ada_in_a.id)
+            )
+            .await,
+            Err(DomainError::NotFound)
+        ));
+
+        run_as(acting_as(&admin_a, &admin_of_a), async {
+            assert!(matches!(
+                manage_members.suspend(admin_of_a.id).await,
+                Err(DomainError::Validation(_))
+            ));
+            manage_members.reactivate(ada_in_a.id).await.unwrap();
+        })
+        .await;
+        let in_a = identity_provider
+            .authenticate(&credential, Some(a))
+            .await
+            .unwrap();
+        assert_eq!(in_a.unwrap().member.unwrap().id, ada_in_a.id);
+    }
+}
@@ -1,21 +1,33 @@
+use crate::application::ports::identity::IdentityProvider;
 use crate::application::ports::output::MetadataRepository;
 use crate::domain::metadata::{FieldMetadata, ObjectMetadata};
+use crate::domain::permissions::{objects, Permission};
 use crate::domain::DomainError;
 use std::sync::Arc;
 use uuid::Uuid;
 
 pub struct ManageMetadata {
     metadata_repo: Arc<dyn MetadataRepository>,
+    identity_provider: Arc<dyn IdentityProvider>,
 }
 
 impl ManageMetadata {
-    pub fn new(metadata_repo: Arc<dyn MetadataRepository>) -> Self {
-        Self { metadata_repo }
+    pub fn new(
+        metadata_repo: Arc<dyn MetadataRepository>,
+        identity_provider: Arc<dyn IdentityProvider>,
+    ) -> Self {
+        Self {
+            metadata_repo,
+            identity_provider,
+        }
     }
 
     pub async fn get_schema(
         &self,
     ) -> Result<Vec<(ObjectMetadata, Vec<FieldMetadata>)>, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::OBJECT_METADATA))
+            .await?;
         self.metadata_repo.get_schema().await
     }
 
@@ -26,6 +38,9 @@ impl ManageMetadata {
         description: Option<String>,
         workspace_id: Uuid,
     ) -> Result<ObjectMetadata, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::create(objects::OBJECT_METADATA))
+            .await?;
         // Check for existing
         if (self
             .metadata_repo
@@ -59,6 +74,9 @@ impl ManageMetadata {
         field_type: crate::domain::metadata::FieldType,
         settings: Option<serde_json::Value>,
     ) -> Result<FieldMetadata, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::create(objects::OBJECT_METADATA))
+            .await?;
         let field = FieldMetadata {
             id: Uuid::new_v4(),
             created_at: chrono::Utc::now(),
@@ -1,4 +1,7 @@
+use crate::application::events::record_events::{RecordEvent, RecordEvents};
+use crate::application::ports::identity::IdentityProvider;
 use crate::application::ports::output::NoteRepository;
+use crate::domain::permissions::{objects, Permission};
 use crate::domain::{DomainError, Note};
 use serde::Deserialize;
 use std::sync::Arc;
@@ -13,28 +16,48 @@ pub struct UpdateNoteInput {
 
 pub struct ManageNote {
     note_repo: Arc<dyn NoteRepository>,
+    identity_provider: Arc<dyn IdentityProvider>,
+    record_events: Arc<RecordEvents>,
 }
 
 impl ManageNote {
-    pub fn new(note_repo: Arc<dyn NoteRepository>) -> Self {
-        Self { note_repo }
+    pub fn new(
+        note_repo: Arc<dyn NoteRepository>,
+        identity_provider: Arc<dyn IdentityProvider>,
+        record_events: Arc<RecordEvents>,
+    ) -> Self {
+        Self {
+            note_repo,
+            identity_provider,
+            record_events,
+        }
     }
 
     pub async fn get_all(&self) -> Result<Vec<Note>, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::NOTE))
+            .await?;
         self.note_repo.find_all().await
     }
 
     pub async fn get_by_id(&self, id: Uuid) -> Result<Option<Note>, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::NOTE))
+            .await?;
         self.note_repo.find_by_id(id).await
     }
 
     pub async fn update(&self, input: UpdateNoteInput) -> Result<Note, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::update(objects::NOTE))
+            .await?;
         // First fetch the existing note
         let existing = self
             .note_repo
             .find_by_id(input.id)
             .await?
             .ok_or(DomainError::NotFound)?;
+        let previous = existing.clone();
 
         // Validate title if provided (INV-INT-002)
         if let Some(ref title) = input.title {
@@ -55,10 +78,35 @@ impl ManageNote {
             workspace_id: existing.workspace_id,
         };
 
-        self.note_repo.update(updated).await
+        let updated = self.note_repo.update(updated).await?;
+        self.record_events
+            .publish(RecordEvent::updated(
+                objects::NOTE,
+                updated.workspace_id,
+                &previous,
+                &updated,
+            ))
+            .await;
+        Ok(updated)
     }
 
     pub async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        self.note_repo.delete(id).await
+        self.identity_provider
+            .authorize(&Permission::delete(objects::NOTE))
+            .await?;
+        let note = self
+            .note_repo
+            .find_by_id(id)
+            .await?
+            .ok_or(DomainError::NotFound)?;
+        self.note_repo.delete(id).await?;
+        self.record_events
+            .publish(RecordEvent::deleted(
+                objects::NOTE,
+                note.workspace_id,
+                &note,
+            ))
+            .await;
+        Ok(())
     }
 }
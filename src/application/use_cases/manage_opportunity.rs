@@ -1,4 +1,7 @@
+use crate::application::events::record_events::{RecordEvent, RecordEvents};
+use crate::application::ports::identity::IdentityProvider;
 use crate::application::ports::output::OpportunityRepository;
+use crate::domain::permissions::{objects, Permission};
 use crate::domain::{DomainError, Opportunity, OpportunityStage};
 use serde::Deserialize;
 use std::sync::Arc;
@@ -15,32 +18,55 @@ pub struct UpdateOpportunityInput {
     pub company_id: Option<Uuid>,
     pub point_of_contact_id: Option<Uuid>,
     pub owner_id: Option<Uuid>,
+    /// Version the change is based on; a newer stored version is a conflict.
+    /// Without it the update is checked against the version just read.
+    pub expected_version: Option<i32>,
 }
 
 pub struct ManageOpportunity {
     opportunity_repo: Arc<dyn OpportunityRepository>,
+    identity_provider: Arc<dyn IdentityProvider>,
+    record_events: Arc<RecordEvents>,
 }
 
 impl ManageOpportunity {
-    pub fn new(opportunity_repo: Arc<dyn OpportunityRepository>) -> Self {
-        Self { opportunity_repo }
+    pub fn new(
+        opportunity_repo: Arc<dyn OpportunityRepository>,
+        identity_provider: Arc<dyn IdentityProvider>,
+        record_events: Arc<RecordEvents>,
+    ) -> Self {
+        Self {
+            opportunity_repo,
+            identity_provider,
+            record_events,
+        }
     }
 
     pub async fn get_all(&self) -> Result<Vec<Opportunity>, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::OPPORTUNITY))
+            .await?;
         self.opportunity_repo.find_all().await
     }
 
     pub async fn get_by_id(&self, id: Uuid) -> Result<Option<Opportunity>, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::OPPORTUNITY))
+            .await?;
         self.opportunity_repo.find_by_id(id).await
     }
 
     pub async fn update(&self, input: UpdateOpportunityInput) -> Result<Opportunity, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::update(objects::OPPORTUNITY))
+            .await?;
         // First fetch the existing opportunity
         let existing = self
             .opportunity_repo
             .find_by_id(input.id)
             .await?
             .ok_or(DomainError::NotFound)?;
+        let previous = existing.clone();
 
         // Parse stage if provided
         let stage = if let Some(stage_str) = input.stage {
@@ -72,12 +98,38 @@ impl ManageOpportunity {
             owner_id: input.owner_id.or(existing.owner_id),
             position: existing.position,
             workspace_id: existing.workspace_id,
+            version: input.expected_version.unwrap_or(existing.version),
         };
 
-        self.opportunity_repo.update(updated).await
+        let updated = self.opportunity_repo.update(updated).await?;
+        self.record_events
+            .publish(RecordEvent::updated(
+                objects::OPPORTUNITY,
+                updated.workspace_id,
+                &previous,
+                &updated,
+            ))
+            .await;
+        Ok(updated)
     }
 
     pub async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        self.opportunity_repo.delete(id).await
+        self.identity_provider
+            .authorize(&Permission::delete(objects::OPPORTUNITY))
+            .await?;
+        let opportunity = self
+            .opportunity_repo
+            .find_by_id(id)
+            .await?
+            .ok_or(DomainError::NotFound)?;
+        self.opportunity_repo.delete(id).await?;
+        self.record_events
+            .publish(RecordEvent::deleted(
+                objects::OPPORTUNITY,
+                opportunity.workspace_id,
+                &opportunity,
+            ))
+            .await;
+        Ok(())
     }
 }
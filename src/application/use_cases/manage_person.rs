@@ -1,18 +1,112 @@
+use crate::application::events::record_events::{RecordEvent, RecordEvents};
+use crate::application::ports::identity::IdentityProvider;
 use crate::application::ports::output::PersonRepository;
-use crate::domain::DomainError;
+use crate::application::ports::query::{ListQuery, Page};
+use crate::domain::permissions::{objects, Permission};
+use crate::domain::{DomainError, HardGuard, Person};
+use serde::Deserialize;
 use std::sync::Arc;
 use uuid::Uuid;
 
+#[derive(Deserialize)]
+pub struct UpdatePersonInput {
+    pub id: Uuid,
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub position: Option<i32>,
+    pub company_id: Option<Uuid>,
+}
+
 pub struct ManagePerson {
     person_repo: Arc<dyn PersonRepository>,
+    identity_provider: Arc<dyn IdentityProvider>,
+    record_events: Arc<RecordEvents>,
 }
 
 impl ManagePerson {
-    pub fn new(person_repo: Arc<dyn PersonRepository>) -> Self {
-        Self { person_repo }
+    pub fn new(
+        person_repo: Arc<dyn PersonRepository>,
+        identity_provider: Arc<dyn IdentityProvider>,
+        record_events: Arc<RecordEvents>,
+    ) -> Self {
+        Self {
+            person_repo,
+            identity_provider,
+            record_events,
+        }
+    }
+
+    /// A page of the workspace's people, see `ListQuery`
+    pub async fn list(&self, query: &ListQuery) -> Result<Page<Person>, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::PERSON))
+            .await?;
+        self.person_repo.find_page(query).await
+    }
+
+    pub async fn get_all(&self) -> Result<Vec<Person>, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::PERSON))
+            .await?;
+        self.person_repo.find_all().await
+    }
+
+    pub async fn update(&self, input: UpdatePersonInput) -> Result<Person, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::update(objects::PERSON))
+            .await?;
+        let existing = self
+            .person_repo
+            .find_by_id(input.id)
+            .await?
+            .ok_or(DomainError::NotFound)?;
+        let previous = existing.clone();
+
+        if let Some(email) = &input.email {
+            let taken = self.person_repo.find_by_email(email).await?;
+            if taken.is_some_and(|p| p.id != existing.id) {
+                return Err(DomainError::Validation("Email already exists".to_string()));
+            }
+        }
+
+        let updated = Person {
+            name: input.name.unwrap_or(existing.name),
+            email: input.email.unwrap_or(existing.email),
+            position: input.position.unwrap_or(existing.position),
+            company_id: input.company_id.or(existing.company_id),
+            ..existing
+        };
+        updated.validate()?;
+
+        let updated = self.person_repo.update(updated).await?;
+        self.record_events
+            .publish(RecordEvent::updated(
+                objects::PERSON,
+                updated.workspace_id,
+                &previous,
+                &updated,
+            ))
+            .await;
+        Ok(updated)
     }
 
     pub async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        self.person_repo.delete(id).await
+        self.identity_provider
+            .authorize(&Permission::delete(objects::PERSON))
+            .await?;
+        let person = self
+            .person_repo
+            .find_by_id(id)
+            .await?
+            .ok_or(DomainError::NotFound)?;
+        self.person_repo.delete(id).await?;
+        self.record_events
+            .publish(RecordEvent::deleted(
+                objects::PERSON,
+                person.workspace_id,
+                &person,
+            ))
+            .await;
+        Ok(())
     }
 }
@@ -0,0 +1,210 @@
+use crate::application::context::{current_member, current_workspace_id};
+use crate::application::ports::identity::IdentityProvider;
+use crate::application::ports::output::{RoleRepository, WorkspaceRepository};
+use crate::application::use_cases::manage_members::is_last_admin;
+use crate::domain::permissions::{objects, Action, Permission, Role, RoleDefinition};
+use crate::domain::{DomainError, WorkspaceMember};
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct ManageRoles {
+    role_repo: Arc<dyn RoleRepository>,
+    workspace_repo: Arc<dyn WorkspaceRepository>,
+    identity_provider: Arc<dyn IdentityProvider>,
+}
+
+impl ManageRoles {
+    pub fn new(
+        role_repo: Arc<dyn RoleRepository>,
+        workspace_repo: Arc<dyn WorkspaceRepository>,
+        identity_provider: Arc<dyn IdentityProvider>,
+    ) -> Self {
+        Self {
+            role_repo,
+            workspace_repo,
+            identity_provider,
+        }
+    }
+
+    fn workspace_id() -> Result<Uuid, DomainError> {
+        current_workspace_id()
+            .ok_or_else(|| DomainError::Permission("Not a member of any workspace".to_string()))
+    }
+
+    /// Custom roles of the current workspace (built-in roles are implicit)
+    pub async fn list(&self) -> Result<Vec<RoleDefinition>, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::WORKSPACE_MEMBER))
+            .await?;
+        self.role_repo.find_all(Self::workspace_id()?).await
+    }
+
+    pub async fn create(
+        &self,
+        name: String,
+        permissions: HashMap<String, Vec<Action>>,
+    ) -> Result<RoleDefinition, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::create(objects::WORKSPACE_MEMBER))
+            .await?;
+        let workspace_id = Self::workspace_id()?;
+
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(DomainError::Validation("Role name is required".to_string()));
+        }
+        if !matches!(Role::parse(&name), Role::Custom(_)) {
+            return Err(DomainError::Validation(format!(
+                "{} is a built-in role",
+                name
+            )));
+        }
+        if self
+            .role_repo
+            .find_by_name(workspace_id, &name)
+            .await?
+            .is_some()
+        {
+            return Err(DomainError::Validation("Role already exists".to_string()));
+        }
+
+        let role = RoleDefinition {
+            id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            workspace_id,
+            name,
+            permissions,
+        };
+
+        self.role_repo.create(role).await
+    }
+
+    pub async fn assign(
+        &self,
+        member_id: Uuid,
+        role_name: String,
+    ) -> Result<WorkspaceMember, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::update(objects::WORKSPACE_MEMBER))
+            .await?;
+        let workspace_id = Self::workspace_id()?;
+
+        let members = self.workspace_repo.find_members(workspace_id).await?;
+        let mut member = members
+            .iter()
+            .find(|m| m.id == member_id)
+            .cloned()
+            .ok_or(DomainError::NotFound)?;
+
+        let role = resolve_role(self.role_repo.as_ref(), workspace_id, &role_name).await?;
+        ensure_can_grant(&role)?;
+        if role != Role::Admin && is_last_admin(&members, &member) {
+            return Err(DomainError::InvalidState(
+                "Cannot demote the last admin of a workspace".to_string(),
+            ));
+        }
+
+        member.role = role.name().to_string();
+        member.updated_at = Utc::now();
+        self.workspace_repo.update_member(member).await
+    }
+}
+
+/// Parses `role_name`, failing for custom roles that are not defined in the workspace
+pub async fn resolve_role(
+    role_repo: &dyn RoleRepository,
+    workspace_id: Uuid,
+    role_name: &str,
+) -> Result<Role, DomainError> {
+    let role = Role::parse(role_name);
+    if let Role::Custom(name) = &role {
+        if role_repo.find_by_name(workspace_id, name).await?.is_none() {
+            return Err(DomainError::Validation(format!("Unknown role: {}", name)));
+        }
+    }
+    Ok(role)
+}
+
+/// Only admins hand out the Admin role, so that a custom role allowed to manage members
+/// cannot raise anyone, itself included, above its own permissions
+pub fn ensure_can_grant(role: &Role) -> Result<(), DomainError> {
+    let is_admin = current_member().is_some_and(|m| Role::parse(&m.role) == Role::Admin);
+    if *role == Role::Admin && !is_admin {
+        return Err(DomainError::Permission(
+            "Only admins can grant the Admin role".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::context::run_as;
+    use crate::application::use_cases::testing::{
+        acting_as, custom_role, identity_provider, member, user, workspace,
+    };
+    use crate::infrastructure::persistence::in_memory_repo::InMemoryRepo;
+
+    #[tokio::test]
+    async fn test_the_last_admin_cannot_be_demoted() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let manage_roles = ManageRoles::new(repo.clone(), repo.clone(), identity_provider(&repo));
+        let a = workspace(&repo).await;
+        let ada = user(&repo, "ada@example.com").await;
+        let admin = member(&repo, a, &ada, "Admin").await;
+        let grace = user(&repo, "grace@example.com").await;
+        let other = member(&repo, a, &grace, "Member").await;
+
+        run_as(acting_as(&ada, &admin), async {
+            assert!(matches!(
+                manage_roles.assign(admin.id, "Member".to_string()).await,
+                Err(DomainError::InvalidState(_))
+            ));
+
+            // Once someone else is admin, the role can be handed over
+            manage_roles
+                .assign(other.id, "Admin".to_string())
+                .await
+                .unwrap();
+            let demoted = manage_roles.assign(admin.id, "Member".to_string()).await;
+            assert_eq!(demoted.unwrap().role, "Member");
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_only_admins_grant_the_admin_role() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let manage_roles = ManageRoles::new(repo.clone(), repo.clone(), identity_provider(&repo));
+        let a = workspace(&repo).await;
+        let ada = user(&repo, "ada@example.com").await;
+        member(&repo, a, &ada, "Admin").await;
+        custom_role(
+            &repo,
+            a,
+            "Recruiter",
+            &[(objects::WORKSPACE_MEMBER, &[Action::Read, Action::Update])],
+        )
+        .await;
+        let grace = user(&repo, "grace@example.com").await;
+        let recruiter = member(&repo, a, &grace, "Recruiter").await;
+        let alan = user(&repo, "alan@example.com").await;
+        let other = member(&repo, a, &alan, "ReadOnly").await;
+
+        run_as(acting_as(&grace, &recruiter), async {
+            for member_id in [recruiter.id, other.id] {
+                assert!(matches!(
+                    manage_roles.assign(member_id, "Admin".to_string()).await,
+                    Err(DomainError::Permission(_))
+                ));
+            }
+            let promoted = manage_roles.assign(other.id, "Member".to_string()).await;
+            assert_eq!(promoted.unwrap().role, "Member");
+        })
+        .await;
+    }
+}
@@ -0,0 +1,145 @@
+use crate::application::ports::identity::PasswordHasher;
+use crate::application::ports::output::{SessionRepository, UserRepository};
+use crate::domain::{entities::Session, entities::User, states::UserState, DomainError};
+use crate::shared::token;
+use chrono::{Duration, Utc};
+use std::sync::{Arc, OnceLock};
+use uuid::Uuid;
+
+pub const SESSION_TTL_DAYS: i64 = 30;
+
+pub struct ManageSession {
+    user_repo: Arc<dyn UserRepository>,
+    session_repo: Arc<dyn SessionRepository>,
+    password_hasher: Arc<dyn PasswordHasher>,
+    /// Hash of a random password, verified against when the email has no account
+    dummy_hash: OnceLock<String>,
+}
+
+impl ManageSession {
+    pub fn new(
+        user_repo: Arc<dyn UserRepository>,
+        session_repo: Arc<dyn SessionRepository>,
+        password_hasher: Arc<dyn PasswordHasher>,
+    ) -> Self {
+        Self {
+            user_repo,
+            session_repo,
+            password_hasher,
+            dummy_hash: OnceLock::new(),
+        }
+    }
+
+    /// Verifies the credentials and opens a new session.
+    /// Returns the user together with the raw session token; only its hash is persisted.
+    pub async fn login(&self, email: &str, password: &str) -> Result<(User, String), DomainError> {
+        // Same error for unknown email and wrong password to avoid account enumeration
+        let invalid = || DomainError::Validation("Invalid email or password".to_string());
+
+        let Some(user) = self.user_repo.find_by_email(email).await? else {
+            // Takes as long as a wrong password, so that timing does not reveal the accounts
+            let _ = self.password_hasher.verify(password, self.dummy_hash());
+            return Err(invalid());
+        };
+
+        // Legacy or malformed hashes are treated as a failed login
+        let verified = self
+            .password_hasher
+            .verify(password, &user.password_hash)
+            .unwrap_or(false);
+        if !verified {
+            return Err(invalid());
+        }
+
+        if matches!(user.state, UserState::Suspended | UserState::Deleted) {
+            return Err(DomainError::Permission("Account is disabled".to_string()));
+        }
+
+        let raw_token = self.open(&user, None).await?;
+        Ok((user, raw_token))
+    }
+
+    /// Opens a session for an already authenticated user and returns its raw token.
+    /// `sso_workspace_id` records the workspace whose single sign-on vouched for the user.
+    pub async fn open(
+        &self,
+        user: &User,
+        sso_workspace_id: Option<Uuid>,
+    ) -> Result<String, DomainError> {
+        let raw_token = token::generate();
+        let now = Utc::now();
+        let session = Session {
+            id: Uuid::new_v4(),
+            created_at: now,
+            user_id: user.id,
+            token_hash: token::hash(&raw_token),
+            expires_at: now + Duration::days(SESSION_TTL_DAYS),
+            sso_workspace_id,
+        };
+        self.session_repo.create(session).await?;
+        Ok(raw_token)
+    }
+
+    fn dummy_hash(&self) -> &str {
+        self.dummy_hash.get_or_init(|| {
+            self.password_hasher
+                .hash(&token::generate())
+                .unwrap_or_default()
+        })
+    }
+
+    pub async fn logout(&self, raw_token: &str) -> Result<(), DomainError> {
+        self.session_repo
+            .delete_by_token_hash(&token::hash(raw_token))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::use_cases::testing::user;
+    use crate::infrastructure::identity::Argon2PasswordHasher;
+    use crate::infrastructure::persistence::in_memory_repo::InMemoryRepo;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Argon2 hasher that counts the verifications
+    #[derive(Default)]
+    struct CountingHasher {
+        argon2: Argon2PasswordHasher,
+        verified: AtomicUsize,
+    }
+
+    impl PasswordHasher for CountingHasher {
+        fn hash(&self, password: &str) -> Result<String, String> {
+            self.argon2.hash(password)
+        }
+
+        fn verify(&self, password: &str, password_hash: &str) -> Result<bool, String> {
+            self.verified.fetch_add(1, Ordering::SeqCst);
+            self.argon2.verify(password, password_hash)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unknown_emails_take_a_password_verification_too() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let hasher = Arc::new(CountingHasher::default());
+        let manage_session = ManageSession::new(repo.clone(), repo.clone(), hasher.clone());
+        let mut ada = user(&repo, "ada@example.com").await;
+        ada.password_hash = hasher.hash("correct horse").unwrap();
+        UserRepository::update(repo.as_ref(), ada).await.unwrap();
+
+        for email in ["ada@example.com", "nobody@example.com"] {
+            assert!(matches!(
+                manage_session.login(email, "wrong").await,
+                Err(DomainError::Validation(_))
+            ));
+        }
+        assert_eq!(hasher.verified.load(Ordering::SeqCst), 2);
+        assert!(manage_session
+            .login("ada@example.com", "correct horse")
+            .await
+            .is_ok());
+    }
+}
@@ -0,0 +1,252 @@
+use crate::application::context::current_member;
+use crate::application::ports::identity::{
+    IdentityProvider, SsoAuthorization, SsoClaims, SsoProvider,
+};
+use crate::application::ports::output::{
+    RoleRepository, SsoConfigRepository, UserRepository, WorkspaceRepository,
+};
+use crate::application::use_cases::manage_roles::resolve_role;
+use crate::application::use_cases::manage_session::ManageSession;
+use crate::domain::entities::{SsoConfig, User, WorkspaceMember};
+use crate::domain::permissions::{objects, Permission};
+use crate::domain::states::UserState;
+use crate::domain::DomainError;
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct SsoConfigInput {
+    pub issuer_url: String,
+    pub client_id: String,
+    /// Keeps the stored secret when empty
+    pub client_secret: Option<String>,
+    pub default_role: String,
+    pub enforced: bool,
+}
+
+/// OpenID Connect sign-on per workspace: configuration by admins and the
+/// authorization-code flow, which provisions users and members on first sign-on
+pub struct ManageSso {
+    config_repo: Arc<dyn SsoConfigRepository>,
+    user_repo: Arc<dyn UserRepository>,
+    workspace_repo: Arc<dyn WorkspaceRepository>,
+    role_repo: Arc<dyn RoleRepository>,
+    manage_session: Arc<ManageSession>,
+    sso_provider: Arc<dyn SsoProvider>,
+    identity_provider: Arc<dyn IdentityProvider>,
+}
+
+impl ManageSso {
+    pub fn new(
+        config_repo: Arc<dyn SsoConfigRepository>,
+        user_repo: Arc<dyn UserRepository>,
+        workspace_repo: Arc<dyn WorkspaceRepository>,
+        role_repo: Arc<dyn RoleRepository>,
+        manage_session: Arc<ManageSession>,
+        sso_provider: Arc<dyn SsoProvider>,
+        identity_provider: Arc<dyn IdentityProvider>,
+    ) -> Self {
+        Self {
+            config_repo,
+            user_repo,
+            workspace_repo,
+            role_repo,
+            manage_session,
+            sso_provider,
+            identity_provider,
+        }
+    }
+
+    fn workspace_id() -> Result<Uuid, DomainError> {
+        current_member()
+            .map(|m| m.workspace_id)
+            .ok_or_else(|| DomainError::Permission("Not a member of any workspace".to_string()))
+    }
+
+    /// SSO settings of the current workspace
+    pub async fn get_config(&self) -> Result<SsoConfig, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::WORKSPACE_MEMBER))
+            .await?;
+        self.config_repo
+            .find_by_workspace_id(Self::workspace_id()?)
+            .await?
+            .ok_or(DomainError::NotFound)
+    }
+
+    /// Creates or replaces the SSO settings of the current workspace
+    pub async fn configure(&self, input: SsoConfigInput) -> Result<SsoConfig, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::update(objects::WORKSPACE_MEMBER))
+            .await?;
+        let workspace_id = Self::workspace_id()?;
+
+        let issuer_url = input.issuer_url.trim().trim_end_matches('/').to_string();
+        if !issuer_url.starts_with("https://") && !issuer_url.starts_with("http://") {
+            return Err(DomainError::Validation(
+                "Issuer URL must be an http(s) URL".to_string(),
+            ));
+        }
+        let client_id = input.client_id.trim().to_string();
+        if client_id.is_empty() {
+            return Err(DomainError::Validation(
+                "Client ID cannot be empty".to_string(),
+            ));
+        }
+        let role = resolve_role(self.role_repo.as_ref(), workspace_id, &input.default_role).await?;
+
+        let now = Utc::now();
+        let existing = self.config_repo.find_by_workspace_id(workspace_id).await?;
+        let client_secret = match (input.client_secret.filter(|s| !s.is_empty()), &existing) {
+            (Some(secret), _) => secret,
+            (None, Some(existing)) => existing.client_secret.clone(),
+            (None, None) => {
+                return Err(DomainError::Validation(
+                    "Client secret cannot be empty".to_string(),
+                ))
+            }
+        };
+
+        let config = SsoConfig {
+            id: existing.as_ref().map_or_else(Uuid::new_v4, |c| c.id),
+            created_at: existing.as_ref().map_or(now, |c| c.created_at),
+            updated_at: now,
+            workspace_id,
+            issuer_url,
+            client_id,
+            client_secret,
+            default_role: role.name().to_string(),
+            enforced: input.enforced,
+        };
+        self.config_repo.save(config).await
+    }
+
+    async fn config_for(&self, workspace_id: Uuid) -> Result<SsoConfig, DomainError> {
+        self.config_repo
+            .find_by_workspace_id(workspace_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::InvalidState("Single sign-on is not configured".to_string())
+            })
+    }
+
+    /// Begins sign-on to `workspace_id`; the caller redirects to the returned URL and
+    /// keeps the rest of the authorization for `complete`
+    pub async fn start(
+        &self,
+        workspace_id: Uuid,
+        redirect_uri: &str,
+    ) -> Result<SsoAuthorization, DomainError> {
+        let config = self.config_for(workspace_id).await?;
+        self.sso_provider
+            .authorize(&config, redirect_uri)
+            .await
+            .map_err(DomainError::InfrastructureError)
+    }
+
+    /// Redeems the provider's callback and opens a session. Unknown addresses get an
+    /// account, and users who are not yet members join with the configured default role.
+    /// Returns the user together with the raw session token.
+    pub async fn complete(
+        &self,
+        workspace_id: Uuid,
+        code: &str,
+        redirect_uri: &str,
+        pkce_verifier: &str,
+        nonce: &str,
+    ) -> Result<(User, String), DomainError> {
+        let config = self.config_for(workspace_id).await?;
+        let claims = self
+            .sso_provider
+            .exchange_code(&config, code, redirect_uri, pkce_verifier, nonce)
+            .await
+            .map_err(|e| {
+                tracing::warn!(
+                    "Single sign-on failed for workspace {}: {}",
+                    workspace_id,
+                    e
+                );
+                DomainError::Permission("Single sign-on failed".to_string())
+            })?;
+
+        let user = self.provision_user(&claims).await?;
+        self.provision_member(&config, &user, &claims).await?;
+
+        let raw_token = self.manage_session.open(&user, Some(workspace_id)).await?;
+        Ok((user, raw_token))
+    }
+
+    async fn provision_user(&self, claims: &SsoClaims) -> Result<User, DomainError> {
+        // Matching on an unverified address would let the provider's users take over accounts
+        if !claims.email_verified {
+            return Err(DomainError::Permission(
+                "The identity provider has not verified your email address".to_string(),
+            ));
+        }
+        let email = claims.email.trim().to_string();
+
+        match self.user_repo.find_by_email(&email).await? {
+            Some(mut user) => match user.state {
+                UserState::Active => Ok(user),
+                // The provider vouches for the address
+                UserState::Unverified => {
+                    user.transition_to(UserState::Active)?;
+                    self.user_repo.update(user).await
+                }
+                UserState::Suspended | UserState::Deleted => {
+                    Err(DomainError::Permission("Account is disabled".to_string()))
+                }
+            },
+            None => {
+                let now = Utc::now();
+                // No password: the account signs in through SSO until one is set by a reset
+                let user = User {
+                    id: Uuid::new_v4(),
+                    created_at: now,
+                    updated_at: now,
+                    email,
+                    password_hash: String::new(),
+                    state: UserState::Active,
+                };
+                tracing::info!(
+                    "Creating user {} for SSO subject {}",
+                    user.email,
+                    claims.subject
+                );
+                self.user_repo.create(user).await
+            }
+        }
+    }
+
+    async fn provision_member(
+        &self,
+        config: &SsoConfig,
+        user: &User,
+        claims: &SsoClaims,
+    ) -> Result<WorkspaceMember, DomainError> {
+        let memberships = self.workspace_repo.find_members_by_user_id(user.id).await?;
+        if let Some(member) = memberships
+            .into_iter()
+            .find(|m| m.workspace_id == config.workspace_id)
+        {
+            return Ok(member);
+        }
+
+        let now = Utc::now();
+        let member = WorkspaceMember {
+            id: Uuid::new_v4(),
+            created_at: now,
+            updated_at: now,
+            user_id: user.id,
+            workspace_id: config.workspace_id,
+            role: config.default_role.clone(),
+            name: claims
+                .name
+                .clone()
+                .filter(|n| !n.trim().is_empty())
+                .unwrap_or_else(|| user.email.clone()),
+            suspended_at: None,
+        };
+        self.workspace_repo.add_member(member).await
+    }
+}
@@ -1,4 +1,7 @@
+use crate::application::events::record_events::{RecordEvent, RecordEvents};
+use crate::application::ports::identity::IdentityProvider;
 use crate::application::ports::output::TaskRepository;
+use crate::domain::permissions::{objects, Permission};
 use crate::domain::{DomainError, Task, TaskStatus};
 use serde::Deserialize;
 use std::sync::Arc;
@@ -16,28 +19,48 @@ pub struct UpdateTaskInput {
 
 pub struct ManageTask {
     task_repo: Arc<dyn TaskRepository>,
+    identity_provider: Arc<dyn IdentityProvider>,
+    record_events: Arc<RecordEvents>,
 }
 
 impl ManageTask {
-    pub fn new(task_repo: Arc<dyn TaskRepository>) -> Self {
-        Self { task_repo }
+    pub fn new(
+        task_repo: Arc<dyn TaskRepository>,
+        identity_provider: Arc<dyn IdentityProvider>,
+        record_events: Arc<RecordEvents>,
+    ) -> Self {
+        Self {
+            task_repo,
+            identity_provider,
+            record_events,
+        }
     }
 
     pub async fn get_all(&self) -> Result<Vec<Task>, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::TASK))
+            .await?;
         self.task_repo.find_all().await
     }
 
     pub async fn get_by_id(&self, id: Uuid) -> Result<Option<Task>, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::TASK))
+            .await?;
         self.task_repo.find_by_id(id).await
     }
 
     pub async fn update(&self, input: UpdateTaskInput) -> Result<Task, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::update(objects::TASK))
+            .await?;
         // First fetch the existing task
         let existing = self
             .task_repo
             .find_by_id(input.id)
             .await?
             .ok_or(DomainError::NotFound)?;
+        let previous = existing.clone();
 
         // Parse status if provided
         let status = if let Some(status_str) = input.status {
@@ -66,10 +89,35 @@ impl ManageTask {
             workspace_id: existing.workspace_id,
         };
 
-        self.task_repo.update(updated).await
+        let updated = self.task_repo.update(updated).await?;
+        self.record_events
+            .publish(RecordEvent::updated(
+                objects::TASK,
+                updated.workspace_id,
+                &previous,
+                &updated,
+            ))
+            .await;
+        Ok(updated)
     }
 
     pub async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        self.task_repo.delete(id).await
+        self.identity_provider
+            .authorize(&Permission::delete(objects::TASK))
+            .await?;
+        let task = self
+            .task_repo
+            .find_by_id(id)
+            .await?
+            .ok_or(DomainError::NotFound)?;
+        self.task_repo.delete(id).await?;
+        self.record_events
+            .publish(RecordEvent::deleted(
+                objects::TASK,
+                task.workspace_id,
+                &task,
+            ))
+            .await;
+        Ok(())
     }
 }
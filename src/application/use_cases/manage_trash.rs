@@ -0,0 +1,55 @@
+use crate::application::ports::identity::IdentityProvider;
+use crate::application::ports::output::TrashRepository;
+use crate::domain::permissions::Permission;
+use crate::domain::{DomainError, TrashedKind, TrashedRecord};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct ManageTrash {
+    trash_repo: Arc<dyn TrashRepository>,
+    identity_provider: Arc<dyn IdentityProvider>,
+}
+
+impl ManageTrash {
+    pub fn new(
+        trash_repo: Arc<dyn TrashRepository>,
+        identity_provider: Arc<dyn IdentityProvider>,
+    ) -> Self {
+        Self {
+            trash_repo,
+            identity_provider,
+        }
+    }
+
+    /// Trashed records of every kind the actor can read, most recently deleted first
+    pub async fn list(&self) -> Result<Vec<TrashedRecord>, DomainError> {
+        let mut kinds = Vec::new();
+        for kind in TrashedKind::ALL {
+            match self
+                .identity_provider
+                .authorize(&Permission::read(kind.object()))
+                .await
+            {
+                Ok(()) => kinds.push(kind),
+                Err(DomainError::Permission(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        self.trash_repo.find_trashed(&kinds).await
+    }
+
+    /// Restoring undoes a delete, so it takes the same permission
+    pub async fn restore(&self, kind: TrashedKind, id: Uuid) -> Result<(), DomainError> {
+        self.identity_provider
+            .authorize(&Permission::delete(kind.object()))
+            .await?;
+        self.trash_repo.restore(kind, id).await
+    }
+
+    pub async fn purge(&self, kind: TrashedKind, id: Uuid) -> Result<(), DomainError> {
+        self.identity_provider
+            .authorize(&Permission::delete(kind.object()))
+            .await?;
+        self.trash_repo.purge(kind, id).await
+    }
+}
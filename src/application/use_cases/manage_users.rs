@@ -0,0 +1,149 @@
+use crate::application::context::{current_actor, Actor};
+use crate::application::ports::identity::PasswordHasher;
+use crate::application::ports::output::{SessionRepository, UserRepository, WorkspaceRepository};
+use crate::application::ports::unit_of_work::UnitOfWork;
+use crate::application::use_cases::manage_members::is_last_admin;
+use crate::domain::DomainError;
+use std::sync::Arc;
+
+/// Account lifecycle beyond registration and verification: deletion by the user
+/// themselves. Workspace admins suspend members, not accounts (see `ManageMembers`).
+pub struct ManageUsers {
+    user_repo: Arc<dyn UserRepository>,
+    session_repo: Arc<dyn SessionRepository>,
+    workspace_repo: Arc<dyn WorkspaceRepository>,
+    password_hasher: Arc<dyn PasswordHasher>,
+    unit_of_work: Arc<dyn UnitOfWork>,
+}
+
+impl ManageUsers {
+    pub fn new(
+        user_repo: Arc<dyn UserRepository>,
+        session_repo: Arc<dyn SessionRepository>,
+        workspace_repo: Arc<dyn WorkspaceRepository>,
+        password_hasher: Arc<dyn PasswordHasher>,
+        unit_of_work: Arc<dyn UnitOfWork>,
+    ) -> Self {
+        Self {
+            user_repo,
+            session_repo,
+            workspace_repo,
+            password_hasher,
+            unit_of_work,
+        }
+    }
+
+    /// Deletes the signed-in user's account: memberships and sessions are removed and the
+    /// user is anonymized, all or nothing. Refused while the user is the only active admin
+    /// of a workspace that still has other members.
+    pub async fn delete_account(&self, password: &str) -> Result<(), DomainError> {
+        let mut user = match current_actor() {
+            Some(Actor::User(identity)) => identity.user,
+            _ => {
+                return Err(DomainError::Permission(
+                    "Authentication required".to_string(),
+                ))
+            }
+        };
+
+        let verified = self
+            .password_hasher
+            .verify(password, &user.password_hash)
+            .unwrap_or(false);
+        if !verified {
+            return Err(DomainError::Validation("Invalid password".to_string()));
+        }
+
+        let memberships = self.workspace_repo.find_members_by_user_id(user.id).await?;
+        for membership in &memberships {
+            let members = self
+                .workspace_repo
+                .find_members(membership.workspace_id)
+                .await?;
+            if members.len() > 1 && is_last_admin(&members, membership) {
+                return Err(DomainError::InvalidState(
+                    "Hand over the admin role before deleting your account".to_string(),
+                ));
+            }
+        }
+
+        user.anonymize()?;
+        self.unit_of_work
+            .atomically(async {
+                for membership in memberships {
+                    self.workspace_repo.remove_member(membership.id).await?;
+                }
+                self.session_repo.delete_by_user_id(user.id).await?;
+                self.user_repo.update(user).await?;
+                Ok(())
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::context::run_as;
+    use crate::application::use_cases::testing::{acting_as, member, user, workspace};
+    use crate::domain::states::UserState;
+    use crate::domain::User;
+    use crate::infrastructure::identity::Argon2PasswordHasher;
+    use crate::infrastructure::persistence::in_memory_repo::InMemoryRepo;
+    use chrono::Utc;
+
+    fn manage_users(repo: &Arc<InMemoryRepo>) -> ManageUsers {
+        ManageUsers::new(
+            repo.clone(),
+            repo.clone(),
+            repo.clone(),
+            Arc::new(Argon2PasswordHasher::new()),
+            repo.clone(),
+        )
+    }
+
+    /// A user signing in with the password `correct horse`
+    async fn user_with_password(repo: &InMemoryRepo, email: &str) -> User {
+        let mut user = user(repo, email).await;
+        user.password_hash = Argon2PasswordHasher::new().hash("correct horse").unwrap();
+        UserRepository::update(repo, user).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_suspended_admins_do_not_take_over_from_the_last_admin() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let manage_users = manage_users(&repo);
+        let a = workspace(&repo).await;
+        let ada = user_with_password(&repo, "ada@example.com").await;
+        let admin = member(&repo, a, &ada, "Admin").await;
+        let grace = user(&repo, "grace@example.com").await;
+        let mut suspended = member(&repo, a, &grace, "Admin").await;
+        suspended.suspended_at = Some(Utc::now());
+        repo.update_member(suspended.clone()).await.unwrap();
+
+        run_as(acting_as(&ada, &admin), async {
+            assert!(matches!(
+                manage_users.delete_account("correct horse").await,
+                Err(DomainError::InvalidState(_))
+            ));
+        })
+        .await;
+
+        suspended.suspended_at = None;
+        repo.update_member(suspended).await.unwrap();
+        run_as(acting_as(&ada, &admin), async {
+            manage_users.delete_account("correct horse").await.unwrap();
+        })
+        .await;
+        let ada = UserRepository::find_by_id(repo.as_ref(), ada.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(ada.state, UserState::Deleted);
+        assert!(repo
+            .find_members_by_user_id(ada.id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
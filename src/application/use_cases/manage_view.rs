@@ -1,23 +1,38 @@
+use crate::application::ports::identity::IdentityProvider;
 use crate::application::ports::output::ViewRepository;
 use crate::domain::metadata::{View, ViewType};
+use crate::domain::permissions::{objects, Permission};
 use crate::domain::DomainError;
 use std::sync::Arc;
 use uuid::Uuid;
 
 pub struct ManageView {
     view_repo: Arc<dyn ViewRepository>,
+    identity_provider: Arc<dyn IdentityProvider>,
 }
 
 impl ManageView {
-    pub fn new(view_repo: Arc<dyn ViewRepository>) -> Self {
-        Self { view_repo }
+    pub fn new(
+        view_repo: Arc<dyn ViewRepository>,
+        identity_provider: Arc<dyn IdentityProvider>,
+    ) -> Self {
+        Self {
+            view_repo,
+            identity_provider,
+        }
     }
 
     pub async fn list_by_object(&self, object_metadata_id: Uuid) -> Result<Vec<View>, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::VIEW))
+            .await?;
         self.view_repo.find_by_object(object_metadata_id).await
     }
 
     pub async fn get(&self, id: Uuid) -> Result<View, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::VIEW))
+            .await?;
         self.view_repo
             .find_by_id(id)
             .await?
@@ -33,6 +48,9 @@ impl ManageView {
         sort: serde_json::Value,
         workspace_id: Uuid,
     ) -> Result<View, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::create(objects::VIEW))
+            .await?;
         let view = View {
             id: Uuid::new_v4(),
             created_at: chrono::Utc::now(),
@@ -56,6 +74,9 @@ impl ManageView {
         filters: Option<serde_json::Value>,
         sort: Option<serde_json::Value>,
     ) -> Result<View, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::update(objects::VIEW))
+            .await?;
         let mut view = self.get(id).await?;
 
         if let Some(n) = name {
@@ -73,6 +94,9 @@ impl ManageView {
     }
 
     pub async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        self.identity_provider
+            .authorize(&Permission::delete(objects::VIEW))
+            .await?;
         self.view_repo.delete(id).await
     }
 }
@@ -1,26 +1,44 @@
+use crate::application::ports::identity::IdentityProvider;
 use crate::application::ports::output::WorkflowRepository;
+use crate::domain::permissions::{objects, Permission};
 use crate::domain::{DomainError, Workflow};
 use std::sync::Arc;
 use uuid::Uuid;
 
 pub struct ManageWorkflow {
     workflow_repo: Arc<dyn WorkflowRepository>,
+    identity_provider: Arc<dyn IdentityProvider>,
 }
 
 impl ManageWorkflow {
-    pub fn new(workflow_repo: Arc<dyn WorkflowRepository>) -> Self {
-        Self { workflow_repo }
+    pub fn new(
+        workflow_repo: Arc<dyn WorkflowRepository>,
+        identity_provider: Arc<dyn IdentityProvider>,
+    ) -> Self {
+        Self {
+            workflow_repo,
+            identity_provider,
+        }
     }
 
     pub async fn get_all(&self) -> Result<Vec<Workflow>, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::WORKFLOW))
+            .await?;
         self.workflow_repo.find_all().await
     }
 
     pub async fn get_by_id(&self, id: Uuid) -> Result<Option<Workflow>, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::WORKFLOW))
+            .await?;
         self.workflow_repo.find_by_id(id).await
     }
 
     pub async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        self.identity_provider
+            .authorize(&Permission::delete(objects::WORKFLOW))
+            .await?;
         self.workflow_repo.delete(id).await
     }
 }
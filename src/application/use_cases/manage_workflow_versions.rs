@@ -0,0 +1,459 @@
+use crate::application::ports::identity::IdentityProvider;
+use crate::application::ports::output::{
+    WorkflowRepository, WorkflowRunRepository, WorkflowVersionRepository,
+    WorkflowVersionStepRepository,
+};
+use crate::application::workflow::delay::validate_delay_settings;
+use crate::application::workflow::executor::WorkflowExecutor;
+use crate::application::workflow::graph::{validate_flow_settings, WorkflowGraph};
+use crate::application::workflow::records::{record_step_permission, validate_record_settings};
+use crate::application::workflow::run_context::validate_templates;
+use crate::application::workflow::trigger::WorkflowTrigger;
+use crate::domain::permissions::{objects, Permission};
+use crate::domain::states::{WorkflowStepType, WorkflowVersionStatus};
+use crate::domain::{
+    DomainError, StateMachine, Workflow, WorkflowRun, WorkflowVersion, WorkflowVersionStep,
+};
+use crate::shared::token;
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct AddStepInput {
+    /// Unique within the version, see `WorkflowVersionStep::name`
+    pub name: Option<String>,
+    pub step_type: WorkflowStepType,
+    pub settings: serde_json::Value,
+    /// Appended after the last step when omitted
+    pub position: Option<i32>,
+}
+
+/// Authoring and running workflows: draft versions collect steps, publishing freezes a
+/// version and makes it the one that runs
+pub struct ManageWorkflowVersions {
+    workflow_repo: Arc<dyn WorkflowRepository>,
+    version_repo: Arc<dyn WorkflowVersionRepository>,
+    step_repo: Arc<dyn WorkflowVersionStepRepository>,
+    run_repo: Arc<dyn WorkflowRunRepository>,
+    executor: Arc<WorkflowExecutor>,
+    identity_provider: Arc<dyn IdentityProvider>,
+}
+
+impl ManageWorkflowVersions {
+    pub fn new(
+        workflow_repo: Arc<dyn WorkflowRepository>,
+        version_repo: Arc<dyn WorkflowVersionRepository>,
+        step_repo: Arc<dyn WorkflowVersionStepRepository>,
+        run_repo: Arc<dyn WorkflowRunRepository>,
+        executor: Arc<WorkflowExecutor>,
+        identity_provider: Arc<dyn IdentityProvider>,
+    ) -> Self {
+        Self {
+            workflow_repo,
+            version_repo,
+            step_repo,
+            run_repo,
+            executor,
+            identity_provider,
+        }
+    }
+
+    async fn workflow(&self, id: Uuid) -> Result<Workflow, DomainError> {
+        self.workflow_repo
+            .find_by_id(id)
+            .await?
+            .ok_or(DomainError::NotFound)
+    }
+
+    async fn version(&self, id: Uuid) -> Result<WorkflowVersion, DomainError> {
+        self.version_repo
+            .find_by_id(id)
+            .await?
+            .ok_or(DomainError::NotFound)
+    }
+
+    pub async fn list_versions(
+        &self,
+        workflow_id: Uuid,
+    ) -> Result<Vec<WorkflowVersion>, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::WORKFLOW))
+            .await?;
+        self.version_repo.find_by_workflow_id(workflow_id).await
+    }
+
+    /// Starts an empty draft version of the workflow
+    pub async fn create_version(&self, workflow_id: Uuid) -> Result<WorkflowVersion, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::update(objects::WORKFLOW))
+            .await?;
+        let workflow = self.workflow(workflow_id).await?;
+
+        let version = WorkflowVersion {
+            id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            workflow_id: workflow.id,
+            status: WorkflowVersionStatus::Draft,
+        };
+        self.version_repo.create(version).await
+    }
+
+    pub async fn list_steps(
+        &self,
+        version_id: Uuid,
+    ) -> Result<Vec<WorkflowVersionStep>, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::WORKFLOW))
+            .await?;
+        let mut steps = self.step_repo.find_by_version_id(version_id).await?;
+        for step in &mut steps {
+            if step.step_type == WorkflowStepType::Trigger {
+                if let Some(settings) = step.settings.as_object_mut() {
+                    settings.remove("token_hash");
+                }
+            }
+        }
+        Ok(steps)
+    }
+
+    pub async fn add_step(
+        &self,
+        version_id: Uuid,
+        input: AddStepInput,
+    ) -> Result<WorkflowVersionStep, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::update(objects::WORKFLOW))
+            .await?;
+        let version = self.version(version_id).await?;
+        if version.status != WorkflowVersionStatus::Draft {
+            return Err(DomainError::InvalidState(
+                "Steps can only be added to draft versions".to_string(),
+            ));
+        }
+        if !input.settings.is_object() {
+            return Err(DomainError::Validation(
+                "Step settings must be a JSON object".to_string(),
+            ));
+        }
+        let (settings, webhook_token) = match input.step_type {
+            WorkflowStepType::Trigger => trigger_settings(&input.settings)?,
+            ref step_type => {
+                validate_templates(&input.settings)?;
+                validate_flow_settings(step_type, &input.settings)?;
+                validate_record_settings(step_type, &input.settings)?;
+                if *step_type == WorkflowStepType::Delay {
+                    validate_delay_settings(&input.settings)?;
+                }
+                (input.settings, None)
+            }
+        };
+
+        let steps = self.step_repo.find_by_version_id(version.id).await?;
+        let name = input.name.map(|name| name.trim().to_string());
+        if let Some(name) = &name {
+            let well_formed =
+                !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !well_formed {
+                return Err(DomainError::Validation(
+                    "Step names may only contain letters, digits and underscores".to_string(),
+                ));
+            }
+            if steps.iter().any(|s| s.name.as_ref() == Some(name)) {
+                return Err(DomainError::Validation(format!(
+                    "The version already has a step named {:?}",
+                    name
+                )));
+            }
+        }
+
+        let position = match input.position {
+            Some(position) => position,
+            None => steps.iter().map(|s| s.position + 1).max().unwrap_or(0),
+        };
+
+        let step = WorkflowVersionStep {
+            id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            workflow_version_id: version.id,
+            name,
+            step_type: input.step_type,
+            settings,
+            position,
+        };
+        let mut step = self.step_repo.create(step).await?;
+        // Only the hash is stored, so this is the one time the webhook token is shown
+        if let Some(token) = webhook_token {
+            step.settings = serde_json::json!({ "type": "webhook", "token": token });
+        }
+        Ok(step)
+    }
+
+    /// Makes a draft the workflow's live version; the previously published one is archived.
+    /// Every step must be reachable and no step may loop back, see `WorkflowGraph`.
+    /// Triggered runs act for the workspace, so the publisher must hold the permissions of
+    /// every step, see `step_permission`.
+    pub async fn publish(&self, version_id: Uuid) -> Result<WorkflowVersion, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::update(objects::WORKFLOW))
+            .await?;
+        let mut version = self.version(version_id).await?;
+        let mut workflow = self.workflow(version.workflow_id).await?;
+
+        let steps = self.step_repo.find_by_version_id(version.id).await?;
+        if steps.is_empty() {
+            return Err(DomainError::Validation(
+                "Add at least one step before publishing".to_string(),
+            ));
+        }
+        for step in &steps {
+            if let Some(permission) = step_permission(step)? {
+                self.identity_provider.authorize(&permission).await?;
+            }
+        }
+        WorkflowGraph::new(steps)?.validate()?;
+        version.status = version
+            .status
+            .transition_to(WorkflowVersionStatus::Published)?;
+        version.updated_at = Utc::now();
+        let version = self.version_repo.update(version).await?;
+
+        if let Some(previous_id) = workflow.last_published_version_id {
+            if let Some(mut previous) = self.version_repo.find_by_id(previous_id).await? {
+                previous.status = previous
+                    .status
+                    .transition_to(WorkflowVersionStatus::Archived)?;
+                previous.updated_at = Utc::now();
+                self.version_repo.update(previous).await?;
+            }
+        }
+
+        workflow.last_published_version_id = Some(version.id);
+        workflow.updated_at = Utc::now();
+        self.workflow_repo.update(workflow).await?;
+
+        Ok(version)
+    }
+
+    /// Runs the workflow's published version now, with the caller's permissions and
+    /// `payload` as input. The version must have a manual trigger, or no trigger at all.
+    pub async fn run(
+        &self,
+        workflow_id: Uuid,
+        payload: Option<serde_json::Value>,
+    ) -> Result<WorkflowRun, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::update(objects::WORKFLOW))
+            .await?;
+        let workflow = self.workflow(workflow_id).await?;
+        if let Some(version_id) = workflow.last_published_version_id {
+            let triggers: Vec<_> = self
+                .step_repo
+                .find_by_version_id(version_id)
+                .await?
+                .into_iter()
+                .filter(|s| s.step_type == WorkflowStepType::Trigger)
+                .map(|s| WorkflowTrigger::from_settings(&s.settings))
+                .collect();
+            let manual = triggers
+                .iter()
+                .any(|t| matches!(t, Ok(WorkflowTrigger::Manual)));
+            if !triggers.is_empty() && !manual {
+                return Err(DomainError::InvalidState(
+                    "Workflow has no manual trigger".to_string(),
+                ));
+            }
+        }
+        let input = WorkflowTrigger::Manual.payload_input(payload);
+        self.executor.execute_workflow(&workflow, Some(input)).await
+    }
+
+    pub async fn get_run(&self, id: Uuid) -> Result<WorkflowRun, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::WORKFLOW))
+            .await?;
+        self.run_repo
+            .find_by_id(id)
+            .await?
+            .ok_or(DomainError::NotFound)
+    }
+}
+
+/// The permission a step acts with in a run, `None` for steps that only steer the run
+fn step_permission(step: &WorkflowVersionStep) -> Result<Option<Permission>, DomainError> {
+    match step.step_type {
+        WorkflowStepType::SendEmail => Ok(Some(Permission::create(objects::EMAIL))),
+        ref step_type => record_step_permission(step_type, &step.settings),
+    }
+}
+
+/// Validated settings of a trigger step, and the token of a webhook trigger. Webhook tokens
+/// are always generated here rather than chosen by the author, and only their hash is kept.
+fn trigger_settings(
+    settings: &serde_json::Value,
+) -> Result<(serde_json::Value, Option<String>), DomainError> {
+    let (trigger, webhook_token) = match WorkflowTrigger::from_settings(settings)? {
+        WorkflowTrigger::Webhook { .. } => {
+            let raw_token = token::generate();
+            let token_hash = token::hash(&raw_token);
+            (WorkflowTrigger::Webhook { token_hash }, Some(raw_token))
+        }
+        trigger => (trigger, None),
+    };
+    let settings =
+        serde_json::to_value(trigger).map_err(|e| DomainError::Validation(e.to_string()))?;
+    Ok((settings, webhook_token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::context::run_as;
+    use crate::application::use_cases::create_workflow::{CreateWorkflow, CreateWorkflowInput};
+    use crate::application::use_cases::testing::{
+        acting_as, custom_role, executor, identity_provider, member, user, workflow_versions,
+        workspace,
+    };
+    use crate::application::workflow::launcher::WorkflowLauncher;
+    use crate::domain::permissions::Action;
+    use crate::infrastructure::persistence::in_memory_repo::InMemoryRepo;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_publishers_need_the_permissions_of_record_and_email_steps() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let versions = workflow_versions(&repo);
+        let a = workspace(&repo).await;
+        let ada = user(&repo, "ada@example.com").await;
+        let admin = member(&repo, a, &ada, "Admin").await;
+        custom_role(
+            &repo,
+            a,
+            "Automations",
+            &[
+                (
+                    objects::WORKFLOW,
+                    &[Action::Read, Action::Create, Action::Update],
+                ),
+                (objects::PERSON, &[Action::Read]),
+            ],
+        )
+        .await;
+        let grace = user(&repo, "grace@example.com").await;
+        let automations = member(&repo, a, &grace, "Automations").await;
+
+        let steps = [
+            (
+                WorkflowStepType::CreateRecord,
+                json!({
+                    "object": "person",
+                    "fields": { "name": "Ada", "email": "ada@example.com" },
+                }),
+            ),
+            (
+                WorkflowStepType::SendEmail,
+                json!({
+                    "to_email": "ada@example.com",
+                    "subject": "Welcome",
+                    "body_text": "Hello Ada",
+                }),
+            ),
+        ];
+        for (step_type, settings) in steps {
+            let version = run_as(acting_as(&grace, &automations), async {
+                let workflow = CreateWorkflow::new(repo.clone(), identity_provider(&repo))
+                    .execute(CreateWorkflowInput {
+                        name: "Welcome".to_string(),
+                        workspace_id: a,
+                    })
+                    .await
+                    .unwrap();
+                let version = versions.create_version(workflow.id).await.unwrap();
+                versions
+                    .add_step(
+                        version.id,
+                        AddStepInput {
+                            name: None,
+                            step_type,
+                            settings,
+                            position: None,
+                        },
+                    )
+                    .await
+                    .unwrap();
+
+                // Triggered runs would create people or send email the publisher cannot
+                assert!(matches!(
+                    versions.publish(version.id).await,
+                    Err(DomainError::Permission(_))
+                ));
+                version
+            })
+            .await;
+
+            run_as(acting_as(&ada, &admin), async {
+                let published = versions.publish(version.id).await.unwrap();
+                assert_eq!(published.status, WorkflowVersionStatus::Published);
+            })
+            .await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_webhook_tokens_are_shown_once_and_stored_hashed() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let versions = workflow_versions(&repo);
+        let launcher = WorkflowLauncher::new(repo.clone(), executor(&repo));
+        let a = workspace(&repo).await;
+        let ada = user(&repo, "ada@example.com").await;
+        let admin = member(&repo, a, &ada, "Admin").await;
+
+        let (workflow, version, raw_token) = run_as(acting_as(&ada, &admin), async {
+            let workflow = CreateWorkflow::new(repo.clone(), identity_provider(&repo))
+                .execute(CreateWorkflowInput {
+                    name: "Inbound".to_string(),
+                    workspace_id: a,
+                })
+                .await
+                .unwrap();
+            let version = versions.create_version(workflow.id).await.unwrap();
+            let trigger = versions
+                .add_step(
+                    version.id,
+                    AddStepInput {
+                        name: None,
+                        step_type: WorkflowStepType::Trigger,
+                        settings: json!({ "type": "webhook", "token": "chosen" }),
+                        position: None,
+                    },
+                )
+                .await
+                .unwrap();
+            let raw_token = trigger.settings["token"].as_str().unwrap().to_string();
+            assert_ne!(raw_token, "chosen");
+
+            let stored =
+                WorkflowVersionStepRepository::find_by_version_id(repo.as_ref(), version.id)
+                    .await
+                    .unwrap();
+            assert_eq!(stored[0].settings["token_hash"], token::hash(&raw_token));
+            assert!(stored[0].settings.get("token").is_none());
+            let listed = versions.list_steps(version.id).await.unwrap();
+            assert_eq!(listed[0].settings, json!({ "type": "webhook" }));
+
+            versions.publish(version.id).await.unwrap();
+            (workflow, version, raw_token)
+        })
+        .await;
+
+        assert!(matches!(
+            launcher.run_webhook(workflow.id, "wrong", None).await,
+            Err(DomainError::NotFound)
+        ));
+        let run = launcher
+            .run_webhook(workflow.id, &raw_token, None)
+            .await
+            .unwrap();
+        assert_eq!(run.workflow_version_id, version.id);
+    }
+}
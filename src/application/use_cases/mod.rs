@@ -1,7 +1,11 @@
 #![allow(unused_imports)]
+pub mod check_readiness;
 pub mod create_person;
 pub mod create_workspace;
 pub mod manage_person;
+pub mod manage_roles;
+pub mod manage_session;
+pub mod manage_sso;
 
 pub mod create_calendar_event;
 pub mod create_company;
@@ -17,11 +21,19 @@ pub mod manage_note;
 pub mod manage_opportunity;
 pub mod manage_task;
 pub mod manage_timeline_activity;
+pub mod manage_trash;
+pub mod manage_users;
 pub mod manage_workflow;
+pub mod manage_workflow_versions;
 pub mod record_board_card;
 pub mod register_user;
+pub mod reset_password;
+pub mod verify_email;
 
+pub mod manage_api_keys;
 pub mod manage_email_template;
+pub mod manage_invitations;
+pub mod manage_members;
 pub mod receive_email;
 pub mod send_email;
 
@@ -32,4 +44,7 @@ pub mod manage_lead;
 pub mod manage_metadata;
 pub mod manage_view;
 
+#[cfg(test)]
+pub mod testing;
+
 pub use record_board_card::RecordBoardCard;
@@ -1,4 +1,7 @@
+use crate::application::ports::identity::IdentityProvider;
 use crate::application::ports::output::{EmailRepository, TimelineActivityRepository};
+use crate::application::ports::unit_of_work::UnitOfWork;
+use crate::domain::permissions::{objects, Permission};
 use crate::domain::{DomainError, Email, EmailDirection, EmailStatus, HardGuard, TimelineActivity};
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
@@ -13,25 +16,41 @@ pub struct ReceiveEmailInput {
     pub body_text: String,
     pub body_html: Option<String>,
     pub received_at: DateTime<Utc>,
+    /// Workspace the mail was delivered to
+    pub workspace_id: Uuid,
 }
 
 pub struct ReceiveEmail {
     email_repo: Arc<dyn EmailRepository>,
     timeline_repo: Arc<dyn TimelineActivityRepository>,
+    identity_provider: Arc<dyn IdentityProvider>,
+    unit_of_work: Arc<dyn UnitOfWork>,
 }
 
 impl ReceiveEmail {
     pub fn new(
         email_repo: Arc<dyn EmailRepository>,
         timeline_repo: Arc<dyn TimelineActivityRepository>,
+        identity_provider: Arc<dyn IdentityProvider>,
+        unit_of_work: Arc<dyn UnitOfWork>,
     ) -> Self {
         Self {
             email_repo,
             timeline_repo,
+            identity_provider,
+            unit_of_work,
         }
     }
 
     pub async fn execute(&self, input: ReceiveEmailInput) -> Result<Email, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::create(objects::EMAIL))
+            .await?;
+        // The email and its timeline entry are stored together
+        self.unit_of_work.atomically(self.receive(input)).await
+    }
+
+    async fn receive(&self, input: ReceiveEmailInput) -> Result<Email, DomainError> {
         // 1. Create inbound email record
         let email = Email {
             id: Uuid::new_v4(),
@@ -58,7 +77,7 @@ impl ReceiveEmail {
             workflow_id: None,
             workflow_run_id: None,
             metadata: None,
-            workspace_id: Uuid::default(), // TODO: Resolve workspace from To address or domain
+            workspace_id: input.workspace_id,
         };
 
         // Validate email
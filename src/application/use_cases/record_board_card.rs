@@ -1,12 +1,17 @@
+use crate::application::events::record_events::{RecordEvent, RecordEvents};
+use crate::application::ports::identity::IdentityProvider;
 use crate::application::ports::input::RecordUseCase;
 use crate::application::ports::output::OpportunityRepository;
-use crate::domain::OpportunityStage;
+use crate::domain::permissions::{objects, Permission};
+use crate::domain::{DomainError, OpportunityStage};
 use async_trait::async_trait;
 use std::sync::Arc;
 use uuid::Uuid;
 
 pub struct RecordBoardCard {
     pub opportunity_repo: Arc<dyn OpportunityRepository>,
+    pub identity_provider: Arc<dyn IdentityProvider>,
+    pub record_events: Arc<RecordEvents>,
 }
 
 #[async_trait]
@@ -15,31 +20,104 @@ impl RecordUseCase for RecordBoardCard {
         &self,
         card_id: Uuid,
         new_stage: OpportunityStage,
-    ) -> Result<(), String> {
+        expected_version: i32,
+    ) -> Result<(), DomainError> {
+        self.identity_provider
+            .authorize(&Permission::update(objects::OPPORTUNITY))
+            .await?;
+
         let mut opportunity = self
             .opportunity_repo
             .find_by_id(card_id)
-            .await
-            .map_err(|e| e.to_string())?
-            .ok_or("Opportunity not found")?;
+            .await?
+            .ok_or(DomainError::NotFound)?;
+        let previous = opportunity.clone();
 
         opportunity.stage = new_stage;
         opportunity.updated_at = chrono::Utc::now();
+        // A card dragged from a stale board must not undo someone else's move
+        opportunity.version = expected_version;
 
         // In a real app, we'd check invariants here
 
-        self.opportunity_repo
-            .update(opportunity)
-            .await
-            .map_err(|e| e.to_string())?;
+        let opportunity = self.opportunity_repo.update(opportunity).await?;
+        self.record_events
+            .publish(RecordEvent::updated(
+                objects::OPPORTUNITY,
+                opportunity.workspace_id,
+                &previous,
+                &opportunity,
+            ))
+            .await;
 
         Ok(())
     }
 
     async fn list_opportunities(&self) -> Result<Vec<crate::domain::Opportunity>, String> {
+        self.identity_provider
+            .authorize(&Permission::read(objects::OPPORTUNITY))
+            .await
+            .map_err(|e| e.to_string())?;
         self.opportunity_repo
             .find_all()
             .await
             .map_err(|e| e.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::context::run_as;
+    use crate::application::use_cases::testing::{
+        acting_as, identity_provider, member, user, workspace,
+    };
+    use crate::domain::Opportunity;
+    use crate::infrastructure::messaging::InMemoryEventBus;
+    use crate::infrastructure::persistence::in_memory_repo::InMemoryRepo;
+
+    #[tokio::test]
+    async fn test_moves_from_a_stale_board_conflict() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let board = RecordBoardCard {
+            opportunity_repo: repo.clone(),
+            identity_provider: identity_provider(&repo),
+            record_events: Arc::new(RecordEvents::new(Arc::new(InMemoryEventBus::new()))),
+        };
+        let a = workspace(&repo).await;
+        let ada = user(&repo, "ada@example.com").await;
+        let admin = member(&repo, a, &ada, "Admin").await;
+
+        run_as(acting_as(&ada, &admin), async {
+            let card = OpportunityRepository::create(
+                repo.as_ref(),
+                Opportunity {
+                    workspace_id: a,
+                    ..Opportunity::new("Acme".to_string(), OpportunityStage::Prospecting, 1_000)
+                },
+            )
+            .await
+            .unwrap();
+
+            board
+                .move_board_card(card.id, OpportunityStage::Qualification, card.version)
+                .await
+                .unwrap();
+            // A second board still showing the card as it was
+            assert!(matches!(
+                board
+                    .move_board_card(card.id, OpportunityStage::Negotiation, card.version)
+                    .await,
+                Err(DomainError::Conflict(_))
+            ));
+
+            let stored = OpportunityRepository::find_by_id(repo.as_ref(), card.id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(stored.stage, OpportunityStage::Qualification);
+            assert_eq!(stored.version, card.version + 1);
+        })
+        .await;
+    }
+}
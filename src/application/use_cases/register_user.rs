@@ -1,22 +1,43 @@
+use crate::application::ports::identity::PasswordHasher;
 use crate::application::ports::output::UserRepository;
+use crate::application::use_cases::verify_email::VerifyEmail;
 use crate::domain::{entities::User, states::UserState, DomainError};
 use chrono::Utc;
 use std::sync::Arc;
 use uuid::Uuid;
 
+pub const MIN_PASSWORD_LENGTH: usize = 8;
+
+/// Password policy shared by registration and password reset
+pub fn validate_password(password: &str) -> Result<(), DomainError> {
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(DomainError::Validation(format!(
+            "Password must be at least {} characters",
+            MIN_PASSWORD_LENGTH
+        )));
+    }
+    Ok(())
+}
+
 pub struct RegisterUser {
     pub user_repo: Arc<dyn UserRepository>,
+    pub password_hasher: Arc<dyn PasswordHasher>,
+    pub verify_email: Arc<VerifyEmail>,
 }
 
 impl RegisterUser {
     pub async fn execute(&self, email: String, password: String) -> Result<User, DomainError> {
+        validate_password(&password)?;
+
         // Check if user exists
-        if let Some(_) = self.user_repo.find_by_email(&email).await? {
+        if self.user_repo.find_by_email(&email).await?.is_some() {
             return Err(DomainError::Validation("User already exists".to_string()));
         }
 
-        // Mock hashing for demo
-        let password_hash = format!("hashed_{}", password);
+        let password_hash = self
+            .password_hasher
+            .hash(&password)
+            .map_err(DomainError::InfrastructureError)?;
 
         let user = User {
             id: Uuid::new_v4(),
@@ -27,6 +48,13 @@ impl RegisterUser {
             updated_at: Utc::now(),
         };
 
-        self.user_repo.create(user).await
+        let user = self.user_repo.create(user).await?;
+
+        // The account exists either way; a lost mail can be resent after logging in
+        if let Err(e) = self.verify_email.send(&user).await {
+            tracing::warn!("Could not send verification email to {}: {}", user.email, e);
+        }
+
+        Ok(user)
     }
 }
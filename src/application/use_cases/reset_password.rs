@@ -0,0 +1,294 @@
+use crate::application::ports::email::{EmailProvider, SendEmailRequest};
+use crate::application::ports::identity::PasswordHasher;
+use crate::application::ports::output::{SessionRepository, UserRepository, UserTokenRepository};
+use crate::application::use_cases::register_user::validate_password;
+use crate::domain::entities::{User, UserToken};
+use crate::domain::states::{UserState, UserTokenPurpose};
+use crate::domain::DomainError;
+use crate::shared::token;
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub const RESET_TTL_MINUTES: i64 = 60;
+/// Reset emails sent to one address per hour
+pub const MAX_RESET_REQUESTS_PER_HOUR: u64 = 3;
+
+/// "Forgot password" flow: a single-use token is emailed and exchanged for a new password
+pub struct ResetPassword {
+    user_repo: Arc<dyn UserRepository>,
+    token_repo: Arc<dyn UserTokenRepository>,
+    session_repo: Arc<dyn SessionRepository>,
+    password_hasher: Arc<dyn PasswordHasher>,
+    email_provider: Arc<dyn EmailProvider>,
+    /// Where the app is served, e.g. `https://oxicrm.app`; reset links point there
+    app_url: String,
+}
+
+impl ResetPassword {
+    pub fn new(
+        user_repo: Arc<dyn UserRepository>,
+        token_repo: Arc<dyn UserTokenRepository>,
+        session_repo: Arc<dyn SessionRepository>,
+        password_hasher: Arc<dyn PasswordHasher>,
+        email_provider: Arc<dyn EmailProvider>,
+        app_url: String,
+    ) -> Self {
+        Self {
+            user_repo,
+            token_repo,
+            session_repo,
+            password_hasher,
+            email_provider,
+            app_url,
+        }
+    }
+
+    /// Emails a reset link if `email` belongs to an account that may sign in. Succeeds
+    /// the same way whether or not a mail was sent, so callers cannot probe for accounts.
+    pub async fn request(&self, email: &str) -> Result<(), DomainError> {
+        let user = match self.user_repo.find_by_email(email.trim()).await? {
+            Some(user) if matches!(user.state, UserState::Unverified | UserState::Active) => user,
+            _ => return Ok(()),
+        };
+
+        let now = Utc::now();
+        let recent = self
+            .token_repo
+            .count_since(
+                user.id,
+                UserTokenPurpose::PasswordReset,
+                now - Duration::hours(1),
+            )
+            .await?;
+        if recent >= MAX_RESET_REQUESTS_PER_HOUR {
+            tracing::warn!("Password reset rate limit reached for {}", user.email);
+            return Ok(());
+        }
+
+        let raw_token = token::generate();
+        let reset = UserToken {
+            id: Uuid::new_v4(),
+            created_at: now,
+            user_id: user.id,
+            purpose: UserTokenPurpose::PasswordReset,
+            token_hash: token::hash(&raw_token),
+            expires_at: now + Duration::minutes(RESET_TTL_MINUTES),
+            used_at: None,
+        };
+        self.token_repo.create(reset).await?;
+
+        let request = SendEmailRequest {
+            from: "noreply@oxicrm.com".to_string(),
+            to: user.email.clone(),
+            cc: None,
+            bcc: None,
+            subject: "Reset your password".to_string(),
+            body_text: format!(
+                "Choose a new password at {}/reset-password/{}\n\n\
+                 The link expires in {} minutes. If you did not ask for a reset, \
+                 you can ignore this email.",
+                self.app_url, raw_token, RESET_TTL_MINUTES
+            ),
+            body_html: None,
+            metadata: None,
+        };
+        self.email_provider
+            .send_email(request)
+            .await
+            .map_err(DomainError::InfrastructureError)?;
+
+        Ok(())
+    }
+
+    /// The unused, unexpired reset token behind `raw_token`
+    pub async fn find_valid(&self, raw_token: &str) -> Result<UserToken, DomainError> {
+        let reset = self
+            .token_repo
+            .find_by_token_hash(&token::hash(raw_token))
+            .await?
+            .filter(|t| t.purpose == UserTokenPurpose::PasswordReset)
+            .ok_or(DomainError::NotFound)?;
+        if !reset.is_usable(Utc::now()) {
+            return Err(DomainError::InvalidState(
+                "Reset link has expired or was already used".to_string(),
+            ));
+        }
+        Ok(reset)
+    }
+
+    /// Replaces the password and signs the user out of every existing session. The user's
+    /// other reset links stop working too.
+    pub async fn reset(&self, raw_token: &str, new_password: &str) -> Result<User, DomainError> {
+        let reset = self.find_valid(raw_token).await?;
+        validate_password(new_password)?;
+
+        let mut user = self
+            .user_repo
+            .find_by_id(reset.user_id)
+            .await?
+            .ok_or(DomainError::NotFound)?;
+        if !matches!(user.state, UserState::Unverified | UserState::Active) {
+            return Err(DomainError::Permission("Account is disabled".to_string()));
+        }
+
+        // Consume the token first so it cannot be replayed if a later step fails. Only one
+        // of two concurrent resets with the same token gets to mark it.
+        if !self.token_repo.mark_used(reset.id, Utc::now()).await? {
+            return Err(DomainError::InvalidState(
+                "Reset link has expired or was already used".to_string(),
+            ));
+        }
+
+        user.password_hash = self
+            .password_hasher
+            .hash(new_password)
+            .map_err(DomainError::InfrastructureError)?;
+        // Receiving the reset mail proves ownership of the address
+        if user.state == UserState::Unverified {
+            user.transition_to(UserState::Active)?;
+        }
+        user.updated_at = Utc::now();
+        let user = self.user_repo.update(user).await?;
+
+        self.token_repo
+            .mark_all_used(user.id, UserTokenPurpose::PasswordReset, Utc::now())
+            .await?;
+        self.session_repo.delete_by_user_id(user.id).await?;
+        Ok(user)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::use_cases::testing::{session, user};
+    use crate::infrastructure::email::MockEmailProvider;
+    use crate::infrastructure::identity::Argon2PasswordHasher;
+    use crate::infrastructure::persistence::in_memory_repo::InMemoryRepo;
+
+    fn reset_password(
+        repo: &Arc<InMemoryRepo>,
+        email_provider: &MockEmailProvider,
+    ) -> ResetPassword {
+        ResetPassword::new(
+            repo.clone(),
+            repo.clone(),
+            repo.clone(),
+            Arc::new(Argon2PasswordHasher::new()),
+            Arc::new(email_provider.clone()),
+            "https://oxicrm.test".to_string(),
+        )
+    }
+
+    /// The raw tokens of the reset links sent so far, oldest first. Links are absolute.
+    async fn sent_tokens(email_provider: &MockEmailProvider) -> Vec<String> {
+        email_provider
+            .get_sent_emails()
+            .await
+            .iter()
+            .filter_map(|email| {
+                email
+                    .body_text
+                    .split("https://oxicrm.test/reset-password/")
+                    .nth(1)
+            })
+            .map(|rest| rest.split_whitespace().next().unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_reset_links_are_only_sent_to_accounts() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let email_provider = MockEmailProvider::new();
+        let reset_password = reset_password(&repo, &email_provider);
+        let ada = user(&repo, "ada@example.com").await;
+
+        reset_password.request("nobody@example.com").await.unwrap();
+        assert!(email_provider.get_sent_emails().await.is_empty());
+
+        reset_password.request(" ada@example.com ").await.unwrap();
+        let sent = email_provider.get_sent_emails().await;
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].to, ada.email);
+        let tokens = sent_tokens(&email_provider).await;
+        let reset = reset_password.find_valid(&tokens[0]).await.unwrap();
+        assert_eq!(reset.user_id, ada.id);
+    }
+
+    #[tokio::test]
+    async fn test_reset_requests_are_rate_limited() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let email_provider = MockEmailProvider::new();
+        let reset_password = reset_password(&repo, &email_provider);
+        user(&repo, "ada@example.com").await;
+
+        for _ in 0..MAX_RESET_REQUESTS_PER_HOUR + 2 {
+            reset_password.request("ada@example.com").await.unwrap();
+        }
+        assert_eq!(
+            email_provider.get_sent_emails().await.len() as u64,
+            MAX_RESET_REQUESTS_PER_HOUR
+        );
+    }
+
+    #[tokio::test]
+    async fn test_expired_reset_links_are_rejected() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let reset_password = reset_password(&repo, &MockEmailProvider::new());
+        let ada = user(&repo, "ada@example.com").await;
+        let raw_token = token::generate();
+        UserTokenRepository::create(
+            repo.as_ref(),
+            UserToken {
+                id: Uuid::new_v4(),
+                created_at: Utc::now() - Duration::minutes(RESET_TTL_MINUTES + 1),
+                user_id: ada.id,
+                purpose: UserTokenPurpose::PasswordReset,
+                token_hash: token::hash(&raw_token),
+                expires_at: Utc::now() - Duration::minutes(1),
+                used_at: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        assert!(matches!(
+            reset_password.reset(&raw_token, "a new password").await,
+            Err(DomainError::InvalidState(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_resets_use_the_link_once_and_sign_the_user_out() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let email_provider = MockEmailProvider::new();
+        let reset_password = reset_password(&repo, &email_provider);
+        let ada = user(&repo, "ada@example.com").await;
+        let signed_in = session(&repo, &ada).await;
+        reset_password.request("ada@example.com").await.unwrap();
+        reset_password.request("ada@example.com").await.unwrap();
+        let tokens = sent_tokens(&email_provider).await;
+
+        let reset = reset_password
+            .reset(&tokens[1], "a new password")
+            .await
+            .unwrap();
+        assert!(Argon2PasswordHasher::new()
+            .verify("a new password", &reset.password_hash)
+            .unwrap());
+        let session =
+            SessionRepository::find_by_token_hash(repo.as_ref(), &token::hash(&signed_in))
+                .await
+                .unwrap();
+        assert!(session.is_none());
+
+        // Neither the link just used nor the earlier one works anymore
+        for raw_token in &tokens {
+            assert!(matches!(
+                reset_password.reset(raw_token, "another password").await,
+                Err(DomainError::InvalidState(_))
+            ));
+        }
+    }
+}
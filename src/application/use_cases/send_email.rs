@@ -1,7 +1,12 @@
-use crate::application::ports::email::{EmailProvider, SendEmailRequest, TemplateEngine};
+use crate::application::ports::email::{
+    EmailProvider, SendEmailRequest, SendEmailResponse, TemplateEngine,
+};
+use crate::application::ports::identity::IdentityProvider;
 use crate::application::ports::output::{
     EmailRepository, EmailTemplateRepository, TimelineActivityRepository,
 };
+use crate::application::ports::unit_of_work::UnitOfWork;
+use crate::domain::permissions::{objects, Permission};
 use crate::domain::{DomainError, Email, EmailDirection, EmailStatus, HardGuard, TimelineActivity};
 use chrono::Utc;
 use serde::Deserialize;
@@ -34,6 +39,8 @@ pub struct SendEmail {
     timeline_repo: Arc<dyn TimelineActivityRepository>,
     email_provider: Arc<dyn EmailProvider>,
     template_engine: Arc<dyn TemplateEngine>,
+    identity_provider: Arc<dyn IdentityProvider>,
+    unit_of_work: Arc<dyn UnitOfWork>,
 }
 
 impl SendEmail {
@@ -43,6 +50,8 @@ impl SendEmail {
         timeline_repo: Arc<dyn TimelineActivityRepository>,
         email_provider: Arc<dyn EmailProvider>,
         template_engine: Arc<dyn TemplateEngine>,
+        identity_provider: Arc<dyn IdentityProvider>,
+        unit_of_work: Arc<dyn UnitOfWork>,
     ) -> Self {
         Self {
             email_repo,
@@ -50,10 +59,15 @@ impl SendEmail {
             timeline_repo,
             email_provider,
             template_engine,
+            identity_provider,
+            unit_of_work,
         }
     }
 
     pub async fn execute(&self, input: SendEmailInput) -> Result<Email, DomainError> {
+        self.identity_provider
+            .authorize(&Permission::create(objects::EMAIL))
+            .await?;
         // 1. Resolve template if provided
         let (subject, body_text, body_html, template_id) =
             if let Some(template_id) = input.template_id {
@@ -132,7 +146,9 @@ impl SendEmail {
         // Validate email
         email.validate()?;
 
-        // Create email record
+        // Create email record. The pending record is committed before the provider call so
+        // that no transaction stays open across the network and a crash mid-send leaves a
+        // trace; the bookkeeping after the call is atomic.
         let email = self.email_repo.create(email).await?;
 
         // 3. Send via provider
@@ -149,6 +165,16 @@ impl SendEmail {
 
         let send_result = self.email_provider.send_email(send_request).await;
 
+        self.unit_of_work
+            .atomically(self.record_result(email, send_result))
+            .await
+    }
+
+    async fn record_result(
+        &self,
+        email: Email,
+        send_result: Result<SendEmailResponse, String>,
+    ) -> Result<Email, DomainError> {
         // 4. Update email status based on result
         let mut updated_email = email.clone();
         match send_result {
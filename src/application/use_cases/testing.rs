@@ -0,0 +1,284 @@
+//! Fixtures for use case tests: an in-memory store, the identity provider over it and the
+//! users, members and sessions the tests act as.
+
+use crate::application::context::Actor;
+use crate::application::events::record_events::RecordEvents;
+use crate::application::ports::identity::Identity;
+use crate::application::ports::output::{
+    RoleRepository, SessionRepository, UserRepository, WorkspaceRepository,
+};
+use crate::application::use_cases::create_company::CreateCompany;
+use crate::application::use_cases::create_lead::CreateLead;
+use crate::application::use_cases::create_note::CreateNote;
+use crate::application::use_cases::create_opportunity::CreateOpportunity;
+use crate::application::use_cases::create_person::CreatePerson;
+use crate::application::use_cases::create_task::CreateTask;
+use crate::application::use_cases::manage_company::ManageCompany;
+use crate::application::use_cases::manage_custom_object_data::ManageCustomObjectData;
+use crate::application::use_cases::manage_lead::ManageLead;
+use crate::application::use_cases::manage_note::ManageNote;
+use crate::application::use_cases::manage_opportunity::ManageOpportunity;
+use crate::application::use_cases::manage_person::ManagePerson;
+use crate::application::use_cases::manage_task::ManageTask;
+use crate::application::use_cases::manage_workflow_versions::ManageWorkflowVersions;
+use crate::application::use_cases::send_email::SendEmail;
+use crate::application::workflow::executor::WorkflowExecutor;
+use crate::application::workflow::records::RecordActions;
+use crate::domain::permissions::{Action, RoleDefinition};
+use crate::domain::states::{UserState, WorkspaceState};
+use crate::domain::{Session, User, Workspace, WorkspaceMember};
+use crate::infrastructure::email::{MockEmailProvider, SimpleTemplateEngine};
+use crate::infrastructure::identity::RepositoryIdentityProvider;
+use crate::infrastructure::messaging::InMemoryEventBus;
+use crate::infrastructure::persistence::in_memory_repo::InMemoryRepo;
+use crate::shared::token;
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub fn identity_provider(repo: &Arc<InMemoryRepo>) -> Arc<RepositoryIdentityProvider> {
+    Arc::new(RepositoryIdentityProvider::new(
+        repo.clone(),
+        repo.clone(),
+        repo.clone(),
+        repo.clone(),
+        repo.clone(),
+        repo.clone(),
+    ))
+}
+
+pub async fn workspace(repo: &InMemoryRepo) -> Uuid {
+    let id = Uuid::new_v4();
+    WorkspaceRepository::create(
+        repo,
+        Workspace {
+            id,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            subdomain: id.simple().to_string(),
+            state: WorkspaceState::Active,
+        },
+    )
+    .await
+    .unwrap();
+    id
+}
+
+pub async fn user(repo: &InMemoryRepo, email: &str) -> User {
+    UserRepository::create(
+        repo,
+        User {
+            id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            email: email.to_string(),
+            password_hash: String::new(),
+            state: UserState::Active,
+        },
+    )
+    .await
+    .unwrap()
+}
+
+/// Adds `user` to the workspace with `role`
+pub async fn member(
+    repo: &InMemoryRepo,
+    workspace_id: Uuid,
+    user: &User,
+    role: &str,
+) -> WorkspaceMember {
+    repo.add_member(WorkspaceMember {
+        id: Uuid::new_v4(),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        user_id: user.id,
+        workspace_id,
+        role: role.to_string(),
+        name: user.email.clone(),
+        suspended_at: None,
+    })
+    .await
+    .unwrap()
+}
+
+/// Defines a custom role granting the actions paired with each object
+pub async fn custom_role(
+    repo: &InMemoryRepo,
+    workspace_id: Uuid,
+    name: &str,
+    permissions: &[(&str, &[Action])],
+) {
+    RoleRepository::create(
+        repo,
+        RoleDefinition {
+            id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            workspace_id,
+            name: name.to_string(),
+            permissions: permissions
+                .iter()
+                .map(|(object, actions)| (object.to_string(), actions.to_vec()))
+                .collect(),
+        },
+    )
+    .await
+    .unwrap();
+}
+
+/// `SendEmail` delivering through `email_provider`
+pub fn send_email(repo: &Arc<InMemoryRepo>, email_provider: &MockEmailProvider) -> Arc<SendEmail> {
+    Arc::new(SendEmail::new(
+        repo.clone(),
+        repo.clone(),
+        repo.clone(),
+        Arc::new(email_provider.clone()),
+        Arc::new(SimpleTemplateEngine::new()),
+        identity_provider(repo),
+        repo.clone(),
+    ))
+}
+
+/// The use cases record steps go through over `repo`, as in `main`, publishing record
+/// events to `event_bus`
+pub fn record_actions(
+    repo: &Arc<InMemoryRepo>,
+    event_bus: &Arc<InMemoryEventBus>,
+) -> Arc<RecordActions> {
+    let identity_provider = identity_provider(repo);
+    let event_bus = event_bus.clone();
+    let record_events = Arc::new(RecordEvents::new(event_bus.clone()));
+    Arc::new(RecordActions {
+        create_person: Arc::new(CreatePerson::new(
+            repo.clone(),
+            identity_provider.clone(),
+            record_events.clone(),
+        )),
+        manage_person: Arc::new(ManagePerson::new(
+            repo.clone(),
+            identity_provider.clone(),
+            record_events.clone(),
+        )),
+        person_repo: repo.clone(),
+        create_company: Arc::new(CreateCompany::new(
+            repo.clone(),
+            identity_provider.clone(),
+            record_events.clone(),
+        )),
+        manage_company: Arc::new(ManageCompany::new(
+            repo.clone(),
+            identity_provider.clone(),
+            record_events.clone(),
+        )),
+        company_repo: repo.clone(),
+        create_opportunity: Arc::new(CreateOpportunity::new(
+            repo.clone(),
+            identity_provider.clone(),
+            record_events.clone(),
+        )),
+        manage_opportunity: Arc::new(ManageOpportunity::new(
+            repo.clone(),
+            identity_provider.clone(),
+            record_events.clone(),
+        )),
+        create_task: Arc::new(CreateTask::new(
+            repo.clone(),
+            identity_provider.clone(),
+            record_events.clone(),
+        )),
+        manage_task: Arc::new(ManageTask::new(
+            repo.clone(),
+            identity_provider.clone(),
+            record_events.clone(),
+        )),
+        create_note: Arc::new(CreateNote::new(
+            repo.clone(),
+            identity_provider.clone(),
+            record_events.clone(),
+        )),
+        manage_note: Arc::new(ManageNote::new(
+            repo.clone(),
+            identity_provider.clone(),
+            record_events.clone(),
+        )),
+        create_lead: Arc::new(CreateLead::new(
+            repo.clone(),
+            event_bus,
+            identity_provider.clone(),
+            record_events.clone(),
+        )),
+        manage_lead: Arc::new(ManageLead::new(
+            repo.clone(),
+            repo.clone(),
+            identity_provider.clone(),
+            record_events.clone(),
+        )),
+        manage_custom_object_data: Arc::new(ManageCustomObjectData::new(
+            repo.clone(),
+            repo.clone(),
+            identity_provider.clone(),
+            record_events,
+        )),
+        identity_provider,
+    })
+}
+
+/// The workflow executor over `repo`, with record steps going through `record_actions`
+pub fn executor(repo: &Arc<InMemoryRepo>) -> Arc<WorkflowExecutor> {
+    executor_on(repo, &Arc::new(InMemoryEventBus::new()))
+}
+
+/// `executor` with the record events of its steps published to `event_bus`
+pub fn executor_on(
+    repo: &Arc<InMemoryRepo>,
+    event_bus: &Arc<InMemoryEventBus>,
+) -> Arc<WorkflowExecutor> {
+    Arc::new(WorkflowExecutor::new(
+        repo.clone(),
+        repo.clone(),
+        repo.clone(),
+        repo.clone(),
+        send_email(repo, &MockEmailProvider::new()),
+        record_actions(repo, event_bus),
+    ))
+}
+
+/// `ManageWorkflowVersions` over `repo`, running workflows with `executor`
+pub fn workflow_versions(repo: &Arc<InMemoryRepo>) -> ManageWorkflowVersions {
+    ManageWorkflowVersions::new(
+        repo.clone(),
+        repo.clone(),
+        repo.clone(),
+        repo.clone(),
+        executor(repo),
+        identity_provider(repo),
+    )
+}
+
+/// `user` acting as `member`, as a request would after authenticating
+pub fn acting_as(user: &User, member: &WorkspaceMember) -> Actor {
+    Actor::User(Box::new(Identity {
+        user: user.clone(),
+        member: Some(member.clone()),
+        api_key: None,
+    }))
+}
+
+/// Opens a session for `user` and returns its token
+pub async fn session(repo: &InMemoryRepo, user: &User) -> String {
+    let raw = token::generate();
+    SessionRepository::create(
+        repo,
+        Session {
+            id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            user_id: user.id,
+            token_hash: token::hash(&raw),
+            expires_at: Utc::now() + Duration::days(1),
+            sso_workspace_id: None,
+        },
+    )
+    .await
+    .unwrap();
+    raw
+}
@@ -0,0 +1,201 @@
+use crate::application::context::{current_actor, Actor};
+use crate::application::ports::email::{EmailProvider, SendEmailRequest};
+use crate::application::ports::output::{UserRepository, UserTokenRepository};
+use crate::domain::entities::{User, UserToken};
+use crate::domain::states::{UserState, UserTokenPurpose};
+use crate::domain::DomainError;
+use crate::shared::token;
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub const VERIFICATION_TTL_HOURS: i64 = 48;
+
+/// Confirms that a user owns their email address, moving them from `Unverified` to `Active`
+pub struct VerifyEmail {
+    user_repo: Arc<dyn UserRepository>,
+    token_repo: Arc<dyn UserTokenRepository>,
+    email_provider: Arc<dyn EmailProvider>,
+    /// Where the app is served, e.g. `https://oxicrm.app`; verification links point there
+    app_url: String,
+}
+
+impl VerifyEmail {
+    pub fn new(
+        user_repo: Arc<dyn UserRepository>,
+        token_repo: Arc<dyn UserTokenRepository>,
+        email_provider: Arc<dyn EmailProvider>,
+        app_url: String,
+    ) -> Self {
+        Self {
+            user_repo,
+            token_repo,
+            email_provider,
+            app_url,
+        }
+    }
+
+    /// Emails `user` a verification link. Users are not workspace members yet at this
+    /// point, so the mail goes straight to the provider instead of through `SendEmail`.
+    pub async fn send(&self, user: &User) -> Result<(), DomainError> {
+        if user.state != UserState::Unverified {
+            return Err(DomainError::InvalidState(
+                "Email is already verified".to_string(),
+            ));
+        }
+
+        let raw_token = token::generate();
+        let now = Utc::now();
+        let verification = UserToken {
+            id: Uuid::new_v4(),
+            created_at: now,
+            user_id: user.id,
+            purpose: UserTokenPurpose::EmailVerification,
+            token_hash: token::hash(&raw_token),
+            expires_at: now + Duration::hours(VERIFICATION_TTL_HOURS),
+            used_at: None,
+        };
+        self.token_repo.create(verification).await?;
+
+        let request = SendEmailRequest {
+            from: "noreply@oxicrm.com".to_string(),
+            to: user.email.clone(),
+            cc: None,
+            bcc: None,
+            subject: "Verify your email address".to_string(),
+            body_text: format!(
+                "Confirm your email address at {}/verify-email/{}\n\n\
+                 The link expires in {} hours.",
+                self.app_url, raw_token, VERIFICATION_TTL_HOURS
+            ),
+            body_html: None,
+            metadata: None,
+        };
+        self.email_provider
+            .send_email(request)
+            .await
+            .map_err(DomainError::InfrastructureError)?;
+
+        Ok(())
+    }
+
+    /// Sends a fresh link to the signed-in user
+    pub async fn resend(&self) -> Result<(), DomainError> {
+        match current_actor() {
+            Some(Actor::User(identity)) => self.send(&identity.user).await,
+            _ => Err(DomainError::Permission(
+                "Authentication required".to_string(),
+            )),
+        }
+    }
+
+    /// Consumes an emailed token and activates its user
+    pub async fn verify(&self, raw_token: &str) -> Result<User, DomainError> {
+        let verification = self
+            .token_repo
+            .find_by_token_hash(&token::hash(raw_token))
+            .await?
+            .filter(|t| t.purpose == UserTokenPurpose::EmailVerification)
+            .ok_or(DomainError::NotFound)?;
+        // Only one of two concurrent requests with the same token gets to mark it used
+        if !verification.is_usable(Utc::now())
+            || !self
+                .token_repo
+                .mark_used(verification.id, Utc::now())
+                .await?
+        {
+            return Err(DomainError::InvalidState(
+                "Verification link has expired or was already used".to_string(),
+            ));
+        }
+
+        let mut user = self
+            .user_repo
+            .find_by_id(verification.user_id)
+            .await?
+            .ok_or(DomainError::NotFound)?;
+        user.transition_to(UserState::Active)?;
+        self.user_repo.update(user).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::use_cases::testing::user;
+    use crate::infrastructure::email::MockEmailProvider;
+    use crate::infrastructure::persistence::in_memory_repo::InMemoryRepo;
+
+    fn verify_email(repo: &Arc<InMemoryRepo>, email_provider: &MockEmailProvider) -> VerifyEmail {
+        VerifyEmail::new(
+            repo.clone(),
+            repo.clone(),
+            Arc::new(email_provider.clone()),
+            "https://oxicrm.test".to_string(),
+        )
+    }
+
+    async fn unverified(repo: &InMemoryRepo, email: &str) -> User {
+        let mut user = user(repo, email).await;
+        user.state = UserState::Unverified;
+        UserRepository::update(repo, user).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_verification_links_are_absolute() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let email_provider = MockEmailProvider::new();
+        let verify_email = verify_email(&repo, &email_provider);
+        let ada = unverified(&repo, "ada@example.com").await;
+
+        verify_email.send(&ada).await.unwrap();
+        let sent = email_provider.get_sent_emails().await;
+        let raw_token = sent[0]
+            .body_text
+            .split("https://oxicrm.test/verify-email/")
+            .nth(1)
+            .and_then(|rest| rest.split_whitespace().next())
+            .unwrap();
+
+        let verified = verify_email.verify(raw_token).await.unwrap();
+        assert_eq!(verified.id, ada.id);
+        assert_eq!(verified.state, UserState::Active);
+    }
+
+    #[tokio::test]
+    async fn test_verification_links_are_used_once() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let verify_email = verify_email(&repo, &MockEmailProvider::new());
+        let ada = unverified(&repo, "ada@example.com").await;
+        let raw_token = token::generate();
+        UserTokenRepository::create(
+            repo.as_ref(),
+            UserToken {
+                id: Uuid::new_v4(),
+                created_at: Utc::now(),
+                user_id: ada.id,
+                purpose: UserTokenPurpose::EmailVerification,
+                token_hash: token::hash(&raw_token),
+                expires_at: Utc::now() + Duration::hours(VERIFICATION_TTL_HOURS),
+                used_at: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let (first, second) = tokio::join!(
+            verify_email.verify(&raw_token),
+            verify_email.verify(&raw_token)
+        );
+        assert_eq!([&first, &second].iter().filter(|r| r.is_ok()).count(), 1);
+        assert!(matches!(
+            verify_email.verify(&raw_token).await,
+            Err(DomainError::InvalidState(_))
+        ));
+        let ada = UserRepository::find_by_id(repo.as_ref(), ada.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(ada.state, UserState::Active);
+    }
+}
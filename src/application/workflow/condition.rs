@@ -0,0 +1,232 @@
+//! Conditions of `IfElse` and `Condition` steps, e.g.
+//! `{"all": [{"field": "trigger.record.stage", "operator": "eq", "value": "Won"},
+//! {"any": [...]}]}`. Fields are paths into the run context; values may use templates.
+
+use crate::application::workflow::run_context::{parse_path, RunContext};
+use crate::domain::DomainError;
+use serde::Deserialize;
+use serde_json::Value;
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Operator {
+    Eq,
+    Neq,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    /// Substring of a text, or element of a list
+    Contains,
+    NotContains,
+    /// Null, missing, or an empty text, list or object; takes no value
+    IsEmpty,
+    IsNotEmpty,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum Condition {
+    All {
+        all: Vec<Condition>,
+    },
+    Any {
+        any: Vec<Condition>,
+    },
+    Compare {
+        field: String,
+        operator: Operator,
+        #[serde(default)]
+        value: Value,
+    },
+}
+
+impl Condition {
+    /// Parses and validates the `condition` of a step's settings
+    pub fn from_settings(settings: &Value) -> Result<Self, DomainError> {
+        let condition = settings
+            .get("condition")
+            .ok_or_else(|| DomainError::Validation("Missing condition in settings".to_string()))?;
+        let condition: Self = serde_json::from_value(condition.clone()).map_err(|_| {
+            DomainError::Validation(
+                "Invalid condition, expected {all: [...]}, {any: [...]} or {field, operator, value}"
+                    .to_string(),
+            )
+        })?;
+        condition.validate()?;
+        Ok(condition)
+    }
+
+    fn validate(&self) -> Result<(), DomainError> {
+        match self {
+            Condition::All { all: conditions } | Condition::Any { any: conditions } => {
+                if conditions.is_empty() {
+                    return Err(DomainError::Validation(
+                        "Condition groups cannot be empty".to_string(),
+                    ));
+                }
+                conditions.iter().try_for_each(Condition::validate)
+            }
+            Condition::Compare { field, .. } => parse_path(field).map(|_| ()),
+        }
+    }
+
+    pub fn evaluate(&self, context: &RunContext) -> Result<bool, DomainError> {
+        match self {
+            Condition::All { all } => {
+                for condition in all {
+                    if !condition.evaluate(context)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            Condition::Any { any } => {
+                for condition in any {
+                    if condition.evaluate(context)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            Condition::Compare {
+                field,
+                operator,
+                value,
+            } => Ok(compare(&context.get(field)?, *operator, value)),
+        }
+    }
+}
+
+/// Numbers compare as numbers and texts in byte order, which suits ISO dates. Ordering
+/// other values, or values of different types, never holds.
+fn compare(actual: &Value, operator: Operator, expected: &Value) -> bool {
+    match operator {
+        Operator::Eq => equals(actual, expected),
+        Operator::Neq => !equals(actual, expected),
+        Operator::Gt => order(actual, expected) == Some(Ordering::Greater),
+        Operator::Gte => matches!(
+            order(actual, expected),
+            Some(Ordering::Greater | Ordering::Equal)
+        ),
+        Operator::Lt => order(actual, expected) == Some(Ordering::Less),
+        Operator::Lte => matches!(
+            order(actual, expected),
+            Some(Ordering::Less | Ordering::Equal)
+        ),
+        Operator::Contains => contains(actual, expected),
+        Operator::NotContains => !contains(actual, expected),
+        Operator::IsEmpty => is_empty(actual),
+        Operator::IsNotEmpty => !is_empty(actual),
+    }
+}
+
+/// Numbers are equal whatever their representation, other values when identical
+pub fn equals(actual: &Value, expected: &Value) -> bool {
+    match (actual.as_f64(), expected.as_f64()) {
+        (Some(a), Some(b)) => a == b,
+        _ => actual == expected,
+    }
+}
+
+fn order(actual: &Value, expected: &Value) -> Option<Ordering> {
+    match (actual, expected) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64()?.partial_cmp(&b.as_f64()?),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
+
+fn contains(actual: &Value, expected: &Value) -> bool {
+    match (actual, expected) {
+        (Value::String(text), Value::String(part)) => text.contains(part.as_str()),
+        (Value::Array(items), _) => items.iter().any(|item| equals(item, expected)),
+        _ => false,
+    }
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(s) => s.is_empty(),
+        Value::Array(items) => items.is_empty(),
+        Value::Object(fields) => fields.is_empty(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn context() -> RunContext {
+        RunContext::new(Some(json!({
+            "record": {
+                "stage": "Won",
+                "amount": 1500,
+                "name": "Acme renewal",
+                "tags": ["vip", "emea"],
+                "closed_at": "2024-03-01",
+                "notes": "",
+            },
+        })))
+    }
+
+    fn holds(condition: Value) -> bool {
+        Condition::from_settings(&json!({ "condition": condition }))
+            .unwrap()
+            .evaluate(&context())
+            .unwrap()
+    }
+
+    fn compare(field: &str, operator: &str, value: Value) -> Value {
+        json!({ "field": format!("trigger.record.{}", field), "operator": operator, "value": value })
+    }
+
+    #[test]
+    fn test_comparisons() {
+        assert!(holds(compare("stage", "eq", json!("Won"))));
+        assert!(holds(compare("amount", "eq", json!(1500.0))));
+        assert!(holds(compare("stage", "neq", json!("Lost"))));
+        assert!(holds(compare("amount", "gt", json!(1000))));
+        assert!(holds(compare("amount", "lte", json!(1500))));
+        assert!(!holds(compare("amount", "lt", json!("2000"))));
+        assert!(holds(compare("closed_at", "gte", json!("2024-01-01"))));
+        assert!(holds(compare("name", "contains", json!("renewal"))));
+        assert!(holds(compare("tags", "contains", json!("vip"))));
+        assert!(holds(compare("tags", "not_contains", json!("apac"))));
+        assert!(holds(compare("notes", "is_empty", Value::Null)));
+        assert!(holds(compare("missing", "is_empty", Value::Null)));
+        assert!(holds(compare("tags", "is_not_empty", Value::Null)));
+    }
+
+    #[test]
+    fn test_groups() {
+        let won = compare("stage", "eq", json!("Won"));
+        let small = compare("amount", "lt", json!(100));
+        let vip = compare("tags", "contains", json!("vip"));
+
+        assert!(!holds(json!({ "all": [won, small] })));
+        assert!(holds(json!({ "any": [won, small] })));
+        assert!(holds(json!({ "all": [won, { "any": [small, vip] }] })));
+    }
+
+    #[test]
+    fn test_invalid_conditions_are_rejected() {
+        for condition in [
+            json!({ "all": [] }),
+            json!({ "field": "trigger.record.stage", "operator": "like", "value": "W%" }),
+            json!({ "field": "record.stage", "operator": "eq", "value": "Won" }),
+            json!({ "operator": "eq", "value": "Won" }),
+        ] {
+            assert!(
+                Condition::from_settings(&json!({ "condition": condition })).is_err(),
+                "{} was accepted",
+                condition
+            );
+        }
+        assert!(Condition::from_settings(&json!({})).is_err());
+    }
+}
@@ -0,0 +1,148 @@
+//! How long a `Delay` step parks its run: for a duration, in `seconds`, `minutes`, `hours`
+//! and `days` that add up, e.g. `{"days": 1, "hours": 2}`, or until a time, e.g.
+//! `{"until": "{{trigger.record.due_at}}"}`. A time that has already passed does not wait.
+
+use crate::domain::DomainError;
+use chrono::{DateTime, Duration, Utc};
+use serde_json::Value;
+
+const UNITS: [(&str, i64); 4] = [
+    ("seconds", 1),
+    ("minutes", 60),
+    ("hours", 60 * 60),
+    ("days", 24 * 60 * 60),
+];
+
+enum Delay {
+    For(Duration),
+    Until(DateTime<Utc>),
+}
+
+/// When a run reaching the step at `now` continues, given the step's resolved settings
+pub fn wake_at(settings: &Value, now: DateTime<Utc>) -> Result<DateTime<Utc>, DomainError> {
+    let delay = parse(settings)?.ok_or_else(|| {
+        DomainError::Validation("Delay settings must be numbers and times".to_string())
+    })?;
+    match delay {
+        Delay::For(duration) => now
+            .checked_add_signed(duration)
+            .ok_or_else(|| DomainError::Validation("Delay is too long".to_string())),
+        Delay::Until(until) => Ok(until),
+    }
+}
+
+/// Checks the settings of a `Delay` step when it is added; values given by templates are
+/// only known when the run gets there
+pub fn validate_delay_settings(settings: &Value) -> Result<(), DomainError> {
+    parse(settings).map(|_| ())
+}
+
+/// `None` while some value is still a template
+fn parse(settings: &Value) -> Result<Option<Delay>, DomainError> {
+    let until = settings.get("until");
+    let units: Vec<(&str, &Value, i64)> = UNITS
+        .iter()
+        .filter_map(|&(unit, seconds)| settings.get(unit).map(|value| (unit, value, seconds)))
+        .collect();
+
+    match (until, units.is_empty()) {
+        (None, true) => Err(DomainError::Validation(
+            "Missing delay in settings, e.g. {\"minutes\": 30} or {\"until\": <time>}".to_string(),
+        )),
+        (Some(_), false) => Err(DomainError::Validation(
+            "Delays wait either for a duration or until a time, not both".to_string(),
+        )),
+        (Some(until), true) => match until {
+            Value::String(time) if is_template(time) => Ok(None),
+            Value::String(time) => DateTime::parse_from_rfc3339(time)
+                .map(|time| Some(Delay::Until(time.with_timezone(&Utc))))
+                .map_err(|_| {
+                    DomainError::Validation(format!(
+                        "until must be an RFC 3339 time, not {:?}",
+                        time
+                    ))
+                }),
+            _ => Err(DomainError::Validation(
+                "until must be an RFC 3339 time".to_string(),
+            )),
+        },
+        (None, false) => {
+            let mut total: i64 = 0;
+            for (unit, value, seconds) in units {
+                let invalid = format!("{} must be a whole number >= 0", unit);
+                let amount = match value {
+                    Value::String(amount) if is_template(amount) => return Ok(None),
+                    _ => value
+                        .as_i64()
+                        .filter(|amount| *amount >= 0)
+                        .ok_or(DomainError::Validation(invalid))?,
+                };
+                total = amount
+                    .checked_mul(seconds)
+                    .and_then(|amount| total.checked_add(amount))
+                    .ok_or_else(|| DomainError::Validation("Delay is too long".to_string()))?;
+            }
+            Duration::try_seconds(total)
+                .map(|duration| Some(Delay::For(duration)))
+                .ok_or_else(|| DomainError::Validation("Delay is too long".to_string()))
+        }
+    }
+}
+
+fn is_template(value: &str) -> bool {
+    value.contains("{{")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 30, 9, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_durations_add_up_and_times_are_kept() {
+        assert_eq!(
+            wake_at(&json!({ "days": 1, "hours": 2, "minutes": 30 }), now()).unwrap(),
+            Utc.with_ymd_and_hms(2024, 1, 31, 11, 30, 0).unwrap()
+        );
+        assert_eq!(
+            wake_at(&json!({ "seconds": 0, "next": "done" }), now()).unwrap(),
+            now()
+        );
+        assert_eq!(
+            wake_at(&json!({ "until": "2024-01-01T08:00:00+01:00" }), now()).unwrap(),
+            Utc.with_ymd_and_hms(2024, 1, 1, 7, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_delays_are_rejected() {
+        for settings in [
+            json!({}),
+            json!({ "minutes": -5 }),
+            json!({ "minutes": 1.5 }),
+            json!({ "hours": "two" }),
+            json!({ "until": "tomorrow" }),
+            json!({ "until": "2024-02-01T00:00:00Z", "days": 1 }),
+            json!({ "days": i64::MAX }),
+        ] {
+            assert!(
+                validate_delay_settings(&settings).is_err(),
+                "{} was accepted",
+                settings
+            );
+        }
+    }
+
+    #[test]
+    fn test_templates_are_checked_once_resolved() {
+        let settings = json!({ "days": "{{trigger.payload.days}}" });
+        validate_delay_settings(&settings).unwrap();
+        assert!(wake_at(&settings, now()).is_err());
+        validate_delay_settings(&json!({ "until": "{{trigger.record.due_at}}" })).unwrap();
+    }
+}
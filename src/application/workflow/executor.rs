@@ -1,107 +1,311 @@
+use crate::application::context::{self, Actor};
 use crate::application::ports::output::{
-    WorkflowRunRepository, WorkflowVersionRepository, WorkflowVersionStepRepository,
+    WorkflowRepository, WorkflowRunRepository, WorkflowVersionRepository,
+    WorkflowVersionStepRepository,
 };
 use crate::application::use_cases::send_email::{SendEmail, SendEmailInput};
-use crate::domain::states::{WorkflowRunStatus, WorkflowStepType};
-use crate::domain::{DomainError, WorkflowRun, WorkflowVersionStep};
-use chrono::Utc;
+use crate::application::workflow::condition::Condition;
+use crate::application::workflow::delay;
+use crate::application::workflow::graph::WorkflowGraph;
+use crate::application::workflow::records::RecordActions;
+use crate::application::workflow::run_context::{step_key, RunContext};
+use crate::domain::states::{WorkflowRunStatus, WorkflowStepType, WorkflowVersionStatus};
+use crate::domain::{DomainError, Workflow, WorkflowRun, WorkflowVersionStep};
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use std::collections::HashSet;
 use std::sync::Arc;
 use uuid::Uuid;
 
+/// Where a run goes after a step
+enum Flow {
+    /// On to the next step, along the branch the step's condition chose
+    Next { matched: bool },
+    /// On to the next step once the time has come
+    WaitUntil(DateTime<Utc>),
+}
+
 pub struct WorkflowExecutor {
+    workflow_repo: Arc<dyn WorkflowRepository>,
     workflow_run_repo: Arc<dyn WorkflowRunRepository>,
     workflow_version_repo: Arc<dyn WorkflowVersionRepository>,
     workflow_step_repo: Arc<dyn WorkflowVersionStepRepository>,
     send_email_use_case: Arc<SendEmail>,
+    record_actions: Arc<RecordActions>,
 }
 
 impl WorkflowExecutor {
     pub fn new(
+        workflow_repo: Arc<dyn WorkflowRepository>,
         workflow_run_repo: Arc<dyn WorkflowRunRepository>,
         workflow_version_repo: Arc<dyn WorkflowVersionRepository>,
         workflow_step_repo: Arc<dyn WorkflowVersionStepRepository>,
         send_email_use_case: Arc<SendEmail>,
+        record_actions: Arc<RecordActions>,
     ) -> Self {
         Self {
+            workflow_repo,
             workflow_run_repo,
             workflow_version_repo,
             workflow_step_repo,
             send_email_use_case,
+            record_actions,
         }
     }
 
+    /// Runs the published version of `workflow` with `input`, see `WorkflowTrigger`, until it
+    /// ends or a `Delay` step parks it. Step failures are recorded on the returned run rather
+    /// than returned as errors.
     pub async fn execute_workflow(
         &self,
-        workflow_version_id: Uuid,
+        workflow: &Workflow,
+        input: Option<serde_json::Value>,
     ) -> Result<WorkflowRun, DomainError> {
-        // 1. Create workflow run
-        let mut workflow_run = WorkflowRun {
+        let workflow_version_id = workflow.last_published_version_id.ok_or_else(|| {
+            DomainError::InvalidState("Workflow has no published version".to_string())
+        })?;
+        let version = self
+            .workflow_version_repo
+            .find_by_id(workflow_version_id)
+            .await?
+            .ok_or(DomainError::NotFound)?;
+        if version.status != WorkflowVersionStatus::Published {
+            return Err(DomainError::InvalidState(
+                "Only published versions can run".to_string(),
+            ));
+        }
+
+        // 1. Get workflow steps, linked as the version's graph
+        let steps = self
+            .workflow_step_repo
+            .find_by_version_id(workflow_version_id)
+            .await?;
+        let graph = WorkflowGraph::new(steps)?;
+
+        // Records the steps write must not trigger this workflow again
+        let mut chain = context::workflow_chain();
+        chain.push(workflow.id);
+
+        // 2. Create workflow run
+        let workflow_run = WorkflowRun {
             id: Uuid::new_v4(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
             workflow_version_id,
             status: WorkflowRunStatus::Running,
+            input,
             output: None,
             error: None,
+            cursor: graph.first().map(|step| step.id),
+            wake_at: None,
+            workflow_chain: chain.clone(),
         };
 
-        let mut workflow_run = self.workflow_run_repo.create(workflow_run.clone()).await?;
+        let workflow_run = self.workflow_run_repo.create(workflow_run.clone()).await?;
+        context::run_in_workflow_chain(chain, self.run_steps(&graph, workflow, workflow_run)).await
+    }
 
-        // 2. Get workflow steps (ordered by position)
-        let mut steps = self
+    /// Continues a run from its cursor: one parked by a `Delay` step once it is due, or one
+    /// still running when the process stopped, whose interrupted step then runs again. The
+    /// run acts on behalf of its workspace, as triggered runs do, in the workflow chain it
+    /// started in.
+    pub async fn resume(&self, mut workflow_run: WorkflowRun) -> Result<WorkflowRun, DomainError> {
+        let version = self
+            .workflow_version_repo
+            .find_by_id(workflow_run.workflow_version_id)
+            .await?
+            .ok_or(DomainError::NotFound)?;
+        let workflow = self
+            .workflow_repo
+            .find_by_id(version.workflow_id)
+            .await?
+            .ok_or(DomainError::NotFound)?;
+        let steps = self
             .workflow_step_repo
-            .find_by_version_id(workflow_version_id)
+            .find_by_version_id(version.id)
             .await?;
+        let graph = match WorkflowGraph::new(steps) {
+            Ok(graph) => graph,
+            Err(e) => {
+                let context = RunContext::from_run(&workflow_run);
+                return self.fail(workflow_run, &context, e).await;
+            }
+        };
 
-        steps.sort_by_key(|step| step.position);
+        workflow_run.status = WorkflowRunStatus::Running;
+        workflow_run.wake_at = None;
+        workflow_run.updated_at = Utc::now();
+        let workflow_run = self.workflow_run_repo.update(workflow_run).await?;
 
-        // 3. Execute each step based on step_type
-        for step in steps {
-            let execute_result = self.execute_step(&step, &workflow_run).await;
+        let mut chain = workflow_run.workflow_chain.clone();
+        if chain.is_empty() {
+            chain.push(workflow.id);
+        }
+        let run =
+            context::run_in_workflow_chain(chain, self.run_steps(&graph, &workflow, workflow_run));
+        context::run_as(Actor::Workspace(workflow.workspace_id), run).await
+    }
 
-            if let Err(e) = execute_result {
-                // Mark workflow as failed
-                workflow_run.status = WorkflowRunStatus::Failed;
-                workflow_run.error = Some(e.to_string());
-                workflow_run.updated_at = Utc::now();
+    /// Runs the steps from the run's cursor on. The cursor and the outputs are saved after
+    /// every step, so that the run can be resumed from there.
+    async fn run_steps(
+        &self,
+        graph: &WorkflowGraph,
+        workflow: &Workflow,
+        mut workflow_run: WorkflowRun,
+    ) -> Result<WorkflowRun, DomainError> {
+        // 3. Execute the steps along the graph, each with its settings resolved against the
+        // input and the outputs of the steps before it
+        let mut context = RunContext::from_run(&workflow_run);
+        let mut current = match workflow_run.cursor {
+            Some(cursor) => match graph.step(cursor) {
+                Some(step) => Some(step),
+                None => {
+                    let error =
+                        DomainError::InvalidState(format!("Step {} no longer exists", cursor));
+                    return self.fail(workflow_run, &context, error).await;
+                }
+            },
+            None => None,
+        };
+        let mut executed = HashSet::new();
+        while let Some(step) = current {
+            let execute_result = if executed.insert(step.id) {
+                match context.resolve(&step.settings) {
+                    Ok(settings) => {
+                        self.execute_flow_step(step, &settings, &context, workflow, &workflow_run)
+                            .await
+                    }
+                    Err(e) => Err(e),
+                }
+            } else {
+                // Published versions cannot loop, but older ones were not checked
+                Err(DomainError::InvalidState(format!(
+                    "Step {} would run twice",
+                    step_key(step)
+                )))
+            };
+
+            let (output, flow) = match execute_result {
+                Ok(result) => result,
+                Err(e) => return self.fail(workflow_run, &context, e).await,
+            };
+            if let Some(output) = output {
+                context.record(step, output);
+            }
+            let wake_at = match flow {
+                Flow::Next { matched } => {
+                    current = graph.next(step, matched);
+                    None
+                }
+                Flow::WaitUntil(wake_at) => {
+                    current = graph.next(step, true);
+                    Some(wake_at).filter(|wake_at| *wake_at > Utc::now())
+                }
+            };
+            if current.is_none() {
+                break;
+            }
+
+            workflow_run.cursor = current.map(|next| next.id);
+            workflow_run.output = Some(context.output());
+            workflow_run.updated_at = Utc::now();
+            if let Some(wake_at) = wake_at {
+                // Parked until the run worker picks it up again
+                workflow_run.status = WorkflowRunStatus::Pending;
+                workflow_run.wake_at = Some(wake_at);
                 return self.workflow_run_repo.update(workflow_run).await;
             }
+            workflow_run = self.workflow_run_repo.update(workflow_run).await?;
         }
 
         // 4. Mark workflow as completed
         workflow_run.status = WorkflowRunStatus::Completed;
+        workflow_run.output = Some(context.output());
+        workflow_run.cursor = None;
         workflow_run.updated_at = Utc::now();
         self.workflow_run_repo.update(workflow_run).await
     }
 
+    /// Ends the run with the error of the step that failed; the outputs of the steps that
+    /// ran before stay on the run
+    async fn fail(
+        &self,
+        mut workflow_run: WorkflowRun,
+        context: &RunContext,
+        error: DomainError,
+    ) -> Result<WorkflowRun, DomainError> {
+        workflow_run.status = WorkflowRunStatus::Failed;
+        workflow_run.output = Some(context.output());
+        workflow_run.error = Some(error.to_string());
+        workflow_run.cursor = None;
+        workflow_run.wake_at = None;
+        workflow_run.updated_at = Utc::now();
+        self.workflow_run_repo.update(workflow_run).await
+    }
+
+    /// Branching steps evaluate their condition, recording whether it matched, and delays
+    /// work out when the run continues; the others execute and always match
+    async fn execute_flow_step(
+        &self,
+        step: &WorkflowVersionStep,
+        settings: &serde_json::Value,
+        context: &RunContext,
+        workflow: &Workflow,
+        workflow_run: &WorkflowRun,
+    ) -> Result<(Option<serde_json::Value>, Flow), DomainError> {
+        match step.step_type {
+            WorkflowStepType::IfElse | WorkflowStepType::Condition => {
+                let matched = Condition::from_settings(settings)?.evaluate(context)?;
+                Ok((Some(json!({ "matched": matched })), Flow::Next { matched }))
+            }
+            WorkflowStepType::Delay => {
+                let wake_at = delay::wake_at(settings, Utc::now())?;
+                Ok((
+                    Some(json!({ "wake_at": wake_at })),
+                    Flow::WaitUntil(wake_at),
+                ))
+            }
+            _ => {
+                let output = self
+                    .execute_step(step, settings, workflow, workflow_run)
+                    .await?;
+                Ok((output, Flow::Next { matched: true }))
+            }
+        }
+    }
+
+    /// Runs one step with its resolved settings; what it returns is the step's output
     async fn execute_step(
         &self,
         step: &WorkflowVersionStep,
+        settings: &serde_json::Value,
+        workflow: &Workflow,
         workflow_run: &WorkflowRun,
-    ) -> Result<(), DomainError> {
+    ) -> Result<Option<serde_json::Value>, DomainError> {
         match &step.step_type {
+            // Triggers only decide when the run starts
+            WorkflowStepType::Trigger => Ok(None),
             WorkflowStepType::SendEmail => {
-                self.execute_send_email_step(&step.settings, workflow_run)
+                self.execute_send_email_step(settings, workflow, workflow_run)
                     .await
             }
-            WorkflowStepType::CreateRecord => {
-                // TODO: Implement create record step
-                tracing::warn!("CreateRecord step not implemented yet");
-                Ok(())
-            }
-            WorkflowStepType::IfElse => {
-                // TODO: Implement if-else conditional step
-                tracing::warn!("IfElse step not implemented yet");
-                Ok(())
-            }
+            WorkflowStepType::CreateRecord
+            | WorkflowStepType::UpdateRecord
+            | WorkflowStepType::FindRecords
+            | WorkflowStepType::DeleteRecord => self
+                .record_actions
+                .execute(&step.step_type, settings, workflow.workspace_id)
+                .await
+                .map(Some),
             WorkflowStepType::Form => {
                 // TODO: Implement form step
                 tracing::warn!("Form step not implemented yet");
-                Ok(())
+                Ok(None)
             }
             _ => {
                 tracing::warn!("Step type not implemented yet");
-                Ok(())
+                Ok(None)
             }
         }
     }
@@ -109,8 +313,9 @@ impl WorkflowExecutor {
     async fn execute_send_email_step(
         &self,
         settings: &serde_json::Value,
+        workflow: &Workflow,
         workflow_run: &WorkflowRun,
-    ) -> Result<(), DomainError> {
+    ) -> Result<Option<serde_json::Value>, DomainError> {
         // Parse settings JSON
         // Expected format:
         // {
@@ -180,13 +385,16 @@ impl WorkflowExecutor {
             company_id: None,
             opportunity_id: None,
             task_id: None,
-            workflow_id: None,
+            workflow_id: Some(workflow.id),
             workflow_run_id: Some(workflow_run.id),
-            workspace_id: Uuid::default(), // TODO: Propagate workspace_id from WorkflowRun
+            workspace_id: workflow.workspace_id,
         };
 
-        self.send_email_use_case.execute(input).await?;
+        let email = self.send_email_use_case.execute(input).await?;
 
-        Ok(())
+        // Later steps can read the queued email, e.g. `{{steps.welcome.id}}`
+        serde_json::to_value(email)
+            .map(Some)
+            .map_err(|e| DomainError::InfrastructureError(e.to_string()))
     }
 }
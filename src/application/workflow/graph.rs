@@ -0,0 +1,330 @@
+//! The order in which the steps of a version run. A step continues with the step at the
+//! next position unless its settings say otherwise:
+//! - `"next": "<step>"` continues with that step, and `"next": null` ends the run
+//! - an `IfElse` step continues with its `then` or its `else` step, both required
+//! - a `Condition` step ends the run when its condition does not hold
+//!
+//! Steps are addressed by name, or by id when unnamed. Triggers only start runs and take no
+//! part in the graph, which starts at the first other step.
+
+use crate::application::workflow::condition::Condition;
+use crate::application::workflow::run_context::step_key;
+use crate::domain::states::WorkflowStepType;
+use crate::domain::{DomainError, WorkflowVersionStep};
+use serde_json::Value;
+use uuid::Uuid;
+
+pub struct WorkflowGraph {
+    /// Ordered by position
+    steps: Vec<WorkflowVersionStep>,
+    edges: Vec<Edges>,
+}
+
+/// Indexes into `steps`; `None` ends the run
+enum Edges {
+    Next(Option<usize>),
+    Branch {
+        then: Option<usize>,
+        otherwise: Option<usize>,
+    },
+}
+
+enum Target {
+    Following,
+    End,
+    Step(String),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Visit {
+    Pending,
+    InProgress,
+    Done,
+}
+
+impl WorkflowGraph {
+    /// Links the steps of a version; every step they continue with must exist
+    pub fn new(mut steps: Vec<WorkflowVersionStep>) -> Result<Self, DomainError> {
+        steps.retain(|step| step.step_type != WorkflowStepType::Trigger);
+        steps.sort_by_key(|step| step.position);
+
+        let index_of = |from: usize, target: Target| match target {
+            Target::Following => Ok(Some(from + 1).filter(|&next| next < steps.len())),
+            Target::End => Ok(None),
+            Target::Step(key) => steps
+                .iter()
+                .position(|step| step_key(step) == key)
+                .map(Some)
+                .ok_or_else(|| {
+                    DomainError::Validation(format!(
+                        "Step {:?} continues with unknown step {:?}",
+                        step_key(&steps[from]),
+                        key
+                    ))
+                }),
+        };
+        let edges = steps
+            .iter()
+            .enumerate()
+            .map(|(i, step)| match step.step_type {
+                WorkflowStepType::IfElse => Ok(Edges::Branch {
+                    then: index_of(i, target(&step.settings, "then", true)?)?,
+                    otherwise: index_of(i, target(&step.settings, "else", true)?)?,
+                }),
+                _ => Ok(Edges::Next(index_of(
+                    i,
+                    target(&step.settings, "next", false)?,
+                )?)),
+            })
+            .collect::<Result<_, DomainError>>()?;
+
+        Ok(Self { steps, edges })
+    }
+
+    /// Rejects steps that no run reaches and loops, so that every run ends
+    pub fn validate(&self) -> Result<(), DomainError> {
+        let mut visits = vec![Visit::Pending; self.steps.len()];
+        if !self.steps.is_empty() {
+            self.visit(0, &mut visits)?;
+        }
+        match visits.iter().position(|visit| *visit == Visit::Pending) {
+            Some(unreachable) => Err(DomainError::Validation(format!(
+                "Step {:?} can never run",
+                step_key(&self.steps[unreachable])
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    fn visit(&self, i: usize, visits: &mut [Visit]) -> Result<(), DomainError> {
+        visits[i] = Visit::InProgress;
+        let successors = match self.edges[i] {
+            Edges::Next(next) => [next, None],
+            Edges::Branch { then, otherwise } => [then, otherwise],
+        };
+        for next in successors.into_iter().flatten() {
+            match visits[next] {
+                Visit::Pending => self.visit(next, visits)?,
+                Visit::InProgress => {
+                    return Err(DomainError::Validation(format!(
+                        "Step {:?} loops back to step {:?}",
+                        step_key(&self.steps[i]),
+                        step_key(&self.steps[next])
+                    )))
+                }
+                Visit::Done => {}
+            }
+        }
+        visits[i] = Visit::Done;
+        Ok(())
+    }
+
+    pub fn first(&self) -> Option<&WorkflowVersionStep> {
+        self.steps.first()
+    }
+
+    /// A step by id, such as the cursor of a run
+    pub fn step(&self, id: Uuid) -> Option<&WorkflowVersionStep> {
+        self.steps.iter().find(|step| step.id == id)
+    }
+
+    /// The step to run after `step`, given whether its condition matched; steps without a
+    /// condition always match
+    pub fn next(&self, step: &WorkflowVersionStep, matched: bool) -> Option<&WorkflowVersionStep> {
+        let i = self.steps.iter().position(|s| s.id == step.id)?;
+        let next = match self.edges[i] {
+            Edges::Next(next) if matched => next,
+            Edges::Next(_) => None,
+            Edges::Branch { then, .. } if matched => then,
+            Edges::Branch { otherwise, .. } => otherwise,
+        };
+        next.map(|next| &self.steps[next])
+    }
+}
+
+/// Checks the flow settings of a step when it is added: where it continues and, for
+/// branching steps, the condition. Whether the steps it names exist is left to publishing.
+pub fn validate_flow_settings(
+    step_type: &WorkflowStepType,
+    settings: &Value,
+) -> Result<(), DomainError> {
+    match step_type {
+        WorkflowStepType::IfElse => {
+            Condition::from_settings(settings)?;
+            target(settings, "then", true)?;
+            target(settings, "else", true)?;
+        }
+        WorkflowStepType::Condition => {
+            Condition::from_settings(settings)?;
+            target(settings, "next", false)?;
+        }
+        _ => {
+            target(settings, "next", false)?;
+        }
+    }
+    Ok(())
+}
+
+fn target(settings: &Value, key: &str, required: bool) -> Result<Target, DomainError> {
+    match settings.get(key) {
+        None if required => Err(DomainError::Validation(format!(
+            "Missing {} in settings, the step to continue with or null to end the run",
+            key
+        ))),
+        None => Ok(Target::Following),
+        Some(Value::Null) => Ok(Target::End),
+        Some(Value::String(step)) => Ok(Target::Step(step.clone())),
+        Some(_) => Err(DomainError::Validation(format!(
+            "{} must name a step, or be null to end the run",
+            key
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use serde_json::json;
+
+    fn step(name: &str, step_type: WorkflowStepType, settings: Value) -> WorkflowVersionStep {
+        WorkflowVersionStep {
+            id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            workflow_version_id: Uuid::new_v4(),
+            name: Some(name.to_string()),
+            step_type,
+            settings,
+            position: 0,
+        }
+    }
+
+    fn action(name: &str, settings: Value) -> WorkflowVersionStep {
+        step(name, WorkflowStepType::SendEmail, settings)
+    }
+
+    fn branch(name: &str, then: Value, otherwise: Value) -> WorkflowVersionStep {
+        let condition = json!({ "field": "trigger.won", "operator": "eq", "value": true });
+        step(
+            name,
+            WorkflowStepType::IfElse,
+            json!({ "condition": condition, "then": then, "else": otherwise }),
+        )
+    }
+
+    fn graph(steps: Vec<WorkflowVersionStep>) -> Result<WorkflowGraph, DomainError> {
+        let steps = steps
+            .into_iter()
+            .enumerate()
+            .map(|(position, step)| WorkflowVersionStep {
+                position: position as i32,
+                ..step
+            })
+            .collect();
+        WorkflowGraph::new(steps)
+    }
+
+    /// Names of the steps a run goes through when every condition matches, or none does
+    fn path(graph: &WorkflowGraph, matched: bool) -> Vec<String> {
+        let mut path = Vec::new();
+        let mut current = graph.first();
+        while let Some(step) = current {
+            path.push(step_key(step));
+            let conditional = matches!(
+                step.step_type,
+                WorkflowStepType::IfElse | WorkflowStepType::Condition
+            );
+            current = graph.next(step, matched || !conditional);
+        }
+        path
+    }
+
+    #[test]
+    fn test_steps_follow_positions_and_branches() {
+        let graph = graph(vec![
+            step(
+                "on_create",
+                WorkflowStepType::Trigger,
+                json!({"type": "manual"}),
+            ),
+            branch("won", json!("thank"), json!("nudge")),
+            action("thank", json!({ "next": "log" })),
+            action("nudge", json!({})),
+            action("log", json!({ "next": null })),
+            action("unused", json!({})),
+        ])
+        .unwrap();
+
+        assert_eq!(path(&graph, true), ["won", "thank", "log"]);
+        assert_eq!(path(&graph, false), ["won", "nudge", "log"]);
+        assert!(matches!(
+            graph.validate(),
+            Err(DomainError::Validation(message)) if message.contains("unused")
+        ));
+    }
+
+    #[test]
+    fn test_failed_conditions_end_the_run() {
+        let condition = json!({ "field": "trigger.won", "operator": "eq", "value": true });
+        let graph = graph(vec![
+            step(
+                "only_won",
+                WorkflowStepType::Condition,
+                json!({ "condition": condition }),
+            ),
+            action("thank", json!({})),
+        ])
+        .unwrap();
+
+        graph.validate().unwrap();
+        assert_eq!(path(&graph, true), ["only_won", "thank"]);
+        assert_eq!(path(&graph, false), ["only_won"]);
+    }
+
+    #[test]
+    fn test_loops_and_unknown_steps_are_rejected() {
+        let looping = graph(vec![
+            action("a", json!({})),
+            branch("b", json!("a"), json!(null)),
+        ])
+        .unwrap();
+        assert!(matches!(
+            looping.validate(),
+            Err(DomainError::Validation(message)) if message.contains("loops back")
+        ));
+
+        assert!(graph(vec![action("a", json!({ "next": "missing" }))]).is_err());
+        assert!(graph(vec![branch("b", json!(null), json!(null))])
+            .unwrap()
+            .validate()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_flow_settings_are_checked_when_steps_are_added() {
+        let condition = json!({ "field": "trigger.won", "operator": "eq", "value": true });
+        for (step_type, settings) in [
+            (
+                WorkflowStepType::IfElse,
+                json!({ "condition": condition, "then": "a" }),
+            ),
+            (
+                WorkflowStepType::IfElse,
+                json!({ "then": "a", "else": "b" }),
+            ),
+            (WorkflowStepType::Condition, json!({})),
+            (WorkflowStepType::SendEmail, json!({ "next": 3 })),
+        ] {
+            assert!(
+                validate_flow_settings(&step_type, &settings).is_err(),
+                "{} was accepted",
+                settings
+            );
+        }
+        validate_flow_settings(
+            &WorkflowStepType::IfElse,
+            &json!({ "condition": condition, "then": "a", "else": null }),
+        )
+        .unwrap();
+    }
+}
@@ -0,0 +1,151 @@
+use crate::application::context::{self, Actor};
+use crate::application::events::record_events::RecordEvent;
+use crate::application::ports::output::WorkflowRepository;
+use crate::application::workflow::executor::WorkflowExecutor;
+use crate::application::workflow::trigger::WorkflowTrigger;
+use crate::domain::{DomainError, Workflow, WorkflowRun};
+use crate::shared::token;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Starts runs of the published workflows whose triggers fire. Each event starts a workflow
+/// at most once, however many of its triggers match, and never a workflow whose run wrote
+/// the record. Runs act on behalf of the workflow's workspace.
+pub struct WorkflowLauncher {
+    workflow_repo: Arc<dyn WorkflowRepository>,
+    executor: Arc<WorkflowExecutor>,
+}
+
+impl WorkflowLauncher {
+    pub fn new(
+        workflow_repo: Arc<dyn WorkflowRepository>,
+        executor: Arc<WorkflowExecutor>,
+    ) -> Self {
+        Self {
+            workflow_repo,
+            executor,
+        }
+    }
+
+    /// Runs the workflows of the event's workspace that watch the record
+    pub async fn on_record_event(
+        &self,
+        event: &RecordEvent,
+    ) -> Result<Vec<WorkflowRun>, DomainError> {
+        let triggers = self.triggers(Some(event.workspace_id)).await?;
+        let matching = triggers.iter().filter(|(workflow, trigger)| {
+            trigger.matches(event) && !event.workflow_chain.contains(&workflow.id)
+        });
+        let launches = first_per_workflow(matching)
+            .map(|(workflow, trigger)| (workflow, trigger.record_input(event)));
+        Ok(self.launch(launches, &event.workflow_chain).await)
+    }
+
+    /// Runs the workflows whose schedule fell due in `(since, until]`
+    pub async fn run_due_schedules(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<WorkflowRun>, DomainError> {
+        let triggers = self.triggers(None).await?;
+        let due = triggers.iter().filter(|(_, t)| t.is_due(since, until));
+        let launches = first_per_workflow(due).map(|(workflow, trigger)| {
+            let scheduled = serde_json::json!({ "scheduled_at": until });
+            (workflow, trigger.payload_input(Some(scheduled)))
+        });
+        Ok(self.launch(launches, &[]).await)
+    }
+
+    /// Runs a workflow for a call to its webhook. Callers are anonymous, the token being
+    /// their only credential, so an unknown workflow and a wrong token look the same.
+    pub async fn run_webhook(
+        &self,
+        workflow_id: Uuid,
+        given_token: &str,
+        payload: Option<Value>,
+    ) -> Result<WorkflowRun, DomainError> {
+        context::run_as_system(async {
+            let triggers = self.triggers(None).await?;
+            let (workflow, trigger) = triggers
+                .iter()
+                .find(|(workflow, trigger)| {
+                    workflow.id == workflow_id
+                        && matches!(trigger, WorkflowTrigger::Webhook { token_hash }
+                            if !token_hash.is_empty() && *token_hash == token::hash(given_token))
+                })
+                .ok_or(DomainError::NotFound)?;
+            self.execute(workflow, trigger.payload_input(payload), &[])
+                .await
+        })
+        .await
+    }
+
+    /// Published triggers, skipping (and logging) those whose settings no longer parse
+    async fn triggers(
+        &self,
+        workspace_id: Option<Uuid>,
+    ) -> Result<Vec<(Workflow, WorkflowTrigger)>, DomainError> {
+        let steps = self
+            .workflow_repo
+            .find_published_triggers(workspace_id)
+            .await?;
+        Ok(steps
+            .into_iter()
+            .filter_map(
+                |(workflow, step)| match WorkflowTrigger::from_settings(&step.settings) {
+                    Ok(trigger) => Some((workflow, trigger)),
+                    Err(e) => {
+                        tracing::warn!(
+                            "Skipping trigger {} of workflow {}: {}",
+                            step.id,
+                            workflow.id,
+                            e
+                        );
+                        None
+                    }
+                },
+            )
+            .collect())
+    }
+
+    /// A failed launch does not keep the other workflows from running
+    async fn launch<'a>(
+        &self,
+        launches: impl Iterator<Item = (&'a Workflow, Value)>,
+        chain: &[Uuid],
+    ) -> Vec<WorkflowRun> {
+        let mut runs = Vec::new();
+        for (workflow, input) in launches {
+            match self.execute(workflow, input, chain).await {
+                Ok(run) => runs.push(run),
+                Err(e) => tracing::error!("Failed to start workflow {}: {}", workflow.id, e),
+            }
+        }
+        runs
+    }
+
+    async fn execute(
+        &self,
+        workflow: &Workflow,
+        input: Value,
+        chain: &[Uuid],
+    ) -> Result<WorkflowRun, DomainError> {
+        let run = self.executor.execute_workflow(workflow, Some(input));
+        context::run_as(
+            Actor::Workspace(workflow.workspace_id),
+            context::run_in_workflow_chain(chain.to_vec(), run),
+        )
+        .await
+    }
+}
+
+fn first_per_workflow<'a>(
+    triggers: impl Iterator<Item = &'a (Workflow, WorkflowTrigger)>,
+) -> impl Iterator<Item = (&'a Workflow, &'a WorkflowTrigger)> {
+    let mut started = std::collections::HashSet::new();
+    triggers
+        .filter(move |(workflow, _)| started.insert(workflow.id))
+        .map(|(workflow, trigger)| (workflow, trigger))
+}
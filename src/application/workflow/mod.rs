@@ -1 +1,8 @@
+pub mod condition;
+pub mod delay;
 pub mod executor;
+pub mod graph;
+pub mod launcher;
+pub mod records;
+pub mod run_context;
+pub mod trigger;
@@ -0,0 +1,663 @@
+//! Steps that write and read records of a built-in or custom object, e.g.
+//! `{"object": "person", "fields": {"name": "{{trigger.payload.name}}"}}`:
+//! - `CreateRecord` creates a record from `fields`
+//! - `UpdateRecord` changes the `fields` of the record `id`, leaving the others as they are
+//! - `FindRecords` lists up to `limit` records whose fields equal those in `where`
+//! - `DeleteRecord` deletes the record `id`
+//!
+//! The created, updated or deleted record is the step's output, and found records are under
+//! `records`. Steps go through the use cases, so permissions, validation and record events
+//! apply as they do to requests.
+
+use crate::application::events::record_events::custom_record;
+use crate::application::ports::identity::IdentityProvider;
+use crate::application::ports::output::{CompanyRepository, PersonRepository};
+use crate::application::ports::query::{ListQuery, Page, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE};
+use crate::application::use_cases::create_company::{CreateCompany, CreateCompanyInput};
+use crate::application::use_cases::create_lead::{CreateLead, CreateLeadInput};
+use crate::application::use_cases::create_note::{CreateNote, CreateNoteInput};
+use crate::application::use_cases::create_opportunity::{
+    CreateOpportunity, CreateOpportunityInput,
+};
+use crate::application::use_cases::create_person::CreatePerson;
+use crate::application::use_cases::create_task::{CreateTask, CreateTaskInput};
+use crate::application::use_cases::manage_company::{ManageCompany, UpdateCompanyInput};
+use crate::application::use_cases::manage_custom_object_data::ManageCustomObjectData;
+use crate::application::use_cases::manage_lead::ManageLead;
+use crate::application::use_cases::manage_note::{ManageNote, UpdateNoteInput};
+use crate::application::use_cases::manage_opportunity::{
+    ManageOpportunity, UpdateOpportunityInput,
+};
+use crate::application::use_cases::manage_person::{ManagePerson, UpdatePersonInput};
+use crate::application::use_cases::manage_task::{ManageTask, UpdateTaskInput};
+use crate::application::workflow::condition::equals;
+use crate::domain::permissions::{objects, Permission};
+use crate::domain::states::{LeadSource, LeadStatus, WorkflowStepType};
+use crate::domain::DomainError;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::future::Future;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// The objects record steps and record triggers work on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordObject {
+    Person,
+    Company,
+    Opportunity,
+    Task,
+    Note,
+    Lead,
+    /// Records of the custom object with this metadata id
+    Custom(Uuid),
+}
+
+impl RecordObject {
+    /// Parses an object key such as `person` or `custom_object:<id>`
+    pub fn parse(object: &str) -> Option<Self> {
+        match object {
+            objects::PERSON => Some(Self::Person),
+            objects::COMPANY => Some(Self::Company),
+            objects::OPPORTUNITY => Some(Self::Opportunity),
+            objects::TASK => Some(Self::Task),
+            objects::NOTE => Some(Self::Note),
+            objects::LEAD => Some(Self::Lead),
+            _ => object
+                .strip_prefix("custom_object:")
+                .and_then(|id| Uuid::parse_str(id).ok())
+                .map(Self::Custom),
+        }
+    }
+
+    fn key(&self) -> String {
+        match self {
+            Self::Person => objects::PERSON.to_string(),
+            Self::Company => objects::COMPANY.to_string(),
+            Self::Opportunity => objects::OPPORTUNITY.to_string(),
+            Self::Task => objects::TASK.to_string(),
+            Self::Note => objects::NOTE.to_string(),
+            Self::Lead => objects::LEAD.to_string(),
+            Self::Custom(id) => objects::custom_object(*id),
+        }
+    }
+}
+
+/// Settings of a record step once its templates are resolved
+#[derive(Deserialize)]
+struct RecordSettings {
+    object: String,
+    #[serde(default)]
+    id: Option<Uuid>,
+    #[serde(default)]
+    fields: Map<String, Value>,
+    #[serde(default, rename = "where")]
+    filter: Map<String, Value>,
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+impl RecordSettings {
+    fn parse(settings: &Value) -> Result<(Self, RecordObject), DomainError> {
+        let settings: Self = serde_json::from_value(settings.clone())
+            .map_err(|e| DomainError::Validation(format!("Invalid record step: {}", e)))?;
+        let object = parse_object(&settings.object)?;
+        Ok((settings, object))
+    }
+
+    fn id(&self) -> Result<Uuid, DomainError> {
+        self.id
+            .ok_or_else(|| DomainError::Validation("Missing id in settings".to_string()))
+    }
+}
+
+/// The fields of a person a step can set when creating one
+#[derive(Deserialize)]
+struct PersonFields {
+    name: String,
+    email: String,
+    #[serde(default)]
+    position: i32,
+}
+
+/// Leads change through their status and assignment only
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LeadFields {
+    #[serde(default)]
+    status: Option<LeadStatus>,
+    #[serde(default)]
+    assigned_to_id: Option<Uuid>,
+}
+
+/// The use cases record steps go through, one pair per built-in object
+pub struct RecordActions {
+    pub create_person: Arc<CreatePerson>,
+    pub manage_person: Arc<ManagePerson>,
+    pub person_repo: Arc<dyn PersonRepository>,
+    pub create_company: Arc<CreateCompany>,
+    pub manage_company: Arc<ManageCompany>,
+    pub company_repo: Arc<dyn CompanyRepository>,
+    pub create_opportunity: Arc<CreateOpportunity>,
+    pub manage_opportunity: Arc<ManageOpportunity>,
+    pub create_task: Arc<CreateTask>,
+    pub manage_task: Arc<ManageTask>,
+    pub create_note: Arc<CreateNote>,
+    pub manage_note: Arc<ManageNote>,
+    pub create_lead: Arc<CreateLead>,
+    pub manage_lead: Arc<ManageLead>,
+    pub manage_custom_object_data: Arc<ManageCustomObjectData>,
+    pub identity_provider: Arc<dyn IdentityProvider>,
+}
+
+impl RecordActions {
+    /// Runs a record step with its resolved settings and returns the step's output
+    pub async fn execute(
+        &self,
+        step_type: &WorkflowStepType,
+        settings: &Value,
+        workspace_id: Uuid,
+    ) -> Result<Value, DomainError> {
+        let (settings, object) = RecordSettings::parse(settings)?;
+        match step_type {
+            WorkflowStepType::CreateRecord => {
+                self.create(object, settings.fields, workspace_id).await
+            }
+            WorkflowStepType::UpdateRecord => {
+                self.update(object, settings.id()?, settings.fields).await
+            }
+            WorkflowStepType::FindRecords => {
+                let limit = settings
+                    .limit
+                    .unwrap_or(DEFAULT_PAGE_SIZE as usize)
+                    .min(MAX_PAGE_SIZE as usize);
+                let records = self.find(object, &settings.filter, limit).await?;
+                Ok(json!({ "count": records.len(), "records": records }))
+            }
+            WorkflowStepType::DeleteRecord => self.delete(object, settings.id()?).await,
+            _ => Err(DomainError::InvalidState(format!(
+                "{:?} is not a record step",
+                step_type
+            ))),
+        }
+    }
+
+    async fn create(
+        &self,
+        object: RecordObject,
+        mut fields: Map<String, Value>,
+        workspace_id: Uuid,
+    ) -> Result<Value, DomainError> {
+        if let RecordObject::Custom(object_id) = object {
+            let record = self
+                .manage_custom_object_data
+                .create_record(object_id, Value::Object(fields), workspace_id)
+                .await?;
+            return Ok(custom_record(&record));
+        }
+        if object == RecordObject::Person {
+            let person: PersonFields = input(object, fields)?;
+            let person = self
+                .create_person
+                .execute(person.name, person.email, person.position, workspace_id)
+                .await?;
+            return to_json(&person);
+        }
+
+        fields.insert("workspace_id".to_string(), json!(workspace_id));
+        if object == RecordObject::Lead {
+            fields
+                .entry("source")
+                .or_insert_with(|| json!(LeadSource::default()));
+        }
+        match object {
+            RecordObject::Company => {
+                let input: CreateCompanyInput = input(object, fields)?;
+                to_json(&self.create_company.execute(input).await?)
+            }
+            RecordObject::Opportunity => {
+                let input: CreateOpportunityInput = input(object, fields)?;
+                to_json(&self.create_opportunity.execute(input).await?)
+            }
+            RecordObject::Task => {
+                let input: CreateTaskInput = input(object, fields)?;
+                to_json(&self.create_task.execute(input).await?)
+            }
+            RecordObject::Note => {
+                let input: CreateNoteInput = input(object, fields)?;
+                to_json(&self.create_note.execute(input).await?)
+            }
+            RecordObject::Lead => {
+                let input: CreateLeadInput = input(object, fields)?;
+                to_json(&self.create_lead.execute(input).await?)
+            }
+            RecordObject::Person | RecordObject::Custom(_) => unreachable!("created above"),
+        }
+    }
+
+    async fn update(
+        &self,
+        object: RecordObject,
+        id: Uuid,
+        mut fields: Map<String, Value>,
+    ) -> Result<Value, DomainError> {
+        match object {
+            RecordObject::Company => {
+                // Company updates replace every field, so the others are carried over
+                let mut merged = as_map(self.get(object, id).await?);
+                merged.extend(fields);
+                let input: UpdateCompanyInput = input(object, merged)?;
+                return to_json(&self.manage_company.update(id, input).await?);
+            }
+            RecordObject::Lead => {
+                let changes: LeadFields = input(object, fields)?;
+                let mut lead = self.manage_lead.get(id).await?;
+                if let Some(status) = changes.status {
+                    lead = self.manage_lead.update_status(id, status).await?;
+                }
+                if let Some(assignee) = changes.assigned_to_id {
+                    lead = self.manage_lead.assign(id, assignee).await?;
+                }
+                return to_json(&lead);
+            }
+            RecordObject::Custom(_) => {
+                let mut properties = as_map(self.get(object, id).await?);
+                for key in ["id", "object_metadata_id", "created_at", "updated_at"] {
+                    properties.remove(key);
+                }
+                properties.extend(fields);
+                let record = self
+                    .manage_custom_object_data
+                    .update_record(id, Value::Object(properties), None)
+                    .await?;
+                return Ok(custom_record(&record));
+            }
+            _ => {}
+        }
+
+        fields.insert("id".to_string(), json!(id));
+        match object {
+            RecordObject::Person => {
+                let input: UpdatePersonInput = input(object, fields)?;
+                to_json(&self.manage_person.update(input).await?)
+            }
+            RecordObject::Opportunity => {
+                let input: UpdateOpportunityInput = input(object, fields)?;
+                to_json(&self.manage_opportunity.update(input).await?)
+            }
+            RecordObject::Task => {
+                let input: UpdateTaskInput = input(object, fields)?;
+                to_json(&self.manage_task.update(input).await?)
+            }
+            RecordObject::Note => {
+                let input: UpdateNoteInput = input(object, fields)?;
+                to_json(&self.manage_note.update(input).await?)
+            }
+            RecordObject::Company | RecordObject::Lead | RecordObject::Custom(_) => {
+                unreachable!("updated above")
+            }
+        }
+    }
+
+    async fn delete(&self, object: RecordObject, id: Uuid) -> Result<Value, DomainError> {
+        let record = self.get(object, id).await?;
+        match object {
+            RecordObject::Person => self.manage_person.delete(id).await?,
+            RecordObject::Company => self.manage_company.delete(id).await?,
+            RecordObject::Opportunity => self.manage_opportunity.delete(id).await?,
+            RecordObject::Task => self.manage_task.delete(id).await?,
+            RecordObject::Note => self.manage_note.delete(id).await?,
+            RecordObject::Lead => self.manage_lead.delete(id).await?,
+            RecordObject::Custom(_) => self.manage_custom_object_data.delete_record(id).await?,
+        }
+        Ok(record)
+    }
+
+    /// One record as JSON, not found unless it is a record of `object`
+    async fn get(&self, object: RecordObject, id: Uuid) -> Result<Value, DomainError> {
+        let record = match object {
+            RecordObject::Person => {
+                self.authorize_read(objects::PERSON).await?;
+                self.person_repo.find_by_id(id).await?.map(|p| to_json(&p))
+            }
+            RecordObject::Company => {
+                self.authorize_read(objects::COMPANY).await?;
+                self.company_repo.find_by_id(id).await?.map(|c| to_json(&c))
+            }
+            RecordObject::Opportunity => self
+                .manage_opportunity
+                .get_by_id(id)
+                .await?
+                .map(|o| to_json(&o)),
+            RecordObject::Task => self.manage_task.get_by_id(id).await?.map(|t| to_json(&t)),
+            RecordObject::Note => self.manage_note.get_by_id(id).await?.map(|n| to_json(&n)),
+            RecordObject::Lead => Some(to_json(&self.manage_lead.get(id).await?)),
+            RecordObject::Custom(object_id) => self
+                .manage_custom_object_data
+                .get_record(id)
+                .await?
+                .filter(|record| record.object_metadata_id == object_id)
+                .map(|record| Ok(custom_record(&record))),
+        };
+        record.ok_or(DomainError::NotFound)?
+    }
+
+    /// The first `limit` records of `object` in the workspace matching `filter`, as JSON.
+    /// Objects with paginated lists are read a page at a time, and no further than needed.
+    async fn find(
+        &self,
+        object: RecordObject,
+        filter: &Map<String, Value>,
+        limit: usize,
+    ) -> Result<Vec<Value>, DomainError> {
+        let records = match object {
+            RecordObject::Person => {
+                self.authorize_read(objects::PERSON).await?;
+                find_paged(
+                    |query| async move { self.person_repo.find_page(&query).await },
+                    filter,
+                    limit,
+                )
+                .await?
+            }
+            RecordObject::Company => {
+                self.authorize_read(objects::COMPANY).await?;
+                find_paged(
+                    |query| async move { self.company_repo.find_page(&query).await },
+                    filter,
+                    limit,
+                )
+                .await?
+            }
+            RecordObject::Lead => {
+                find_paged(
+                    |query| async move { self.manage_lead.list(&query).await },
+                    filter,
+                    limit,
+                )
+                .await?
+            }
+            RecordObject::Opportunity => to_json_all(self.manage_opportunity.get_all().await?)?,
+            RecordObject::Task => to_json_all(self.manage_task.get_all().await?)?,
+            RecordObject::Note => to_json_all(self.manage_note.get_all().await?)?,
+            RecordObject::Custom(object_id) => self
+                .manage_custom_object_data
+                .list_records(object_id)
+                .await?
+                .iter()
+                .map(custom_record)
+                .collect(),
+        };
+        Ok(records
+            .into_iter()
+            .filter(|record| matches_filter(record, filter))
+            .take(limit)
+            .collect())
+    }
+
+    async fn authorize_read(&self, object: &str) -> Result<(), DomainError> {
+        self.identity_provider
+            .authorize(&Permission::read(object))
+            .await
+    }
+}
+
+/// Checks the settings of a record step when it is added. Values may still be templates,
+/// so only their shape is checked; the object must be given as is.
+pub fn validate_record_settings(
+    step_type: &WorkflowStepType,
+    settings: &Value,
+) -> Result<(), DomainError> {
+    let needs_id = match step_type {
+        WorkflowStepType::CreateRecord | WorkflowStepType::FindRecords => false,
+        WorkflowStepType::UpdateRecord | WorkflowStepType::DeleteRecord => true,
+        _ => return Ok(()),
+    };
+    let object = settings
+        .get("object")
+        .and_then(Value::as_str)
+        .ok_or_else(|| DomainError::Validation("Missing object in settings".to_string()))?;
+    parse_object(object)?;
+    for key in ["fields", "where"] {
+        if settings.get(key).is_some_and(|value| !value.is_object()) {
+            return Err(DomainError::Validation(format!(
+                "{} must be an object of field values",
+                key
+            )));
+        }
+    }
+    if needs_id && settings.get("id").is_none() {
+        return Err(DomainError::Validation(
+            "Missing id in settings, the record to change".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// The permission a record step acts with, `None` for other steps. Settings must have
+/// passed `validate_record_settings`.
+pub fn record_step_permission(
+    step_type: &WorkflowStepType,
+    settings: &Value,
+) -> Result<Option<Permission>, DomainError> {
+    let permission = match step_type {
+        WorkflowStepType::CreateRecord => Permission::create,
+        WorkflowStepType::UpdateRecord => Permission::update,
+        WorkflowStepType::FindRecords => Permission::read,
+        WorkflowStepType::DeleteRecord => Permission::delete,
+        _ => return Ok(None),
+    };
+    let object = settings
+        .get("object")
+        .and_then(Value::as_str)
+        .ok_or_else(|| DomainError::Validation("Missing object in settings".to_string()))?;
+    Ok(Some(permission(parse_object(object)?.key())))
+}
+
+fn parse_object(object: &str) -> Result<RecordObject, DomainError> {
+    RecordObject::parse(object).ok_or_else(|| {
+        DomainError::Validation(format!(
+            "Unknown object {:?}, expected a built-in object or custom_object:<id>",
+            object
+        ))
+    })
+}
+
+/// Pages through a list in its default order until `limit` records match `filter`. Filters
+/// compare JSON values loosely, see `equals`, so they are applied here rather than in the
+/// query.
+async fn find_paged<T, F, Fut>(
+    fetch: F,
+    filter: &Map<String, Value>,
+    limit: usize,
+) -> Result<Vec<Value>, DomainError>
+where
+    T: Serialize,
+    F: Fn(ListQuery) -> Fut,
+    Fut: Future<Output = Result<Page<T>, DomainError>>,
+{
+    let mut found = Vec::new();
+    // Unfiltered, the first page is the answer
+    let page_size = if filter.is_empty() {
+        limit as u64
+    } else {
+        MAX_PAGE_SIZE
+    };
+    let mut query = ListQuery {
+        limit: Some(page_size),
+        ..ListQuery::default()
+    };
+    loop {
+        let page = fetch(query.clone()).await?;
+        for record in to_json_all(page.items)? {
+            if matches_filter(&record, filter) {
+                found.push(record);
+            }
+        }
+        match page.next_cursor {
+            Some(cursor) if found.len() < limit => query.cursor = Some(cursor),
+            _ => return Ok(found),
+        }
+    }
+}
+
+/// Missing fields read as null, as they do in conditions
+fn matches_filter(record: &Value, filter: &Map<String, Value>) -> bool {
+    filter
+        .iter()
+        .all(|(field, expected)| equals(record.get(field).unwrap_or(&Value::Null), expected))
+}
+
+fn input<T: DeserializeOwned>(
+    object: RecordObject,
+    fields: Map<String, Value>,
+) -> Result<T, DomainError> {
+    serde_json::from_value(Value::Object(fields))
+        .map_err(|e| DomainError::Validation(format!("Invalid fields for {}: {}", object.key(), e)))
+}
+
+fn as_map(record: Value) -> Map<String, Value> {
+    match record {
+        Value::Object(fields) => fields,
+        _ => Map::new(),
+    }
+}
+
+fn to_json<T: Serialize>(record: &T) -> Result<Value, DomainError> {
+    serde_json::to_value(record).map_err(|e| DomainError::InfrastructureError(e.to_string()))
+}
+
+fn to_json_all<T: Serialize>(records: Vec<T>) -> Result<Vec<Value>, DomainError> {
+    records.iter().map(to_json).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::context::run_as;
+    use crate::application::use_cases::testing::{
+        acting_as, member, record_actions, user, workspace,
+    };
+    use crate::infrastructure::messaging::InMemoryEventBus;
+    use crate::infrastructure::persistence::in_memory_repo::InMemoryRepo;
+
+    #[test]
+    fn test_objects_parse_from_their_keys() {
+        let id = Uuid::new_v4();
+        for object in [
+            RecordObject::Person,
+            RecordObject::Lead,
+            RecordObject::Custom(id),
+        ] {
+            assert_eq!(RecordObject::parse(&object.key()), Some(object));
+        }
+        assert_eq!(RecordObject::parse("workflow"), None);
+        assert_eq!(RecordObject::parse("custom_object:42"), None);
+    }
+
+    #[test]
+    fn test_record_settings_are_checked_when_steps_are_added() {
+        let custom = format!("custom_object:{}", Uuid::new_v4());
+        for (step_type, settings) in [
+            (WorkflowStepType::CreateRecord, json!({ "fields": {} })),
+            (
+                WorkflowStepType::CreateRecord,
+                json!({ "object": "workflow", "fields": {} }),
+            ),
+            (
+                WorkflowStepType::CreateRecord,
+                json!({ "object": "person", "fields": "{{trigger.record}}" }),
+            ),
+            (
+                WorkflowStepType::UpdateRecord,
+                json!({ "object": custom, "fields": { "tier": "gold" } }),
+            ),
+            (WorkflowStepType::DeleteRecord, json!({ "object": "lead" })),
+            (
+                WorkflowStepType::FindRecords,
+                json!({ "object": "task", "where": ["done"] }),
+            ),
+        ] {
+            assert!(
+                validate_record_settings(&step_type, &settings).is_err(),
+                "{} was accepted",
+                settings
+            );
+        }
+
+        validate_record_settings(
+            &WorkflowStepType::UpdateRecord,
+            &json!({
+                "object": custom,
+                "id": "{{steps.find.records.0.id}}",
+                "fields": { "tier": "{{trigger.payload.tier}}" },
+            }),
+        )
+        .unwrap();
+        validate_record_settings(&WorkflowStepType::SendEmail, &json!({})).unwrap();
+    }
+
+    #[test]
+    fn test_filters_match_equal_fields() {
+        let record = json!({ "name": "Ada", "amount": 1500, "phone": null });
+        assert!(matches_filter(&record, &Map::new()));
+        assert!(matches_filter(
+            &record,
+            &as_map(json!({ "name": "Ada", "amount": 1500.0 }))
+        ));
+        assert!(matches_filter(&record, &as_map(json!({ "missing": null }))));
+        assert!(!matches_filter(&record, &as_map(json!({ "name": "Bob" }))));
+    }
+
+    #[tokio::test]
+    async fn test_found_records_span_pages_up_to_the_limit() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let actions = record_actions(&repo, &Arc::new(InMemoryEventBus::new()));
+        let a = workspace(&repo).await;
+        let ada = user(&repo, "ada@example.com").await;
+        let admin = member(&repo, a, &ada, "Admin").await;
+
+        run_as(acting_as(&ada, &admin), async {
+            let find = |filter: Value, limit: usize| {
+                let actions = actions.clone();
+                async move {
+                    actions
+                        .execute(
+                            &WorkflowStepType::FindRecords,
+                            &json!({ "object": "person", "where": filter, "limit": limit }),
+                            a,
+                        )
+                        .await
+                        .unwrap()
+                }
+            };
+            // More people than fit in one page, only the last of them a Grace
+            let count = MAX_PAGE_SIZE as usize + 5;
+            for i in 0..count {
+                let name = if i + 1 == count { "Grace" } else { "Ada" };
+                actions
+                    .execute(
+                        &WorkflowStepType::CreateRecord,
+                        &json!({
+                            "object": "person",
+                            "fields": { "name": name, "email": format!("{}@example.com", i) },
+                        }),
+                        a,
+                    )
+                    .await
+                    .unwrap();
+            }
+
+            let found = find(json!({ "name": "Grace" }), 10).await;
+            assert_eq!(found["count"], 1);
+            assert_eq!(
+                found["records"][0]["email"],
+                format!("{}@example.com", count - 1)
+            );
+            assert_eq!(find(json!({ "name": "Ada" }), 3).await["count"], 3);
+            assert_eq!(find(json!({}), 5).await["count"], 5);
+        })
+        .await;
+    }
+}
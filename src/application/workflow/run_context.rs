@@ -0,0 +1,254 @@
+//! What the steps of a run can read. String settings may embed `{{path}}` expressions, such
+//! as `{{trigger.record.email}}` or `{{steps.create_person.id}}`, which are resolved against
+//! the run's input and the outputs of the steps that ran before.
+
+use crate::domain::{DomainError, WorkflowRun, WorkflowVersionStep};
+use serde_json::{json, Map, Value};
+
+/// The run's input under `trigger` and step outputs under `steps`, keyed by step name
+#[derive(Debug, Clone)]
+pub struct RunContext {
+    trigger: Value,
+    steps: Map<String, Value>,
+}
+
+impl RunContext {
+    pub fn new(input: Option<Value>) -> Self {
+        Self {
+            trigger: input.unwrap_or(Value::Null),
+            steps: Map::new(),
+        }
+    }
+
+    /// The context of a run so far: its input and the outputs it saved
+    pub fn from_run(run: &WorkflowRun) -> Self {
+        let mut context = Self::new(run.input.clone());
+        if let Some(Value::Object(steps)) = run.output.as_ref().and_then(|o| o.get("steps")) {
+            context.steps = steps.clone();
+        }
+        context
+    }
+
+    pub fn record(&mut self, step: &WorkflowVersionStep, output: Value) {
+        self.steps.insert(step_key(step), output);
+    }
+
+    /// What the run stores as its output
+    pub fn output(&self) -> Value {
+        json!({ "steps": self.steps })
+    }
+
+    /// Step settings with their expressions replaced by the values they point to
+    pub fn resolve(&self, settings: &Value) -> Result<Value, DomainError> {
+        match settings {
+            Value::String(template) => self.render(template),
+            Value::Array(items) => items.iter().map(|item| self.resolve(item)).collect(),
+            Value::Object(fields) => fields
+                .iter()
+                .map(|(key, value)| Ok((key.clone(), self.resolve(value)?)))
+                .collect::<Result<Map<_, _>, DomainError>>()
+                .map(Value::Object),
+            _ => Ok(settings.clone()),
+        }
+    }
+
+    /// A string that is a single expression takes the value as is, so that numbers and
+    /// objects keep their type; expressions within text are formatted as email templates are
+    fn render(&self, template: &str) -> Result<Value, DomainError> {
+        let parts = parse(template)?;
+        if let [Part::Path(path)] = parts.as_slice() {
+            return self.lookup(path);
+        }
+        let mut rendered = String::new();
+        for part in parts {
+            match part {
+                Part::Text(text) => rendered.push_str(text),
+                Part::Path(path) => match self.lookup(&path)? {
+                    Value::String(s) => rendered.push_str(&s),
+                    Value::Null => {}
+                    value => rendered.push_str(&value.to_string()),
+                },
+            }
+        }
+        Ok(Value::String(rendered))
+    }
+
+    /// The value at a path such as `trigger.record.email`
+    pub fn get(&self, path: &str) -> Result<Value, DomainError> {
+        self.lookup(&parse_path(path)?)
+    }
+
+    /// Missing fields read as null, but a step that has not run is an error
+    fn lookup(&self, path: &[&str]) -> Result<Value, DomainError> {
+        let (root, fields) = match path {
+            ["trigger", fields @ ..] => (&self.trigger, fields),
+            ["steps", step, fields @ ..] => {
+                let output = self.steps.get(*step).ok_or_else(|| {
+                    DomainError::Validation(format!("Step {:?} has not run", step))
+                })?;
+                (output, fields)
+            }
+            _ => return Err(invalid(&path.join("."))),
+        };
+        let value = fields.iter().try_fold(root, |value, field| match value {
+            Value::Array(items) => field.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => value.get(*field),
+        });
+        Ok(value.cloned().unwrap_or(Value::Null))
+    }
+}
+
+/// The key of a step's output, see `WorkflowVersionStep::name`
+pub fn step_key(step: &WorkflowVersionStep) -> String {
+    step.name.clone().unwrap_or_else(|| step.id.to_string())
+}
+
+/// Checks the expressions of step settings when the step is added
+pub fn validate_templates(settings: &Value) -> Result<(), DomainError> {
+    match settings {
+        Value::String(template) => parse(template).map(|_| ()),
+        Value::Array(items) => items.iter().try_for_each(validate_templates),
+        Value::Object(fields) => fields.values().try_for_each(validate_templates),
+        _ => Ok(()),
+    }
+}
+
+enum Part<'a> {
+    Text(&'a str),
+    Path(Vec<&'a str>),
+}
+
+fn parse(template: &str) -> Result<Vec<Part<'_>>, DomainError> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        if start > 0 {
+            parts.push(Part::Text(&rest[..start]));
+        }
+        let expression = &rest[start + 2..];
+        let end = expression.find("}}").ok_or_else(|| {
+            DomainError::Validation(format!("Unclosed expression in {:?}", template))
+        })?;
+        parts.push(Part::Path(parse_path(expression[..end].trim())?));
+        rest = &expression[end + 2..];
+    }
+    if !rest.is_empty() {
+        parts.push(Part::Text(rest));
+    }
+    Ok(parts)
+}
+
+/// Dot-separated names, starting at `trigger` or `steps.<step>`; numbers index arrays
+pub fn parse_path(expression: &str) -> Result<Vec<&str>, DomainError> {
+    let path: Vec<&str> = expression.split('.').collect();
+    let well_formed = path.iter().all(|segment| {
+        !segment.is_empty()
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    });
+    match path.as_slice() {
+        ["trigger", ..] | ["steps", _, ..] if well_formed => Ok(path),
+        _ => Err(invalid(expression)),
+    }
+}
+
+fn invalid(expression: &str) -> DomainError {
+    DomainError::Validation(format!(
+        "Invalid path {:?}, expected trigger.<field> or steps.<step>.<field>",
+        expression
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::states::WorkflowStepType;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn step(name: Option<&str>) -> WorkflowVersionStep {
+        WorkflowVersionStep {
+            id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            workflow_version_id: Uuid::new_v4(),
+            name: name.map(str::to_string),
+            step_type: WorkflowStepType::CreateRecord,
+            settings: json!({}),
+            position: 0,
+        }
+    }
+
+    fn context() -> RunContext {
+        let mut context = RunContext::new(Some(json!({
+            "type": "record_created",
+            "record": {"name": "Ada", "email": "ada@example.com", "tags": ["vip"]},
+        })));
+        context.record(
+            &step(Some("create_person")),
+            json!({"id": 42, "phone": null}),
+        );
+        context
+    }
+
+    #[test]
+    fn test_expressions_resolve_against_trigger_and_step_outputs() {
+        let settings = json!({
+            "to_email": "{{trigger.record.email}}",
+            "subject": "Welcome {{ trigger.record.name }} (#{{steps.create_person.id}})",
+            "person_id": "{{steps.create_person.id}}",
+            "body_text": "Call {{steps.create_person.phone}}{{trigger.record.missing}}",
+            "cc_emails": ["{{trigger.record.tags.0}}@example.com"],
+            "retries": 3,
+        });
+
+        assert_eq!(
+            context().resolve(&settings).unwrap(),
+            json!({
+                "to_email": "ada@example.com",
+                "subject": "Welcome Ada (#42)",
+                "person_id": 42,
+                "body_text": "Call ",
+                "cc_emails": ["vip@example.com"],
+                "retries": 3,
+            })
+        );
+    }
+
+    #[test]
+    fn test_unnamed_steps_are_addressed_by_id() {
+        let mut context = context();
+        let unnamed = step(None);
+        context.record(&unnamed, json!({"id": 7}));
+
+        let settings = json!(format!("{{{{steps.{}.id}}}}", unnamed.id));
+        assert_eq!(context.resolve(&settings).unwrap(), json!(7));
+        assert_eq!(
+            context.output()["steps"][unnamed.id.to_string()],
+            json!({"id": 7})
+        );
+    }
+
+    #[test]
+    fn test_invalid_expressions_are_rejected() {
+        for template in [
+            "{{trigger.record.email",
+            "{{record.email}}",
+            "{{steps}}",
+            "{{trigger..email}}",
+            "{{trigger.record.email | upper}}",
+        ] {
+            assert!(
+                validate_templates(&json!({ "to_email": template })).is_err(),
+                "{} was accepted",
+                template
+            );
+        }
+        assert!(validate_templates(&json!({"subject": "Hi {{trigger.record.name}}"})).is_ok());
+
+        // Well formed, but only known once the run gets there
+        let settings = json!({ "id": "{{steps.not_yet.id}}" });
+        assert!(validate_templates(&settings).is_ok());
+        assert!(context().resolve(&settings).is_err());
+    }
+}
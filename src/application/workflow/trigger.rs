@@ -0,0 +1,269 @@
+//! What starts a workflow. A `Trigger` step of the published version holds one definition
+//! in its settings, e.g. `{"type": "record_created", "object": "person"}`, and each run it
+//! starts gets the trigger and the record or payload that fired it as input.
+
+use crate::application::events::record_events::{RecordChange, RecordEvent};
+use crate::application::workflow::records::RecordObject;
+use crate::domain::DomainError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WorkflowTrigger {
+    RecordCreated {
+        object: String,
+    },
+    RecordUpdated {
+        object: String,
+    },
+    RecordDeleted {
+        object: String,
+    },
+    /// An update that set `field` to `value`, or changed it at all without a value
+    FieldChanged {
+        object: String,
+        field: String,
+        #[serde(default)]
+        value: Option<Value>,
+    },
+    /// Runs started by a user through the API
+    Manual,
+    /// Runs started by a request to the workflow's webhook, which must carry the token
+    /// hashed here. The token itself is only shown when the trigger is added.
+    Webhook {
+        #[serde(default)]
+        token_hash: String,
+    },
+    /// Standard five-field cron expression, evaluated in UTC
+    Cron {
+        schedule: String,
+    },
+}
+
+impl WorkflowTrigger {
+    /// Parses and validates the settings of a `Trigger` step
+    pub fn from_settings(settings: &Value) -> Result<Self, DomainError> {
+        let trigger: Self = serde_json::from_value(settings.clone())
+            .map_err(|e| DomainError::Validation(format!("Invalid trigger: {}", e)))?;
+        match &trigger {
+            WorkflowTrigger::RecordCreated { object }
+            | WorkflowTrigger::RecordUpdated { object }
+            | WorkflowTrigger::RecordDeleted { object } => validate_object(object)?,
+            WorkflowTrigger::FieldChanged { object, field, .. } => {
+                validate_object(object)?;
+                if field.trim().is_empty() {
+                    return Err(DomainError::Validation(
+                        "Field triggers need a field".to_string(),
+                    ));
+                }
+            }
+            WorkflowTrigger::Cron { schedule } => {
+                parse_schedule(schedule)?;
+            }
+            WorkflowTrigger::Manual | WorkflowTrigger::Webhook { .. } => {}
+        }
+        Ok(trigger)
+    }
+
+    /// Whether the record event fires this trigger
+    pub fn matches(&self, event: &RecordEvent) -> bool {
+        match (self, event.change) {
+            (WorkflowTrigger::RecordCreated { object }, RecordChange::Created)
+            | (WorkflowTrigger::RecordUpdated { object }, RecordChange::Updated)
+            | (WorkflowTrigger::RecordDeleted { object }, RecordChange::Deleted) => {
+                *object == event.object
+            }
+            (
+                WorkflowTrigger::FieldChanged {
+                    object,
+                    field,
+                    value,
+                },
+                RecordChange::Updated,
+            ) => {
+                let after = event.record.get(field);
+                let before = event.previous.as_ref().and_then(|p| p.get(field));
+                *object == event.object
+                    && after != before
+                    && value.as_ref().is_none_or(|value| after == Some(value))
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether the schedule of a cron trigger fell due in `(since, until]`
+    pub fn is_due(&self, since: DateTime<Utc>, until: DateTime<Utc>) -> bool {
+        match self {
+            WorkflowTrigger::Cron { schedule } => parse_schedule(schedule)
+                .ok()
+                .and_then(|schedule| schedule.after(&since).next())
+                .is_some_and(|next| next <= until),
+            _ => false,
+        }
+    }
+
+    /// Input of a run started by a record event
+    pub fn record_input(&self, event: &RecordEvent) -> Value {
+        json!({
+            "type": self.type_name(),
+            "object": event.object,
+            "record": event.record,
+            "previous": event.previous,
+        })
+    }
+
+    /// Input of a run started by a user, a webhook or the schedule, with what they sent
+    pub fn payload_input(&self, payload: Option<Value>) -> Value {
+        json!({
+            "type": self.type_name(),
+            "payload": payload,
+        })
+    }
+
+    fn type_name(&self) -> &'static str {
+        match self {
+            WorkflowTrigger::RecordCreated { .. } => "record_created",
+            WorkflowTrigger::RecordUpdated { .. } => "record_updated",
+            WorkflowTrigger::RecordDeleted { .. } => "record_deleted",
+            WorkflowTrigger::FieldChanged { .. } => "field_changed",
+            WorkflowTrigger::Manual => "manual",
+            WorkflowTrigger::Webhook { .. } => "webhook",
+            WorkflowTrigger::Cron { .. } => "cron",
+        }
+    }
+}
+
+fn validate_object(object: &str) -> Result<(), DomainError> {
+    match RecordObject::parse(object) {
+        Some(_) => Ok(()),
+        None => Err(DomainError::Validation(format!(
+            "Triggers cannot watch {:?}",
+            object
+        ))),
+    }
+}
+
+/// The `cron` crate expects seconds first, which five-field expressions leave at zero
+fn parse_schedule(schedule: &str) -> Result<cron::Schedule, DomainError> {
+    let fields = schedule.split_whitespace().count();
+    let expression = match fields {
+        5 => format!("0 {}", schedule.trim()),
+        _ => {
+            return Err(DomainError::Validation(format!(
+                "Schedule {:?} must have five fields",
+                schedule
+            )))
+        }
+    };
+    cron::Schedule::from_str(&expression)
+        .map_err(|e| DomainError::Validation(format!("Invalid schedule {:?}: {}", schedule, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use uuid::Uuid;
+
+    fn trigger(settings: Value) -> WorkflowTrigger {
+        WorkflowTrigger::from_settings(&settings).unwrap()
+    }
+
+    fn updated(object: &str, previous: Value, record: Value) -> RecordEvent {
+        RecordEvent::updated(object, Uuid::new_v4(), &previous, &record)
+    }
+
+    #[test]
+    fn test_settings_are_validated() {
+        assert_eq!(
+            trigger(json!({"type": "record_created", "object": "person"})),
+            WorkflowTrigger::RecordCreated {
+                object: "person".to_string()
+            }
+        );
+        let custom = format!("custom_object:{}", Uuid::new_v4());
+        trigger(json!({"type": "record_deleted", "object": custom}));
+        trigger(json!({"type": "cron", "schedule": "*/15 9-17 * * MON-FRI"}));
+
+        for settings in [
+            json!({}),
+            json!({"type": "record_created"}),
+            json!({"type": "record_created", "object": "workflow"}),
+            json!({"type": "record_created", "object": "custom_object:42"}),
+            json!({"type": "field_changed", "object": "lead", "field": " "}),
+            json!({"type": "cron", "schedule": "0 * * * * *"}),
+            json!({"type": "cron", "schedule": "61 * * * *"}),
+        ] {
+            assert!(
+                WorkflowTrigger::from_settings(&settings).is_err(),
+                "{} was accepted",
+                settings
+            );
+        }
+    }
+
+    #[test]
+    fn test_record_events_match_their_object_and_change() {
+        let created = trigger(json!({"type": "record_created", "object": "person"}));
+        let event = RecordEvent::created("person", Uuid::new_v4(), &json!({"name": "Ada"}));
+        assert!(created.matches(&event));
+        assert!(!created.matches(&RecordEvent {
+            object: "company".to_string(),
+            ..event.clone()
+        }));
+        assert!(!created.matches(&RecordEvent::deleted("person", Uuid::new_v4(), &json!({}))));
+    }
+
+    #[test]
+    fn test_field_changes_match_only_when_the_field_changes() {
+        let won = trigger(json!({
+            "type": "field_changed",
+            "object": "opportunity",
+            "field": "stage",
+            "value": "Won",
+        }));
+        let any = trigger(json!({
+            "type": "field_changed",
+            "object": "opportunity",
+            "field": "stage",
+        }));
+
+        let moved_to_won = updated(
+            "opportunity",
+            json!({"stage": "Proposal"}),
+            json!({"stage": "Won"}),
+        );
+        assert!(won.matches(&moved_to_won));
+        assert!(any.matches(&moved_to_won));
+
+        let moved_to_lost = updated(
+            "opportunity",
+            json!({"stage": "Proposal"}),
+            json!({"stage": "Lost"}),
+        );
+        assert!(!won.matches(&moved_to_lost));
+        assert!(any.matches(&moved_to_lost));
+
+        let renamed = updated(
+            "opportunity",
+            json!({"stage": "Won", "name": "Old"}),
+            json!({"stage": "Won", "name": "New"}),
+        );
+        assert!(!won.matches(&renamed));
+        assert!(!any.matches(&renamed));
+    }
+
+    #[test]
+    fn test_cron_triggers_are_due_once_per_occurrence() {
+        let hourly = trigger(json!({"type": "cron", "schedule": "30 * * * *"}));
+        let at = |h, m, s| Utc.with_ymd_and_hms(2024, 1, 30, h, m, s).unwrap();
+
+        assert!(hourly.is_due(at(9, 29, 10), at(9, 30, 10)));
+        assert!(!hourly.is_due(at(9, 30, 10), at(9, 31, 10)));
+        assert!(!hourly.is_due(at(9, 30, 0), at(9, 31, 0)));
+        assert!(!WorkflowTrigger::Manual.is_due(at(0, 0, 0), at(23, 0, 0)));
+    }
+}
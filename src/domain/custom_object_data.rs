@@ -10,4 +10,6 @@ pub struct CustomObjectData {
     pub object_metadata_id: Uuid,
     pub properties: serde_json::Value,
     pub workspace_id: Uuid,
+    /// Incremented by every update; an update based on an older version is a conflict
+    pub version: i32,
 }
@@ -1,7 +1,9 @@
+use super::invariants::{DomainError, StateMachine};
+use super::permissions::{ApiKeyScope, Permission};
 use super::states::{
     ConnectedAccountStatus, EmailDirection, EmailStatus, LeadSource, LeadStatus, OpportunityStage,
-    TaskStatus, UserState, WorkflowRunStatus, WorkflowStepType, WorkflowVersionStatus,
-    WorkspaceState,
+    TaskStatus, UserState, UserTokenPurpose, WorkflowRunStatus, WorkflowStepType,
+    WorkflowVersionStatus, WorkspaceState,
 };
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -25,6 +27,33 @@ pub struct WorkspaceMember {
     pub workspace_id: Uuid,
     pub role: String,
     pub name: String,
+    /// Set while an admin has suspended the member from the workspace
+    pub suspended_at: Option<DateTime<Utc>>,
+}
+
+/// Pending offer for an email address to join a workspace with a given role
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Invitation {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub workspace_id: Uuid,
+    pub email: String,
+    pub role: String,
+    /// Member who sent the invitation
+    pub invited_by: Uuid,
+    /// SHA-256 digest of the emailed token
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub accepted_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl Invitation {
+    pub fn is_pending(&self, now: DateTime<Utc>) -> bool {
+        self.accepted_at.is_none() && self.revoked_at.is_none() && self.expires_at > now
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,6 +66,116 @@ pub struct User {
     pub state: UserState,
 }
 
+/// Personal access token of a workspace member for the JSON API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub workspace_id: Uuid,
+    /// Member the key acts as
+    pub member_id: Uuid,
+    pub name: String,
+    /// Leading characters of the token, to tell keys apart in listings
+    pub token_prefix: String,
+    /// SHA-256 digest of the token
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+    pub scope: ApiKeyScope,
+    /// Object keys the key is limited to; `None` for every object
+    pub objects: Option<Vec<String>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKey {
+    /// Marks API key tokens so they can be told apart from session tokens
+    pub const TOKEN_PREFIX: &'static str = "oxi_";
+
+    /// Whether the key's restrictions admit `permission`; the member's role is checked
+    /// separately
+    pub fn allows(&self, permission: &Permission) -> bool {
+        self.scope.allows(permission.action)
+            && self
+                .objects
+                .as_ref()
+                .is_none_or(|objects| objects.contains(&permission.object))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub user_id: Uuid,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    /// Workspace whose single sign-on opened the session; `None` for password logins
+    pub sso_workspace_id: Option<Uuid>,
+}
+
+impl Session {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+}
+
+impl User {
+    /// Moves the user to `next` if `UserState` allows it
+    pub fn transition_to(&mut self, next: UserState) -> Result<(), DomainError> {
+        self.state = self.state.transition_to(next)?;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Right-to-erasure deletion: the row stays so references remain valid, but the
+    /// email is replaced and the password cleared so the account can never sign in
+    pub fn anonymize(&mut self) -> Result<(), DomainError> {
+        self.transition_to(UserState::Deleted)?;
+        self.email = format!("deleted-{}@users.invalid", self.id);
+        self.password_hash = String::new();
+        Ok(())
+    }
+}
+
+/// OpenID Connect single sign-on settings of a workspace
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SsoConfig {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub workspace_id: Uuid,
+    /// Issuer URL; the provider metadata is discovered below it
+    pub issuer_url: String,
+    pub client_id: String,
+    #[serde(skip_serializing)]
+    pub client_secret: String,
+    /// Role given to members created on their first sign-on
+    pub default_role: String,
+    /// Members may only use the workspace through sessions opened by its SSO
+    pub enforced: bool,
+}
+
+/// Single-use token emailed to a user, e.g. to confirm their email address
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserToken {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub user_id: Uuid,
+    pub purpose: UserTokenPurpose,
+    /// SHA-256 digest of the emailed token
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+}
+
+impl UserToken {
+    pub fn is_usable(&self, now: DateTime<Utc>) -> bool {
+        self.used_at.is_none() && self.expires_at > now
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Person {
     pub id: Uuid,
@@ -62,6 +201,8 @@ pub struct Company {
     pub employees_count: i32,
     pub position: i32,
     pub workspace_id: Uuid,
+    /// Incremented by every update; an update based on an older version is a conflict
+    pub version: i32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -80,6 +221,8 @@ pub struct Opportunity {
     pub company_id: Option<Uuid>,
     pub owner_id: Option<Uuid>,
     pub workspace_id: Uuid,
+    /// Incremented by every update; an update based on an older version is a conflict
+    pub version: i32,
 }
 
 impl Opportunity {
@@ -99,6 +242,7 @@ impl Opportunity {
             updated_at: Utc::now(),
             deleted_at: None,
             workspace_id: Uuid::nil(), // Placeholder, should be passed in
+            version: 1,
         }
     }
 }
@@ -130,6 +274,55 @@ pub struct Note {
     pub workspace_id: Uuid,
 }
 
+/// Kind of record that is moved to the trash when deleted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrashedKind {
+    Person,
+    Company,
+    Opportunity,
+    Task,
+    Note,
+    Lead,
+}
+
+impl TrashedKind {
+    pub const ALL: [TrashedKind; 6] = [
+        TrashedKind::Person,
+        TrashedKind::Company,
+        TrashedKind::Opportunity,
+        TrashedKind::Task,
+        TrashedKind::Note,
+        TrashedKind::Lead,
+    ];
+
+    /// Object key of the kind in permission checks, also its name in URLs
+    pub fn object(&self) -> &'static str {
+        match self {
+            TrashedKind::Person => super::permissions::objects::PERSON,
+            TrashedKind::Company => super::permissions::objects::COMPANY,
+            TrashedKind::Opportunity => super::permissions::objects::OPPORTUNITY,
+            TrashedKind::Task => super::permissions::objects::TASK,
+            TrashedKind::Note => super::permissions::objects::NOTE,
+            TrashedKind::Lead => super::permissions::objects::LEAD,
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.object() == value)
+    }
+}
+
+/// A soft-deleted record as listed in the trash
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashedRecord {
+    pub kind: TrashedKind,
+    pub id: Uuid,
+    /// Name, title or full name of the record
+    pub name: String,
+    pub deleted_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskTarget {
     pub id: Uuid,
@@ -164,6 +357,8 @@ pub struct WorkflowVersionStep {
     pub id: Uuid,
     pub created_at: DateTime<Utc>,
     pub workflow_version_id: Uuid,
+    /// Later steps read this step's output as `steps.<name>`, or `steps.<id>` without one
+    pub name: Option<String>,
     pub step_type: WorkflowStepType,
     pub settings: serde_json::Value,
     pub position: i32,
@@ -176,8 +371,18 @@ pub struct WorkflowRun {
     pub updated_at: DateTime<Utc>,
     pub workflow_version_id: Uuid,
     pub status: WorkflowRunStatus,
+    /// What started the run, see `WorkflowTrigger`
+    pub input: Option<serde_json::Value>,
     pub output: Option<serde_json::Value>,
     pub error: Option<String>,
+    /// The step the run continues with, saved after every step; `None` once it has ended
+    pub cursor: Option<Uuid>,
+    /// When a run parked by a `Delay` step is due to continue
+    pub wake_at: Option<DateTime<Utc>>,
+    /// The workflows whose runs led to this one, ending with its own; records its steps
+    /// write do not trigger them again, also once the run is resumed
+    #[serde(default)]
+    pub workflow_chain: Vec<Uuid>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -238,6 +443,7 @@ pub struct EmailTemplate {
     pub body_text: String,
     pub body_html: Option<String>,
     pub category: String,
+    pub workspace_id: Uuid,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
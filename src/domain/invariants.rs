@@ -1,4 +1,5 @@
 use super::entities::{Email, EmailTemplate, Lead, Person};
+use super::states::{UserState, WorkflowVersionStatus};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -13,12 +14,51 @@ pub enum DomainError {
     InvalidState(String),
     #[error("Infrastructure error: {0}")]
     InfrastructureError(String),
+    /// The entity changed since it was read; reload it and retry
+    #[error("Conflict: {0}")]
+    Conflict(String),
 }
 
 pub trait HardGuard {
     fn validate(&self) -> Result<(), DomainError>;
 }
 
+/// Lifecycle rules of a state enum; use cases move entities only through `transition_to`
+pub trait StateMachine: Copy + std::fmt::Debug {
+    fn can_transition_to(&self, next: Self) -> bool;
+
+    fn transition_to(&self, next: Self) -> Result<Self, DomainError> {
+        if !self.can_transition_to(next) {
+            return Err(DomainError::InvalidState(format!(
+                "{:?} -> {:?}",
+                self, next
+            )));
+        }
+        Ok(next)
+    }
+}
+
+impl StateMachine for UserState {
+    fn can_transition_to(&self, next: Self) -> bool {
+        use UserState::*;
+        matches!(
+            (self, next),
+            (Unverified, Active)
+                | (Active, Suspended)
+                | (Suspended, Active)
+                | (Unverified | Active | Suspended, Deleted)
+        )
+    }
+}
+
+impl StateMachine for WorkflowVersionStatus {
+    fn can_transition_to(&self, next: Self) -> bool {
+        use WorkflowVersionStatus::*;
+        // Steps can only change while a version is a draft; publishing freezes it
+        matches!((self, next), (Draft, Published) | (Draft | Published, Archived))
+    }
+}
+
 impl HardGuard for Person {
     fn validate(&self) -> Result<(), DomainError> {
         if self.name.trim().is_empty() {
@@ -118,3 +158,36 @@ impl HardGuard for Lead {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_lifecycle() {
+        use UserState::*;
+
+        assert_eq!(Unverified.transition_to(Active).unwrap(), Active);
+        assert_eq!(Active.transition_to(Suspended).unwrap(), Suspended);
+        assert_eq!(Suspended.transition_to(Active).unwrap(), Active);
+        assert_eq!(Suspended.transition_to(Deleted).unwrap(), Deleted);
+
+        // Suspension is for verified accounts; deletion is final
+        assert!(Unverified.transition_to(Suspended).is_err());
+        assert!(Active.transition_to(Unverified).is_err());
+        assert!(Deleted.transition_to(Active).is_err());
+        assert!(Active.transition_to(Active).is_err());
+    }
+
+    #[test]
+    fn workflow_version_lifecycle() {
+        use WorkflowVersionStatus::*;
+
+        assert_eq!(Draft.transition_to(Published).unwrap(), Published);
+        assert_eq!(Published.transition_to(Archived).unwrap(), Archived);
+        assert_eq!(Draft.transition_to(Archived).unwrap(), Archived);
+
+        assert!(Published.transition_to(Draft).is_err());
+        assert!(Archived.transition_to(Published).is_err());
+    }
+}
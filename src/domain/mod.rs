@@ -2,6 +2,7 @@ pub mod custom_object_data;
 pub mod entities;
 pub mod invariants;
 pub mod metadata;
+pub mod permissions;
 pub mod states;
 
 pub use entities::*;
@@ -0,0 +1,213 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Built-in object keys used in permission checks
+pub mod objects {
+    pub const PERSON: &str = "person";
+    pub const COMPANY: &str = "company";
+    pub const OPPORTUNITY: &str = "opportunity";
+    pub const TASK: &str = "task";
+    pub const NOTE: &str = "note";
+    pub const WORKFLOW: &str = "workflow";
+    pub const CALENDAR_EVENT: &str = "calendar_event";
+    pub const TIMELINE_ACTIVITY: &str = "timeline_activity";
+    pub const LEAD: &str = "lead";
+    pub const EMAIL: &str = "email";
+    pub const EMAIL_TEMPLATE: &str = "email_template";
+    pub const VIEW: &str = "view";
+    /// Schema management (custom objects and fields)
+    pub const OBJECT_METADATA: &str = "object_metadata";
+    /// Workspace administration (members and roles)
+    pub const WORKSPACE_MEMBER: &str = "workspace_member";
+
+    /// Object key of the records of a custom object
+    pub fn custom_object(object_metadata_id: uuid::Uuid) -> String {
+        format!("custom_object:{}", object_metadata_id)
+    }
+}
+
+/// Wildcard object key in a role definition
+pub const ANY_OBJECT: &str = "*";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Action {
+    Read,
+    Create,
+    Update,
+    Delete,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Permission {
+    pub object: String,
+    pub action: Action,
+}
+
+impl Permission {
+    pub fn new(object: impl Into<String>, action: Action) -> Self {
+        Self {
+            object: object.into(),
+            action,
+        }
+    }
+
+    pub fn read(object: impl Into<String>) -> Self {
+        Self::new(object, Action::Read)
+    }
+
+    pub fn create(object: impl Into<String>) -> Self {
+        Self::new(object, Action::Create)
+    }
+
+    pub fn update(object: impl Into<String>) -> Self {
+        Self::new(object, Action::Update)
+    }
+
+    pub fn delete(object: impl Into<String>) -> Self {
+        Self::new(object, Action::Delete)
+    }
+}
+
+impl std::fmt::Display for Permission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} {}", self.action, self.object)
+    }
+}
+
+/// Role of a workspace member, stored as its name in `WorkspaceMember.role`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Role {
+    /// Full access, including schema and workspace administration
+    Admin,
+    /// Full access to records, read-only access to schema and administration
+    Member,
+    /// Read access to everything
+    ReadOnly,
+    /// Workspace-defined role, resolved through a `RoleDefinition`
+    Custom(String),
+}
+
+impl Role {
+    pub fn parse(name: &str) -> Self {
+        match name {
+            "Admin" => Role::Admin,
+            "Member" => Role::Member,
+            "ReadOnly" => Role::ReadOnly,
+            other => Role::Custom(other.to_string()),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Role::Admin => "Admin",
+            Role::Member => "Member",
+            Role::ReadOnly => "ReadOnly",
+            Role::Custom(name) => name,
+        }
+    }
+
+    /// Decision for built-in roles; `None` for custom roles, which need their definition
+    pub fn allows(&self, permission: &Permission) -> Option<bool> {
+        match self {
+            Role::Admin => Some(true),
+            Role::Member => Some(
+                permission.action == Action::Read
+                    || (permission.object != objects::OBJECT_METADATA
+                        && permission.object != objects::WORKSPACE_MEMBER),
+            ),
+            Role::ReadOnly => Some(permission.action == Action::Read),
+            Role::Custom(_) => None,
+        }
+    }
+}
+
+/// Access an API key grants on top of (never beyond) its member's role
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ApiKeyScope {
+    ReadOnly,
+    ReadWrite,
+}
+
+impl ApiKeyScope {
+    pub fn allows(&self, action: Action) -> bool {
+        match self {
+            ApiKeyScope::ReadOnly => action == Action::Read,
+            ApiKeyScope::ReadWrite => true,
+        }
+    }
+}
+
+/// Custom role with explicit per-object grants
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoleDefinition {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub workspace_id: Uuid,
+    pub name: String,
+    /// Object key (or `*`) to the actions granted on it
+    pub permissions: HashMap<String, Vec<Action>>,
+}
+
+impl RoleDefinition {
+    pub fn allows(&self, permission: &Permission) -> bool {
+        [permission.object.as_str(), ANY_OBJECT]
+            .iter()
+            .filter_map(|key| self.permissions.get(*key))
+            .any(|actions| actions.contains(&permission.action))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn built_in_roles() {
+        let edit_schema = Permission::create(objects::OBJECT_METADATA);
+        let create_person = Permission::create(objects::PERSON);
+        let read_person = Permission::read(objects::PERSON);
+
+        assert_eq!(Role::Admin.allows(&edit_schema), Some(true));
+        assert_eq!(Role::Member.allows(&edit_schema), Some(false));
+        assert_eq!(Role::Member.allows(&create_person), Some(true));
+        assert_eq!(Role::ReadOnly.allows(&create_person), Some(false));
+        assert_eq!(Role::ReadOnly.allows(&read_person), Some(true));
+        assert_eq!(Role::parse("Sales").allows(&read_person), None);
+    }
+
+    #[test]
+    fn api_key_scope() {
+        assert!(ApiKeyScope::ReadOnly.allows(Action::Read));
+        assert!(!ApiKeyScope::ReadOnly.allows(Action::Update));
+        assert!(ApiKeyScope::ReadWrite.allows(Action::Delete));
+    }
+
+    #[test]
+    fn custom_role_grants() {
+        let object_id = Uuid::new_v4();
+        let role = RoleDefinition {
+            id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            workspace_id: Uuid::new_v4(),
+            name: "Sales".to_string(),
+            permissions: HashMap::from([
+                (ANY_OBJECT.to_string(), vec![Action::Read]),
+                (
+                    objects::LEAD.to_string(),
+                    vec![Action::Create, Action::Update],
+                ),
+                (objects::custom_object(object_id), vec![Action::Create]),
+            ]),
+        };
+
+        assert!(role.allows(&Permission::read(objects::COMPANY)));
+        assert!(role.allows(&Permission::update(objects::LEAD)));
+        assert!(!role.allows(&Permission::delete(objects::LEAD)));
+        assert!(role.allows(&Permission::create(objects::custom_object(object_id))));
+        assert!(!role.allows(&Permission::create(objects::PERSON)));
+    }
+}
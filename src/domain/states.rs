@@ -32,6 +32,13 @@ pub enum UserState {
     Deleted,
 }
 
+/// What a `UserToken` was issued for; a token is only accepted for its own purpose
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UserTokenPurpose {
+    EmailVerification,
+    PasswordReset,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum WorkspaceState {
     Pending,
@@ -75,6 +82,9 @@ pub enum WorkflowStepType {
     Condition,
     Delay,
     CreateRecord,
+    UpdateRecord,
+    FindRecords,
+    DeleteRecord,
     SendEmail,
     IfElse,
     Form,
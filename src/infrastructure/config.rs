@@ -0,0 +1,263 @@
+//! Server configuration, read from the environment once at startup.
+//!
+//! A `.env` file in the working directory is loaded first (see `dotenvy`); variables that are
+//! already set take precedence over it. Every setting but `DATABASE_URL` on Postgres builds
+//! has a default.
+
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ConfigError {
+    #[error("{0} must be set")]
+    Missing(&'static str),
+    #[error("{name} must be {expected}, got {value:?}")]
+    Invalid {
+        name: &'static str,
+        value: String,
+        expected: &'static str,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub database: DatabaseConfig,
+    /// `LISTEN_ADDR`, `0.0.0.0:3001` by default
+    pub listen_addr: String,
+    /// `APP_DOMAIN`, the domain workspaces are subdomains of
+    pub app_domain: String,
+    /// `APP_SCHEME`, used to build absolute links
+    pub app_scheme: String,
+    /// `TRASH_RETENTION_DAYS`, how long trashed records stay restorable
+    pub trash_retention_days: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct DatabaseConfig {
+    /// `DATABASE_URL`; SQLite builds fall back to a throwaway in-memory database
+    pub url: String,
+    /// `DATABASE_MAX_CONNECTIONS`
+    pub max_connections: u32,
+    /// `DATABASE_MIN_CONNECTIONS`, kept open while idle
+    pub min_connections: u32,
+    /// `DATABASE_CONNECT_TIMEOUT_SECS`, to open a new connection
+    pub connect_timeout: Duration,
+    /// `DATABASE_ACQUIRE_TIMEOUT_SECS`, to wait for a pooled connection
+    pub acquire_timeout: Duration,
+    /// `DATABASE_IDLE_TIMEOUT_SECS`, before an idle connection above the minimum is closed
+    pub idle_timeout: Duration,
+    #[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+    pub sqlite: SqliteConfig,
+}
+
+/// Pragmas applied to every SQLite connection
+#[derive(Debug, Clone)]
+#[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+pub struct SqliteConfig {
+    /// `SQLITE_JOURNAL_MODE`, `wal` so that readers do not block the writer
+    pub journal_mode: String,
+    /// `SQLITE_SYNCHRONOUS`, `normal` being durable enough under WAL
+    pub synchronous: String,
+    /// `SQLITE_BUSY_TIMEOUT_MS`, how long a statement waits on a locked database
+    pub busy_timeout: Duration,
+    /// `SQLITE_FOREIGN_KEYS`
+    pub foreign_keys: bool,
+}
+
+const JOURNAL_MODES: [&str; 6] = ["delete", "truncate", "persist", "memory", "wal", "off"];
+const SYNCHRONOUS: [&str; 4] = ["off", "normal", "full", "extra"];
+
+impl Config {
+    /// Reads the configuration from `.env` and the process environment
+    pub fn from_env() -> Result<Self, ConfigError> {
+        dotenvy::dotenv().ok();
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    /// Reads the configuration through `var`, which looks up one variable
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Self, ConfigError> {
+        let vars = Vars(&var);
+        let url = match var("DATABASE_URL") {
+            Some(url) => url,
+            None if cfg!(feature = "sqlite") => "sqlite::memory:".to_owned(),
+            None => return Err(ConfigError::Missing("DATABASE_URL")),
+        };
+        let database = DatabaseConfig {
+            url,
+            max_connections: vars.number("DATABASE_MAX_CONNECTIONS", 10)?,
+            min_connections: vars.number("DATABASE_MIN_CONNECTIONS", 1)?,
+            connect_timeout: vars.seconds("DATABASE_CONNECT_TIMEOUT_SECS", 10)?,
+            acquire_timeout: vars.seconds("DATABASE_ACQUIRE_TIMEOUT_SECS", 10)?,
+            idle_timeout: vars.seconds("DATABASE_IDLE_TIMEOUT_SECS", 600)?,
+            sqlite: SqliteConfig {
+                journal_mode: vars.one_of("SQLITE_JOURNAL_MODE", &JOURNAL_MODES, "wal")?,
+                synchronous: vars.one_of("SQLITE_SYNCHRONOUS", &SYNCHRONOUS, "normal")?,
+                busy_timeout: Duration::from_millis(vars.number("SQLITE_BUSY_TIMEOUT_MS", 5000)?),
+                foreign_keys: vars.flag("SQLITE_FOREIGN_KEYS", true)?,
+            },
+        };
+        if database.max_connections == 0 {
+            return Err(ConfigError::Invalid {
+                name: "DATABASE_MAX_CONNECTIONS",
+                value: "0".to_owned(),
+                expected: "at least 1",
+            });
+        }
+        if database.min_connections > database.max_connections {
+            return Err(ConfigError::Invalid {
+                name: "DATABASE_MIN_CONNECTIONS",
+                value: database.min_connections.to_string(),
+                expected: "at most DATABASE_MAX_CONNECTIONS",
+            });
+        }
+
+        Ok(Config {
+            database,
+            listen_addr: vars.text("LISTEN_ADDR", "0.0.0.0:3001"),
+            app_domain: vars.text("APP_DOMAIN", "localhost"),
+            app_scheme: vars.one_of("APP_SCHEME", &["http", "https"], "http")?,
+            trash_retention_days: vars.number("TRASH_RETENTION_DAYS", 30)?,
+        })
+    }
+
+    /// Where the app is served, e.g. `https://oxicrm.app`, for links in emails
+    pub fn app_url(&self) -> String {
+        format!("{}://{}", self.app_scheme, self.app_domain)
+    }
+}
+
+#[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+impl DatabaseConfig {
+    pub fn is_sqlite(&self) -> bool {
+        self.url.starts_with("sqlite:")
+    }
+
+    /// An in-memory SQLite database is shared by the connections of its pool and lives as long
+    /// as one of them is open
+    pub fn is_in_memory(&self) -> bool {
+        self.is_sqlite() && (self.url.contains(":memory:") || self.url.contains("mode=memory"))
+    }
+}
+
+/// Typed lookups of the variables, falling back to a default when unset
+struct Vars<'a>(&'a dyn Fn(&str) -> Option<String>);
+
+impl Vars<'_> {
+    fn text(&self, name: &'static str, default: &str) -> String {
+        (self.0)(name).unwrap_or_else(|| default.to_owned())
+    }
+
+    fn number<T: std::str::FromStr>(
+        &self,
+        name: &'static str,
+        default: T,
+    ) -> Result<T, ConfigError> {
+        match (self.0)(name) {
+            None => Ok(default),
+            Some(value) => value.trim().parse().map_err(|_| ConfigError::Invalid {
+                name,
+                value,
+                expected: "a non-negative integer",
+            }),
+        }
+    }
+
+    fn seconds(&self, name: &'static str, default: u64) -> Result<Duration, ConfigError> {
+        self.number(name, default).map(Duration::from_secs)
+    }
+
+    fn flag(&self, name: &'static str, default: bool) -> Result<bool, ConfigError> {
+        match (self.0)(name) {
+            None => Ok(default),
+            Some(value) => match value.trim().to_ascii_lowercase().as_str() {
+                "1" | "true" | "on" | "yes" => Ok(true),
+                "0" | "false" | "off" | "no" => Ok(false),
+                _ => Err(ConfigError::Invalid {
+                    name,
+                    value,
+                    expected: "true or false",
+                }),
+            },
+        }
+    }
+
+    fn one_of(
+        &self,
+        name: &'static str,
+        allowed: &[&str],
+        default: &str,
+    ) -> Result<String, ConfigError> {
+        let value = self.text(name, default).trim().to_ascii_lowercase();
+        if allowed.contains(&value.as_str()) {
+            Ok(value)
+        } else {
+            Err(ConfigError::Invalid {
+                name,
+                value,
+                expected: "a supported value",
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn config(vars: &[(&str, &str)]) -> Result<Config, ConfigError> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        Config::from_vars(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn test_defaults_apply_when_unset() {
+        let config = config(&[("DATABASE_URL", "sqlite://crm.db")]).unwrap();
+        assert_eq!(config.database.max_connections, 10);
+        assert_eq!(config.database.acquire_timeout, Duration::from_secs(10));
+        assert_eq!(config.database.sqlite.journal_mode, "wal");
+        assert_eq!(
+            config.database.sqlite.busy_timeout,
+            Duration::from_millis(5000)
+        );
+        assert!(config.database.sqlite.foreign_keys);
+        assert_eq!(config.listen_addr, "0.0.0.0:3001");
+        assert_eq!(config.trash_retention_days, 30);
+        assert!(config.database.is_sqlite());
+        assert!(!config.database.is_in_memory());
+    }
+
+    #[test]
+    fn test_settings_are_read_and_validated() {
+        let config = config(&[
+            ("DATABASE_URL", "postgres://localhost/crm"),
+            ("DATABASE_MAX_CONNECTIONS", "32"),
+            ("DATABASE_IDLE_TIMEOUT_SECS", "60"),
+            ("SQLITE_SYNCHRONOUS", "FULL"),
+            ("SQLITE_FOREIGN_KEYS", "off"),
+        ])
+        .unwrap();
+        assert_eq!(config.database.max_connections, 32);
+        assert_eq!(config.database.idle_timeout, Duration::from_secs(60));
+        assert_eq!(config.database.sqlite.synchronous, "full");
+        assert!(!config.database.sqlite.foreign_keys);
+        assert!(!config.database.is_sqlite());
+
+        let invalid = |name: &str, value: &str| match config_with(name, value) {
+            Err(ConfigError::Invalid { name: invalid, .. }) => assert_eq!(invalid, name),
+            other => panic!("{}={} was accepted: {:?}", name, value, other.map(|_| ())),
+        };
+        invalid("DATABASE_MAX_CONNECTIONS", "many");
+        invalid("DATABASE_MAX_CONNECTIONS", "0");
+        invalid("DATABASE_MIN_CONNECTIONS", "11");
+        invalid("SQLITE_JOURNAL_MODE", "fast");
+        invalid("SQLITE_FOREIGN_KEYS", "maybe");
+    }
+
+    fn config_with(name: &str, value: &str) -> Result<Config, ConfigError> {
+        config(&[("DATABASE_URL", "sqlite::memory:"), (name, value)])
+    }
+}
@@ -0,0 +1,82 @@
+//! Readiness checks of the dependencies `main` starts: the database pool and the spawned
+//! background tasks.
+
+use crate::application::ports::health::HealthCheck;
+use async_trait::async_trait;
+use sea_orm::DatabaseConnection;
+use tokio::task::JoinHandle;
+
+/// Round trip to the database through the pool
+pub struct DatabaseHealth {
+    db: DatabaseConnection,
+}
+
+impl DatabaseHealth {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for DatabaseHealth {
+    fn name(&self) -> &str {
+        "database"
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        self.db.ping().await.map_err(|e| e.to_string())
+    }
+}
+
+/// A job worker or event subscriber loop, healthy for as long as its task runs
+pub struct TaskHealth {
+    name: String,
+    task: JoinHandle<()>,
+}
+
+impl TaskHealth {
+    pub fn new(name: impl Into<String>, task: JoinHandle<()>) -> Self {
+        Self {
+            name: name.into(),
+            task,
+        }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for TaskHealth {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        if self.task.is_finished() {
+            Err("Stopped".to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_tasks_are_healthy_until_they_stop() {
+        let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+        let health = TaskHealth::new(
+            "worker",
+            tokio::spawn(async move {
+                let _ = stopped.await;
+            }),
+        );
+        assert_eq!(health.check().await, Ok(()));
+
+        stop.send(()).unwrap();
+        while !health.task.is_finished() {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(health.check().await, Err("Stopped".to_string()));
+    }
+}
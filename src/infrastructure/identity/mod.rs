@@ -1,18 +1,439 @@
-use crate::application::ports::identity::IdentityProvider;
+pub mod oidc;
+
+use crate::application::ports::identity::{Credential, Identity, IdentityProvider, PasswordHasher};
+use crate::application::ports::output::{
+    ApiKeyRepository, RoleRepository, SessionRepository, SsoConfigRepository, UserRepository,
+    WorkspaceRepository,
+};
+use crate::domain::entities::{ApiKey, User};
+use crate::domain::permissions::{Permission, Role};
+use crate::domain::states::UserState;
+use crate::shared::token;
+use argon2::password_hash::{PasswordHash, PasswordHasher as _, PasswordVerifier, SaltString};
+use argon2::Argon2;
 use async_trait::async_trait;
+use chrono::Utc;
+use rand::rngs::OsRng;
+use std::sync::Arc;
 use uuid::Uuid;
 
-pub struct MockIdentityProvider;
+/// Resolves credentials against the persisted sessions, API keys, users and workspace
+/// memberships, and permissions against the member's role and the key's scope
+pub struct RepositoryIdentityProvider {
+    user_repo: Arc<dyn UserRepository>,
+    session_repo: Arc<dyn SessionRepository>,
+    api_key_repo: Arc<dyn ApiKeyRepository>,
+    workspace_repo: Arc<dyn WorkspaceRepository>,
+    role_repo: Arc<dyn RoleRepository>,
+    sso_config_repo: Arc<dyn SsoConfigRepository>,
+}
+
+impl RepositoryIdentityProvider {
+    pub fn new(
+        user_repo: Arc<dyn UserRepository>,
+        session_repo: Arc<dyn SessionRepository>,
+        api_key_repo: Arc<dyn ApiKeyRepository>,
+        workspace_repo: Arc<dyn WorkspaceRepository>,
+        role_repo: Arc<dyn RoleRepository>,
+        sso_config_repo: Arc<dyn SsoConfigRepository>,
+    ) -> Self {
+        Self {
+            user_repo,
+            session_repo,
+            api_key_repo,
+            workspace_repo,
+            role_repo,
+            sso_config_repo,
+        }
+    }
+}
+
+impl std::fmt::Debug for RepositoryIdentityProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RepositoryIdentityProvider").finish()
+    }
+}
+
+impl RepositoryIdentityProvider {
+    async fn authenticate_session(
+        &self,
+        raw_token: &str,
+        workspace_id: Option<Uuid>,
+    ) -> Result<Option<Identity>, String> {
+        let session = match self
+            .session_repo
+            .find_by_token_hash(&token::hash(raw_token))
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            Some(s) if !s.is_expired(Utc::now()) => s,
+            _ => return Ok(None),
+        };
+
+        let user = match self.active_user(session.user_id).await? {
+            Some(u) => u,
+            None => return Ok(None),
+        };
+
+        // Oldest membership is the default workspace; suspended members act as outsiders
+        let mut memberships = self
+            .workspace_repo
+            .find_members_by_user_id(user.id)
+            .await
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .filter(|m| m.suspended_at.is_none());
+        let mut member = match workspace_id {
+            Some(id) => memberships.find(|m| m.workspace_id == id),
+            None => memberships.next(),
+        };
+
+        // SSO-only workspaces ignore sessions their own provider did not open
+        if let Some(m) = &member {
+            let enforced = self
+                .sso_config_repo
+                .find_by_workspace_id(m.workspace_id)
+                .await
+                .map_err(|e| e.to_string())?
+                .is_some_and(|c| c.enforced);
+            if enforced && session.sso_workspace_id != Some(m.workspace_id) {
+                member = None;
+            }
+        }
+
+        Ok(Some(Identity {
+            user,
+            member,
+            api_key: None,
+        }))
+    }
+
+    /// API keys always act as their member, in the key's own workspace
+    async fn authenticate_api_key(
+        &self,
+        raw_token: &str,
+        workspace_id: Option<Uuid>,
+    ) -> Result<Option<Identity>, String> {
+        let api_key = match self
+            .api_key_repo
+            .find_by_token_hash(&token::hash(raw_token))
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            Some(k) if k.revoked_at.is_none() => k,
+            _ => return Ok(None),
+        };
+        if workspace_id.is_some_and(|id| id != api_key.workspace_id) {
+            return Ok(None);
+        }
+
+        let member = match self
+            .workspace_repo
+            .find_member_by_id(api_key.member_id)
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            Some(m) if m.suspended_at.is_none() => m,
+            _ => return Ok(None),
+        };
+        let user = match self.active_user(member.user_id).await? {
+            Some(u) => u,
+            None => return Ok(None),
+        };
+
+        self.api_key_repo
+            .record_use(api_key.id, Utc::now())
+            .await
+            .map_err(|e| e.to_string())?;
+
+        Ok(Some(Identity {
+            user,
+            member: Some(member),
+            api_key: Some(api_key),
+        }))
+    }
+
+    /// The user, unless suspended or deleted
+    async fn active_user(&self, user_id: Uuid) -> Result<Option<User>, String> {
+        let user = self
+            .user_repo
+            .find_by_id(user_id)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(user.filter(|u| !matches!(u.state, UserState::Suspended | UserState::Deleted)))
+    }
+}
 
 #[async_trait]
-impl IdentityProvider for MockIdentityProvider {
-    async fn get_current_user_id(&self) -> Result<Option<Uuid>, String> {
-        // Mock returning a fixed UUID or None
-        Ok(Some(Uuid::nil()))
+impl IdentityProvider for RepositoryIdentityProvider {
+    async fn authenticate(
+        &self,
+        credential: &Credential,
+        workspace_id: Option<Uuid>,
+    ) -> Result<Option<Identity>, String> {
+        match credential {
+            Credential::Bearer(t) if t.starts_with(ApiKey::TOKEN_PREFIX) => {
+                self.authenticate_api_key(t, workspace_id).await
+            }
+            // Other bearer tokens are session tokens (e.g. non-browser clients)
+            Credential::Session(t) | Credential::Bearer(t) => {
+                self.authenticate_session(t, workspace_id).await
+            }
+        }
     }
 
-    async fn has_permission(&self, _user_id: Uuid, _permission: &str) -> Result<bool, String> {
-        // Mock allowing everything
-        Ok(true)
+    async fn has_permission(
+        &self,
+        identity: &Identity,
+        permission: &Permission,
+    ) -> Result<bool, String> {
+        // Workspace data is only accessible to members
+        let member = match &identity.member {
+            Some(m) => m,
+            None => return Ok(false),
+        };
+
+        // A key can only narrow what its member's role allows
+        if identity
+            .api_key
+            .as_ref()
+            .is_some_and(|key| !key.allows(permission))
+        {
+            return Ok(false);
+        }
+
+        let role = Role::parse(&member.role);
+        if let Some(allowed) = role.allows(permission) {
+            return Ok(allowed);
+        }
+
+        // Unknown custom roles grant nothing
+        let definition = self
+            .role_repo
+            .find_by_name(member.workspace_id, role.name())
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(definition.is_some_and(|d| d.allows(permission)))
+    }
+}
+
+/// Argon2id password hasher producing PHC strings (salt and parameters are
+/// embedded in the hash, so verification needs no extra state)
+#[derive(Debug, Default)]
+pub struct Argon2PasswordHasher {
+    argon2: Argon2<'static>,
+}
+
+impl Argon2PasswordHasher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl PasswordHasher for Argon2PasswordHasher {
+    fn hash(&self, password: &str) -> Result<String, String> {
+        let salt = SaltString::generate(&mut OsRng);
+        self.argon2
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| e.to_string())
+    }
+
+    fn verify(&self, password: &str, password_hash: &str) -> Result<bool, String> {
+        let parsed = PasswordHash::new(password_hash).map_err(|e| e.to_string())?;
+        match self.argon2.verify_password(password.as_bytes(), &parsed) {
+            Ok(()) => Ok(true),
+            Err(argon2::password_hash::Error::Password) => Ok(false),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::use_cases::testing::{
+        identity_provider, member, session, user, workspace,
+    };
+    use crate::domain::permissions::ApiKeyScope;
+    use crate::domain::{Session, WorkspaceMember};
+    use crate::infrastructure::persistence::in_memory_repo::InMemoryRepo;
+    use chrono::Duration;
+
+    /// Issues a read-write key acting as `member` and returns its token
+    async fn api_key(repo: &InMemoryRepo, member: &WorkspaceMember) -> (ApiKey, String) {
+        let raw = format!("{}{}", ApiKey::TOKEN_PREFIX, token::generate());
+        let key = ApiKeyRepository::create(
+            repo,
+            ApiKey {
+                id: Uuid::new_v4(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                workspace_id: member.workspace_id,
+                member_id: member.id,
+                name: "CI".to_string(),
+                token_prefix: raw[..8].to_string(),
+                token_hash: token::hash(&raw),
+                scope: ApiKeyScope::ReadWrite,
+                objects: None,
+                last_used_at: None,
+                revoked_at: None,
+            },
+        )
+        .await
+        .unwrap();
+        (key, raw)
+    }
+
+    /// The member `credential` resolves to, `None` when it is rejected outright
+    async fn member_id(
+        provider: &RepositoryIdentityProvider,
+        credential: Credential,
+        workspace_id: Option<Uuid>,
+    ) -> Option<Option<Uuid>> {
+        provider
+            .authenticate(&credential, workspace_id)
+            .await
+            .unwrap()
+            .map(|identity| identity.member.map(|m| m.id))
+    }
+
+    #[tokio::test]
+    async fn test_bearer_sessions_resolve_the_member_of_the_workspace() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let provider = identity_provider(&repo);
+        let (a, b, c) = (
+            workspace(&repo).await,
+            workspace(&repo).await,
+            workspace(&repo).await,
+        );
+        let ada = user(&repo, "ada@example.com").await;
+        let in_a = member(&repo, a, &ada, "Admin").await;
+        let in_b = member(&repo, b, &ada, "Member").await;
+        let token = session(&repo, &ada).await;
+        let bearer = || Credential::Bearer(token.clone());
+
+        assert_eq!(
+            member_id(&provider, bearer(), Some(b)).await,
+            Some(Some(in_b.id))
+        );
+        assert_eq!(
+            member_id(&provider, bearer(), Some(a)).await,
+            Some(Some(in_a.id))
+        );
+        // The oldest membership when no workspace is routed, none outside the user's own
+        assert_eq!(
+            member_id(&provider, bearer(), None).await,
+            Some(Some(in_a.id))
+        );
+        assert_eq!(member_id(&provider, bearer(), Some(c)).await, Some(None));
+        assert_eq!(
+            member_id(&provider, Credential::Bearer(token::generate()), None).await,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_expired_sessions_are_rejected() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let provider = identity_provider(&repo);
+        let a = workspace(&repo).await;
+        let ada = user(&repo, "ada@example.com").await;
+        member(&repo, a, &ada, "Admin").await;
+        let raw = token::generate();
+        SessionRepository::create(
+            repo.as_ref(),
+            Session {
+                id: Uuid::new_v4(),
+                created_at: Utc::now() - Duration::days(2),
+                user_id: ada.id,
+                token_hash: token::hash(&raw),
+                expires_at: Utc::now() - Duration::minutes(1),
+                sso_workspace_id: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        for credential in [Credential::Session(raw.clone()), Credential::Bearer(raw)] {
+            assert_eq!(member_id(&provider, credential, Some(a)).await, None);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_revoked_api_keys_are_rejected() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let provider = identity_provider(&repo);
+        let (a, b) = (workspace(&repo).await, workspace(&repo).await);
+        let ada = user(&repo, "ada@example.com").await;
+        let in_a = member(&repo, a, &ada, "Admin").await;
+        member(&repo, b, &ada, "Admin").await;
+        let (key, raw) = api_key(&repo, &in_a).await;
+        let bearer = || Credential::Bearer(raw.clone());
+
+        let identity = provider
+            .authenticate(&bearer(), Some(a))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(identity.member.map(|m| m.id), Some(in_a.id));
+        assert_eq!(identity.api_key.map(|k| k.id), Some(key.id));
+        // Keys only act in their own workspace
+        assert_eq!(member_id(&provider, bearer(), Some(b)).await, None);
+
+        ApiKeyRepository::update(
+            repo.as_ref(),
+            ApiKey {
+                revoked_at: Some(Utc::now()),
+                ..key
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(member_id(&provider, bearer(), Some(a)).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_suspended_and_deleted_users_are_rejected() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let provider = identity_provider(&repo);
+        let a = workspace(&repo).await;
+
+        for state in [UserState::Suspended, UserState::Deleted] {
+            let grace = user(&repo, &format!("{:?}@example.com", state)).await;
+            let in_a = member(&repo, a, &grace, "Member").await;
+            let token = session(&repo, &grace).await;
+            let (_, raw_key) = api_key(&repo, &in_a).await;
+            UserRepository::update(repo.as_ref(), User { state, ..grace })
+                .await
+                .unwrap();
+
+            for credential in [
+                Credential::Session(token.clone()),
+                Credential::Bearer(token),
+                Credential::Bearer(raw_key),
+            ] {
+                assert_eq!(member_id(&provider, credential, Some(a)).await, None);
+            }
+        }
+    }
+
+    #[test]
+    fn test_argon2_hash_and_verify() {
+        let hasher = Argon2PasswordHasher::new();
+
+        let hash = hasher.hash("correct horse battery staple").unwrap();
+        assert!(hash.starts_with("$argon2id$"));
+        assert!(hasher
+            .verify("correct horse battery staple", &hash)
+            .unwrap());
+        assert!(!hasher.verify("wrong password", &hash).unwrap());
+    }
+
+    #[test]
+    fn test_argon2_rejects_legacy_hash_format() {
+        let hasher = Argon2PasswordHasher::new();
+
+        assert!(hasher.verify("password", "hashed_password").is_err());
     }
 }
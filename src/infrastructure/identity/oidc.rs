@@ -0,0 +1,468 @@
+use crate::application::ports::identity::{SsoAuthorization, SsoClaims, SsoProvider};
+use crate::domain::entities::SsoConfig;
+use crate::shared::token;
+use async_trait::async_trait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+
+const SCOPES: &str = "openid email profile";
+
+/// Subset of the provider metadata served at `/.well-known/openid-configuration`
+#[derive(Debug, Deserialize)]
+struct ProviderMetadata {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    id_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct IdTokenClaims {
+    sub: String,
+    nonce: Option<String>,
+    email: Option<String>,
+    /// Some providers send the flag as a string
+    email_verified: Option<serde_json::Value>,
+    name: Option<String>,
+}
+
+/// Generic OpenID Connect client: authorization-code flow with PKCE (S256) and ID
+/// tokens validated against the issuer's published JWKS
+pub struct OidcSsoProvider {
+    http: reqwest::Client,
+}
+
+impl OidcSsoProvider {
+    pub fn new() -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .expect("Failed to build HTTP client");
+        Self { http }
+    }
+
+    async fn discover(&self, issuer_url: &str) -> Result<ProviderMetadata, String> {
+        let issuer_url = issuer_url.trim_end_matches('/');
+        let metadata: ProviderMetadata = self
+            .http
+            .get(format!("{}/.well-known/openid-configuration", issuer_url))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| format!("Discovery failed: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Invalid provider metadata: {}", e))?;
+
+        // Metadata for another issuer would make every later check meaningless
+        if metadata.issuer.trim_end_matches('/') != issuer_url {
+            return Err(format!(
+                "Provider metadata is for issuer {}, expected {}",
+                metadata.issuer, issuer_url
+            ));
+        }
+        Ok(metadata)
+    }
+
+    async fn validate_id_token(
+        &self,
+        metadata: &ProviderMetadata,
+        client_id: &str,
+        id_token: &str,
+        nonce: &str,
+    ) -> Result<SsoClaims, String> {
+        let header = jsonwebtoken::decode_header(id_token)
+            .map_err(|e| format!("Malformed ID token: {}", e))?;
+        // Symmetric algorithms would be verified with the client secret, not the issuer's keys
+        if matches!(
+            header.alg,
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512
+        ) {
+            return Err(format!("Unsupported ID token algorithm {:?}", header.alg));
+        }
+
+        let jwks: JwkSet = self
+            .http
+            .get(&metadata.jwks_uri)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| format!("Fetching JWKS failed: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Invalid JWKS: {}", e))?;
+        let jwk = match &header.kid {
+            Some(kid) => jwks.find(kid),
+            // Without a key ID the signing key is only unambiguous if it is the only one
+            None if jwks.keys.len() == 1 => jwks.keys.first(),
+            None => None,
+        }
+        .ok_or("No matching signing key in JWKS")?;
+        let key = DecodingKey::from_jwk(jwk).map_err(|e| format!("Unusable JWK: {}", e))?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_audience(&[client_id]);
+        validation.set_issuer(&[&metadata.issuer]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+        let claims = jsonwebtoken::decode::<IdTokenClaims>(id_token, &key, &validation)
+            .map_err(|e| format!("Invalid ID token: {}", e))?
+            .claims;
+
+        if claims.nonce.as_deref() != Some(nonce) {
+            return Err("ID token nonce does not match".to_string());
+        }
+        let email = claims.email.ok_or("ID token has no email claim")?;
+        let email_verified = matches!(claims.email_verified, Some(serde_json::Value::Bool(true)))
+            || matches!(&claims.email_verified, Some(serde_json::Value::String(s)) if s == "true");
+
+        Ok(SsoClaims {
+            subject: claims.sub,
+            email,
+            email_verified,
+            name: claims.name,
+        })
+    }
+}
+
+impl Default for OidcSsoProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// PKCE S256 code challenge of `verifier`
+fn code_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+#[async_trait]
+impl SsoProvider for OidcSsoProvider {
+    async fn authorize(
+        &self,
+        config: &SsoConfig,
+        redirect_uri: &str,
+    ) -> Result<SsoAuthorization, String> {
+        let metadata = self.discover(&config.issuer_url).await?;
+
+        let state = token::generate();
+        let nonce = token::generate();
+        let pkce_verifier = token::generate();
+        let url = reqwest::Url::parse_with_params(
+            &metadata.authorization_endpoint,
+            &[
+                ("response_type", "code"),
+                ("client_id", config.client_id.as_str()),
+                ("redirect_uri", redirect_uri),
+                ("scope", SCOPES),
+                ("state", state.as_str()),
+                ("nonce", nonce.as_str()),
+                ("code_challenge", code_challenge(&pkce_verifier).as_str()),
+                ("code_challenge_method", "S256"),
+            ],
+        )
+        .map_err(|e| format!("Invalid authorization endpoint: {}", e))?;
+
+        Ok(SsoAuthorization {
+            url: url.to_string(),
+            state,
+            nonce,
+            pkce_verifier,
+        })
+    }
+
+    async fn exchange_code(
+        &self,
+        config: &SsoConfig,
+        code: &str,
+        redirect_uri: &str,
+        pkce_verifier: &str,
+        nonce: &str,
+    ) -> Result<SsoClaims, String> {
+        let metadata = self.discover(&config.issuer_url).await?;
+
+        let response: TokenResponse = self
+            .http
+            .post(&metadata.token_endpoint)
+            .form(&[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", redirect_uri),
+                ("client_id", config.client_id.as_str()),
+                ("client_secret", config.client_secret.as_str()),
+                ("code_verifier", pkce_verifier),
+            ])
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| format!("Token request failed: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Invalid token response: {}", e))?;
+
+        self.validate_id_token(&metadata, &config.client_id, &response.id_token, nonce)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::{Query, State};
+    use axum::http::{header, StatusCode};
+    use axum::response::{IntoResponse, Response};
+    use axum::routing::{get, post};
+    use axum::{Form, Json, Router};
+    use chrono::Utc;
+    use jsonwebtoken::{EncodingKey, Header};
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use uuid::Uuid;
+
+    const TEST_KEY_PEM: &str = include_str!("oidc_test_key.pem");
+    /// Base64url modulus of `TEST_KEY_PEM`
+    const TEST_KEY_N: &str = "m3giyZVQfEjeDt4RasMWsXtuPWTzhKfLPghwiD6z8S19cR8t6571S6639VFh2l4B6_xTrXXz4v6wzaVHeSU1X1hsduiwSRRYzAMgY59we9i6MTshvSTmHTtgZ_tR9riXsrbus7NMwmzBB0hr_vRfynRSZzr1QCf-2-0THJdgreiQAgfrzW_KqTEkwM5ClxdzKdsJ2q8lre9WupdIvBaBSDf1DGZGoddIzmfRGYpfZcCOK1Q9ahbVCeE1Q27pPBsX5BnfPFYODfV2aGNDP1eaREmTyrMbFF8AKEG-9CYpmgffcYjXZIQ0_eEI4kjRffxIgTq9anXD5b1Zt6YYbOw3Xw";
+    const CLIENT_ID: &str = "oxicrm";
+    const CLIENT_SECRET: &str = "s3cret";
+    const REDIRECT_URI: &str = "http://acme.localhost/sso/callback";
+
+    struct Grant {
+        client_id: String,
+        redirect_uri: String,
+        code_challenge: String,
+        nonce: String,
+    }
+
+    /// In-process stand-in for an OpenID provider
+    struct MockIssuer {
+        issuer: String,
+        /// Audience put in ID tokens instead of the requesting client
+        audience: Option<String>,
+        grants: Mutex<HashMap<String, Grant>>,
+    }
+
+    async fn discovery(State(mock): State<Arc<MockIssuer>>) -> Json<serde_json::Value> {
+        Json(serde_json::json!({
+            "issuer": mock.issuer,
+            "authorization_endpoint": format!("{}/authorize", mock.issuer),
+            "token_endpoint": format!("{}/token", mock.issuer),
+            "jwks_uri": format!("{}/jwks", mock.issuer),
+        }))
+    }
+
+    /// Signs the user in immediately and redirects back with a code
+    async fn authorize(
+        State(mock): State<Arc<MockIssuer>>,
+        Query(params): Query<HashMap<String, String>>,
+    ) -> Response {
+        if params.get("code_challenge_method").map(String::as_str) != Some("S256") {
+            return StatusCode::BAD_REQUEST.into_response();
+        }
+        let code = token::generate();
+        let redirect_uri = params["redirect_uri"].clone();
+        mock.grants.lock().unwrap().insert(
+            code.clone(),
+            Grant {
+                client_id: params["client_id"].clone(),
+                redirect_uri: redirect_uri.clone(),
+                code_challenge: params["code_challenge"].clone(),
+                nonce: params["nonce"].clone(),
+            },
+        );
+        let location = format!("{}?code={}&state={}", redirect_uri, code, params["state"]);
+        (StatusCode::FOUND, [(header::LOCATION, location)]).into_response()
+    }
+
+    async fn token_endpoint(
+        State(mock): State<Arc<MockIssuer>>,
+        Form(params): Form<HashMap<String, String>>,
+    ) -> Response {
+        let invalid_grant = || {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": "invalid_grant" })),
+            )
+                .into_response()
+        };
+        let grant = match mock.grants.lock().unwrap().remove(&params["code"]) {
+            Some(g) => g,
+            None => return invalid_grant(),
+        };
+        if params["client_id"] != grant.client_id
+            || params["client_secret"] != CLIENT_SECRET
+            || params["redirect_uri"] != grant.redirect_uri
+            || code_challenge(&params["code_verifier"]) != grant.code_challenge
+        {
+            return invalid_grant();
+        }
+
+        let now = Utc::now().timestamp();
+        let claims = serde_json::json!({
+            "iss": mock.issuer,
+            "sub": "user-1",
+            "aud": mock.audience.clone().unwrap_or(grant.client_id),
+            "iat": now,
+            "exp": now + 300,
+            "nonce": grant.nonce,
+            "email": "jane@example.com",
+            "email_verified": true,
+            "name": "Jane Doe",
+        });
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = Some("test-key".to_string());
+        let key = EncodingKey::from_rsa_pem(TEST_KEY_PEM.as_bytes()).unwrap();
+        let id_token = jsonwebtoken::encode(&header, &claims, &key).unwrap();
+        Json(serde_json::json!({ "id_token": id_token, "token_type": "Bearer" })).into_response()
+    }
+
+    async fn jwks() -> Json<serde_json::Value> {
+        Json(serde_json::json!({
+            "keys": [{
+                "kty": "RSA",
+                "kid": "test-key",
+                "use": "sig",
+                "alg": "RS256",
+                "n": TEST_KEY_N,
+                "e": "AQAB",
+            }]
+        }))
+    }
+
+    /// Starts a mock issuer and returns an SSO configuration pointing at it
+    async fn start_issuer(audience: Option<&str>) -> SsoConfig {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let issuer = format!("http://{}", listener.local_addr().unwrap());
+        let mock = Arc::new(MockIssuer {
+            issuer: issuer.clone(),
+            audience: audience.map(str::to_string),
+            grants: Mutex::new(HashMap::new()),
+        });
+        let app = Router::new()
+            .route("/.well-known/openid-configuration", get(discovery))
+            .route("/authorize", get(authorize))
+            .route("/token", post(token_endpoint))
+            .route("/jwks", get(jwks))
+            .with_state(mock);
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        SsoConfig {
+            id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            workspace_id: Uuid::new_v4(),
+            issuer_url: issuer,
+            client_id: CLIENT_ID.to_string(),
+            client_secret: CLIENT_SECRET.to_string(),
+            default_role: "Member".to_string(),
+            enforced: false,
+        }
+    }
+
+    /// Follows the authorization URL like a browser would and returns the code
+    async fn sign_in(authorization: &SsoAuthorization) -> String {
+        let browser = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap();
+        let response = browser.get(&authorization.url).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::FOUND);
+        let location = response.headers()[header::LOCATION].to_str().unwrap();
+        let callback = reqwest::Url::parse(location).unwrap();
+        let params: HashMap<_, _> = callback.query_pairs().into_owned().collect();
+        assert_eq!(params["state"], authorization.state);
+        params["code"].clone()
+    }
+
+    #[tokio::test]
+    async fn test_code_flow_returns_verified_claims() {
+        let config = start_issuer(None).await;
+        let provider = OidcSsoProvider::new();
+
+        let authorization = provider.authorize(&config, REDIRECT_URI).await.unwrap();
+        let code = sign_in(&authorization).await;
+        let claims = provider
+            .exchange_code(
+                &config,
+                &code,
+                REDIRECT_URI,
+                &authorization.pkce_verifier,
+                &authorization.nonce,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(claims.subject, "user-1");
+        assert_eq!(claims.email, "jane@example.com");
+        assert!(claims.email_verified);
+        assert_eq!(claims.name.as_deref(), Some("Jane Doe"));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_wrong_pkce_verifier() {
+        let config = start_issuer(None).await;
+        let provider = OidcSsoProvider::new();
+
+        let authorization = provider.authorize(&config, REDIRECT_URI).await.unwrap();
+        let code = sign_in(&authorization).await;
+        let result = provider
+            .exchange_code(
+                &config,
+                &code,
+                REDIRECT_URI,
+                &token::generate(),
+                &authorization.nonce,
+            )
+            .await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_nonce_mismatch() {
+        let config = start_issuer(None).await;
+        let provider = OidcSsoProvider::new();
+
+        let authorization = provider.authorize(&config, REDIRECT_URI).await.unwrap();
+        let code = sign_in(&authorization).await;
+        let result = provider
+            .exchange_code(
+                &config,
+                &code,
+                REDIRECT_URI,
+                &authorization.pkce_verifier,
+                "replayed-nonce",
+            )
+            .await;
+
+        assert!(result.unwrap_err().contains("nonce"));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_id_token_for_other_client() {
+        let config = start_issuer(Some("another-client")).await;
+        let provider = OidcSsoProvider::new();
+
+        let authorization = provider.authorize(&config, REDIRECT_URI).await.unwrap();
+        let code = sign_in(&authorization).await;
+        let result = provider
+            .exchange_code(
+                &config,
+                &code,
+                REDIRECT_URI,
+                &authorization.pkce_verifier,
+                &authorization.nonce,
+            )
+            .await;
+
+        assert!(result.unwrap_err().contains("Invalid ID token"));
+    }
+}
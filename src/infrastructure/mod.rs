@@ -1,6 +1,8 @@
 pub mod billing;
+pub mod config;
 pub mod email;
 pub mod external;
+pub mod health;
 pub mod identity;
 pub mod messaging;
 pub mod persistence;
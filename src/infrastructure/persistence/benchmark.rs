@@ -0,0 +1,450 @@
+//! Latency and query plans of the hot list and lookup paths over a seeded database.
+//!
+//! `bench_hot_paths` seeds `BENCH_RECORDS` rows (100k by default) spread over several
+//! workspaces, then fails when a path does not use its workspace index or when its p95
+//! latency exceeds `BENCH_BUDGET_MS`. It is ignored by default; run it with
+//! `cargo test --release bench_hot_paths -- --ignored --nocapture`, adding
+//! `--features postgres` for the Postgres run. `test_hot_paths_use_workspace_indexes` only
+//! checks the plans, over a small SQLite seed, on every test run.
+
+use super::conformance::member_of;
+use super::mapper::Mapper;
+use super::sea_orm_repo::SeaOrmRepositories;
+use crate::application::context::run_as;
+use crate::application::ports::output::{
+    CompanyRepository, EmailRepository, LeadRepository, PersonRepository,
+    TimelineActivityRepository,
+};
+use crate::application::ports::query::ListQuery;
+use crate::domain::states::{EmailDirection, EmailStatus, LeadSource, LeadStatus, WorkspaceState};
+use crate::domain::{Company, DomainError, Email, Lead, Person, TimelineActivity, Workspace};
+use chrono::{Duration, Utc};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::*;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration as Elapsed, Instant};
+use uuid::Uuid;
+
+const WORKSPACES: usize = 10;
+/// Lead owners per workspace
+const OWNERS: usize = 5;
+/// Rows per `INSERT`, under the bind parameter limits of both backends
+const BATCH: usize = 500;
+const RUNS: usize = 20;
+
+/// A seeded database and the repositories over it, recording every statement they run
+struct Bench {
+    db: DatabaseConnection,
+    repos: SeaOrmRepositories,
+    statements: Arc<Mutex<Vec<Statement>>>,
+    workspace_id: Uuid,
+    person_id: Uuid,
+    company_id: Uuid,
+    owner_id: Uuid,
+}
+
+/// Timings and plan of one hot path
+struct Report {
+    path: &'static str,
+    p50: Elapsed,
+    p95: Elapsed,
+    plan: String,
+}
+
+impl Bench {
+    /// Seeds `records` rows, split evenly between people, companies, leads, emails and
+    /// timeline activities
+    async fn seed(mut db: DatabaseConnection, records: usize) -> Self {
+        Migrator::up(&db, None).await.unwrap();
+
+        let now = Utc::now();
+        let workspaces: Vec<Uuid> = (0..WORKSPACES).map(|_| Uuid::new_v4()).collect();
+        let owners: Vec<Uuid> = (0..WORKSPACES * OWNERS).map(|_| Uuid::new_v4()).collect();
+        let per_kind = records / 5;
+        let people: Vec<Uuid> = (0..per_kind).map(|_| Uuid::new_v4()).collect();
+        let companies: Vec<Uuid> = (0..per_kind).map(|_| Uuid::new_v4()).collect();
+        let workspace_of = |i: usize| workspaces[i % WORKSPACES];
+        let created_at = |i: usize| now - Duration::seconds(i as i64);
+
+        insert_all(
+            &db,
+            workspaces.iter().map(|&id| Workspace {
+                id,
+                created_at: now,
+                updated_at: now,
+                subdomain: id.simple().to_string(),
+                state: WorkspaceState::Active,
+            }),
+        )
+        .await;
+        insert_all(
+            &db,
+            (0..per_kind).map(|i| Person {
+                id: people[i],
+                created_at: created_at(i),
+                updated_at: created_at(i),
+                deleted_at: None,
+                name: format!("Person {}", i),
+                email: format!("person{}@bench.test", i),
+                position: i as i32,
+                company_id: Some(companies[i]),
+                workspace_id: workspace_of(i),
+            }),
+        )
+        .await;
+        insert_all(
+            &db,
+            (0..per_kind).map(|i| Company {
+                id: companies[i],
+                created_at: created_at(i),
+                updated_at: created_at(i),
+                deleted_at: None,
+                name: format!("Company {}", i),
+                domain_name: format!("company{}.bench.test", i),
+                address: None,
+                employees_count: (i % 500) as i32,
+                position: i as i32,
+                workspace_id: workspace_of(i),
+                version: 1,
+            }),
+        )
+        .await;
+        insert_all(
+            &db,
+            (0..per_kind).map(|i| Lead {
+                id: Uuid::new_v4(),
+                created_at: created_at(i),
+                updated_at: created_at(i),
+                deleted_at: None,
+                first_name: format!("Lead {}", i),
+                last_name: "Bench".to_string(),
+                email: format!("lead{}@bench.test", i),
+                phone: None,
+                company_name: None,
+                job_title: None,
+                source: LeadSource::WebForm,
+                status: [
+                    LeadStatus::New,
+                    LeadStatus::Contacted,
+                    LeadStatus::Qualified,
+                    LeadStatus::Unqualified,
+                    LeadStatus::Converted,
+                ][i % 5],
+                score: (i * 37 % 100) as i32,
+                notes: None,
+                position: i as i32,
+                // Every third lead is unassigned, the rest go round the owners of its
+                // workspace
+                assigned_to_id: (i % 3 != 0)
+                    .then(|| owners[i % WORKSPACES * OWNERS + i / WORKSPACES % OWNERS]),
+                converted_person_id: None,
+                converted_company_id: None,
+                converted_opportunity_id: None,
+                converted_at: None,
+                last_contacted_at: None,
+                workspace_id: workspace_of(i),
+            }),
+        )
+        .await;
+        insert_all(
+            &db,
+            (0..per_kind).map(|i| Email {
+                id: Uuid::new_v4(),
+                created_at: created_at(i),
+                updated_at: created_at(i),
+                direction: EmailDirection::Outbound,
+                status: [EmailStatus::Pending, EmailStatus::Sent, EmailStatus::Failed][i % 3],
+                from_email: "crm@bench.test".to_string(),
+                to_email: format!("person{}@bench.test", i),
+                cc_emails: None,
+                bcc_emails: None,
+                subject: format!("Email {}", i),
+                body_text: String::new(),
+                body_html: None,
+                sent_at: None,
+                failed_at: None,
+                error_message: None,
+                email_template_id: None,
+                timeline_activity_id: None,
+                person_id: Some(people[i]),
+                company_id: Some(companies[i]),
+                opportunity_id: None,
+                task_id: None,
+                workflow_id: None,
+                workflow_run_id: None,
+                metadata: None,
+                workspace_id: workspace_of(i),
+            }),
+        )
+        .await;
+        insert_all(
+            &db,
+            (0..per_kind).map(|i| TimelineActivity {
+                id: Uuid::new_v4(),
+                created_at: created_at(i),
+                name: "email.sent".to_string(),
+                workspace_member_id: None,
+                person_id: Some(people[i]),
+                company_id: Some(companies[i]),
+                opportunity_id: None,
+                task_id: None,
+                note_id: None,
+                calendar_event_id: None,
+                workflow_id: None,
+                workspace_id: workspace_of(i),
+            }),
+        )
+        .await;
+        db.execute_unprepared("ANALYZE").await.unwrap();
+
+        let statements = Arc::new(Mutex::new(Vec::new()));
+        let log = statements.clone();
+        db.set_metric_callback(move |info| log.lock().unwrap().push(info.statement.clone()));
+        Bench {
+            repos: SeaOrmRepositories::new(db.clone()),
+            db,
+            statements,
+            workspace_id: workspaces[0],
+            person_id: people[0],
+            company_id: companies[0],
+            owner_id: owners[0],
+        }
+    }
+
+    /// Runs `call` `RUNS` times as a member of the measured workspace and explains the
+    /// statements of its last run
+    async fn measure<T, F, Fut>(&self, path: &'static str, call: F) -> Report
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, DomainError>>,
+    {
+        let mut timings = Vec::with_capacity(RUNS);
+        run_as(member_of(self.workspace_id), async {
+            for _ in 0..RUNS {
+                self.statements.lock().unwrap().clear();
+                let start = Instant::now();
+                call().await.unwrap();
+                timings.push(start.elapsed());
+            }
+        })
+        .await;
+        timings.sort();
+
+        let statements = std::mem::take(&mut *self.statements.lock().unwrap());
+        let mut plan = Vec::new();
+        for statement in statements {
+            plan.extend(self.explain(statement).await);
+        }
+        Report {
+            path,
+            p50: timings[RUNS / 2],
+            p95: timings[RUNS * 95 / 100],
+            plan: plan.join("\n"),
+        }
+    }
+
+    async fn explain(&self, statement: Statement) -> Vec<String> {
+        let backend = self.db.get_database_backend();
+        let (prefix, column) = match backend {
+            DatabaseBackend::Sqlite => ("EXPLAIN QUERY PLAN", "detail"),
+            _ => ("EXPLAIN", "QUERY PLAN"),
+        };
+        let explain = Statement::from_sql_and_values(
+            backend,
+            format!("{} {}", prefix, statement.sql),
+            statement.values.unwrap_or(Values(Vec::new())),
+        );
+        self.db
+            .query_all(explain)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|row| row.try_get::<String>("", column).unwrap())
+            .collect()
+    }
+
+    /// Every hot path, with the index it is expected to use
+    async fn run(&self) -> Vec<(Report, &'static str)> {
+        let repos = &self.repos;
+        let first_page = ListQuery::default();
+        vec![
+            (
+                self.measure("people.find_page", || repos.people.find_page(&first_page))
+                    .await,
+                "idx_person_workspace_created_at",
+            ),
+            (
+                self.measure("people.find_by_email", || {
+                    repos.people.find_by_email("person0@bench.test")
+                })
+                .await,
+                "idx_person_workspace_email_unique",
+            ),
+            (
+                self.measure("companies.find_page", || {
+                    repos.companies.find_page(&first_page)
+                })
+                .await,
+                "idx_company_workspace_created_at",
+            ),
+            (
+                self.measure("leads.find_page", || repos.leads.find_page(&first_page))
+                    .await,
+                "idx_lead_workspace_score",
+            ),
+            (
+                self.measure("leads.find_by_email", || {
+                    repos.leads.find_by_email("lead0@bench.test")
+                })
+                .await,
+                "idx_lead_workspace_email_unique",
+            ),
+            (
+                self.measure("leads.find_by_status", || {
+                    repos.leads.find_by_status(LeadStatus::Qualified)
+                })
+                .await,
+                "idx_lead_workspace_status_score",
+            ),
+            (
+                self.measure("leads.find_by_assigned_to", || {
+                    repos.leads.find_by_assigned_to(self.owner_id)
+                })
+                .await,
+                "idx_lead_workspace_assigned_to_score",
+            ),
+            (
+                self.measure("leads.find_high_score", || repos.leads.find_high_score(95))
+                    .await,
+                "idx_lead_workspace_score",
+            ),
+            (
+                self.measure("emails.find_page", || repos.emails.find_page(&first_page))
+                    .await,
+                "idx_email_workspace_created_at",
+            ),
+            (
+                self.measure("emails.find_pending", || repos.emails.find_pending())
+                    .await,
+                "idx_email_workspace_status_created_at",
+            ),
+            (
+                self.measure("emails.find_by_person_id", || {
+                    repos.emails.find_by_person_id(self.person_id)
+                })
+                .await,
+                "idx_email_workspace_person_created_at",
+            ),
+            (
+                self.measure("timeline.find_by_person_id", || {
+                    repos.timeline.find_by_person_id(self.person_id)
+                })
+                .await,
+                "idx_timeline_activity_workspace_person_created_at",
+            ),
+            (
+                self.measure("timeline.find_by_company_id", || {
+                    repos.timeline.find_by_company_id(self.company_id)
+                })
+                .await,
+                "idx_timeline_activity_workspace_company_created_at",
+            ),
+        ]
+    }
+}
+
+async fn insert_all<D: Mapper>(db: &DatabaseConnection, rows: impl IntoIterator<Item = D>) {
+    let mut models: Vec<_> = rows.into_iter().map(D::to_active_model).collect();
+    while !models.is_empty() {
+        let batch: Vec<_> = models.drain(..models.len().min(BATCH)).collect();
+        D::Entity::insert_many(batch).exec(db).await.unwrap();
+    }
+}
+
+fn assert_uses_indexes(reports: &[(Report, &str)]) {
+    for (report, index) in reports {
+        assert!(
+            report.plan.contains(index),
+            "{} does not use {}:\n{}",
+            report.path,
+            index,
+            report.plan
+        );
+    }
+}
+
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    std::env::var(name)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+async fn bench(db: DatabaseConnection) {
+    let records = env_or("BENCH_RECORDS", 100_000);
+    let budget = Elapsed::from_millis(env_or("BENCH_BUDGET_MS", 50));
+    let bench = Bench::seed(db, records).await;
+    let reports = bench.run().await;
+
+    println!("{} records, p95 budget {:?}", records, budget);
+    for (report, _) in &reports {
+        println!(
+            "{:<28} p50 {:>10.2?}  p95 {:>10.2?}",
+            report.path, report.p50, report.p95
+        );
+    }
+    assert_uses_indexes(&reports);
+    for (report, _) in &reports {
+        assert!(
+            report.p95 <= budget,
+            "{} took {:?} at p95",
+            report.path,
+            report.p95
+        );
+    }
+}
+
+#[cfg(feature = "sqlite")]
+async fn sqlite_db() -> DatabaseConnection {
+    Database::connect("sqlite::memory:").await.unwrap()
+}
+
+/// A schema of its own in the database at `TEST_POSTGRES_URL`, as in the conformance suite
+#[cfg(feature = "postgres")]
+async fn postgres_db() -> DatabaseConnection {
+    let url = std::env::var("TEST_POSTGRES_URL")
+        .unwrap_or_else(|_| "postgres://postgres@localhost/postgres".to_string());
+    let schema = format!("bench_{}", Uuid::new_v4().simple());
+    Database::connect(&url)
+        .await
+        .unwrap()
+        .execute_unprepared(&format!("CREATE SCHEMA {}", schema))
+        .await
+        .unwrap();
+
+    let mut options = ConnectOptions::new(url);
+    options.set_schema_search_path(schema);
+    Database::connect(options).await.unwrap()
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn test_hot_paths_use_workspace_indexes() {
+    let bench = Bench::seed(sqlite_db().await, 1_000).await;
+    assert_uses_indexes(&bench.run().await);
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+#[ignore = "seeds 100k records; run with --release"]
+async fn bench_hot_paths_sqlite() {
+    bench(sqlite_db().await).await;
+}
+
+#[cfg(feature = "postgres")]
+#[tokio::test]
+#[ignore = "seeds 100k records; run with --release"]
+async fn bench_hot_paths_postgres() {
+    bench(postgres_db().await).await;
+}
@@ -0,0 +1,1457 @@
+//! Behaviour every repository implementation must share, run against `InMemoryRepo` and each
+//! enabled SeaORM backend so that fast in-memory tests stay faithful to the database.
+
+use super::in_memory_repo::InMemoryRepo;
+use super::sea_orm_repo::SeaOrmRepositories;
+use crate::application::context::{run_as, run_as_system, Actor};
+use crate::application::ports::identity::Identity;
+use crate::application::ports::output::{
+    CompanyRepository, CustomObjectDataRepository, EmailRepository, EmailTemplateRepository,
+    InvitationRepository, LeadRepository, MetadataRepository, PersonRepository, TaskRepository,
+    TaskTargetRepository, TimelineActivityRepository, TrashRepository, UserRepository,
+    UserTokenRepository, WorkflowRepository, WorkflowRunRepository, WorkflowVersionRepository,
+    WorkflowVersionStepRepository, WorkspaceRepository,
+};
+use crate::application::ports::query::{Filter, FilterOp, ListQuery, Sort};
+use crate::application::ports::unit_of_work::UnitOfWork;
+use crate::domain::custom_object_data::CustomObjectData;
+use crate::domain::metadata::ObjectMetadata;
+use crate::domain::states::{
+    EmailDirection, EmailStatus, LeadSource, LeadStatus, TaskStatus, UserState, UserTokenPurpose,
+    WorkflowRunStatus, WorkflowStepType, WorkflowVersionStatus, WorkspaceState,
+};
+use crate::domain::{
+    Company, DomainError, Email, EmailTemplate, Invitation, Lead, Person, Task, TaskTarget,
+    TimelineActivity, TrashedKind, User, UserToken, Workflow, WorkflowRun, WorkflowVersion,
+    WorkflowVersionStep, Workspace, WorkspaceMember,
+};
+use chrono::{Duration, Utc};
+use sea_orm::DatabaseConnection;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// The ports under test, from one implementation
+struct Repos {
+    people: Arc<dyn PersonRepository>,
+    companies: Arc<dyn CompanyRepository>,
+    users: Arc<dyn UserRepository>,
+    user_tokens: Arc<dyn UserTokenRepository>,
+    workspaces: Arc<dyn WorkspaceRepository>,
+    invitations: Arc<dyn InvitationRepository>,
+    tasks: Arc<dyn TaskRepository>,
+    task_targets: Arc<dyn TaskTargetRepository>,
+    timeline: Arc<dyn TimelineActivityRepository>,
+    trash: Arc<dyn TrashRepository>,
+    leads: Arc<dyn LeadRepository>,
+    workflows: Arc<dyn WorkflowRepository>,
+    workflow_versions: Arc<dyn WorkflowVersionRepository>,
+    workflow_steps: Arc<dyn WorkflowVersionStepRepository>,
+    workflow_runs: Arc<dyn WorkflowRunRepository>,
+    emails: Arc<dyn EmailRepository>,
+    email_templates: Arc<dyn EmailTemplateRepository>,
+    metadata: Arc<dyn MetadataRepository>,
+    custom_objects: Arc<dyn CustomObjectDataRepository>,
+    unit_of_work: Arc<dyn UnitOfWork>,
+}
+
+impl Repos {
+    fn in_memory() -> Self {
+        let repo = Arc::new(InMemoryRepo::new());
+        Repos {
+            people: repo.clone(),
+            companies: repo.clone(),
+            users: repo.clone(),
+            user_tokens: repo.clone(),
+            workspaces: repo.clone(),
+            invitations: repo.clone(),
+            tasks: repo.clone(),
+            task_targets: repo.clone(),
+            timeline: repo.clone(),
+            trash: repo.clone(),
+            leads: repo.clone(),
+            workflows: repo.clone(),
+            workflow_versions: repo.clone(),
+            workflow_steps: repo.clone(),
+            workflow_runs: repo.clone(),
+            emails: repo.clone(),
+            email_templates: repo.clone(),
+            metadata: repo.clone(),
+            custom_objects: repo.clone(),
+            unit_of_work: repo,
+        }
+    }
+
+    fn sea_orm(db: DatabaseConnection) -> Self {
+        let repos = SeaOrmRepositories::new(db);
+        Repos {
+            people: repos.people,
+            companies: repos.companies,
+            users: repos.users.clone(),
+            user_tokens: repos.users,
+            workspaces: repos.workspaces.clone(),
+            invitations: repos.workspaces,
+            tasks: repos.tasks.clone(),
+            task_targets: repos.tasks,
+            timeline: repos.timeline,
+            trash: repos.trash,
+            leads: repos.leads,
+            workflows: repos.workflows.clone(),
+            workflow_versions: repos.workflows.clone(),
+            workflow_steps: repos.workflows.clone(),
+            workflow_runs: repos.workflows,
+            emails: repos.emails.clone(),
+            email_templates: repos.emails,
+            metadata: repos.metadata.clone(),
+            custom_objects: repos.metadata,
+            unit_of_work: repos.unit_of_work,
+        }
+    }
+}
+
+/// Declares a `#[tokio::test]` per implementation for each conformance test, which gets an
+/// empty repository
+macro_rules! conformance_tests {
+    ($($test:ident),* $(,)?) => {
+        mod in_memory {
+            $(
+                #[tokio::test]
+                async fn $test() {
+                    super::$test(super::Repos::in_memory()).await
+                }
+            )*
+        }
+
+        #[cfg(feature = "sqlite")]
+        mod sqlite {
+            $(
+                #[tokio::test]
+                async fn $test() {
+                    super::$test(super::sqlite_repo().await).await
+                }
+            )*
+        }
+
+        #[cfg(feature = "postgres")]
+        mod postgres {
+            $(
+                #[tokio::test]
+                async fn $test() {
+                    super::$test(super::postgres_repo().await).await
+                }
+            )*
+        }
+    };
+}
+
+conformance_tests!(
+    test_reads_are_limited_to_the_current_workspace,
+    test_custom_records_are_limited_to_the_current_workspace,
+    test_writes_cannot_reach_another_workspace,
+    test_anonymous_queries_are_rejected,
+    test_updates_based_on_a_stale_version_conflict,
+    test_unit_of_work_commits_or_rolls_back_together,
+    test_trash_restores_links_and_purges_expired_records,
+    test_trashed_records_are_hidden_from_reads,
+    test_list_queries_filter_sort_and_paginate,
+    test_list_queries_reject_unknown_fields,
+    test_unique_keys_are_enforced,
+    test_unique_keys_hold_within_a_workspace,
+    test_trashed_records_release_their_unique_keys,
+    test_people_updates_keep_emails_unique,
+    test_tokens_are_used_once,
+    test_invitations_are_accepted_once,
+    test_updates_of_missing_rows_are_not_found,
+    test_workflow_versions_follow_their_workflow,
+    test_published_triggers_come_from_the_live_version,
+    test_parked_runs_are_due_once_their_time_comes,
+    test_pending_emails_are_sent_oldest_first,
+);
+
+#[cfg(feature = "sqlite")]
+async fn sqlite_repo() -> Repos {
+    use migration::{Migrator, MigratorTrait};
+    use sea_orm::Database;
+
+    let db = Database::connect("sqlite::memory:").await.unwrap();
+    Migrator::up(&db, None).await.unwrap();
+    Repos::sea_orm(db)
+}
+
+/// Runs in a schema of its own in the database at `TEST_POSTGRES_URL`, a local server by
+/// default
+#[cfg(feature = "postgres")]
+async fn postgres_repo() -> Repos {
+    use migration::{Migrator, MigratorTrait};
+    use sea_orm::{ConnectOptions, ConnectionTrait, Database};
+
+    let url = std::env::var("TEST_POSTGRES_URL")
+        .unwrap_or_else(|_| "postgres://postgres@localhost/postgres".to_string());
+    let schema = format!("test_{}", Uuid::new_v4().simple());
+    Database::connect(&url)
+        .await
+        .unwrap()
+        .execute_unprepared(&format!("CREATE SCHEMA {}", schema))
+        .await
+        .unwrap();
+
+    let mut options = ConnectOptions::new(url);
+    options.set_schema_search_path(schema);
+    let db = Database::connect(options).await.unwrap();
+    Migrator::up(&db, None).await.unwrap();
+    Repos::sea_orm(db)
+}
+
+/// Owner of the records of a test; Postgres enforces the workspace foreign keys
+async fn workspace(repo: &Repos) -> Uuid {
+    let id = Uuid::new_v4();
+    repo.workspaces
+        .create(Workspace {
+            id,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            subdomain: id.simple().to_string(),
+            state: WorkspaceState::Active,
+        })
+        .await
+        .unwrap();
+    id
+}
+
+fn user(email: &str) -> User {
+    User {
+        id: Uuid::new_v4(),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        email: email.to_string(),
+        password_hash: String::new(),
+        state: UserState::Active,
+    }
+}
+
+pub(super) fn member_of(workspace_id: Uuid) -> Actor {
+    let user = user(&format!("{}@example.com", Uuid::new_v4()));
+    Actor::User(Box::new(Identity {
+        member: Some(WorkspaceMember {
+            id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            user_id: user.id,
+            workspace_id,
+            role: "Admin".to_string(),
+            name: "Member".to_string(),
+            suspended_at: None,
+        }),
+        user,
+        api_key: None,
+    }))
+}
+
+fn company(workspace_id: Uuid) -> Company {
+    Company {
+        id: Uuid::new_v4(),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        deleted_at: None,
+        name: "Acme".to_string(),
+        domain_name: "acme.test".to_string(),
+        address: None,
+        employees_count: 10,
+        position: 0,
+        workspace_id,
+        version: 1,
+    }
+}
+
+fn person(workspace_id: Uuid) -> Person {
+    Person {
+        id: Uuid::new_v4(),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        deleted_at: None,
+        name: "Ada".to_string(),
+        email: "ada@acme.test".to_string(),
+        position: 0,
+        company_id: None,
+        workspace_id,
+    }
+}
+
+fn lead(workspace_id: Uuid, first_name: &str, score: i32) -> Lead {
+    Lead {
+        id: Uuid::new_v4(),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        deleted_at: None,
+        first_name: first_name.to_string(),
+        last_name: "Lead".to_string(),
+        email: format!("{}@leads.test", first_name.to_lowercase()),
+        phone: None,
+        company_name: None,
+        job_title: None,
+        source: LeadSource::WebForm,
+        status: LeadStatus::New,
+        score,
+        notes: None,
+        position: 0,
+        assigned_to_id: None,
+        converted_person_id: None,
+        converted_company_id: None,
+        converted_opportunity_id: None,
+        converted_at: None,
+        last_contacted_at: None,
+        workspace_id,
+    }
+}
+
+fn email(workspace_id: Uuid, subject: &str, status: EmailStatus) -> Email {
+    Email {
+        id: Uuid::new_v4(),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        direction: EmailDirection::Outbound,
+        status,
+        from_email: "crm@acme.test".to_string(),
+        to_email: "ada@acme.test".to_string(),
+        cc_emails: None,
+        bcc_emails: None,
+        subject: subject.to_string(),
+        body_text: String::new(),
+        body_html: None,
+        sent_at: None,
+        failed_at: None,
+        error_message: None,
+        email_template_id: None,
+        timeline_activity_id: None,
+        person_id: None,
+        company_id: None,
+        opportunity_id: None,
+        task_id: None,
+        workflow_id: None,
+        workflow_run_id: None,
+        metadata: None,
+        workspace_id,
+    }
+}
+
+fn email_template(workspace_id: Uuid) -> EmailTemplate {
+    EmailTemplate {
+        id: Uuid::new_v4(),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        name: "welcome".to_string(),
+        subject: "Welcome".to_string(),
+        body_text: "Hello {{name}}".to_string(),
+        body_html: None,
+        category: "manual".to_string(),
+        workspace_id,
+    }
+}
+
+fn filter(field: &str, op: FilterOp, value: &str) -> Filter {
+    Filter {
+        field: field.to_string(),
+        op,
+        value: value.to_string(),
+    }
+}
+
+async fn test_reads_are_limited_to_the_current_workspace(repo: Repos) {
+    let (a, b) = (workspace(&repo).await, workspace(&repo).await);
+
+    let (acme, welcome) = run_as(member_of(a), async {
+        repo.people.create(person(a)).await.unwrap();
+        let welcome = repo.email_templates.create(email_template(a)).await;
+        (
+            repo.companies.create(company(a)).await.unwrap(),
+            welcome.unwrap(),
+        )
+    })
+    .await;
+
+    run_as(member_of(b), async {
+        assert!(repo.people.find_all().await.unwrap().is_empty());
+        assert!(repo
+            .people
+            .find_by_email("ada@acme.test")
+            .await
+            .unwrap()
+            .is_none());
+        assert!(repo.companies.find_all().await.unwrap().is_empty());
+        assert!(repo.companies.find_by_id(acme.id).await.unwrap().is_none());
+        assert!(repo.email_templates.find_all().await.unwrap().is_empty());
+        assert!(repo
+            .email_templates
+            .find_by_id(welcome.id)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(repo
+            .email_templates
+            .find_by_name("welcome")
+            .await
+            .unwrap()
+            .is_none());
+        // Template names are unique within a workspace only
+        repo.email_templates
+            .create(email_template(b))
+            .await
+            .unwrap();
+    })
+    .await;
+
+    run_as(member_of(a), async {
+        assert_eq!(repo.people.find_all().await.unwrap().len(), 1);
+        assert!(repo.companies.find_by_id(acme.id).await.unwrap().is_some());
+        let templates = repo.email_templates.find_all().await.unwrap();
+        assert_eq!(templates.len(), 1);
+        assert_eq!(templates[0].id, welcome.id);
+    })
+    .await;
+
+    let grace = Person {
+        email: "grace@globex.test".to_string(),
+        ..person(b)
+    };
+    run_as(member_of(b), repo.people.create(grace))
+        .await
+        .unwrap();
+    let everyone = run_as_system(repo.people.find_all()).await;
+    assert_eq!(everyone.unwrap().len(), 2);
+    let workspace_work = run_as(Actor::Workspace(b), repo.people.find_all()).await;
+    assert_eq!(workspace_work.unwrap().len(), 1);
+}
+
+async fn test_custom_records_are_limited_to_the_current_workspace(repo: Repos) {
+    let (a, b) = (workspace(&repo).await, workspace(&repo).await);
+
+    let object = run_as(member_of(a), async {
+        let object = repo
+            .metadata
+            .create_object(ObjectMetadata {
+                id: Uuid::new_v4(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                name_singular: "project".to_string(),
+                name_plural: "projects".to_string(),
+                description: None,
+                workspace_id: a,
+            })
+            .await
+            .unwrap();
+        repo.custom_objects
+            .create(CustomObjectData {
+                id: Uuid::new_v4(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                object_metadata_id: object.id,
+                properties: serde_json::json!({ "name": "Apollo" }),
+                workspace_id: a,
+                version: 1,
+            })
+            .await
+            .unwrap();
+        object
+    })
+    .await;
+
+    run_as(member_of(b), async {
+        assert!(repo
+            .metadata
+            .find_object_by_name("project")
+            .await
+            .unwrap()
+            .is_none());
+        assert!(repo.metadata.get_schema().await.unwrap().is_empty());
+        assert!(repo
+            .custom_objects
+            .find_by_object_metadata_id(object.id)
+            .await
+            .unwrap()
+            .is_empty());
+    })
+    .await;
+
+    run_as(member_of(a), async {
+        assert_eq!(
+            repo.custom_objects
+                .find_by_object_metadata_id(object.id)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    })
+    .await;
+}
+
+async fn test_writes_cannot_reach_another_workspace(repo: Repos) {
+    let (a, b) = (workspace(&repo).await, workspace(&repo).await);
+
+    let acme = run_as(member_of(a), repo.companies.create(company(a)))
+        .await
+        .unwrap();
+    let welcome = run_as(member_of(a), repo.email_templates.create(email_template(a)))
+        .await
+        .unwrap();
+
+    run_as(member_of(b), async {
+        let overwritten = EmailTemplate {
+            subject: "Hijacked".to_string(),
+            ..welcome.clone()
+        };
+        assert!(matches!(
+            repo.email_templates.update(overwritten).await,
+            Err(DomainError::Permission(_) | DomainError::NotFound)
+        ));
+        assert!(matches!(
+            repo.email_templates.delete(welcome.id).await,
+            Err(DomainError::NotFound)
+        ));
+        let renamed = Company {
+            name: "Hijacked".to_string(),
+            ..acme.clone()
+        };
+        assert!(matches!(
+            repo.companies.update(renamed).await,
+            Err(DomainError::NotFound)
+        ));
+        assert!(matches!(
+            repo.companies.delete(acme.id).await,
+            Err(DomainError::NotFound)
+        ));
+        assert!(matches!(
+            repo.people.create(person(a)).await,
+            Err(DomainError::Permission(_))
+        ));
+    })
+    .await;
+
+    run_as(member_of(a), async {
+        let stored = repo.companies.find_by_id(acme.id).await.unwrap().unwrap();
+        assert_eq!(stored.name, "Acme");
+        assert!(repo.people.find_all().await.unwrap().is_empty());
+        let template = repo.email_templates.find_by_id(welcome.id).await.unwrap();
+        assert_eq!(template.unwrap().subject, "Welcome");
+    })
+    .await;
+}
+
+async fn test_anonymous_queries_are_rejected(repo: Repos) {
+    assert!(matches!(
+        repo.people.find_all().await,
+        Err(DomainError::Permission(_))
+    ));
+    assert!(matches!(
+        repo.companies.create(company(Uuid::new_v4())).await,
+        Err(DomainError::Permission(_))
+    ));
+}
+
+async fn test_updates_based_on_a_stale_version_conflict(repo: Repos) {
+    let a = workspace(&repo).await;
+
+    run_as(member_of(a), async {
+        let acme = repo.companies.create(company(a)).await.unwrap();
+
+        let first = Company {
+            name: "Acme Corp".to_string(),
+            ..acme.clone()
+        };
+        let first = repo.companies.update(first).await.unwrap();
+        assert_eq!(first.version, acme.version + 1);
+
+        // A second writer still holding the original version
+        let stale = Company {
+            name: "Acme Inc".to_string(),
+            ..acme.clone()
+        };
+        assert!(matches!(
+            repo.companies.update(stale).await,
+            Err(DomainError::Conflict(_))
+        ));
+        let stored = repo.companies.find_by_id(acme.id).await.unwrap().unwrap();
+        assert_eq!(stored.name, "Acme Corp");
+
+        let missing = Company {
+            id: Uuid::new_v4(),
+            ..acme
+        };
+        assert!(matches!(
+            repo.companies.update(missing).await,
+            Err(DomainError::NotFound)
+        ));
+    })
+    .await;
+}
+
+async fn test_unit_of_work_commits_or_rolls_back_together(repo: Repos) {
+    let uow = &*repo.unit_of_work;
+    let a = workspace(&repo).await;
+
+    run_as(member_of(a), async {
+        let failed: Result<(), DomainError> = uow
+            .atomically(async {
+                repo.people.create(person(a)).await?;
+                repo.companies.create(company(a)).await?;
+                Err(DomainError::Validation("later step failed".to_string()))
+            })
+            .await;
+        assert!(failed.is_err());
+        assert!(repo.people.find_all().await.unwrap().is_empty());
+        assert!(repo.companies.find_all().await.unwrap().is_empty());
+
+        uow.atomically(async {
+            repo.people.create(person(a)).await?;
+            // A failing nested unit only undoes its own writes
+            let nested: Result<(), DomainError> = uow
+                .atomically(async {
+                    repo.companies.create(company(a)).await?;
+                    Err(DomainError::NotFound)
+                })
+                .await;
+            assert!(nested.is_err());
+            Ok(())
+        })
+        .await
+        .unwrap();
+        assert_eq!(repo.people.find_all().await.unwrap().len(), 1);
+        assert!(repo.companies.find_all().await.unwrap().is_empty());
+    })
+    .await;
+}
+
+async fn test_trash_restores_links_and_purges_expired_records(repo: Repos) {
+    let a = workspace(&repo).await;
+
+    run_as(member_of(a), async {
+        let ada = repo.people.create(person(a)).await.unwrap();
+        let task = repo
+            .tasks
+            .create(Task {
+                id: Uuid::new_v4(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                deleted_at: None,
+                title: "Call Ada".to_string(),
+                body: None,
+                status: TaskStatus::Todo,
+                position: 0,
+                assignee_id: None,
+                due_at: None,
+                workspace_id: a,
+            })
+            .await
+            .unwrap();
+        repo.task_targets
+            .create(TaskTarget {
+                id: Uuid::new_v4(),
+                created_at: Utc::now(),
+                task_id: task.id,
+                person_id: Some(ada.id),
+                company_id: None,
+                opportunity_id: None,
+            })
+            .await
+            .unwrap();
+        let activity = repo
+            .timeline
+            .create(TimelineActivity {
+                id: Uuid::new_v4(),
+                created_at: Utc::now(),
+                name: "person.created".to_string(),
+                workspace_member_id: None,
+                person_id: Some(ada.id),
+                company_id: None,
+                opportunity_id: None,
+                task_id: None,
+                note_id: None,
+                calendar_event_id: None,
+                workflow_id: None,
+                workspace_id: a,
+            })
+            .await
+            .unwrap();
+
+        // Deleting trashes the person with its links
+        repo.people.delete(ada.id).await.unwrap();
+        assert!(repo.people.find_all().await.unwrap().is_empty());
+        assert!(repo
+            .task_targets
+            .find_by_task_id(task.id)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(repo
+            .timeline
+            .find_by_person_id(ada.id)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(matches!(
+            repo.people.delete(ada.id).await,
+            Err(DomainError::NotFound)
+        ));
+        let trashed = repo.trash.find_trashed(&TrashedKind::ALL).await.unwrap();
+        assert_eq!(trashed.len(), 1);
+        assert_eq!(trashed[0].kind, TrashedKind::Person);
+        assert_eq!(trashed[0].name, "Ada");
+
+        // Restoring brings the links back
+        repo.trash
+            .restore(TrashedKind::Person, ada.id)
+            .await
+            .unwrap();
+        assert_eq!(repo.people.find_all().await.unwrap().len(), 1);
+        assert_eq!(
+            repo.task_targets
+                .find_by_task_id(task.id)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            repo.timeline.find_by_person_id(ada.id).await.unwrap().len(),
+            1
+        );
+        assert!(repo
+            .trash
+            .find_trashed(&TrashedKind::ALL)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Purging only removes what was trashed before the cutoff
+        repo.people.delete(ada.id).await.unwrap();
+        let purged = repo
+            .trash
+            .purge_deleted_before(Utc::now() - Duration::days(1))
+            .await
+            .unwrap();
+        assert_eq!(purged, 0);
+        let purged = repo
+            .trash
+            .purge_deleted_before(Utc::now() + Duration::seconds(1))
+            .await
+            .unwrap();
+        assert_eq!(purged, 1);
+        assert!(repo
+            .trash
+            .find_trashed(&TrashedKind::ALL)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(matches!(
+            repo.trash.restore(TrashedKind::Person, ada.id).await,
+            Err(DomainError::NotFound)
+        ));
+        assert!(repo
+            .task_targets
+            .find_by_task_id(task.id)
+            .await
+            .unwrap()
+            .is_empty());
+        // The activity was trashed with the person, so it went with it
+        assert!(matches!(
+            repo.timeline.delete(activity.id).await,
+            Err(DomainError::NotFound)
+        ));
+    })
+    .await;
+}
+
+async fn test_trashed_records_are_hidden_from_reads(repo: Repos) {
+    let a = workspace(&repo).await;
+
+    run_as(member_of(a), async {
+        let ada = repo.people.create(person(a)).await.unwrap();
+        let owner = Uuid::new_v4();
+        let grace = Lead {
+            assigned_to_id: Some(owner),
+            ..lead(a, "Grace", 80)
+        };
+        let lead = repo.leads.create(grace).await.unwrap();
+        assert_eq!(
+            repo.leads.find_by_assigned_to(owner).await.unwrap().len(),
+            1
+        );
+        repo.people.delete(ada.id).await.unwrap();
+        repo.leads.delete(lead.id).await.unwrap();
+
+        assert!(repo
+            .people
+            .find_by_email(&ada.email)
+            .await
+            .unwrap()
+            .is_none());
+        let people = repo.people.find_page(&ListQuery::default()).await.unwrap();
+        assert!(people.items.is_empty());
+
+        assert!(repo.leads.find_by_id(lead.id).await.unwrap().is_none());
+        assert!(repo
+            .leads
+            .find_by_email(&lead.email)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(repo
+            .leads
+            .find_by_status(LeadStatus::New)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(repo
+            .leads
+            .find_by_assigned_to(owner)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(repo.leads.find_unassigned().await.unwrap().is_empty());
+        assert!(repo.leads.find_high_score(0).await.unwrap().is_empty());
+        assert!(repo
+            .leads
+            .find_page(&ListQuery::default())
+            .await
+            .unwrap()
+            .items
+            .is_empty());
+
+        let trashed = repo.trash.find_trashed(&[TrashedKind::Lead]).await.unwrap();
+        assert_eq!(trashed.len(), 1);
+        assert_eq!(trashed[0].name, "Grace Lead");
+    })
+    .await;
+}
+
+async fn test_list_queries_filter_sort_and_paginate(repo: Repos) {
+    let a = workspace(&repo).await;
+
+    run_as(member_of(a), async {
+        for (name, score) in [("Ada", 50), ("Alan", 40), ("Barbara", 30), ("Grace", 20)] {
+            repo.leads.create(lead(a, name, score)).await.unwrap();
+        }
+        let qualified = Lead {
+            status: LeadStatus::Qualified,
+            ..lead(a, "Edsger", 45)
+        };
+        repo.leads.create(qualified).await.unwrap();
+
+        // Highest score first by default, one page at a time
+        let mut query = ListQuery {
+            limit: Some(2),
+            filters: vec![filter("status", FilterOp::Eq, "new")],
+            ..ListQuery::default()
+        };
+        let first = repo.leads.find_page(&query).await.unwrap();
+        let names: Vec<_> = first.items.iter().map(|l| l.first_name.as_str()).collect();
+        assert_eq!(names, ["Ada", "Alan"]);
+        query.cursor = first.next_cursor;
+        assert!(query.cursor.is_some());
+        let second = repo.leads.find_page(&query).await.unwrap();
+        let names: Vec<_> = second.items.iter().map(|l| l.first_name.as_str()).collect();
+        assert_eq!(names, ["Barbara", "Grace"]);
+        assert!(second.next_cursor.is_none());
+
+        let query = ListQuery {
+            sort: Some(Sort::asc("first_name")),
+            filters: vec![
+                filter("score", FilterOp::Gte, "30"),
+                filter("first_name", FilterOp::Contains, "a"),
+                filter("status", FilterOp::Neq, "converted"),
+            ],
+            ..ListQuery::default()
+        };
+        let page = repo.leads.find_page(&query).await.unwrap();
+        let names: Vec<_> = page.items.iter().map(|l| l.first_name.as_str()).collect();
+        assert_eq!(names, ["Ada", "Alan", "Barbara"]);
+
+        let query = ListQuery {
+            sort: Some(Sort::desc("email")),
+            filters: vec![filter("name", FilterOp::Contains, "Ad")],
+            ..ListQuery::default()
+        };
+        repo.people.create(person(a)).await.unwrap();
+        let grace = Person {
+            name: "Grace".to_string(),
+            email: "grace@acme.test".to_string(),
+            ..person(a)
+        };
+        repo.people.create(grace).await.unwrap();
+        let page = repo.people.find_page(&query).await.unwrap();
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].name, "Ada");
+    })
+    .await;
+}
+
+async fn test_list_queries_reject_unknown_fields(repo: Repos) {
+    let a = workspace(&repo).await;
+
+    run_as(member_of(a), async {
+        let unknown = ListQuery {
+            filters: vec![filter("notes", FilterOp::Eq, "x")],
+            ..ListQuery::default()
+        };
+        assert!(matches!(
+            repo.leads.find_page(&unknown).await,
+            Err(DomainError::Validation(_))
+        ));
+        let unsortable = ListQuery {
+            sort: Some(Sort::asc("phone")),
+            ..ListQuery::default()
+        };
+        assert!(matches!(
+            repo.leads.find_page(&unsortable).await,
+            Err(DomainError::Validation(_))
+        ));
+        let not_a_number = ListQuery {
+            filters: vec![filter("score", FilterOp::Gt, "high")],
+            ..ListQuery::default()
+        };
+        assert!(matches!(
+            repo.leads.find_page(&not_a_number).await,
+            Err(DomainError::Validation(_))
+        ));
+    })
+    .await;
+}
+
+async fn test_tokens_are_used_once(repo: Repos) {
+    let ada = repo.users.create(user("ada@example.com")).await.unwrap();
+    let token = |purpose| UserToken {
+        id: Uuid::new_v4(),
+        created_at: Utc::now(),
+        user_id: ada.id,
+        purpose,
+        token_hash: Uuid::new_v4().to_string(),
+        expires_at: Utc::now() + Duration::hours(1),
+        used_at: None,
+    };
+    let first = token(UserTokenPurpose::PasswordReset);
+    let second = token(UserTokenPurpose::PasswordReset);
+    let verification = token(UserTokenPurpose::EmailVerification);
+    for token in [&first, &second, &verification] {
+        repo.user_tokens.create(token.clone()).await.unwrap();
+    }
+
+    let now = Utc::now();
+    assert!(repo.user_tokens.mark_used(first.id, now).await.unwrap());
+    assert!(!repo.user_tokens.mark_used(first.id, now).await.unwrap());
+    assert!(!repo
+        .user_tokens
+        .mark_used(Uuid::new_v4(), now)
+        .await
+        .unwrap());
+
+    // Only the unused tokens for the purpose are marked
+    let later = now + Duration::minutes(1);
+    repo.user_tokens
+        .mark_all_used(ada.id, UserTokenPurpose::PasswordReset, later)
+        .await
+        .unwrap();
+    let used_at = |token: &UserToken| {
+        let token_hash = token.token_hash.clone();
+        let tokens = repo.user_tokens.clone();
+        async move {
+            tokens
+                .find_by_token_hash(&token_hash)
+                .await
+                .unwrap()
+                .unwrap()
+                .used_at
+        }
+    };
+    assert!(used_at(&first).await.unwrap() < later);
+    assert!(used_at(&second).await.is_some());
+    assert!(used_at(&verification).await.is_none());
+}
+
+async fn test_invitations_are_accepted_once(repo: Repos) {
+    let a = workspace(&repo).await;
+    let ada = repo.users.create(user("ada@example.com")).await.unwrap();
+    let admin = repo
+        .workspaces
+        .add_member(WorkspaceMember {
+            id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            user_id: ada.id,
+            workspace_id: a,
+            role: "Admin".to_string(),
+            name: ada.email.clone(),
+            suspended_at: None,
+        })
+        .await
+        .unwrap();
+    let invitation = |email: &str| Invitation {
+        id: Uuid::new_v4(),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        workspace_id: a,
+        email: email.to_string(),
+        role: "Member".to_string(),
+        invited_by: admin.id,
+        token_hash: Uuid::new_v4().to_string(),
+        expires_at: Utc::now() + Duration::days(1),
+        accepted_at: None,
+        revoked_at: None,
+    };
+    let open = repo
+        .invitations
+        .create(invitation("alan@example.com"))
+        .await
+        .unwrap();
+    let revoked = repo
+        .invitations
+        .create(Invitation {
+            revoked_at: Some(Utc::now()),
+            ..invitation("grace@example.com")
+        })
+        .await
+        .unwrap();
+
+    let now = Utc::now();
+    assert!(repo.invitations.mark_accepted(open.id, now).await.unwrap());
+    assert!(!repo.invitations.mark_accepted(open.id, now).await.unwrap());
+    assert!(!repo
+        .invitations
+        .mark_accepted(revoked.id, now)
+        .await
+        .unwrap());
+
+    let accepted = repo.invitations.find_by_id(open.id).await.unwrap().unwrap();
+    assert!(accepted.accepted_at.is_some());
+    assert!(repo.invitations.find_open(a).await.unwrap().is_empty());
+}
+
+async fn test_unique_keys_are_enforced(repo: Repos) {
+    let a = workspace(&repo).await;
+
+    repo.users.create(user("ada@example.com")).await.unwrap();
+    assert!(matches!(
+        repo.users.create(user("ada@example.com")).await,
+        Err(DomainError::Validation(_))
+    ));
+
+    run_as(member_of(a), async {
+        repo.people.create(person(a)).await.unwrap();
+        assert!(matches!(
+            repo.people.create(person(a)).await,
+            Err(DomainError::Validation(_))
+        ));
+
+        repo.companies.create(company(a)).await.unwrap();
+        assert!(matches!(
+            repo.companies.create(company(a)).await,
+            Err(DomainError::Validation(_))
+        ));
+        assert_eq!(repo.companies.find_all().await.unwrap().len(), 1);
+    })
+    .await;
+}
+
+async fn test_unique_keys_hold_within_a_workspace(repo: Repos) {
+    let (a, b) = (workspace(&repo).await, workspace(&repo).await);
+
+    for workspace_id in [a, b] {
+        run_as(member_of(workspace_id), async {
+            repo.people.create(person(workspace_id)).await.unwrap();
+            repo.companies.create(company(workspace_id)).await.unwrap();
+            repo.leads
+                .create(lead(workspace_id, "Ada", 50))
+                .await
+                .unwrap();
+
+            let ada = repo.people.find_by_email("ada@acme.test").await.unwrap();
+            assert_eq!(ada.unwrap().workspace_id, workspace_id);
+            let lead = repo.leads.find_by_email("ada@leads.test").await.unwrap();
+            assert_eq!(lead.unwrap().workspace_id, workspace_id);
+        })
+        .await;
+    }
+}
+
+async fn test_trashed_records_release_their_unique_keys(repo: Repos) {
+    let a = workspace(&repo).await;
+
+    run_as(member_of(a), async {
+        let ada = repo.people.create(person(a)).await.unwrap();
+        let acme = repo.companies.create(company(a)).await.unwrap();
+        let ada_lead = repo.leads.create(lead(a, "Ada", 50)).await.unwrap();
+        repo.people.delete(ada.id).await.unwrap();
+        repo.companies.delete(acme.id).await.unwrap();
+        repo.leads.delete(ada_lead.id).await.unwrap();
+
+        // The trashed records no longer hold their email or domain
+        repo.people.create(person(a)).await.unwrap();
+        repo.companies.create(company(a)).await.unwrap();
+        repo.leads.create(lead(a, "Ada", 50)).await.unwrap();
+
+        // ...so they cannot come back while the new records do
+        for (kind, id) in [
+            (TrashedKind::Person, ada.id),
+            (TrashedKind::Company, acme.id),
+            (TrashedKind::Lead, ada_lead.id),
+        ] {
+            assert!(matches!(
+                repo.trash.restore(kind, id).await,
+                Err(DomainError::Validation(_))
+            ));
+        }
+        assert_eq!(
+            repo.trash
+                .find_trashed(&TrashedKind::ALL)
+                .await
+                .unwrap()
+                .len(),
+            3
+        );
+    })
+    .await;
+}
+
+async fn test_people_updates_keep_emails_unique(repo: Repos) {
+    let (a, b) = (workspace(&repo).await, workspace(&repo).await);
+
+    let grace = run_as(member_of(a), async {
+        repo.people.create(person(a)).await.unwrap();
+        let grace = Person {
+            email: "grace@acme.test".to_string(),
+            ..person(a)
+        };
+        let grace = repo.people.create(grace).await.unwrap();
+
+        let renamed = Person {
+            name: "Grace Hopper".to_string(),
+            ..grace.clone()
+        };
+        let renamed = repo.people.update(renamed).await.unwrap();
+        assert_eq!(renamed.name, "Grace Hopper");
+        assert_eq!(
+            repo.people
+                .find_by_id(grace.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .name,
+            "Grace Hopper"
+        );
+
+        let taken = Person {
+            email: "ada@acme.test".to_string(),
+            ..grace.clone()
+        };
+        assert!(matches!(
+            repo.people.update(taken).await,
+            Err(DomainError::Validation(_))
+        ));
+        grace
+    })
+    .await;
+
+    run_as(member_of(b), async {
+        assert!(matches!(
+            repo.people.update(grace).await,
+            Err(DomainError::Permission(_) | DomainError::NotFound)
+        ));
+    })
+    .await;
+}
+
+async fn test_updates_of_missing_rows_are_not_found(repo: Repos) {
+    assert!(matches!(
+        repo.users.update(user("ada@example.com")).await,
+        Err(DomainError::NotFound)
+    ));
+}
+
+async fn test_workflow_versions_follow_their_workflow(repo: Repos) {
+    let (a, b) = (workspace(&repo).await, workspace(&repo).await);
+
+    let (workflow, version) = run_as(member_of(a), async {
+        let workflow = repo
+            .workflows
+            .create(Workflow {
+                id: Uuid::new_v4(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                name: "Welcome".to_string(),
+                last_published_version_id: None,
+                workspace_id: a,
+            })
+            .await
+            .unwrap();
+        let version = repo
+            .workflow_versions
+            .create(WorkflowVersion {
+                id: Uuid::new_v4(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                workflow_id: workflow.id,
+                status: WorkflowVersionStatus::Draft,
+            })
+            .await
+            .unwrap();
+        (workflow, version)
+    })
+    .await;
+
+    run_as(member_of(b), async {
+        assert!(repo.workflow_versions.find_all().await.unwrap().is_empty());
+        assert!(repo
+            .workflow_versions
+            .find_by_id(version.id)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(matches!(
+            repo.workflow_versions
+                .find_by_workflow_id(workflow.id)
+                .await,
+            Err(DomainError::NotFound)
+        ));
+        let published = WorkflowVersion {
+            status: WorkflowVersionStatus::Published,
+            ..version.clone()
+        };
+        assert!(matches!(
+            repo.workflow_versions.update(published).await,
+            Err(DomainError::NotFound)
+        ));
+    })
+    .await;
+
+    run_as(member_of(a), async {
+        let versions = repo
+            .workflow_versions
+            .find_by_workflow_id(workflow.id)
+            .await
+            .unwrap();
+        assert_eq!(versions.len(), 1);
+        assert_eq!(versions[0].status, WorkflowVersionStatus::Draft);
+    })
+    .await;
+}
+
+async fn test_published_triggers_come_from_the_live_version(repo: Repos) {
+    let (a, b) = (workspace(&repo).await, workspace(&repo).await);
+
+    let welcome = published_workflow(&repo, a, "Welcome").await;
+    let follow_up = published_workflow(&repo, b, "Follow up").await;
+
+    run_as(member_of(a), async {
+        let triggers = repo.workflows.find_published_triggers(None).await.unwrap();
+        assert_eq!(triggers.len(), 1);
+        let (workflow, step) = &triggers[0];
+        assert_eq!(workflow.id, welcome.id);
+        assert_eq!(step.step_type, WorkflowStepType::Trigger);
+        assert_eq!(
+            Some(step.workflow_version_id),
+            welcome.last_published_version_id
+        );
+    })
+    .await;
+
+    run_as_system(async {
+        let all = repo.workflows.find_published_triggers(None).await.unwrap();
+        assert_eq!(all.len(), 2);
+        let of_b = repo
+            .workflows
+            .find_published_triggers(Some(b))
+            .await
+            .unwrap();
+        assert_eq!(of_b.len(), 1);
+        assert_eq!(of_b[0].0.id, follow_up.id);
+    })
+    .await;
+}
+
+async fn test_parked_runs_are_due_once_their_time_comes(repo: Repos) {
+    let (a, b) = (workspace(&repo).await, workspace(&repo).await);
+    let welcome = published_workflow(&repo, a, "Welcome").await;
+    let follow_up = published_workflow(&repo, b, "Follow up").await;
+
+    let now = Utc::now();
+    let run = |workflow: &Workflow, status, wake_at| WorkflowRun {
+        id: Uuid::new_v4(),
+        created_at: now,
+        updated_at: now,
+        workflow_version_id: workflow.last_published_version_id.unwrap(),
+        status,
+        input: None,
+        output: None,
+        error: None,
+        cursor: Some(Uuid::new_v4()),
+        wake_at,
+        workflow_chain: vec![Uuid::new_v4(), workflow.id],
+    };
+    let due = run(
+        &welcome,
+        WorkflowRunStatus::Pending,
+        Some(now - Duration::minutes(1)),
+    );
+    let later = run(
+        &welcome,
+        WorkflowRunStatus::Pending,
+        Some(now + Duration::hours(1)),
+    );
+    let running = run(&welcome, WorkflowRunStatus::Running, None);
+    let due_elsewhere = run(
+        &follow_up,
+        WorkflowRunStatus::Pending,
+        Some(now - Duration::minutes(2)),
+    );
+
+    run_as(member_of(a), async {
+        for run in [&due, &later, &running] {
+            repo.workflow_runs.create(run.clone()).await.unwrap();
+        }
+        let ids = |runs: Vec<WorkflowRun>| runs.into_iter().map(|r| r.id).collect::<Vec<_>>();
+        assert_eq!(
+            ids(repo.workflow_runs.find_due(now).await.unwrap()),
+            [due.id]
+        );
+        assert_eq!(
+            ids(repo
+                .workflow_runs
+                .find_running_before(now + Duration::seconds(1))
+                .await
+                .unwrap()),
+            [running.id]
+        );
+        // Runs saved since are not the ones a previous process left running
+        assert!(repo
+            .workflow_runs
+            .find_running_before(now - Duration::seconds(1))
+            .await
+            .unwrap()
+            .is_empty());
+
+        // The cursor and wake-up time are saved with the status
+        let resumed = WorkflowRun {
+            status: WorkflowRunStatus::Running,
+            cursor: None,
+            wake_at: None,
+            ..due.clone()
+        };
+        repo.workflow_runs.update(resumed).await.unwrap();
+        let stored = repo
+            .workflow_runs
+            .find_by_id(due.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.cursor, None);
+        assert_eq!(stored.wake_at, None);
+        assert_eq!(stored.workflow_chain, due.workflow_chain);
+        assert!(repo.workflow_runs.find_due(now).await.unwrap().is_empty());
+    })
+    .await;
+
+    run_as(member_of(b), async {
+        repo.workflow_runs
+            .create(due_elsewhere.clone())
+            .await
+            .unwrap();
+    })
+    .await;
+
+    run_as_system(async {
+        let due = repo.workflow_runs.find_due(now).await.unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id, due_elsewhere.id);
+        assert_eq!(due[0].cursor, due_elsewhere.cursor);
+        let running = repo
+            .workflow_runs
+            .find_running_before(now + Duration::seconds(1))
+            .await
+            .unwrap();
+        assert_eq!(running.len(), 2);
+    })
+    .await;
+}
+
+/// A workflow whose published version and newer draft each have a trigger and an action
+async fn published_workflow(repo: &Repos, workspace_id: Uuid, name: &str) -> Workflow {
+    run_as(member_of(workspace_id), async {
+        let mut workflow = repo
+            .workflows
+            .create(Workflow {
+                id: Uuid::new_v4(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                name: name.to_string(),
+                last_published_version_id: None,
+                workspace_id,
+            })
+            .await
+            .unwrap();
+        let mut version_ids = Vec::new();
+        for status in [
+            WorkflowVersionStatus::Published,
+            WorkflowVersionStatus::Draft,
+        ] {
+            let version = repo
+                .workflow_versions
+                .create(WorkflowVersion {
+                    id: Uuid::new_v4(),
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                    workflow_id: workflow.id,
+                    status,
+                })
+                .await
+                .unwrap();
+            for (position, step_type) in [WorkflowStepType::Trigger, WorkflowStepType::SendEmail]
+                .into_iter()
+                .enumerate()
+            {
+                repo.workflow_steps
+                    .create(WorkflowVersionStep {
+                        id: Uuid::new_v4(),
+                        created_at: Utc::now(),
+                        workflow_version_id: version.id,
+                        name: None,
+                        step_type,
+                        settings: serde_json::json!({ "type": "manual" }),
+                        position: position as i32,
+                    })
+                    .await
+                    .unwrap();
+            }
+            version_ids.push(version.id);
+        }
+        workflow.last_published_version_id = Some(version_ids[0]);
+        repo.workflows.update(workflow).await.unwrap()
+    })
+    .await
+}
+
+async fn test_pending_emails_are_sent_oldest_first(repo: Repos) {
+    let a = workspace(&repo).await;
+
+    run_as(member_of(a), async {
+        let now = Utc::now();
+        for (minutes_ago, subject, status) in [
+            (1, "Newest", EmailStatus::Pending),
+            (3, "Oldest", EmailStatus::Pending),
+            (2, "Delivered", EmailStatus::Sent),
+        ] {
+            let email = Email {
+                created_at: now - Duration::minutes(minutes_ago),
+                ..email(a, subject, status)
+            };
+            repo.emails.create(email).await.unwrap();
+        }
+
+        let pending = repo.emails.find_pending().await.unwrap();
+        let subjects: Vec<_> = pending.iter().map(|e| e.subject.as_str()).collect();
+        assert_eq!(subjects, ["Oldest", "Newest"]);
+
+        // Lists show the newest first
+        let page = repo.emails.find_page(&ListQuery::default()).await.unwrap();
+        let subjects: Vec<_> = page.items.iter().map(|e| e.subject.as_str()).collect();
+        assert_eq!(subjects, ["Newest", "Delivered", "Oldest"]);
+    })
+    .await;
+}
@@ -0,0 +1,152 @@
+//! Opens the connection pool described by `DatabaseConfig`.
+//!
+//! SeaORM does not expose SQLite pragmas, so SQLite pools are built with sqlx directly and
+//! handed to SeaORM; every connection of the pool gets the same pragmas.
+
+use crate::infrastructure::config::DatabaseConfig;
+use sea_orm::{ConnectOptions, Database, DatabaseConnection, DbErr};
+
+pub async fn connect(config: &DatabaseConfig) -> Result<DatabaseConnection, DbErr> {
+    let mut options = ConnectOptions::new(config.url.clone());
+    options
+        .max_connections(config.max_connections)
+        .min_connections(config.min_connections)
+        .connect_timeout(config.connect_timeout)
+        .acquire_timeout(config.acquire_timeout)
+        .idle_timeout(config.idle_timeout);
+
+    #[cfg(feature = "sqlite")]
+    if config.is_sqlite() {
+        return connect_sqlite(config, options).await;
+    }
+    Database::connect(options).await
+}
+
+#[cfg(feature = "sqlite")]
+async fn connect_sqlite(
+    config: &DatabaseConfig,
+    options: ConnectOptions,
+) -> Result<DatabaseConnection, DbErr> {
+    use sea_orm::{RuntimeErr, SqlxSqliteConnector};
+    use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqliteSynchronous};
+    use std::str::FromStr;
+
+    let sqlx_err = |e: sqlx::Error| DbErr::Conn(RuntimeErr::SqlxError(e));
+    let pragma_err =
+        |name: &str, value: &str| DbErr::Custom(format!("Unsupported SQLite {} {:?}", name, value));
+    let sqlite = &config.sqlite;
+    let mut connect = SqliteConnectOptions::from_str(&config.url)
+        .map_err(sqlx_err)?
+        .journal_mode(
+            SqliteJournalMode::from_str(&sqlite.journal_mode)
+                .map_err(|_| pragma_err("journal mode", &sqlite.journal_mode))?,
+        )
+        .synchronous(
+            SqliteSynchronous::from_str(&sqlite.synchronous)
+                .map_err(|_| pragma_err("synchronous setting", &sqlite.synchronous))?,
+        )
+        .busy_timeout(sqlite.busy_timeout)
+        .foreign_keys(sqlite.foreign_keys);
+
+    let mut pool = options.pool_options::<sqlx::Sqlite>();
+    if config.is_in_memory() {
+        // The connections of the pool open the same database through the shared cache, and
+        // closing the last one would drop the data
+        connect = connect.shared_cache(true);
+        pool = pool
+            .min_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None);
+    }
+    let pool = pool.connect_with(connect).await.map_err(sqlx_err)?;
+    Ok(SqlxSqliteConnector::from_sqlx_sqlite_pool(pool))
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+    use crate::infrastructure::config::Config;
+    use sea_orm::{ConnectionTrait, Statement, TransactionTrait};
+
+    async fn pragma(db: &DatabaseConnection, name: &str) -> String {
+        let row = db
+            .query_one(Statement::from_string(
+                db.get_database_backend(),
+                format!("PRAGMA {}", name),
+            ))
+            .await
+            .unwrap()
+            .unwrap();
+        match row.try_get_by_index::<String>(0) {
+            Ok(value) => value,
+            Err(_) => row.try_get_by_index::<i64>(0).unwrap().to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_connections_get_the_configured_pragmas() {
+        let path = std::env::temp_dir().join(format!("oxicrm-{}.db", uuid::Uuid::new_v4()));
+        let url = format!("sqlite://{}?mode=rwc", path.display());
+        let config = Config::from_vars(|name| match name {
+            "DATABASE_URL" => Some(url.clone()),
+            "SQLITE_BUSY_TIMEOUT_MS" => Some("1500".to_owned()),
+            _ => None,
+        })
+        .unwrap();
+
+        let db = connect(&config.database).await.unwrap();
+        assert_eq!(pragma(&db, "journal_mode").await, "wal");
+        // NORMAL
+        assert_eq!(pragma(&db, "synchronous").await, "1");
+        assert_eq!(pragma(&db, "busy_timeout").await, "1500");
+        assert_eq!(pragma(&db, "foreign_keys").await, "1");
+
+        db.close().await.unwrap();
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_pools_share_one_database_between_connections() {
+        let config = Config::from_vars(|name| match name {
+            "DATABASE_URL" => Some("sqlite::memory:".to_owned()),
+            "DATABASE_ACQUIRE_TIMEOUT_SECS" => Some("1".to_owned()),
+            _ => None,
+        })
+        .unwrap();
+        let db = connect(&config.database).await.unwrap();
+        db.execute_unprepared("CREATE TABLE a (id INTEGER); CREATE TABLE b (id INTEGER)")
+            .await
+            .unwrap();
+        let count = |table: &str| {
+            let sql = format!("SELECT COUNT(*) FROM {}", table);
+            let db = db.clone();
+            async move {
+                let row = db
+                    .query_one(Statement::from_string(db.get_database_backend(), sql))
+                    .await
+                    .unwrap()
+                    .unwrap();
+                row.try_get_by_index::<i64>(0).unwrap()
+            }
+        };
+
+        // A transaction holds its connection while other requests read through another one;
+        // writers take turns, so a second write waits for the commit
+        let txn = db.begin().await.unwrap();
+        txn.execute_unprepared("INSERT INTO a VALUES (1)")
+            .await
+            .unwrap();
+        assert_eq!(count("b").await, 0);
+        let write = tokio::spawn({
+            let db = db.clone();
+            async move { db.execute_unprepared("INSERT INTO b VALUES (1)").await }
+        });
+        txn.commit().await.unwrap();
+        write.await.unwrap().unwrap();
+
+        assert_eq!(count("a").await, 1);
+        assert_eq!(count("b").await, 1);
+    }
+}
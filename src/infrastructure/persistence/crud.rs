@@ -0,0 +1,129 @@
+//! Generic reads and writes shared by the SeaORM repositories: run a query or a write,
+//! map the rows through `Mapper` and the error through `map_db_err`.
+//!
+//! Tenant isolation stays with the caller, which builds its selects with `workspace_scope`;
+//! the `_scoped` writes apply the same restriction to inserts and updates.
+
+use super::errors::map_db_err;
+use super::mapper::Mapper;
+use super::query::{self as list_query, Field};
+use super::workspace_scope::{self, WorkspaceScoped};
+use crate::application::ports::query::{ListQuery, Page, Sort};
+use crate::domain::DomainError;
+use sea_orm::*;
+use uuid::Uuid;
+
+type Column<D> = <<D as Mapper>::Entity as EntityTrait>::Column;
+
+pub async fn find_one<D: Mapper>(
+    db: &impl ConnectionTrait,
+    select: Select<D::Entity>,
+) -> Result<Option<D>, DomainError> {
+    select
+        .one(db)
+        .await
+        .map_err(map_db_err)?
+        .map(D::from_model)
+        .transpose()
+}
+
+pub async fn find_all<D: Mapper>(
+    db: &impl ConnectionTrait,
+    select: Select<D::Entity>,
+) -> Result<Vec<D>, DomainError> {
+    select
+        .all(db)
+        .await
+        .map_err(map_db_err)?
+        .into_iter()
+        .map(D::from_model)
+        .collect()
+}
+
+/// One page of `select` for a list query (see `query::fetch_page`)
+pub async fn find_page<D: Mapper>(
+    db: &impl ConnectionTrait,
+    select: Select<D::Entity>,
+    query: &ListQuery,
+    fields: &[Field<Column<D>>],
+    id_column: Column<D>,
+    default_sort: Sort,
+) -> Result<Page<D>, DomainError> {
+    let page = list_query::fetch_page(db, select, query, fields, id_column, default_sort).await?;
+    Ok(Page {
+        items: page
+            .items
+            .into_iter()
+            .map(D::from_model)
+            .collect::<Result<_, _>>()?,
+        next_cursor: page.next_cursor,
+    })
+}
+
+pub async fn insert<D: Mapper>(db: &impl ConnectionTrait, item: D) -> Result<D, DomainError> {
+    let model = item
+        .to_active_model()
+        .insert(db)
+        .await
+        .map_err(map_db_err)?;
+    D::from_model(model)
+}
+
+/// Inserts a row of a workspace-owned table, rejecting rows of another workspace
+pub async fn insert_scoped<D>(db: &impl ConnectionTrait, item: D) -> Result<D, DomainError>
+where
+    D: Mapper,
+    D::Entity: WorkspaceScoped,
+{
+    let model = item.to_active_model();
+    workspace_scope::check_owner(&model)?;
+    D::from_model(model.insert(db).await.map_err(map_db_err)?)
+}
+
+/// Writes the set columns of `model`; fails with `NotFound` when the row does not exist
+pub async fn update<D: Mapper>(
+    db: &impl ConnectionTrait,
+    model: D::ActiveModel,
+) -> Result<D, DomainError> {
+    D::from_model(model.update(db).await.map_err(map_db_err)?)
+}
+
+/// `update` restricted to the current workspace; rows outside it are reported as missing
+pub async fn update_scoped<D>(
+    db: &impl ConnectionTrait,
+    model: D::ActiveModel,
+) -> Result<D, DomainError>
+where
+    D: Mapper,
+    D::Entity: WorkspaceScoped,
+{
+    let updated = workspace_scope::update(model)?
+        .exec(db)
+        .await
+        .map_err(map_db_err)?;
+    D::from_model(updated)
+}
+
+/// Compare-and-swap `update_scoped`: writes `model` only while the `version` column of the
+/// row `id` still holds `expected`, and fails with `Conflict` once it has moved on
+pub async fn update_versioned<D>(
+    db: &impl ConnectionTrait,
+    id: Uuid,
+    model: D::ActiveModel,
+    version: Column<D>,
+    expected: i32,
+) -> Result<D, DomainError>
+where
+    D: Mapper,
+    D::Entity: WorkspaceScoped,
+    Uuid: Into<<<D::Entity as EntityTrait>::PrimaryKey as PrimaryKeyTrait>::ValueType>,
+{
+    match workspace_scope::update(model)?
+        .filter(version.eq(expected))
+        .exec(db)
+        .await
+    {
+        Ok(updated) => D::from_model(updated),
+        Err(e) => Err(workspace_scope::map_versioned_write_err::<D::Entity>(db, id, e).await),
+    }
+}
@@ -0,0 +1,108 @@
+use crate::domain::DomainError;
+use crate::infrastructure::persistence::mapper::{serde_name, Mapper};
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "api_keys")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub member_id: Uuid,
+    pub name: String,
+    pub token_prefix: String,
+    #[sea_orm(unique)]
+    pub token_hash: String,
+    pub scope: String, // Stored as string, mapped to Enum in domain
+    pub objects: Option<Json>,
+    pub last_used_at: Option<DateTimeWithTimeZone>,
+    pub revoked_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::workspace::Entity",
+        from = "Column::WorkspaceId",
+        to = "super::workspace::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Workspace,
+    #[sea_orm(
+        belongs_to = "super::workspace_member::Entity",
+        from = "Column::MemberId",
+        to = "super::workspace_member::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    WorkspaceMember,
+}
+
+impl Related<super::workspace::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Workspace.def()
+    }
+}
+
+impl Related<super::workspace_member::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::WorkspaceMember.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    pub fn to_domain(&self) -> crate::domain::entities::ApiKey {
+        crate::domain::entities::ApiKey {
+            id: self.id,
+            created_at: self.created_at.into(),
+            updated_at: self.updated_at.into(),
+            workspace_id: self.workspace_id,
+            member_id: self.member_id,
+            name: self.name.clone(),
+            token_prefix: self.token_prefix.clone(),
+            token_hash: self.token_hash.clone(),
+            // Unknown scopes fall back to the narrower one
+            scope: serde_json::from_value(serde_json::Value::String(self.scope.clone()))
+                .unwrap_or(crate::domain::permissions::ApiKeyScope::ReadOnly),
+            objects: self
+                .objects
+                .clone()
+                .and_then(|json| serde_json::from_value(json).ok()),
+            last_used_at: self.last_used_at.map(|d| d.into()),
+            revoked_at: self.revoked_at.map(|d| d.into()),
+        }
+    }
+}
+
+impl Mapper for crate::domain::entities::ApiKey {
+    type Entity = Entity;
+    type Model = Model;
+    type ActiveModel = ActiveModel;
+
+    fn from_model(model: Model) -> Result<Self, DomainError> {
+        Ok(model.to_domain())
+    }
+
+    fn to_active_model(self) -> ActiveModel {
+        ActiveModel {
+            id: Set(self.id),
+            workspace_id: Set(self.workspace_id),
+            member_id: Set(self.member_id),
+            name: Set(self.name),
+            token_prefix: Set(self.token_prefix),
+            token_hash: Set(self.token_hash),
+            scope: Set(serde_name(&self.scope)),
+            objects: Set(self.objects.map(|o| serde_json::json!(o))),
+            last_used_at: Set(self.last_used_at.map(|d| d.into())),
+            revoked_at: Set(self.revoked_at.map(|d| d.into())),
+            created_at: Set(self.created_at.into()),
+            updated_at: Set(self.updated_at.into()),
+        }
+    }
+}
@@ -1,4 +1,7 @@
+use crate::domain::DomainError;
+use crate::infrastructure::persistence::mapper::Mapper;
 use sea_orm::entity::prelude::*;
+use sea_orm::Set;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
@@ -36,3 +39,27 @@ impl Model {
         }
     }
 }
+
+impl Mapper for crate::domain::CalendarEvent {
+    type Entity = Entity;
+    type Model = Model;
+    type ActiveModel = ActiveModel;
+
+    fn from_model(model: Model) -> Result<Self, DomainError> {
+        Ok(model.to_domain())
+    }
+
+    fn to_active_model(self) -> ActiveModel {
+        ActiveModel {
+            id: Set(self.id),
+            connected_account_id: Set(self.connected_account_id),
+            title: Set(self.title),
+            start_time: Set(self.start_time.into()),
+            end_time: Set(self.end_time.into()),
+            description: Set(self.description),
+            created_at: Set(self.created_at.into()),
+            updated_at: Set(self.updated_at.into()),
+            workspace_id: Set(self.workspace_id),
+        }
+    }
+}
@@ -1,4 +1,7 @@
+use crate::domain::DomainError;
+use crate::infrastructure::persistence::mapper::Mapper;
 use sea_orm::entity::prelude::*;
+use sea_orm::Set;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
@@ -15,6 +18,7 @@ pub struct Model {
     pub address: Option<String>, // Stored as JSON or Text
     pub employees_count: i32,
     pub workspace_id: Uuid,
+    pub version: i32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -35,6 +39,32 @@ impl Model {
             employees_count: self.employees_count,
             position: 0, // Default position
             workspace_id: self.workspace_id,
+            version: self.version,
+        }
+    }
+}
+
+impl Mapper for crate::domain::Company {
+    type Entity = Entity;
+    type Model = Model;
+    type ActiveModel = ActiveModel;
+
+    fn from_model(model: Model) -> Result<Self, DomainError> {
+        Ok(model.to_domain())
+    }
+
+    fn to_active_model(self) -> ActiveModel {
+        ActiveModel {
+            id: Set(self.id),
+            created_at: Set(self.created_at.into()),
+            updated_at: Set(self.updated_at.into()),
+            deleted_at: Set(self.deleted_at.map(|d| d.into())),
+            name: Set(self.name),
+            domain_name: Set(self.domain_name),
+            address: Set(self.address),
+            employees_count: Set(self.employees_count),
+            workspace_id: Set(self.workspace_id),
+            version: Set(self.version),
         }
     }
 }
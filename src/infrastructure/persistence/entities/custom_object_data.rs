@@ -1,4 +1,7 @@
+use crate::domain::DomainError;
+use crate::infrastructure::persistence::mapper::Mapper;
 use sea_orm::entity::prelude::*;
+use sea_orm::Set;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
@@ -11,6 +14,7 @@ pub struct Model {
     pub object_metadata_id: Uuid,
     pub properties: Json,
     pub workspace_id: Uuid,
+    pub version: i32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -42,6 +46,29 @@ impl Model {
             object_metadata_id: self.object_metadata_id,
             properties: self.properties,
             workspace_id: self.workspace_id,
+            version: self.version,
+        }
+    }
+}
+
+impl Mapper for crate::domain::custom_object_data::CustomObjectData {
+    type Entity = Entity;
+    type Model = Model;
+    type ActiveModel = ActiveModel;
+
+    fn from_model(model: Model) -> Result<Self, DomainError> {
+        Ok(model.to_domain())
+    }
+
+    fn to_active_model(self) -> ActiveModel {
+        ActiveModel {
+            id: Set(self.id),
+            created_at: Set(self.created_at),
+            updated_at: Set(self.updated_at),
+            object_metadata_id: Set(self.object_metadata_id),
+            properties: Set(self.properties),
+            workspace_id: Set(self.workspace_id),
+            version: Set(self.version),
         }
     }
 }
@@ -1,4 +1,8 @@
+use crate::domain::states::{EmailDirection, EmailStatus};
+use crate::domain::DomainError;
+use crate::infrastructure::persistence::mapper::Mapper;
 use sea_orm::entity::prelude::*;
+use sea_orm::Set;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
@@ -39,8 +43,6 @@ impl ActiveModelBehavior for ActiveModel {}
 
 impl Model {
     pub fn to_domain(self) -> crate::domain::Email {
-        use crate::domain::states::{EmailDirection, EmailStatus};
-
         let direction = match self.direction.as_str() {
             "inbound" => EmailDirection::Inbound,
             _ => EmailDirection::Outbound,
@@ -93,3 +95,63 @@ impl Model {
         }
     }
 }
+
+/// Stored form of a direction, as read back by `to_domain`
+pub fn direction_name(direction: EmailDirection) -> &'static str {
+    match direction {
+        EmailDirection::Outbound => "outbound",
+        EmailDirection::Inbound => "inbound",
+    }
+}
+
+/// Stored form of a status, as read back by `to_domain`
+pub fn status_name(status: EmailStatus) -> &'static str {
+    match status {
+        EmailStatus::Pending => "pending",
+        EmailStatus::Sent => "sent",
+        EmailStatus::Failed => "failed",
+        EmailStatus::Received => "received",
+    }
+}
+
+impl Mapper for crate::domain::Email {
+    type Entity = Entity;
+    type Model = Model;
+    type ActiveModel = ActiveModel;
+
+    fn from_model(model: Model) -> Result<Self, DomainError> {
+        Ok(model.to_domain())
+    }
+
+    fn to_active_model(self) -> ActiveModel {
+        ActiveModel {
+            id: Set(self.id),
+            created_at: Set(self.created_at.into()),
+            updated_at: Set(self.updated_at.into()),
+            direction: Set(direction_name(self.direction).to_string()),
+            status: Set(status_name(self.status).to_string()),
+            from_email: Set(self.from_email),
+            to_email: Set(self.to_email),
+            cc_emails: Set(self.cc_emails.and_then(|cc| serde_json::to_value(cc).ok())),
+            bcc_emails: Set(self
+                .bcc_emails
+                .and_then(|bcc| serde_json::to_value(bcc).ok())),
+            subject: Set(self.subject),
+            body_text: Set(self.body_text),
+            body_html: Set(self.body_html),
+            sent_at: Set(self.sent_at.map(|d| d.into())),
+            failed_at: Set(self.failed_at.map(|d| d.into())),
+            error_message: Set(self.error_message),
+            email_template_id: Set(self.email_template_id),
+            timeline_activity_id: Set(self.timeline_activity_id),
+            person_id: Set(self.person_id),
+            company_id: Set(self.company_id),
+            opportunity_id: Set(self.opportunity_id),
+            task_id: Set(self.task_id),
+            workflow_id: Set(self.workflow_id),
+            workflow_run_id: Set(self.workflow_run_id),
+            metadata: Set(self.metadata),
+            workspace_id: Set(self.workspace_id),
+        }
+    }
+}
@@ -1,4 +1,7 @@
+use crate::domain::DomainError;
+use crate::infrastructure::persistence::mapper::Mapper;
 use sea_orm::entity::prelude::*;
+use sea_orm::Set;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
@@ -8,12 +11,12 @@ pub struct Model {
     pub id: Uuid,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
-    #[sea_orm(unique)]
     pub name: String,
     pub subject: String,
     pub body_text: String,
     pub body_html: Option<String>,
     pub category: String,
+    pub workspace_id: Uuid,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -32,6 +35,31 @@ impl Model {
             body_text: self.body_text,
             body_html: self.body_html,
             category: self.category,
+            workspace_id: self.workspace_id,
+        }
+    }
+}
+
+impl Mapper for crate::domain::EmailTemplate {
+    type Entity = Entity;
+    type Model = Model;
+    type ActiveModel = ActiveModel;
+
+    fn from_model(model: Model) -> Result<Self, DomainError> {
+        Ok(model.to_domain())
+    }
+
+    fn to_active_model(self) -> ActiveModel {
+        ActiveModel {
+            id: Set(self.id),
+            created_at: Set(self.created_at.into()),
+            updated_at: Set(self.updated_at.into()),
+            name: Set(self.name),
+            subject: Set(self.subject),
+            body_text: Set(self.body_text),
+            body_html: Set(self.body_html),
+            category: Set(self.category),
+            workspace_id: Set(self.workspace_id),
         }
     }
 }
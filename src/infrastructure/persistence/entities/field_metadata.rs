@@ -1,5 +1,8 @@
 use crate::domain::metadata::FieldType;
+use crate::domain::DomainError;
+use crate::infrastructure::persistence::mapper::Mapper;
 use sea_orm::entity::prelude::*;
+use sea_orm::Set;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
@@ -60,3 +63,39 @@ impl Model {
         }
     }
 }
+
+/// Stored form of a field type, as read back by `to_domain`
+pub fn type_name(field_type: FieldType) -> &'static str {
+    match field_type {
+        FieldType::Text => "Text",
+        FieldType::Number => "Number",
+        FieldType::Date => "Date",
+        FieldType::Boolean => "Boolean",
+        FieldType::Select => "Select",
+        FieldType::Relation => "Relation",
+        FieldType::Json => "Json",
+    }
+}
+
+impl Mapper for crate::domain::metadata::FieldMetadata {
+    type Entity = Entity;
+    type Model = Model;
+    type ActiveModel = ActiveModel;
+
+    fn from_model(model: Model) -> Result<Self, DomainError> {
+        Ok(model.to_domain())
+    }
+
+    fn to_active_model(self) -> ActiveModel {
+        ActiveModel {
+            id: Set(self.id),
+            created_at: Set(self.created_at),
+            updated_at: Set(self.updated_at),
+            object_metadata_id: Set(self.object_metadata_id),
+            name: Set(self.name),
+            r#type: Set(type_name(self.field_type).to_string()),
+            is_custom: Set(self.is_custom),
+            settings: Set(self.settings),
+        }
+    }
+}
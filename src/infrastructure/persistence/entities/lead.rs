@@ -1,6 +1,9 @@
 use crate::domain::states::{LeadSource, LeadStatus};
+use crate::domain::DomainError;
 use crate::domain::Lead;
+use crate::infrastructure::persistence::mapper::Mapper;
 use sea_orm::entity::prelude::*;
+use sea_orm::Set;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
@@ -80,3 +83,61 @@ impl Model {
         }
     }
 }
+
+/// Stored form of a source, as read back by `to_domain`
+pub fn source_name(source: LeadSource) -> &'static str {
+    match source {
+        LeadSource::WebForm => "web_form",
+        LeadSource::ManualEntry => "manual_entry",
+        LeadSource::Email => "email",
+        LeadSource::Referral => "referral",
+    }
+}
+
+/// Stored form of a status, as read back by `to_domain`
+pub fn status_name(status: LeadStatus) -> &'static str {
+    match status {
+        LeadStatus::New => "new",
+        LeadStatus::Contacted => "contacted",
+        LeadStatus::Qualified => "qualified",
+        LeadStatus::Unqualified => "unqualified",
+        LeadStatus::Converted => "converted",
+    }
+}
+
+impl Mapper for Lead {
+    type Entity = Entity;
+    type Model = Model;
+    type ActiveModel = ActiveModel;
+
+    fn from_model(model: Model) -> Result<Self, DomainError> {
+        Ok(model.to_domain())
+    }
+
+    fn to_active_model(self) -> ActiveModel {
+        ActiveModel {
+            id: Set(self.id),
+            created_at: Set(self.created_at),
+            updated_at: Set(self.updated_at),
+            deleted_at: Set(self.deleted_at),
+            first_name: Set(self.first_name),
+            last_name: Set(self.last_name),
+            email: Set(self.email),
+            phone: Set(self.phone),
+            company_name: Set(self.company_name),
+            job_title: Set(self.job_title),
+            source: Set(source_name(self.source).to_string()),
+            status: Set(status_name(self.status).to_string()),
+            score: Set(self.score),
+            notes: Set(self.notes),
+            position: Set(self.position),
+            assigned_to_id: Set(self.assigned_to_id),
+            converted_person_id: Set(self.converted_person_id),
+            converted_company_id: Set(self.converted_company_id),
+            converted_opportunity_id: Set(self.converted_opportunity_id),
+            converted_at: Set(self.converted_at),
+            last_contacted_at: Set(self.last_contacted_at),
+            workspace_id: Set(self.workspace_id),
+        }
+    }
+}
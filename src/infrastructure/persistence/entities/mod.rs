@@ -1,3 +1,4 @@
+pub mod api_key;
 pub mod calendar_event;
 pub mod calendar_event_participant;
 pub mod company;
@@ -10,14 +11,19 @@ pub mod note;
 pub mod object_metadata;
 pub mod opportunity;
 pub mod person;
+pub mod session;
 pub mod task;
 pub mod task_target;
 pub mod timeline_activity;
 pub mod user;
+pub mod user_token;
 pub mod view;
 pub mod workflow;
 pub mod workflow_run;
 pub mod workflow_version;
 pub mod workflow_version_step;
 pub mod workspace;
+pub mod workspace_invitation;
 pub mod workspace_member;
+pub mod workspace_role;
+pub mod workspace_sso_config;
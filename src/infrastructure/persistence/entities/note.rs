@@ -1,4 +1,7 @@
+use crate::domain::DomainError;
+use crate::infrastructure::persistence::mapper::Mapper;
 use sea_orm::entity::prelude::*;
+use sea_orm::Set;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
@@ -34,3 +37,26 @@ impl Model {
         }
     }
 }
+
+impl Mapper for crate::domain::Note {
+    type Entity = Entity;
+    type Model = Model;
+    type ActiveModel = ActiveModel;
+
+    fn from_model(model: Model) -> Result<Self, DomainError> {
+        Ok(model.to_domain())
+    }
+
+    fn to_active_model(self) -> ActiveModel {
+        ActiveModel {
+            id: Set(self.id),
+            created_at: Set(self.created_at.into()),
+            updated_at: Set(self.updated_at.into()),
+            deleted_at: Set(self.deleted_at.map(|d| d.into())),
+            title: Set(self.title),
+            body_v2: Set(self.body_v2),
+            position: Set(self.position),
+            workspace_id: Set(self.workspace_id),
+        }
+    }
+}
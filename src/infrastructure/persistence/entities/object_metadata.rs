@@ -1,5 +1,8 @@
 use crate::domain::metadata::{FieldType, ViewType};
+use crate::domain::DomainError;
+use crate::infrastructure::persistence::mapper::Mapper;
 use sea_orm::entity::prelude::*;
+use sea_orm::Set;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
@@ -51,3 +54,25 @@ impl Model {
         }
     }
 }
+
+impl Mapper for crate::domain::metadata::ObjectMetadata {
+    type Entity = Entity;
+    type Model = Model;
+    type ActiveModel = ActiveModel;
+
+    fn from_model(model: Model) -> Result<Self, DomainError> {
+        Ok(model.to_domain())
+    }
+
+    fn to_active_model(self) -> ActiveModel {
+        ActiveModel {
+            id: Set(self.id),
+            created_at: Set(self.created_at),
+            updated_at: Set(self.updated_at),
+            name_singular: Set(self.name_singular),
+            name_plural: Set(self.name_plural),
+            description: Set(self.description),
+            workspace_id: Set(self.workspace_id),
+        }
+    }
+}
@@ -1,4 +1,8 @@
+use crate::domain::states::OpportunityStage;
+use crate::domain::DomainError;
+use crate::infrastructure::persistence::mapper::Mapper;
 use sea_orm::entity::prelude::*;
+use sea_orm::Set;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
@@ -18,6 +22,7 @@ pub struct Model {
     pub point_of_contact_id: Option<Uuid>,
     pub owner_id: Option<Uuid>,
     pub workspace_id: Uuid,
+    pub version: i32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -27,8 +32,6 @@ impl ActiveModelBehavior for ActiveModel {}
 
 impl Model {
     pub fn to_domain(self) -> crate::domain::Opportunity {
-        use crate::domain::states::OpportunityStage;
-
         let stage = match self.stage.as_str() {
             "Prospecting" => OpportunityStage::Prospecting,
             "Qualification" => OpportunityStage::Qualification,
@@ -53,6 +56,47 @@ impl Model {
             company_id: self.company_id,
             owner_id: self.owner_id,
             workspace_id: self.workspace_id,
+            version: self.version,
+        }
+    }
+}
+
+/// Stored form of a stage, as read back by `to_domain`
+pub fn stage_name(stage: OpportunityStage) -> &'static str {
+    match stage {
+        OpportunityStage::Prospecting => "Prospecting",
+        OpportunityStage::Qualification => "Qualification",
+        OpportunityStage::Negotiation => "Negotiation",
+        OpportunityStage::Won => "Won",
+        OpportunityStage::Lost => "Lost",
+    }
+}
+
+impl Mapper for crate::domain::Opportunity {
+    type Entity = Entity;
+    type Model = Model;
+    type ActiveModel = ActiveModel;
+
+    fn from_model(model: Model) -> Result<Self, DomainError> {
+        Ok(model.to_domain())
+    }
+
+    fn to_active_model(self) -> ActiveModel {
+        ActiveModel {
+            id: Set(self.id),
+            created_at: Set(self.created_at.into()),
+            updated_at: Set(self.updated_at.into()),
+            deleted_at: Set(self.deleted_at.map(|d| d.into())),
+            name: Set(self.name),
+            stage: Set(stage_name(self.stage).to_string()),
+            amount_micros: Set(self.amount_micros),
+            currency_code: Set(self.currency_code),
+            close_date: Set(self.close_date),
+            company_id: Set(self.company_id),
+            point_of_contact_id: Set(self.point_of_contact_id),
+            owner_id: Set(self.owner_id),
+            workspace_id: Set(self.workspace_id),
+            version: Set(self.version),
         }
     }
 }
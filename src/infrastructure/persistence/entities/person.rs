@@ -1,5 +1,8 @@
+use crate::domain::DomainError;
 use crate::domain::Person;
+use crate::infrastructure::persistence::mapper::Mapper;
 use sea_orm::entity::prelude::*;
+use sea_orm::Set;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
@@ -38,3 +41,27 @@ impl Model {
         }
     }
 }
+
+impl Mapper for Person {
+    type Entity = Entity;
+    type Model = Model;
+    type ActiveModel = ActiveModel;
+
+    fn from_model(model: Model) -> Result<Self, DomainError> {
+        Ok(model.to_domain())
+    }
+
+    fn to_active_model(self) -> ActiveModel {
+        ActiveModel {
+            id: Set(self.id),
+            name: Set(self.name),
+            email: Set(self.email),
+            position: Set(self.position),
+            company_id: Set(self.company_id),
+            created_at: Set(self.created_at),
+            updated_at: Set(self.updated_at),
+            deleted_at: Set(self.deleted_at),
+            workspace_id: Set(self.workspace_id),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use crate::domain::DomainError;
+use crate::infrastructure::persistence::mapper::Mapper;
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "sessions")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub user_id: Uuid,
+    #[sea_orm(unique)]
+    pub token_hash: String,
+    pub expires_at: DateTimeWithTimeZone,
+    pub sso_workspace_id: Option<Uuid>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    pub fn to_domain(&self) -> crate::domain::entities::Session {
+        crate::domain::entities::Session {
+            id: self.id,
+            created_at: self.created_at.into(),
+            user_id: self.user_id,
+            token_hash: self.token_hash.clone(),
+            expires_at: self.expires_at.into(),
+            sso_workspace_id: self.sso_workspace_id,
+        }
+    }
+}
+
+impl Mapper for crate::domain::entities::Session {
+    type Entity = Entity;
+    type Model = Model;
+    type ActiveModel = ActiveModel;
+
+    fn from_model(model: Model) -> Result<Self, DomainError> {
+        Ok(model.to_domain())
+    }
+
+    fn to_active_model(self) -> ActiveModel {
+        ActiveModel {
+            id: Set(self.id),
+            user_id: Set(self.user_id),
+            token_hash: Set(self.token_hash),
+            expires_at: Set(self.expires_at.into()),
+            sso_workspace_id: Set(self.sso_workspace_id),
+            created_at: Set(self.created_at.into()),
+        }
+    }
+}
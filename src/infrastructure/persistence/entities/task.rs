@@ -1,4 +1,8 @@
+use crate::domain::states::TaskStatus;
+use crate::domain::DomainError;
+use crate::infrastructure::persistence::mapper::Mapper;
 use sea_orm::entity::prelude::*;
+use sea_orm::Set;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
@@ -25,8 +29,6 @@ impl ActiveModelBehavior for ActiveModel {}
 
 impl Model {
     pub fn to_domain(self) -> crate::domain::Task {
-        use crate::domain::states::TaskStatus;
-
         let status = match self.status.as_str() {
             "TODO" => TaskStatus::Todo,
             "IN_PROGRESS" => TaskStatus::InProgress,
@@ -49,3 +51,38 @@ impl Model {
         }
     }
 }
+
+/// Stored form of a status, as read back by `to_domain`
+pub fn status_name(status: TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::Todo => "TODO",
+        TaskStatus::InProgress => "IN_PROGRESS",
+        TaskStatus::Done => "DONE",
+    }
+}
+
+impl Mapper for crate::domain::Task {
+    type Entity = Entity;
+    type Model = Model;
+    type ActiveModel = ActiveModel;
+
+    fn from_model(model: Model) -> Result<Self, DomainError> {
+        Ok(model.to_domain())
+    }
+
+    fn to_active_model(self) -> ActiveModel {
+        ActiveModel {
+            id: Set(self.id),
+            created_at: Set(self.created_at.into()),
+            updated_at: Set(self.updated_at.into()),
+            deleted_at: Set(self.deleted_at.map(|d| d.into())),
+            title: Set(self.title),
+            body: Set(self.body),
+            status: Set(status_name(self.status).to_string()),
+            position: Set(self.position),
+            assignee_id: Set(self.assignee_id),
+            due_at: Set(self.due_at.map(|d| d.into())),
+            workspace_id: Set(self.workspace_id),
+        }
+    }
+}
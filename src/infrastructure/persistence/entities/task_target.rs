@@ -1,4 +1,7 @@
+use crate::domain::DomainError;
+use crate::infrastructure::persistence::mapper::Mapper;
 use sea_orm::entity::prelude::*;
+use sea_orm::Set;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
@@ -11,6 +14,8 @@ pub struct Model {
     pub person_id: Option<Uuid>,
     pub company_id: Option<Uuid>,
     pub opportunity_id: Option<Uuid>,
+    /// Set when trashed together with the task or a record it targets
+    pub deleted_at: Option<DateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -30,3 +35,25 @@ impl Model {
         }
     }
 }
+
+impl Mapper for crate::domain::TaskTarget {
+    type Entity = Entity;
+    type Model = Model;
+    type ActiveModel = ActiveModel;
+
+    fn from_model(model: Model) -> Result<Self, DomainError> {
+        Ok(model.to_domain())
+    }
+
+    fn to_active_model(self) -> ActiveModel {
+        ActiveModel {
+            id: Set(self.id),
+            created_at: Set(self.created_at.into()),
+            task_id: Set(self.task_id),
+            person_id: Set(self.person_id),
+            company_id: Set(self.company_id),
+            opportunity_id: Set(self.opportunity_id),
+            deleted_at: Set(None),
+        }
+    }
+}
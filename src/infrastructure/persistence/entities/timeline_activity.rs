@@ -1,4 +1,7 @@
+use crate::domain::DomainError;
+use crate::infrastructure::persistence::mapper::Mapper;
 use sea_orm::entity::prelude::*;
+use sea_orm::Set;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
@@ -17,6 +20,8 @@ pub struct Model {
     pub calendar_event_id: Option<Uuid>,
     pub workflow_id: Option<Uuid>,
     pub workspace_id: Uuid,
+    /// Set when trashed together with a record it is linked to
+    pub deleted_at: Option<DateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -42,3 +47,31 @@ impl Model {
         }
     }
 }
+
+impl Mapper for crate::domain::TimelineActivity {
+    type Entity = Entity;
+    type Model = Model;
+    type ActiveModel = ActiveModel;
+
+    fn from_model(model: Model) -> Result<Self, DomainError> {
+        Ok(model.to_domain())
+    }
+
+    fn to_active_model(self) -> ActiveModel {
+        ActiveModel {
+            id: Set(self.id),
+            created_at: Set(self.created_at.into()),
+            name: Set(self.name),
+            workspace_member_id: Set(self.workspace_member_id),
+            person_id: Set(self.person_id),
+            company_id: Set(self.company_id),
+            opportunity_id: Set(self.opportunity_id),
+            task_id: Set(self.task_id),
+            note_id: Set(self.note_id),
+            calendar_event_id: Set(self.calendar_event_id),
+            workflow_id: Set(self.workflow_id),
+            workspace_id: Set(self.workspace_id),
+            deleted_at: Set(None),
+        }
+    }
+}
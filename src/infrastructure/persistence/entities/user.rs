@@ -1,4 +1,7 @@
+use crate::domain::DomainError;
+use crate::infrastructure::persistence::mapper::{serde_name, Mapper};
 use sea_orm::entity::prelude::*;
+use sea_orm::Set;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
 #[sea_orm(table_name = "users")]
@@ -31,3 +34,24 @@ impl Model {
         }
     }
 }
+
+impl Mapper for crate::domain::entities::User {
+    type Entity = Entity;
+    type Model = Model;
+    type ActiveModel = ActiveModel;
+
+    fn from_model(model: Model) -> Result<Self, DomainError> {
+        Ok(model.to_domain())
+    }
+
+    fn to_active_model(self) -> ActiveModel {
+        ActiveModel {
+            id: Set(self.id),
+            email: Set(self.email),
+            password_hash: Set(self.password_hash),
+            state: Set(serde_name(&self.state)),
+            created_at: Set(self.created_at.into()),
+            updated_at: Set(self.updated_at.into()),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use crate::domain::DomainError;
+use crate::infrastructure::persistence::mapper::{serde_name, Mapper};
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "user_tokens")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub purpose: String, // Stored as string, mapped to Enum in domain
+    #[sea_orm(unique)]
+    pub token_hash: String,
+    pub expires_at: DateTimeWithTimeZone,
+    pub used_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    pub fn to_domain(&self) -> Result<crate::domain::entities::UserToken, String> {
+        Ok(crate::domain::entities::UserToken {
+            id: self.id,
+            created_at: self.created_at.into(),
+            user_id: self.user_id,
+            purpose: serde_json::from_value(serde_json::Value::String(self.purpose.clone()))
+                .map_err(|e| format!("Unknown token purpose {}: {}", self.purpose, e))?,
+            token_hash: self.token_hash.clone(),
+            expires_at: self.expires_at.into(),
+            used_at: self.used_at.map(|d| d.into()),
+        })
+    }
+}
+
+impl Mapper for crate::domain::entities::UserToken {
+    type Entity = Entity;
+    type Model = Model;
+    type ActiveModel = ActiveModel;
+
+    fn from_model(model: Model) -> Result<Self, DomainError> {
+        model.to_domain().map_err(DomainError::InfrastructureError)
+    }
+
+    fn to_active_model(self) -> ActiveModel {
+        ActiveModel {
+            id: Set(self.id),
+            user_id: Set(self.user_id),
+            purpose: Set(serde_name(&self.purpose)),
+            token_hash: Set(self.token_hash),
+            expires_at: Set(self.expires_at.into()),
+            used_at: Set(self.used_at.map(|d| d.into())),
+            created_at: Set(self.created_at.into()),
+        }
+    }
+}
@@ -1,5 +1,8 @@
 use crate::domain::metadata::ViewType;
+use crate::domain::DomainError;
+use crate::infrastructure::persistence::mapper::Mapper;
 use sea_orm::entity::prelude::*;
+use sea_orm::Set;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
@@ -61,3 +64,38 @@ impl Model {
         }
     }
 }
+
+/// Stored form of a view type, as read back by `to_domain`
+pub fn type_name(view_type: ViewType) -> &'static str {
+    match view_type {
+        ViewType::Table => "Table",
+        ViewType::Kanban => "Kanban",
+        ViewType::Calendar => "Calendar",
+        ViewType::List => "List",
+    }
+}
+
+impl Mapper for crate::domain::metadata::View {
+    type Entity = Entity;
+    type Model = Model;
+    type ActiveModel = ActiveModel;
+
+    fn from_model(model: Model) -> Result<Self, DomainError> {
+        Ok(model.to_domain())
+    }
+
+    fn to_active_model(self) -> ActiveModel {
+        ActiveModel {
+            id: Set(self.id),
+            created_at: Set(self.created_at),
+            updated_at: Set(self.updated_at),
+            object_metadata_id: Set(self.object_metadata_id),
+            name: Set(self.name),
+            r#type: Set(type_name(self.view_type).to_string()),
+            filters: Set(self.filters),
+            sort: Set(self.sort),
+            position: Set(self.position),
+            workspace_id: Set(self.workspace_id),
+        }
+    }
+}
@@ -1,5 +1,8 @@
 use crate::domain::states::WorkflowRunStatus;
+use crate::domain::DomainError;
+use crate::infrastructure::persistence::mapper::Mapper;
 use sea_orm::entity::prelude::*;
+use sea_orm::Set;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
@@ -11,8 +14,12 @@ pub struct Model {
     pub updated_at: DateTimeUtc,
     pub workflow_version_id: Uuid,
     pub status: String,
+    pub input: Option<Json>,
     pub output: Option<Json>,
     pub error: Option<String>,
+    pub cursor: Option<Uuid>,
+    pub wake_at: Option<DateTimeUtc>,
+    pub workflow_chain: Option<Json>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -52,8 +59,53 @@ impl Model {
             updated_at: self.updated_at,
             workflow_version_id: self.workflow_version_id,
             status,
+            input: self.input,
             output: self.output,
             error: self.error,
+            cursor: self.cursor,
+            wake_at: self.wake_at,
+            // Runs saved before chains were kept start a new chain when resumed
+            workflow_chain: self
+                .workflow_chain
+                .and_then(|chain| serde_json::from_value(chain).ok())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Stored form of a status, as read back by `to_domain`
+pub fn status_name(status: WorkflowRunStatus) -> &'static str {
+    match status {
+        WorkflowRunStatus::Pending => "pending",
+        WorkflowRunStatus::Running => "running",
+        WorkflowRunStatus::Completed => "completed",
+        WorkflowRunStatus::Failed => "failed",
+        WorkflowRunStatus::Cancelled => "cancelled",
+    }
+}
+
+impl Mapper for crate::domain::WorkflowRun {
+    type Entity = Entity;
+    type Model = Model;
+    type ActiveModel = ActiveModel;
+
+    fn from_model(model: Model) -> Result<Self, DomainError> {
+        Ok(model.to_domain())
+    }
+
+    fn to_active_model(self) -> ActiveModel {
+        ActiveModel {
+            id: Set(self.id),
+            created_at: Set(self.created_at),
+            updated_at: Set(self.updated_at),
+            workflow_version_id: Set(self.workflow_version_id),
+            status: Set(status_name(self.status).to_string()),
+            input: Set(self.input),
+            output: Set(self.output),
+            error: Set(self.error),
+            cursor: Set(self.cursor),
+            wake_at: Set(self.wake_at),
+            workflow_chain: Set(Some(serde_json::json!(self.workflow_chain))),
         }
     }
 }
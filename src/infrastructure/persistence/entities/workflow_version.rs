@@ -1,5 +1,8 @@
 use crate::domain::states::WorkflowVersionStatus;
+use crate::domain::DomainError;
+use crate::infrastructure::persistence::mapper::Mapper;
 use sea_orm::entity::prelude::*;
+use sea_orm::Set;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
@@ -53,6 +56,7 @@ impl Model {
     pub fn to_domain(self) -> crate::domain::WorkflowVersion {
         let status = match self.status.as_str() {
             "draft" => WorkflowVersionStatus::Draft,
+            "active" => WorkflowVersionStatus::Active,
             "published" => WorkflowVersionStatus::Published,
             "archived" => WorkflowVersionStatus::Archived,
             _ => WorkflowVersionStatus::Draft,
@@ -67,3 +71,33 @@ impl Model {
         }
     }
 }
+
+/// Stored form of a status, as read back by `to_domain`
+pub fn status_name(status: WorkflowVersionStatus) -> &'static str {
+    match status {
+        WorkflowVersionStatus::Draft => "draft",
+        WorkflowVersionStatus::Active => "active",
+        WorkflowVersionStatus::Published => "published",
+        WorkflowVersionStatus::Archived => "archived",
+    }
+}
+
+impl Mapper for crate::domain::WorkflowVersion {
+    type Entity = Entity;
+    type Model = Model;
+    type ActiveModel = ActiveModel;
+
+    fn from_model(model: Model) -> Result<Self, DomainError> {
+        Ok(model.to_domain())
+    }
+
+    fn to_active_model(self) -> ActiveModel {
+        ActiveModel {
+            id: Set(self.id),
+            created_at: Set(self.created_at),
+            updated_at: Set(self.updated_at),
+            workflow_id: Set(self.workflow_id),
+            status: Set(status_name(self.status).to_string()),
+        }
+    }
+}
@@ -1,5 +1,8 @@
 use crate::domain::states::WorkflowStepType;
+use crate::domain::DomainError;
+use crate::infrastructure::persistence::mapper::Mapper;
 use sea_orm::entity::prelude::*;
+use sea_orm::Set;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
@@ -9,7 +12,8 @@ pub struct Model {
     pub id: Uuid,
     pub created_at: DateTimeUtc,
     pub workflow_version_id: Uuid,
-    pub step_type: String,
+    pub name: Option<String>,
+    pub r#type: String, // "type" is a reserved keyword
     pub settings: Json,
     pub position: i32,
 }
@@ -36,11 +40,18 @@ impl ActiveModelBehavior for ActiveModel {}
 
 impl Model {
     pub fn to_domain(self) -> crate::domain::WorkflowVersionStep {
-        let step_type = match self.step_type.as_str() {
+        let step_type = match self.r#type.as_str() {
             "trigger" => WorkflowStepType::Trigger,
             "action" => WorkflowStepType::Action,
             "condition" => WorkflowStepType::Condition,
             "delay" => WorkflowStepType::Delay,
+            "create_record" => WorkflowStepType::CreateRecord,
+            "update_record" => WorkflowStepType::UpdateRecord,
+            "find_records" => WorkflowStepType::FindRecords,
+            "delete_record" => WorkflowStepType::DeleteRecord,
+            "send_email" => WorkflowStepType::SendEmail,
+            "if_else" => WorkflowStepType::IfElse,
+            "form" => WorkflowStepType::Form,
             _ => WorkflowStepType::Action,
         };
 
@@ -48,9 +59,49 @@ impl Model {
             id: self.id,
             created_at: self.created_at,
             workflow_version_id: self.workflow_version_id,
+            name: self.name,
             step_type,
             settings: self.settings,
             position: self.position,
         }
     }
 }
+
+/// Stored form of a step type, as read back by `to_domain`
+pub fn type_name(step_type: WorkflowStepType) -> &'static str {
+    match step_type {
+        WorkflowStepType::Trigger => "trigger",
+        WorkflowStepType::Action => "action",
+        WorkflowStepType::Condition => "condition",
+        WorkflowStepType::Delay => "delay",
+        WorkflowStepType::CreateRecord => "create_record",
+        WorkflowStepType::UpdateRecord => "update_record",
+        WorkflowStepType::FindRecords => "find_records",
+        WorkflowStepType::DeleteRecord => "delete_record",
+        WorkflowStepType::SendEmail => "send_email",
+        WorkflowStepType::IfElse => "if_else",
+        WorkflowStepType::Form => "form",
+    }
+}
+
+impl Mapper for crate::domain::WorkflowVersionStep {
+    type Entity = Entity;
+    type Model = Model;
+    type ActiveModel = ActiveModel;
+
+    fn from_model(model: Model) -> Result<Self, DomainError> {
+        Ok(model.to_domain())
+    }
+
+    fn to_active_model(self) -> ActiveModel {
+        ActiveModel {
+            id: Set(self.id),
+            created_at: Set(self.created_at),
+            workflow_version_id: Set(self.workflow_version_id),
+            name: Set(self.name),
+            r#type: Set(type_name(self.step_type).to_string()),
+            settings: Set(self.settings),
+            position: Set(self.position),
+        }
+    }
+}
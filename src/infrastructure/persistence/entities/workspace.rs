@@ -1,4 +1,7 @@
+use crate::domain::DomainError;
+use crate::infrastructure::persistence::mapper::{serde_name, Mapper};
 use sea_orm::entity::prelude::*;
+use sea_orm::Set;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
@@ -45,3 +48,23 @@ impl Model {
         }
     }
 }
+
+impl Mapper for crate::domain::Workspace {
+    type Entity = Entity;
+    type Model = Model;
+    type ActiveModel = ActiveModel;
+
+    fn from_model(model: Model) -> Result<Self, DomainError> {
+        Ok(model.to_domain())
+    }
+
+    fn to_active_model(self) -> ActiveModel {
+        ActiveModel {
+            id: Set(self.id),
+            subdomain: Set(self.subdomain),
+            state: Set(serde_name(&self.state)),
+            created_at: Set(self.created_at.into()),
+            updated_at: Set(self.updated_at.into()),
+        }
+    }
+}
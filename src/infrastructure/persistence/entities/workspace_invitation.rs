@@ -0,0 +1,86 @@
+use crate::domain::DomainError;
+use crate::infrastructure::persistence::mapper::Mapper;
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "workspace_invitations")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub email: String,
+    pub role: String,
+    pub invited_by: Uuid,
+    #[sea_orm(unique)]
+    pub token_hash: String,
+    pub expires_at: DateTimeWithTimeZone,
+    pub accepted_at: Option<DateTimeWithTimeZone>,
+    pub revoked_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::workspace::Entity",
+        from = "Column::WorkspaceId",
+        to = "super::workspace::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Workspace,
+}
+
+impl Related<super::workspace::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Workspace.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    pub fn to_domain(&self) -> crate::domain::entities::Invitation {
+        crate::domain::entities::Invitation {
+            id: self.id,
+            created_at: self.created_at.into(),
+            updated_at: self.updated_at.into(),
+            workspace_id: self.workspace_id,
+            email: self.email.clone(),
+            role: self.role.clone(),
+            invited_by: self.invited_by,
+            token_hash: self.token_hash.clone(),
+            expires_at: self.expires_at.into(),
+            accepted_at: self.accepted_at.map(|d| d.into()),
+            revoked_at: self.revoked_at.map(|d| d.into()),
+        }
+    }
+}
+
+impl Mapper for crate::domain::entities::Invitation {
+    type Entity = Entity;
+    type Model = Model;
+    type ActiveModel = ActiveModel;
+
+    fn from_model(model: Model) -> Result<Self, DomainError> {
+        Ok(model.to_domain())
+    }
+
+    fn to_active_model(self) -> ActiveModel {
+        ActiveModel {
+            id: Set(self.id),
+            workspace_id: Set(self.workspace_id),
+            email: Set(self.email),
+            role: Set(self.role),
+            invited_by: Set(self.invited_by),
+            token_hash: Set(self.token_hash),
+            expires_at: Set(self.expires_at.into()),
+            accepted_at: Set(self.accepted_at.map(|d| d.into())),
+            revoked_at: Set(self.revoked_at.map(|d| d.into())),
+            created_at: Set(self.created_at.into()),
+            updated_at: Set(self.updated_at.into()),
+        }
+    }
+}
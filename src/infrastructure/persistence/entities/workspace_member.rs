@@ -1,4 +1,7 @@
+use crate::domain::DomainError;
+use crate::infrastructure::persistence::mapper::Mapper;
 use sea_orm::entity::prelude::*;
+use sea_orm::Set;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
@@ -12,6 +15,7 @@ pub struct Model {
     pub name: String,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
+    pub suspended_at: Option<DateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -58,6 +62,30 @@ impl Model {
             workspace_id: self.workspace_id,
             role: self.role,
             name: self.name,
+            suspended_at: self.suspended_at.map(Into::into),
+        }
+    }
+}
+
+impl Mapper for crate::domain::WorkspaceMember {
+    type Entity = Entity;
+    type Model = Model;
+    type ActiveModel = ActiveModel;
+
+    fn from_model(model: Model) -> Result<Self, DomainError> {
+        Ok(model.to_domain())
+    }
+
+    fn to_active_model(self) -> ActiveModel {
+        ActiveModel {
+            id: Set(self.id),
+            user_id: Set(self.user_id),
+            workspace_id: Set(self.workspace_id),
+            role: Set(self.role),
+            name: Set(self.name),
+            created_at: Set(self.created_at.into()),
+            updated_at: Set(self.updated_at.into()),
+            suspended_at: Set(self.suspended_at.map(Into::into)),
         }
     }
 }
@@ -0,0 +1,70 @@
+use crate::domain::DomainError;
+use crate::infrastructure::persistence::mapper::Mapper;
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "workspace_roles")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub name: String,
+    pub permissions: Json,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::workspace::Entity",
+        from = "Column::WorkspaceId",
+        to = "super::workspace::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Workspace,
+}
+
+impl Related<super::workspace::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Workspace.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    pub fn to_domain(&self) -> crate::domain::permissions::RoleDefinition {
+        crate::domain::permissions::RoleDefinition {
+            id: self.id,
+            created_at: self.created_at.into(),
+            updated_at: self.updated_at.into(),
+            workspace_id: self.workspace_id,
+            name: self.name.clone(),
+            permissions: serde_json::from_value(self.permissions.clone()).unwrap_or_default(),
+        }
+    }
+}
+
+impl Mapper for crate::domain::permissions::RoleDefinition {
+    type Entity = Entity;
+    type Model = Model;
+    type ActiveModel = ActiveModel;
+
+    fn from_model(model: Model) -> Result<Self, DomainError> {
+        Ok(model.to_domain())
+    }
+
+    fn to_active_model(self) -> ActiveModel {
+        ActiveModel {
+            id: Set(self.id),
+            workspace_id: Set(self.workspace_id),
+            name: Set(self.name),
+            permissions: Set(serde_json::json!(self.permissions)),
+            created_at: Set(self.created_at.into()),
+            updated_at: Set(self.updated_at.into()),
+        }
+    }
+}
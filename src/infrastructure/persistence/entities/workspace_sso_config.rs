@@ -0,0 +1,80 @@
+use crate::domain::DomainError;
+use crate::infrastructure::persistence::mapper::Mapper;
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "workspace_sso_configs")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    #[sea_orm(unique)]
+    pub workspace_id: Uuid,
+    pub issuer_url: String,
+    pub client_id: String,
+    pub client_secret: String,
+    pub default_role: String,
+    pub enforced: bool,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::workspace::Entity",
+        from = "Column::WorkspaceId",
+        to = "super::workspace::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Workspace,
+}
+
+impl Related<super::workspace::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Workspace.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    pub fn to_domain(&self) -> crate::domain::entities::SsoConfig {
+        crate::domain::entities::SsoConfig {
+            id: self.id,
+            created_at: self.created_at.into(),
+            updated_at: self.updated_at.into(),
+            workspace_id: self.workspace_id,
+            issuer_url: self.issuer_url.clone(),
+            client_id: self.client_id.clone(),
+            client_secret: self.client_secret.clone(),
+            default_role: self.default_role.clone(),
+            enforced: self.enforced,
+        }
+    }
+}
+
+impl Mapper for crate::domain::entities::SsoConfig {
+    type Entity = Entity;
+    type Model = Model;
+    type ActiveModel = ActiveModel;
+
+    fn from_model(model: Model) -> Result<Self, DomainError> {
+        Ok(model.to_domain())
+    }
+
+    fn to_active_model(self) -> ActiveModel {
+        ActiveModel {
+            id: Set(self.id),
+            workspace_id: Set(self.workspace_id),
+            issuer_url: Set(self.issuer_url),
+            client_id: Set(self.client_id),
+            client_secret: Set(self.client_secret),
+            default_role: Set(self.default_role),
+            enforced: Set(self.enforced),
+            created_at: Set(self.created_at.into()),
+            updated_at: Set(self.updated_at.into()),
+        }
+    }
+}
@@ -0,0 +1,23 @@
+//! Typed mapping of SeaORM errors onto `DomainError`, so that callers can tell a duplicate
+//! or a missing row from an outage instead of receiving the driver's message.
+
+use crate::domain::DomainError;
+use sea_orm::{DbErr, SqlErr};
+
+/// Maps a database error: a unique violation is a `Validation` error, a row that is missing
+/// (or outside the current workspace) is `NotFound`, and anything else is an
+/// `InfrastructureError`
+pub fn map_db_err(e: DbErr) -> DomainError {
+    if let Some(SqlErr::UniqueConstraintViolation(_)) = e.sql_err() {
+        return duplicate();
+    }
+    match e {
+        DbErr::RecordNotFound(_) | DbErr::RecordNotUpdated => DomainError::NotFound,
+        e => DomainError::InfrastructureError(e.to_string()),
+    }
+}
+
+/// Error of a write that would duplicate a unique key
+pub fn duplicate() -> DomainError {
+    DomainError::Validation("A record with the same value already exists".to_string())
+}
@@ -0,0 +1,2574 @@
+//! Repository implementation that keeps every table in memory, for fast tests.
+//!
+//! `InMemoryRepo` implements the same ports as the SeaORM repositories with the same
+//! semantics: workspace scoping through the ambient actor, soft delete into the trash, list
+//! queries, versioned updates and the database's unique indexes. The conformance suite runs
+//! the same tests against both to keep them in step.
+//!
+//! Each repository call applies its writes atomically under one lock and records how to undo
+//! them. A unit of work collects those undo steps and replays them when it rolls back, so
+//! only its own writes are reverted. Unlike a database transaction, its writes are visible
+//! to other tasks before it commits.
+
+use super::entities::{company, email, lead, person};
+use super::errors::duplicate;
+use super::query::{self as list_query, Field};
+use super::sea_orm_repo::{company_fields, email_fields, lead_fields, person_fields};
+use super::workspace_scope::{current_scope, version_conflict};
+use crate::application::ports::output::{
+    ApiKeyRepository, CalendarEventParticipantRepository, CalendarEventRepository,
+    CompanyRepository, ConnectedAccountRepository, CustomObjectDataRepository, EmailRepository,
+    EmailTemplateRepository, InvitationRepository, LeadRepository, MetadataRepository,
+    NoteRepository, OpportunityRepository, PersonRepository, RoleRepository, SessionRepository,
+    SsoConfigRepository, TaskRepository, TaskTargetRepository, TimelineActivityRepository,
+    TrashRepository, UserRepository, UserTokenRepository, ViewRepository, WorkflowRepository,
+    WorkflowRunRepository, WorkflowVersionRepository, WorkflowVersionStepRepository,
+    WorkspaceRepository,
+};
+use crate::application::ports::query::{FilterOp, ListQuery, Page, Sort, SortDirection};
+use crate::application::ports::unit_of_work::{UnitOfWork, Work};
+use crate::domain::custom_object_data::CustomObjectData;
+use crate::domain::metadata::{FieldMetadata, ObjectMetadata, View};
+use crate::domain::permissions::RoleDefinition;
+use crate::domain::states::{
+    EmailStatus, LeadStatus, UserTokenPurpose, WorkflowRunStatus, WorkflowStepType,
+};
+use crate::domain::{
+    ApiKey, CalendarEvent, CalendarEventParticipant, Company, ConnectedAccount, DomainError, Email,
+    EmailTemplate, Invitation, Lead, Note, Opportunity, Person, Session, SsoConfig, Task,
+    TaskTarget, TimelineActivity, TrashedKind, TrashedRecord, User, UserToken, Workflow,
+    WorkflowRun, WorkflowVersion, WorkflowVersionStep, Workspace, WorkspaceMember,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sea_orm::Value;
+use std::cell::RefCell;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use uuid::Uuid;
+
+type Table<T> = BTreeMap<Uuid, T>;
+
+/// Row of a table whose domain type has no `deleted_at`, but which is trashed along with the
+/// records it links
+#[derive(Debug, Clone)]
+struct Link<T> {
+    row: T,
+    deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Default)]
+struct Tables {
+    users: Table<User>,
+    user_tokens: Table<UserToken>,
+    sessions: Table<Session>,
+    sso_configs: Table<SsoConfig>,
+    api_keys: Table<ApiKey>,
+    workspaces: Table<Workspace>,
+    members: Table<WorkspaceMember>,
+    invitations: Table<Invitation>,
+    roles: Table<RoleDefinition>,
+    people: Table<Person>,
+    companies: Table<Company>,
+    opportunities: Table<Opportunity>,
+    tasks: Table<Task>,
+    notes: Table<Note>,
+    leads: Table<Lead>,
+    task_targets: Table<Link<TaskTarget>>,
+    timeline_activities: Table<Link<TimelineActivity>>,
+    workflows: Table<Workflow>,
+    workflow_versions: Table<WorkflowVersion>,
+    workflow_steps: Table<WorkflowVersionStep>,
+    workflow_runs: Table<WorkflowRun>,
+    connected_accounts: Table<ConnectedAccount>,
+    calendar_events: Table<CalendarEvent>,
+    calendar_event_participants: Table<CalendarEventParticipant>,
+    emails: Table<Email>,
+    email_templates: Table<EmailTemplate>,
+    objects: Table<ObjectMetadata>,
+    fields: Table<FieldMetadata>,
+    views: Table<View>,
+    custom_records: Table<CustomObjectData>,
+}
+
+type Undo = Box<dyn FnOnce(&mut Tables) + Send>;
+
+/// Writes of one repository call, with the steps that undo them
+struct Changes<'a> {
+    tables: &'a mut Tables,
+    undo: Vec<Undo>,
+}
+
+impl Deref for Changes<'_> {
+    type Target = Tables;
+
+    fn deref(&self) -> &Tables {
+        self.tables
+    }
+}
+
+impl Changes<'_> {
+    /// Inserts or replaces the row `id` of `table`
+    fn put<T>(&mut self, table: fn(&mut Tables) -> &mut Table<T>, id: Uuid, row: T)
+    where
+        T: Send + 'static,
+    {
+        let previous = table(self.tables).insert(id, row);
+        self.undo
+            .push(Box::new(move |tables| revert(table(tables), id, previous)));
+    }
+
+    fn remove<T>(&mut self, table: fn(&mut Tables) -> &mut Table<T>, id: Uuid)
+    where
+        T: Send + 'static,
+    {
+        let previous = table(self.tables).remove(&id);
+        self.undo
+            .push(Box::new(move |tables| revert(table(tables), id, previous)));
+    }
+}
+
+fn revert<T>(table: &mut Table<T>, id: Uuid, previous: Option<T>) {
+    match previous {
+        Some(row) => table.insert(id, row),
+        None => table.remove(&id),
+    };
+}
+
+tokio::task_local! {
+    /// Undo steps of the writes made in the ambient unit of work, oldest first
+    static UNIT_OF_WORK: RefCell<Vec<Box<dyn FnOnce() + Send>>>;
+}
+
+#[derive(Default)]
+pub struct InMemoryRepo {
+    tables: Arc<Mutex<Tables>>,
+}
+
+impl InMemoryRepo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn read(&self) -> MutexGuard<'_, Tables> {
+        lock(&self.tables)
+    }
+
+    /// Applies `change` atomically: all of its writes, or none when it fails
+    fn write<R>(
+        &self,
+        change: impl FnOnce(&mut Changes<'_>) -> Result<R, DomainError>,
+    ) -> Result<R, DomainError> {
+        let mut tables = lock(&self.tables);
+        let mut changes = Changes {
+            tables: &mut tables,
+            undo: Vec::new(),
+        };
+        let result = change(&mut changes);
+        let undo = changes.undo;
+        if result.is_err() {
+            undo.into_iter().rev().for_each(|step| step(&mut tables));
+            return result;
+        }
+        drop(tables);
+
+        let shared = self.tables.clone();
+        let _ = UNIT_OF_WORK.try_with(|log| {
+            log.borrow_mut().push(Box::new(move || {
+                let mut tables = lock(&shared);
+                undo.into_iter().rev().for_each(|step| step(&mut tables));
+            }))
+        });
+        result
+    }
+}
+
+fn lock(tables: &Mutex<Tables>) -> MutexGuard<'_, Tables> {
+    tables.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[async_trait]
+impl UnitOfWork for InMemoryRepo {
+    async fn run<'a>(&self, work: Work<'a>) -> Result<(), DomainError> {
+        let (result, undo) = UNIT_OF_WORK
+            .scope(RefCell::new(Vec::new()), async {
+                let result = work.await;
+                (result, UNIT_OF_WORK.with(|log| log.take()))
+            })
+            .await;
+        match result {
+            // An enclosing unit of work can still roll these writes back
+            Ok(()) => {
+                let _ = UNIT_OF_WORK.try_with(|log| log.borrow_mut().extend(undo));
+                Ok(())
+            }
+            Err(e) => {
+                undo.into_iter().rev().for_each(|step| step());
+                Err(e)
+            }
+        }
+    }
+}
+
+/// A row that belongs to a single workspace
+trait Scoped {
+    fn workspace_id(&self) -> Uuid;
+}
+
+macro_rules! scoped {
+    ($($row:ty),* $(,)?) => {
+        $(
+            impl Scoped for $row {
+                fn workspace_id(&self) -> Uuid {
+                    self.workspace_id
+                }
+            }
+        )*
+    };
+}
+
+scoped!(
+    CalendarEvent,
+    Company,
+    CustomObjectData,
+    Email,
+    EmailTemplate,
+    Lead,
+    Note,
+    ObjectMetadata,
+    Opportunity,
+    Person,
+    Task,
+    TimelineActivity,
+    View,
+    Workflow,
+);
+
+impl<T: Scoped> Scoped for Link<T> {
+    fn workspace_id(&self) -> Uuid {
+        self.row.workspace_id()
+    }
+}
+
+fn in_scope(scope: Option<Uuid>, row: &impl Scoped) -> bool {
+    scope.is_none_or(|workspace_id| workspace_id == row.workspace_id())
+}
+
+/// Rows of `table` in the current workspace
+fn visible<T: Scoped>(table: &Table<T>) -> Result<impl Iterator<Item = &T>, DomainError> {
+    let scope = current_scope()?;
+    Ok(table.values().filter(move |row| in_scope(scope, *row)))
+}
+
+/// Row `id` of `table`, if it is in the current workspace
+fn visible_by_id<T: Scoped>(table: &Table<T>, id: Uuid) -> Result<Option<&T>, DomainError> {
+    let scope = current_scope()?;
+    Ok(table.get(&id).filter(|row| in_scope(scope, *row)))
+}
+
+fn ensure_visible<T: Scoped>(table: &Table<T>, id: Uuid) -> Result<(), DomainError> {
+    visible_by_id(table, id)?
+        .map(|_| ())
+        .ok_or(DomainError::NotFound)
+}
+
+/// Rejects a row that would be written to another workspace
+fn check_owner(row: &impl Scoped) -> Result<(), DomainError> {
+    match current_scope()? {
+        Some(workspace_id) if workspace_id != row.workspace_id() => Err(DomainError::Permission(
+            "Record belongs to another workspace".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Fails like the database (see `map_db_err`) when `clashes` finds a row other than `id`
+/// with the same unique key
+fn ensure_unique<T>(
+    table: &Table<T>,
+    id: Uuid,
+    clashes: impl Fn(&T) -> bool,
+) -> Result<(), DomainError> {
+    if table
+        .iter()
+        .any(|(row_id, row)| *row_id != id && clashes(row))
+    {
+        return Err(duplicate());
+    }
+    Ok(())
+}
+
+fn sorted<T>(rows: impl Iterator<Item = T>, order: impl FnMut(&T, &T) -> Ordering) -> Vec<T> {
+    let mut rows: Vec<T> = rows.collect();
+    rows.sort_by(order);
+    rows
+}
+
+/// Enum value as the database stores it
+fn stored(variant: impl Debug) -> Value {
+    Value::from(list_query::snake_case(&format!("{:?}", variant)))
+}
+
+/// Orders two values of the same field; NULL is comparable to nothing, as in SQL
+fn compare(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::String(Some(a)), Value::String(Some(b))) => Some(a.cmp(b)),
+        (Value::Int(Some(a)), Value::Int(Some(b))) => Some(a.cmp(b)),
+        (Value::ChronoDateTimeUtc(Some(a)), Value::ChronoDateTimeUtc(Some(b))) => Some(a.cmp(b)),
+        (Value::Uuid(Some(a)), Value::Uuid(Some(b))) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
+
+fn matches(op: FilterOp, value: &Value, operand: &Value) -> bool {
+    let ordering = compare(value, operand);
+    match op {
+        FilterOp::Eq => ordering == Some(Ordering::Equal),
+        FilterOp::Neq => ordering != Some(Ordering::Equal),
+        FilterOp::Contains => match (value, operand) {
+            (Value::String(Some(value)), Value::String(Some(operand))) => {
+                value.contains(operand.as_str())
+            }
+            _ => false,
+        },
+        FilterOp::Gt => ordering == Some(Ordering::Greater),
+        FilterOp::Gte => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+        FilterOp::Lt => ordering == Some(Ordering::Less),
+        FilterOp::Lte => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+    }
+}
+
+/// `query::fetch_page` over `rows`. `value` reads the column of a field from a row.
+fn fetch_page<T, C: Copy>(
+    rows: impl Iterator<Item = T>,
+    query: &ListQuery,
+    fields: &[Field<C>],
+    value: impl Fn(&T, C) -> Value,
+    id: impl Fn(&T) -> Uuid,
+    default_sort: Sort,
+) -> Result<Page<T>, DomainError> {
+    let mut rows: Vec<T> = rows.collect();
+    for filter in &query.filters {
+        let (field, operand) = list_query::resolve_filter(fields, filter)?;
+        rows.retain(|row| matches(filter.op, &value(row, field.column), &operand));
+    }
+
+    let (sort, sort_field) = list_query::resolve_sort(fields, query, default_sort)?;
+    let column = sort_field.column;
+    // Where a row sorts relative to the position (`sort_value`, `sort_id`), ascending
+    let position = |row: &T, sort_value: &Value, sort_id: Uuid| {
+        compare(&value(row, column), sort_value).map(|o| o.then(id(row).cmp(&sort_id)))
+    };
+
+    if let Some((after, after_id)) = list_query::resume_after(query, &sort, sort_field)? {
+        let later = match sort.direction {
+            SortDirection::Asc => Ordering::Greater,
+            SortDirection::Desc => Ordering::Less,
+        };
+        rows.retain(|row| position(row, &after, after_id) == Some(later));
+    }
+    rows.sort_by(|a, b| {
+        let ordering = position(a, &value(b, column), id(b)).unwrap_or(Ordering::Equal);
+        match sort.direction {
+            SortDirection::Asc => ordering,
+            SortDirection::Desc => ordering.reverse(),
+        }
+    });
+
+    let limit = query.page_size() as usize;
+    let mut next_cursor = None;
+    if rows.len() > limit {
+        rows.truncate(limit);
+        if let Some(last) = rows.last() {
+            next_cursor = list_query::next_cursor(&sort, value(last, column), id(last));
+        }
+    }
+
+    Ok(Page {
+        items: rows,
+        next_cursor,
+    })
+}
+
+fn person_value(person: &Person, column: person::Column) -> Value {
+    use person::Column;
+    match column {
+        Column::Name => person.name.clone().into(),
+        Column::Email => person.email.clone().into(),
+        Column::Position => person.position.into(),
+        Column::CompanyId => person.company_id.into(),
+        Column::CreatedAt => person.created_at.into(),
+        Column::UpdatedAt => person.updated_at.into(),
+        column => unreachable!("{:?} is not a list field", column),
+    }
+}
+
+fn company_value(company: &Company, column: company::Column) -> Value {
+    use company::Column;
+    match column {
+        Column::Name => company.name.clone().into(),
+        Column::DomainName => company.domain_name.clone().into(),
+        Column::Address => company.address.clone().into(),
+        Column::EmployeesCount => company.employees_count.into(),
+        Column::CreatedAt => company.created_at.into(),
+        Column::UpdatedAt => company.updated_at.into(),
+        column => unreachable!("{:?} is not a list field", column),
+    }
+}
+
+fn email_value(email: &Email, column: email::Column) -> Value {
+    use email::Column;
+    match column {
+        Column::Direction => stored(email.direction),
+        Column::Status => stored(email.status),
+        Column::FromEmail => email.from_email.clone().into(),
+        Column::ToEmail => email.to_email.clone().into(),
+        Column::Subject => email.subject.clone().into(),
+        Column::SentAt => email.sent_at.into(),
+        Column::PersonId => email.person_id.into(),
+        Column::CompanyId => email.company_id.into(),
+        Column::OpportunityId => email.opportunity_id.into(),
+        Column::WorkflowId => email.workflow_id.into(),
+        Column::CreatedAt => email.created_at.into(),
+        column => unreachable!("{:?} is not a list field", column),
+    }
+}
+
+fn lead_value(lead: &Lead, column: lead::Column) -> Value {
+    use lead::Column;
+    match column {
+        Column::FirstName => lead.first_name.clone().into(),
+        Column::LastName => lead.last_name.clone().into(),
+        Column::Email => lead.email.clone().into(),
+        Column::Phone => lead.phone.clone().into(),
+        Column::CompanyName => lead.company_name.clone().into(),
+        Column::JobTitle => lead.job_title.clone().into(),
+        Column::Source => stored(lead.source),
+        Column::Status => stored(lead.status),
+        Column::Score => lead.score.into(),
+        Column::Position => lead.position.into(),
+        Column::AssignedToId => lead.assigned_to_id.into(),
+        Column::ConvertedAt => lead.converted_at.into(),
+        Column::LastContactedAt => lead.last_contacted_at.into(),
+        Column::CreatedAt => lead.created_at.into(),
+        Column::UpdatedAt => lead.updated_at.into(),
+        column => unreachable!("{:?} is not a list field", column),
+    }
+}
+
+/// A record that is moved to the trash instead of being deleted
+trait Trashable: Scoped + Clone + Send + 'static {
+    const KIND: TrashedKind;
+    fn rows(tables: &Tables) -> &Table<Self>;
+    fn table(tables: &mut Tables) -> &mut Table<Self>;
+    fn id(&self) -> Uuid;
+    fn deleted_at(&self) -> Option<DateTime<Utc>>;
+    fn set_deleted_at(&mut self, deleted_at: Option<DateTime<Utc>>);
+    fn to_trashed(&self) -> TrashedRecord;
+
+    /// Value that must be unique among the live records of a workspace
+    fn unique_key(&self) -> Option<&str> {
+        None
+    }
+}
+
+macro_rules! trashable {
+    ($row:ident, $table:ident, |$record:ident| $name:expr $(, key: |$keyed:ident| $key:expr)?) => {
+        impl Trashable for $row {
+            const KIND: TrashedKind = TrashedKind::$row;
+
+            fn rows(tables: &Tables) -> &Table<Self> {
+                &tables.$table
+            }
+
+            fn table(tables: &mut Tables) -> &mut Table<Self> {
+                &mut tables.$table
+            }
+
+            fn id(&self) -> Uuid {
+                self.id
+            }
+
+            fn deleted_at(&self) -> Option<DateTime<Utc>> {
+                self.deleted_at
+            }
+
+            fn set_deleted_at(&mut self, deleted_at: Option<DateTime<Utc>>) {
+                self.deleted_at = deleted_at;
+            }
+
+            fn to_trashed(&self) -> TrashedRecord {
+                let $record = self;
+                TrashedRecord {
+                    kind: Self::KIND,
+                    id: self.id,
+                    name: $name,
+                    deleted_at: self.deleted_at.unwrap_or_default(),
+                }
+            }
+
+            $(fn unique_key(&self) -> Option<&str> {
+                let $keyed = self;
+                Some($key)
+            })?
+        }
+    };
+}
+
+trashable!(Person, people, |person| person.name.clone(), key: |person| &person.email);
+trashable!(Company, companies, |company| company.name.clone(), key: |company| &company.domain_name);
+trashable!(Opportunity, opportunities, |opportunity| opportunity
+    .name
+    .clone());
+trashable!(Task, tasks, |task| task.title.clone());
+trashable!(Note, notes, |note| note.title.clone());
+trashable!(Lead, leads, |lead| lead.full_name(), key: |lead| &lead.email);
+
+/// Runs `$body` with `$row` bound to the domain type of `$kind`
+macro_rules! with_row {
+    ($kind:expr, $row:ident => $body:expr) => {
+        match $kind {
+            TrashedKind::Person => {
+                type $row = Person;
+                $body
+            }
+            TrashedKind::Company => {
+                type $row = Company;
+                $body
+            }
+            TrashedKind::Opportunity => {
+                type $row = Opportunity;
+                $body
+            }
+            TrashedKind::Task => {
+                type $row = Task;
+                $body
+            }
+            TrashedKind::Note => {
+                type $row = Note;
+                $body
+            }
+            TrashedKind::Lead => {
+                type $row = Lead;
+                $body
+            }
+        }
+    };
+}
+
+/// Record of `kind` a task target points at, if tasks can target that kind
+fn task_target_link(kind: TrashedKind, target: &TaskTarget) -> Option<Uuid> {
+    match kind {
+        TrashedKind::Person => target.person_id,
+        TrashedKind::Company => target.company_id,
+        TrashedKind::Opportunity => target.opportunity_id,
+        TrashedKind::Task => Some(target.task_id),
+        TrashedKind::Note | TrashedKind::Lead => None,
+    }
+}
+
+/// Link of a timeline activity to a record of `kind`, if activities can link to that kind
+fn timeline_link(kind: TrashedKind, activity: &mut TimelineActivity) -> Option<&mut Option<Uuid>> {
+    match kind {
+        TrashedKind::Person => Some(&mut activity.person_id),
+        TrashedKind::Company => Some(&mut activity.company_id),
+        TrashedKind::Opportunity => Some(&mut activity.opportunity_id),
+        TrashedKind::Task => Some(&mut activity.task_id),
+        TrashedKind::Note => Some(&mut activity.note_id),
+        TrashedKind::Lead => None,
+    }
+}
+
+fn links_to(kind: TrashedKind, activity: &TimelineActivity, id: Uuid) -> bool {
+    timeline_link(kind, &mut activity.clone()).is_some_and(|link| *link == Some(id))
+}
+
+/// Stamps the task targets and timeline activities linked to record `id` of `kind` whose
+/// stamp is `from` with `to`
+fn restamp_links(
+    changes: &mut Changes<'_>,
+    kind: TrashedKind,
+    id: Uuid,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) {
+    let targets: Vec<_> = changes
+        .task_targets
+        .values()
+        .filter(|link| link.deleted_at == from && task_target_link(kind, &link.row) == Some(id))
+        .cloned()
+        .collect();
+    for link in targets {
+        let id = link.row.id;
+        changes.put(
+            |t| &mut t.task_targets,
+            id,
+            Link {
+                deleted_at: to,
+                ..link
+            },
+        );
+    }
+
+    let activities: Vec<_> = changes
+        .timeline_activities
+        .values()
+        .filter(|link| link.deleted_at == from && links_to(kind, &link.row, id))
+        .cloned()
+        .collect();
+    for link in activities {
+        let id = link.row.id;
+        changes.put(
+            |t| &mut t.timeline_activities,
+            id,
+            Link {
+                deleted_at: to,
+                ..link
+            },
+        );
+    }
+}
+
+impl InMemoryRepo {
+    /// Moves the live record `id` to the trash together with its links
+    fn soft_delete<E: Trashable>(&self, id: Uuid) -> Result<(), DomainError> {
+        let scope = current_scope()?;
+        self.write(|changes| {
+            let mut record = E::rows(changes)
+                .get(&id)
+                .filter(|record| in_scope(scope, *record) && record.deleted_at().is_none())
+                .cloned()
+                .ok_or(DomainError::NotFound)?;
+            let deleted_at = Some(Utc::now());
+            record.set_deleted_at(deleted_at);
+            changes.put(E::table, id, record);
+            restamp_links(changes, E::KIND, id, None, deleted_at);
+            Ok(())
+        })
+    }
+
+    fn trashed<E: Trashable>(&self) -> Result<Vec<TrashedRecord>, DomainError> {
+        let tables = self.read();
+        let rows = visible(E::rows(&tables))?;
+        Ok(rows
+            .filter(|record| record.deleted_at().is_some())
+            .map(E::to_trashed)
+            .collect())
+    }
+
+    fn restore_record<E: Trashable>(&self, id: Uuid) -> Result<(), DomainError> {
+        let scope = current_scope()?;
+        self.write(|changes| {
+            let mut record = E::rows(changes)
+                .get(&id)
+                .filter(|record| in_scope(scope, *record) && record.deleted_at().is_some())
+                .cloned()
+                .ok_or(DomainError::NotFound)?;
+            if let Some(key) = record.unique_key() {
+                ensure_unique(E::rows(changes), id, |other| {
+                    other.workspace_id() == record.workspace_id()
+                        && other.unique_key() == Some(key)
+                        && other.deleted_at().is_none()
+                })?;
+            }
+            let deleted_at = record.deleted_at();
+            record.set_deleted_at(None);
+            changes.put(E::table, id, record);
+            restamp_links(changes, E::KIND, id, deleted_at, None);
+            Ok(())
+        })
+    }
+
+    /// Permanently deletes the trashed records of `E` that `purgeable` accepts; returns how many
+    fn purge_where<E: Trashable>(
+        &self,
+        purgeable: impl Fn(&E) -> bool,
+    ) -> Result<u64, DomainError> {
+        let scope = current_scope()?;
+        self.write(|changes| {
+            let ids: Vec<Uuid> = E::rows(changes)
+                .values()
+                .filter(|record| {
+                    in_scope(scope, *record) && record.deleted_at().is_some() && purgeable(record)
+                })
+                .map(E::id)
+                .collect();
+            for &id in &ids {
+                // A task target means nothing without either end
+                let targets: Vec<Uuid> = changes
+                    .task_targets
+                    .values()
+                    .filter(|link| task_target_link(E::KIND, &link.row) == Some(id))
+                    .map(|link| link.row.id)
+                    .collect();
+                for target in targets {
+                    changes.remove(|t| &mut t.task_targets, target);
+                }
+
+                // Activities may also belong to other records, which keep them
+                let activities: Vec<_> = changes
+                    .timeline_activities
+                    .values()
+                    .filter(|link| links_to(E::KIND, &link.row, id))
+                    .cloned()
+                    .collect();
+                for mut link in activities {
+                    let activity = link.row.id;
+                    if link.deleted_at.is_some() {
+                        changes.remove(|t| &mut t.timeline_activities, activity);
+                    } else {
+                        if let Some(column) = timeline_link(E::KIND, &mut link.row) {
+                            *column = None;
+                        }
+                        changes.put(|t| &mut t.timeline_activities, activity, link);
+                    }
+                }
+
+                changes.remove(E::table, id);
+            }
+            Ok(ids.len() as u64)
+        })
+    }
+}
+
+#[async_trait]
+impl PersonRepository for InMemoryRepo {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Person>, DomainError> {
+        let tables = self.read();
+        Ok(visible_by_id(&tables.people, id)?
+            .filter(|p| p.deleted_at.is_none())
+            .cloned())
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Option<Person>, DomainError> {
+        let tables = self.read();
+        let mut rows = visible(&tables.people)?;
+        Ok(rows
+            .find(|p| p.email == email && p.deleted_at.is_none())
+            .cloned())
+    }
+
+    async fn create(&self, person: Person) -> Result<Person, DomainError> {
+        check_owner(&person)?;
+        let person = Person {
+            deleted_at: None,
+            ..person
+        };
+        self.write(|changes| {
+            ensure_unique(&changes.people, person.id, |p| {
+                p.workspace_id == person.workspace_id
+                    && p.email == person.email
+                    && p.deleted_at.is_none()
+            })?;
+            changes.put(|t| &mut t.people, person.id, person.clone());
+            Ok(person)
+        })
+    }
+
+    async fn update(&self, person: Person) -> Result<Person, DomainError> {
+        self.write(|changes| {
+            let current = visible_by_id(&changes.people, person.id)?
+                .filter(|p| p.deleted_at.is_none())
+                .cloned()
+                .ok_or(DomainError::NotFound)?;
+            ensure_unique(&changes.people, person.id, |p| {
+                p.workspace_id == current.workspace_id
+                    && p.email == person.email
+                    && p.deleted_at.is_none()
+            })?;
+            let updated = Person {
+                updated_at: Utc::now(),
+                name: person.name,
+                email: person.email,
+                position: person.position,
+                company_id: person.company_id,
+                ..current
+            };
+            changes.put(|t| &mut t.people, updated.id, updated.clone());
+            Ok(updated)
+        })
+    }
+
+    async fn find_all(&self) -> Result<Vec<Person>, DomainError> {
+        let tables = self.read();
+        let rows = visible(&tables.people)?;
+        Ok(rows.filter(|p| p.deleted_at.is_none()).cloned().collect())
+    }
+
+    async fn find_page(&self, query: &ListQuery) -> Result<Page<Person>, DomainError> {
+        let tables = self.read();
+        let rows = visible(&tables.people)?
+            .filter(|p| p.deleted_at.is_none())
+            .cloned();
+        fetch_page(
+            rows,
+            query,
+            &person_fields(),
+            person_value,
+            |p| p.id,
+            Sort::asc("created_at"),
+        )
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        self.soft_delete::<Person>(id)
+    }
+}
+
+#[async_trait]
+impl OpportunityRepository for InMemoryRepo {
+    async fn find_all(&self) -> Result<Vec<Opportunity>, DomainError> {
+        let tables = self.read();
+        let rows = visible(&tables.opportunities)?;
+        Ok(rows.filter(|o| o.deleted_at.is_none()).cloned().collect())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Opportunity>, DomainError> {
+        let tables = self.read();
+        Ok(visible_by_id(&tables.opportunities, id)?
+            .filter(|o| o.deleted_at.is_none())
+            .cloned())
+    }
+
+    async fn create(&self, opportunity: Opportunity) -> Result<Opportunity, DomainError> {
+        check_owner(&opportunity)?;
+        // Positions are not stored
+        let opportunity = Opportunity {
+            deleted_at: None,
+            position: 0,
+            ..opportunity
+        };
+        self.write(|changes| {
+            changes.put(
+                |t| &mut t.opportunities,
+                opportunity.id,
+                opportunity.clone(),
+            );
+            Ok(opportunity)
+        })
+    }
+
+    async fn update(&self, opportunity: Opportunity) -> Result<Opportunity, DomainError> {
+        self.write(|changes| {
+            let current = visible_by_id(&changes.opportunities, opportunity.id)?
+                .cloned()
+                .ok_or(DomainError::NotFound)?;
+            if current.version != opportunity.version {
+                return Err(version_conflict());
+            }
+            let updated = Opportunity {
+                updated_at: Utc::now(),
+                name: opportunity.name,
+                stage: opportunity.stage,
+                amount_micros: opportunity.amount_micros,
+                currency_code: opportunity.currency_code,
+                close_date: opportunity.close_date,
+                company_id: opportunity.company_id,
+                point_of_contact_id: opportunity.point_of_contact_id,
+                owner_id: opportunity.owner_id,
+                version: opportunity.version + 1,
+                ..current
+            };
+            changes.put(|t| &mut t.opportunities, updated.id, updated.clone());
+            Ok(updated)
+        })
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        self.soft_delete::<Opportunity>(id)
+    }
+}
+
+#[async_trait]
+impl UserRepository for InMemoryRepo {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, DomainError> {
+        Ok(self.read().users.get(&id).cloned())
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Option<User>, DomainError> {
+        Ok(self
+            .read()
+            .users
+            .values()
+            .find(|u| u.email == email)
+            .cloned())
+    }
+
+    async fn create(&self, user: User) -> Result<User, DomainError> {
+        self.write(|changes| {
+            ensure_unique(&changes.users, user.id, |u| u.email == user.email)?;
+            changes.put(|t| &mut t.users, user.id, user.clone());
+            Ok(user)
+        })
+    }
+
+    async fn update(&self, user: User) -> Result<User, DomainError> {
+        self.write(|changes| {
+            let current = changes
+                .users
+                .get(&user.id)
+                .cloned()
+                .ok_or(DomainError::NotFound)?;
+            ensure_unique(&changes.users, user.id, |u| u.email == user.email)?;
+            let updated = User {
+                email: user.email,
+                password_hash: user.password_hash,
+                state: user.state,
+                updated_at: user.updated_at,
+                ..current
+            };
+            changes.put(|t| &mut t.users, updated.id, updated.clone());
+            Ok(updated)
+        })
+    }
+}
+
+#[async_trait]
+impl UserTokenRepository for InMemoryRepo {
+    async fn create(&self, token: UserToken) -> Result<UserToken, DomainError> {
+        self.write(|changes| {
+            ensure_unique(&changes.user_tokens, token.id, |t| {
+                t.token_hash == token.token_hash
+            })?;
+            changes.put(|t| &mut t.user_tokens, token.id, token.clone());
+            Ok(token)
+        })
+    }
+
+    async fn find_by_token_hash(&self, token_hash: &str) -> Result<Option<UserToken>, DomainError> {
+        Ok(self
+            .read()
+            .user_tokens
+            .values()
+            .find(|t| t.token_hash == token_hash)
+            .cloned())
+    }
+
+    async fn mark_used(&self, id: Uuid, used_at: DateTime<Utc>) -> Result<bool, DomainError> {
+        self.write(|changes| match changes.user_tokens.get(&id).cloned() {
+            Some(token) if token.used_at.is_none() => {
+                let used = UserToken {
+                    used_at: Some(used_at),
+                    ..token
+                };
+                changes.put(|t| &mut t.user_tokens, id, used);
+                Ok(true)
+            }
+            _ => Ok(false),
+        })
+    }
+
+    async fn mark_all_used(
+        &self,
+        user_id: Uuid,
+        purpose: UserTokenPurpose,
+        used_at: DateTime<Utc>,
+    ) -> Result<(), DomainError> {
+        self.write(|changes| {
+            let unused: Vec<UserToken> = changes
+                .user_tokens
+                .values()
+                .filter(|t| t.user_id == user_id && t.purpose == purpose && t.used_at.is_none())
+                .cloned()
+                .collect();
+            for token in unused {
+                let used = UserToken {
+                    used_at: Some(used_at),
+                    ..token
+                };
+                changes.put(|t| &mut t.user_tokens, used.id, used);
+            }
+            Ok(())
+        })
+    }
+
+    async fn count_since(
+        &self,
+        user_id: Uuid,
+        purpose: UserTokenPurpose,
+        since: DateTime<Utc>,
+    ) -> Result<u64, DomainError> {
+        Ok(self
+            .read()
+            .user_tokens
+            .values()
+            .filter(|t| t.user_id == user_id && t.purpose == purpose && t.created_at >= since)
+            .count() as u64)
+    }
+}
+
+#[async_trait]
+impl SessionRepository for InMemoryRepo {
+    async fn create(&self, session: Session) -> Result<Session, DomainError> {
+        self.write(|changes| {
+            ensure_unique(&changes.sessions, session.id, |s| {
+                s.token_hash == session.token_hash
+            })?;
+            changes.put(|t| &mut t.sessions, session.id, session.clone());
+            Ok(session)
+        })
+    }
+
+    async fn find_by_token_hash(&self, token_hash: &str) -> Result<Option<Session>, DomainError> {
+        Ok(self
+            .read()
+            .sessions
+            .values()
+            .find(|s| s.token_hash == token_hash)
+            .cloned())
+    }
+
+    async fn delete_by_token_hash(&self, token_hash: &str) -> Result<(), DomainError> {
+        self.write(|changes| {
+            let ids: Vec<Uuid> = changes
+                .sessions
+                .values()
+                .filter(|s| s.token_hash == token_hash)
+                .map(|s| s.id)
+                .collect();
+            for id in ids {
+                changes.remove(|t| &mut t.sessions, id);
+            }
+            Ok(())
+        })
+    }
+
+    async fn delete_by_user_id(&self, user_id: Uuid) -> Result<(), DomainError> {
+        self.write(|changes| {
+            let ids: Vec<Uuid> = changes
+                .sessions
+                .values()
+                .filter(|s| s.user_id == user_id)
+                .map(|s| s.id)
+                .collect();
+            for id in ids {
+                changes.remove(|t| &mut t.sessions, id);
+            }
+            Ok(())
+        })
+    }
+}
+
+#[async_trait]
+impl SsoConfigRepository for InMemoryRepo {
+    async fn find_by_workspace_id(
+        &self,
+        workspace_id: Uuid,
+    ) -> Result<Option<SsoConfig>, DomainError> {
+        Ok(self
+            .read()
+            .sso_configs
+            .values()
+            .find(|c| c.workspace_id == workspace_id)
+            .cloned())
+    }
+
+    async fn save(&self, config: SsoConfig) -> Result<SsoConfig, DomainError> {
+        self.write(|changes| {
+            ensure_unique(&changes.sso_configs, config.id, |c| {
+                c.workspace_id == config.workspace_id
+            })?;
+            changes.put(|t| &mut t.sso_configs, config.id, config.clone());
+            Ok(config)
+        })
+    }
+}
+
+#[async_trait]
+impl ApiKeyRepository for InMemoryRepo {
+    async fn create(&self, api_key: ApiKey) -> Result<ApiKey, DomainError> {
+        self.write(|changes| {
+            ensure_unique(&changes.api_keys, api_key.id, |k| {
+                k.token_hash == api_key.token_hash
+            })?;
+            changes.put(|t| &mut t.api_keys, api_key.id, api_key.clone());
+            Ok(api_key)
+        })
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<ApiKey>, DomainError> {
+        Ok(self.read().api_keys.get(&id).cloned())
+    }
+
+    async fn find_by_token_hash(&self, token_hash: &str) -> Result<Option<ApiKey>, DomainError> {
+        Ok(self
+            .read()
+            .api_keys
+            .values()
+            .find(|k| k.token_hash == token_hash)
+            .cloned())
+    }
+
+    async fn find_by_member_id(&self, member_id: Uuid) -> Result<Vec<ApiKey>, DomainError> {
+        let tables = self.read();
+        let keys = tables
+            .api_keys
+            .values()
+            .filter(|k| k.member_id == member_id && k.revoked_at.is_none())
+            .cloned();
+        Ok(sorted(keys, |a, b| b.created_at.cmp(&a.created_at)))
+    }
+
+    async fn update(&self, api_key: ApiKey) -> Result<ApiKey, DomainError> {
+        self.write(|changes| {
+            let current = changes
+                .api_keys
+                .get(&api_key.id)
+                .cloned()
+                .ok_or(DomainError::NotFound)?;
+            let updated = ApiKey {
+                name: api_key.name,
+                revoked_at: api_key.revoked_at,
+                updated_at: api_key.updated_at,
+                ..current
+            };
+            changes.put(|t| &mut t.api_keys, updated.id, updated.clone());
+            Ok(updated)
+        })
+    }
+
+    async fn record_use(&self, id: Uuid, at: DateTime<Utc>) -> Result<(), DomainError> {
+        self.write(|changes| {
+            let current = changes
+                .api_keys
+                .get(&id)
+                .cloned()
+                .ok_or(DomainError::NotFound)?;
+            let updated = ApiKey {
+                last_used_at: Some(at),
+                ..current
+            };
+            changes.put(|t| &mut t.api_keys, id, updated);
+            Ok(())
+        })
+    }
+}
+
+#[async_trait]
+impl WorkspaceRepository for InMemoryRepo {
+    async fn create(&self, workspace: Workspace) -> Result<Workspace, DomainError> {
+        self.write(|changes| {
+            ensure_unique(&changes.workspaces, workspace.id, |w| {
+                w.subdomain == workspace.subdomain
+            })?;
+            changes.put(|t| &mut t.workspaces, workspace.id, workspace.clone());
+            Ok(workspace)
+        })
+    }
+
+    async fn find_by_subdomain(&self, subdomain: &str) -> Result<Option<Workspace>, DomainError> {
+        Ok(self
+            .read()
+            .workspaces
+            .values()
+            .find(|w| w.subdomain == subdomain)
+            .cloned())
+    }
+
+    async fn add_member(&self, member: WorkspaceMember) -> Result<WorkspaceMember, DomainError> {
+        self.write(|changes| {
+            changes.put(|t| &mut t.members, member.id, member.clone());
+            Ok(member)
+        })
+    }
+
+    async fn find_members_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<WorkspaceMember>, DomainError> {
+        let tables = self.read();
+        let members = tables
+            .members
+            .values()
+            .filter(|m| m.user_id == user_id)
+            .cloned();
+        Ok(sorted(members, |a, b| a.created_at.cmp(&b.created_at)))
+    }
+
+    async fn find_member_by_id(&self, id: Uuid) -> Result<Option<WorkspaceMember>, DomainError> {
+        Ok(self.read().members.get(&id).cloned())
+    }
+
+    async fn update_member(&self, member: WorkspaceMember) -> Result<WorkspaceMember, DomainError> {
+        self.write(|changes| {
+            if !changes.members.contains_key(&member.id) {
+                return Err(DomainError::NotFound);
+            }
+            changes.put(|t| &mut t.members, member.id, member.clone());
+            Ok(member)
+        })
+    }
+
+    async fn find_members(&self, workspace_id: Uuid) -> Result<Vec<WorkspaceMember>, DomainError> {
+        let tables = self.read();
+        let members = tables
+            .members
+            .values()
+            .filter(|m| m.workspace_id == workspace_id)
+            .cloned();
+        Ok(sorted(members, |a, b| a.created_at.cmp(&b.created_at)))
+    }
+
+    async fn remove_member(&self, id: Uuid) -> Result<(), DomainError> {
+        self.write(|changes| {
+            changes.remove(|t| &mut t.members, id);
+            Ok(())
+        })
+    }
+}
+
+#[async_trait]
+impl InvitationRepository for InMemoryRepo {
+    async fn create(&self, invitation: Invitation) -> Result<Invitation, DomainError> {
+        self.write(|changes| {
+            ensure_unique(&changes.invitations, invitation.id, |i| {
+                i.token_hash == invitation.token_hash
+            })?;
+            changes.put(|t| &mut t.invitations, invitation.id, invitation.clone());
+            Ok(invitation)
+        })
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Invitation>, DomainError> {
+        Ok(self.read().invitations.get(&id).cloned())
+    }
+
+    async fn find_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<Invitation>, DomainError> {
+        Ok(self
+            .read()
+            .invitations
+            .values()
+            .find(|i| i.token_hash == token_hash)
+            .cloned())
+    }
+
+    async fn find_open(&self, workspace_id: Uuid) -> Result<Vec<Invitation>, DomainError> {
+        let tables = self.read();
+        let invitations = tables
+            .invitations
+            .values()
+            .filter(|i| {
+                i.workspace_id == workspace_id && i.accepted_at.is_none() && i.revoked_at.is_none()
+            })
+            .cloned();
+        Ok(sorted(invitations, |a, b| b.created_at.cmp(&a.created_at)))
+    }
+
+    async fn update(&self, invitation: Invitation) -> Result<Invitation, DomainError> {
+        self.write(|changes| {
+            let current = changes
+                .invitations
+                .get(&invitation.id)
+                .cloned()
+                .ok_or(DomainError::NotFound)?;
+            let updated = Invitation {
+                role: invitation.role,
+                expires_at: invitation.expires_at,
+                accepted_at: invitation.accepted_at,
+                revoked_at: invitation.revoked_at,
+                updated_at: invitation.updated_at,
+                ..current
+            };
+            changes.put(|t| &mut t.invitations, updated.id, updated.clone());
+            Ok(updated)
+        })
+    }
+
+    async fn mark_accepted(
+        &self,
+        id: Uuid,
+        accepted_at: DateTime<Utc>,
+    ) -> Result<bool, DomainError> {
+        self.write(|changes| match changes.invitations.get(&id).cloned() {
+            Some(invitation)
+                if invitation.accepted_at.is_none() && invitation.revoked_at.is_none() =>
+            {
+                let accepted = Invitation {
+                    accepted_at: Some(accepted_at),
+                    updated_at: accepted_at,
+                    ..invitation
+                };
+                changes.put(|t| &mut t.invitations, id, accepted);
+                Ok(true)
+            }
+            _ => Ok(false),
+        })
+    }
+}
+
+#[async_trait]
+impl RoleRepository for InMemoryRepo {
+    async fn find_all(&self, workspace_id: Uuid) -> Result<Vec<RoleDefinition>, DomainError> {
+        let tables = self.read();
+        let roles = tables
+            .roles
+            .values()
+            .filter(|r| r.workspace_id == workspace_id)
+            .cloned();
+        Ok(sorted(roles, |a, b| a.name.cmp(&b.name)))
+    }
+
+    async fn find_by_name(
+        &self,
+        workspace_id: Uuid,
+        name: &str,
+    ) -> Result<Option<RoleDefinition>, DomainError> {
+        Ok(self
+            .read()
+            .roles
+            .values()
+            .find(|r| r.workspace_id == workspace_id && r.name == name)
+            .cloned())
+    }
+
+    async fn create(&self, role: RoleDefinition) -> Result<RoleDefinition, DomainError> {
+        self.write(|changes| {
+            ensure_unique(&changes.roles, role.id, |r| {
+                r.workspace_id == role.workspace_id && r.name == role.name
+            })?;
+            changes.put(|t| &mut t.roles, role.id, role.clone());
+            Ok(role)
+        })
+    }
+}
+
+#[async_trait]
+impl CompanyRepository for InMemoryRepo {
+    async fn find_all(&self) -> Result<Vec<Company>, DomainError> {
+        let tables = self.read();
+        let rows = visible(&tables.companies)?;
+        Ok(rows.filter(|c| c.deleted_at.is_none()).cloned().collect())
+    }
+
+    async fn find_page(&self, query: &ListQuery) -> Result<Page<Company>, DomainError> {
+        let tables = self.read();
+        let rows = visible(&tables.companies)?
+            .filter(|c| c.deleted_at.is_none())
+            .cloned();
+        fetch_page(
+            rows,
+            query,
+            &company_fields(),
+            company_value,
+            |c| c.id,
+            Sort::asc("created_at"),
+        )
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Company>, DomainError> {
+        let tables = self.read();
+        Ok(visible_by_id(&tables.companies, id)?
+            .filter(|c| c.deleted_at.is_none())
+            .cloned())
+    }
+
+    async fn create(&self, company: Company) -> Result<Company, DomainError> {
+        check_owner(&company)?;
+        // Positions are not stored
+        let company = Company {
+            deleted_at: None,
+            position: 0,
+            ..company
+        };
+        self.write(|changes| {
+            ensure_unique(&changes.companies, company.id, |c| {
+                c.workspace_id == company.workspace_id
+                    && c.domain_name == company.domain_name
+                    && c.deleted_at.is_none()
+            })?;
+            changes.put(|t| &mut t.companies, company.id, company.clone());
+            Ok(company)
+        })
+    }
+
+    async fn update(&self, company: Company) -> Result<Company, DomainError> {
+        self.write(|changes| {
+            let current = visible_by_id(&changes.companies, company.id)?
+                .cloned()
+                .ok_or(DomainError::NotFound)?;
+            if current.version != company.version {
+                return Err(version_conflict());
+            }
+            ensure_unique(&changes.companies, company.id, |c| {
+                c.workspace_id == current.workspace_id
+                    && c.domain_name == company.domain_name
+                    && c.deleted_at.is_none()
+            })?;
+            let updated = Company {
+                updated_at: Utc::now(),
+                name: company.name,
+                domain_name: company.domain_name,
+                address: company.address,
+                employees_count: company.employees_count,
+                version: company.version + 1,
+                ..current
+            };
+            changes.put(|t| &mut t.companies, updated.id, updated.clone());
+            Ok(updated)
+        })
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        self.soft_delete::<Company>(id)
+    }
+}
+
+#[async_trait]
+impl TaskRepository for InMemoryRepo {
+    async fn find_all(&self) -> Result<Vec<Task>, DomainError> {
+        let tables = self.read();
+        let rows = visible(&tables.tasks)?;
+        Ok(rows.filter(|t| t.deleted_at.is_none()).cloned().collect())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Task>, DomainError> {
+        let tables = self.read();
+        Ok(visible_by_id(&tables.tasks, id)?
+            .filter(|t| t.deleted_at.is_none())
+            .cloned())
+    }
+
+    async fn create(&self, task: Task) -> Result<Task, DomainError> {
+        check_owner(&task)?;
+        let task = Task {
+            deleted_at: None,
+            ..task
+        };
+        self.write(|changes| {
+            changes.put(|t| &mut t.tasks, task.id, task.clone());
+            Ok(task)
+        })
+    }
+
+    async fn update(&self, task: Task) -> Result<Task, DomainError> {
+        self.write(|changes| {
+            let current = visible_by_id(&changes.tasks, task.id)?
+                .cloned()
+                .ok_or(DomainError::NotFound)?;
+            let updated = Task {
+                updated_at: Utc::now(),
+                title: task.title,
+                body: task.body,
+                status: task.status,
+                position: task.position,
+                assignee_id: task.assignee_id,
+                due_at: task.due_at,
+                ..current
+            };
+            changes.put(|t| &mut t.tasks, updated.id, updated.clone());
+            Ok(updated)
+        })
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        self.soft_delete::<Task>(id)
+    }
+}
+
+#[async_trait]
+impl NoteRepository for InMemoryRepo {
+    async fn find_all(&self) -> Result<Vec<Note>, DomainError> {
+        let tables = self.read();
+        let rows = visible(&tables.notes)?;
+        Ok(rows.filter(|n| n.deleted_at.is_none()).cloned().collect())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Note>, DomainError> {
+        let tables = self.read();
+        Ok(visible_by_id(&tables.notes, id)?
+            .filter(|n| n.deleted_at.is_none())
+            .cloned())
+    }
+
+    async fn create(&self, note: Note) -> Result<Note, DomainError> {
+        check_owner(&note)?;
+        let note = Note {
+            deleted_at: None,
+            ..note
+        };
+        self.write(|changes| {
+            changes.put(|t| &mut t.notes, note.id, note.clone());
+            Ok(note)
+        })
+    }
+
+    async fn update(&self, note: Note) -> Result<Note, DomainError> {
+        self.write(|changes| {
+            let current = visible_by_id(&changes.notes, note.id)?
+                .cloned()
+                .ok_or(DomainError::NotFound)?;
+            let updated = Note {
+                updated_at: Utc::now(),
+                title: note.title,
+                body_v2: note.body_v2,
+                position: note.position,
+                ..current
+            };
+            changes.put(|t| &mut t.notes, updated.id, updated.clone());
+            Ok(updated)
+        })
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        self.soft_delete::<Note>(id)
+    }
+}
+
+#[async_trait]
+impl TrashRepository for InMemoryRepo {
+    async fn find_trashed(&self, kinds: &[TrashedKind]) -> Result<Vec<TrashedRecord>, DomainError> {
+        let mut records = Vec::new();
+        for kind in kinds {
+            records.extend(with_row!(kind, R => self.trashed::<R>())?);
+        }
+        records.sort_by_key(|record| Reverse(record.deleted_at));
+        Ok(records)
+    }
+
+    async fn restore(&self, kind: TrashedKind, id: Uuid) -> Result<(), DomainError> {
+        with_row!(kind, R => self.restore_record::<R>(id))
+    }
+
+    async fn purge(&self, kind: TrashedKind, id: Uuid) -> Result<(), DomainError> {
+        let purged = with_row!(kind, R => self.purge_where::<R>(|record| record.id == id))?;
+        if purged == 0 {
+            return Err(DomainError::NotFound);
+        }
+        Ok(())
+    }
+
+    async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<u64, DomainError> {
+        let mut purged = 0;
+        for kind in TrashedKind::ALL {
+            purged += with_row!(kind, R => self.purge_where::<R>(|record| {
+                record.deleted_at().is_some_and(|deleted_at| deleted_at < cutoff)
+            }))?;
+        }
+        Ok(purged)
+    }
+}
+
+#[async_trait]
+impl TaskTargetRepository for InMemoryRepo {
+    async fn find_by_task_id(&self, task_id: Uuid) -> Result<Vec<TaskTarget>, DomainError> {
+        let tables = self.read();
+        ensure_visible(&tables.tasks, task_id)?;
+        Ok(tables
+            .task_targets
+            .values()
+            .filter(|link| link.row.task_id == task_id && link.deleted_at.is_none())
+            .map(|link| link.row.clone())
+            .collect())
+    }
+
+    async fn create(&self, task_target: TaskTarget) -> Result<TaskTarget, DomainError> {
+        self.write(|changes| {
+            ensure_visible(&changes.tasks, task_target.task_id)?;
+            let link = Link {
+                row: task_target.clone(),
+                deleted_at: None,
+            };
+            changes.put(|t| &mut t.task_targets, task_target.id, link);
+            Ok(task_target)
+        })
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        self.write(|changes| {
+            let link = changes.task_targets.get(&id).ok_or(DomainError::NotFound)?;
+            ensure_visible(&changes.tasks, link.row.task_id)?;
+            changes.remove(|t| &mut t.task_targets, id);
+            Ok(())
+        })
+    }
+}
+
+#[async_trait]
+impl WorkflowRepository for InMemoryRepo {
+    async fn find_all(&self) -> Result<Vec<Workflow>, DomainError> {
+        let tables = self.read();
+        let rows = visible(&tables.workflows)?;
+        Ok(rows.cloned().collect())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Workflow>, DomainError> {
+        let tables = self.read();
+        Ok(visible_by_id(&tables.workflows, id)?.cloned())
+    }
+
+    async fn create(&self, workflow: Workflow) -> Result<Workflow, DomainError> {
+        check_owner(&workflow)?;
+        self.write(|changes| {
+            changes.put(|t| &mut t.workflows, workflow.id, workflow.clone());
+            Ok(workflow)
+        })
+    }
+
+    async fn update(&self, workflow: Workflow) -> Result<Workflow, DomainError> {
+        self.write(|changes| {
+            let current = visible_by_id(&changes.workflows, workflow.id)?
+                .cloned()
+                .ok_or(DomainError::NotFound)?;
+            let updated = Workflow {
+                name: workflow.name,
+                last_published_version_id: workflow.last_published_version_id,
+                updated_at: workflow.updated_at,
+                ..current
+            };
+            changes.put(|t| &mut t.workflows, updated.id, updated.clone());
+            Ok(updated)
+        })
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        self.write(|changes| {
+            ensure_visible(&changes.workflows, id)?;
+            changes.remove(|t| &mut t.workflows, id);
+            Ok(())
+        })
+    }
+
+    async fn find_published_triggers(
+        &self,
+        workspace_id: Option<Uuid>,
+    ) -> Result<Vec<(Workflow, WorkflowVersionStep)>, DomainError> {
+        let tables = self.read();
+        let workflows = visible(&tables.workflows)?
+            .filter(|w| workspace_id.is_none_or(|id| w.workspace_id == id))
+            .filter_map(|w| Some((w, w.last_published_version_id?)));
+        let mut triggers = Vec::new();
+        for (workflow, version_id) in workflows {
+            let steps = tables.workflow_steps.values().filter(|s| {
+                s.workflow_version_id == version_id && s.step_type == WorkflowStepType::Trigger
+            });
+            triggers.extend(steps.map(|s| (workflow.clone(), s.clone())));
+        }
+        Ok(triggers)
+    }
+}
+
+/// Fails with `NotFound` unless the workflow version exists in a workflow of the current
+/// workspace
+fn ensure_workflow_version_visible(tables: &Tables, version_id: Uuid) -> Result<(), DomainError> {
+    let version = tables
+        .workflow_versions
+        .get(&version_id)
+        .ok_or(DomainError::NotFound)?;
+    ensure_visible(&tables.workflows, version.workflow_id)
+}
+
+/// `Ok(None)` instead of `NotFound`, for finders
+fn found_if<T>(row: T, visibility: Result<(), DomainError>) -> Result<Option<T>, DomainError> {
+    match visibility {
+        Ok(()) => Ok(Some(row)),
+        Err(DomainError::NotFound) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+#[async_trait]
+impl WorkflowVersionRepository for InMemoryRepo {
+    async fn find_all(&self) -> Result<Vec<WorkflowVersion>, DomainError> {
+        let tables = self.read();
+        let scope = current_scope()?;
+        Ok(tables
+            .workflow_versions
+            .values()
+            .filter(|v| {
+                tables
+                    .workflows
+                    .get(&v.workflow_id)
+                    .is_some_and(|w| in_scope(scope, w))
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<WorkflowVersion>, DomainError> {
+        let tables = self.read();
+        match tables.workflow_versions.get(&id) {
+            Some(v) => found_if(v.clone(), ensure_visible(&tables.workflows, v.workflow_id)),
+            None => Ok(None),
+        }
+    }
+
+    async fn find_by_workflow_id(
+        &self,
+        workflow_id: Uuid,
+    ) -> Result<Vec<WorkflowVersion>, DomainError> {
+        let tables = self.read();
+        ensure_visible(&tables.workflows, workflow_id)?;
+        let versions = tables
+            .workflow_versions
+            .values()
+            .filter(|v| v.workflow_id == workflow_id)
+            .cloned();
+        Ok(sorted(versions, |a, b| a.created_at.cmp(&b.created_at)))
+    }
+
+    async fn create(&self, version: WorkflowVersion) -> Result<WorkflowVersion, DomainError> {
+        self.write(|changes| {
+            ensure_visible(&changes.workflows, version.workflow_id)?;
+            changes.put(|t| &mut t.workflow_versions, version.id, version.clone());
+            Ok(version)
+        })
+    }
+
+    async fn update(&self, version: WorkflowVersion) -> Result<WorkflowVersion, DomainError> {
+        self.write(|changes| {
+            ensure_workflow_version_visible(changes, version.id)?;
+            let current = changes.workflow_versions[&version.id].clone();
+            let updated = WorkflowVersion {
+                updated_at: version.updated_at,
+                status: version.status,
+                ..current
+            };
+            changes.put(|t| &mut t.workflow_versions, updated.id, updated.clone());
+            Ok(updated)
+        })
+    }
+}
+
+#[async_trait]
+impl WorkflowVersionStepRepository for InMemoryRepo {
+    async fn find_by_version_id(
+        &self,
+        version_id: Uuid,
+    ) -> Result<Vec<WorkflowVersionStep>, DomainError> {
+        let tables = self.read();
+        ensure_workflow_version_visible(&tables, version_id)?;
+        let steps = tables
+            .workflow_steps
+            .values()
+            .filter(|s| s.workflow_version_id == version_id)
+            .cloned();
+        Ok(sorted(steps, |a, b| a.position.cmp(&b.position)))
+    }
+
+    async fn create(&self, step: WorkflowVersionStep) -> Result<WorkflowVersionStep, DomainError> {
+        self.write(|changes| {
+            ensure_workflow_version_visible(changes, step.workflow_version_id)?;
+            changes.put(|t| &mut t.workflow_steps, step.id, step.clone());
+            Ok(step)
+        })
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        self.write(|changes| {
+            let step = changes
+                .workflow_steps
+                .get(&id)
+                .ok_or(DomainError::NotFound)?;
+            ensure_workflow_version_visible(changes, step.workflow_version_id)?;
+            changes.remove(|t| &mut t.workflow_steps, id);
+            Ok(())
+        })
+    }
+}
+
+impl InMemoryRepo {
+    /// Runs of the workflows in the current workspace that `matching` accepts
+    fn workflow_runs(
+        &self,
+        matching: impl Fn(&WorkflowRun) -> bool,
+    ) -> Result<Vec<WorkflowRun>, DomainError> {
+        let tables = self.read();
+        current_scope()?;
+        Ok(tables
+            .workflow_runs
+            .values()
+            .filter(|r| ensure_workflow_version_visible(&tables, r.workflow_version_id).is_ok())
+            .filter(|r| matching(r))
+            .cloned()
+            .collect())
+    }
+}
+
+#[async_trait]
+impl WorkflowRunRepository for InMemoryRepo {
+    async fn find_all(&self) -> Result<Vec<WorkflowRun>, DomainError> {
+        let runs = self.workflow_runs(|_| true)?;
+        Ok(sorted(runs.into_iter(), |a, b| {
+            b.created_at.cmp(&a.created_at)
+        }))
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<WorkflowRun>, DomainError> {
+        let tables = self.read();
+        match tables.workflow_runs.get(&id) {
+            Some(r) => found_if(
+                r.clone(),
+                ensure_workflow_version_visible(&tables, r.workflow_version_id),
+            ),
+            None => Ok(None),
+        }
+    }
+
+    async fn create(&self, run: WorkflowRun) -> Result<WorkflowRun, DomainError> {
+        self.write(|changes| {
+            ensure_workflow_version_visible(changes, run.workflow_version_id)?;
+            changes.put(|t| &mut t.workflow_runs, run.id, run.clone());
+            Ok(run)
+        })
+    }
+
+    async fn update(&self, run: WorkflowRun) -> Result<WorkflowRun, DomainError> {
+        self.write(|changes| {
+            ensure_workflow_version_visible(changes, run.workflow_version_id)?;
+            let current = changes
+                .workflow_runs
+                .get(&run.id)
+                .cloned()
+                .ok_or(DomainError::NotFound)?;
+            let updated = WorkflowRun {
+                updated_at: run.updated_at,
+                status: run.status,
+                output: run.output,
+                error: run.error,
+                cursor: run.cursor,
+                wake_at: run.wake_at,
+                ..current
+            };
+            changes.put(|t| &mut t.workflow_runs, updated.id, updated.clone());
+            Ok(updated)
+        })
+    }
+
+    async fn find_due(&self, now: DateTime<Utc>) -> Result<Vec<WorkflowRun>, DomainError> {
+        let due = self.workflow_runs(|r| {
+            r.status == WorkflowRunStatus::Pending && r.wake_at.is_some_and(|at| at <= now)
+        })?;
+        Ok(sorted(due.into_iter(), |a, b| a.wake_at.cmp(&b.wake_at)))
+    }
+
+    async fn find_running_before(
+        &self,
+        before: DateTime<Utc>,
+    ) -> Result<Vec<WorkflowRun>, DomainError> {
+        let running = self.workflow_runs(|r| {
+            r.status == WorkflowRunStatus::Running && r.updated_at < before
+        })?;
+        Ok(sorted(running.into_iter(), |a, b| {
+            a.created_at.cmp(&b.created_at)
+        }))
+    }
+}
+
+#[async_trait]
+impl ConnectedAccountRepository for InMemoryRepo {
+    async fn find_all(&self) -> Result<Vec<ConnectedAccount>, DomainError> {
+        Ok(self.read().connected_accounts.values().cloned().collect())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<ConnectedAccount>, DomainError> {
+        Ok(self.read().connected_accounts.get(&id).cloned())
+    }
+
+    async fn create(&self, account: ConnectedAccount) -> Result<ConnectedAccount, DomainError> {
+        self.write(|changes| {
+            changes.put(|t| &mut t.connected_accounts, account.id, account.clone());
+            Ok(account)
+        })
+    }
+
+    async fn update(&self, account: ConnectedAccount) -> Result<ConnectedAccount, DomainError> {
+        self.write(|changes| {
+            if !changes.connected_accounts.contains_key(&account.id) {
+                return Err(DomainError::NotFound);
+            }
+            changes.put(|t| &mut t.connected_accounts, account.id, account.clone());
+            Ok(account)
+        })
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        self.write(|changes| {
+            changes.remove(|t| &mut t.connected_accounts, id);
+            Ok(())
+        })
+    }
+}
+
+#[async_trait]
+impl CalendarEventRepository for InMemoryRepo {
+    async fn find_all(&self) -> Result<Vec<CalendarEvent>, DomainError> {
+        let tables = self.read();
+        let rows = visible(&tables.calendar_events)?;
+        Ok(rows.cloned().collect())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<CalendarEvent>, DomainError> {
+        let tables = self.read();
+        Ok(visible_by_id(&tables.calendar_events, id)?.cloned())
+    }
+
+    async fn create(&self, event: CalendarEvent) -> Result<CalendarEvent, DomainError> {
+        check_owner(&event)?;
+        self.write(|changes| {
+            changes.put(|t| &mut t.calendar_events, event.id, event.clone());
+            Ok(event)
+        })
+    }
+
+    async fn update(&self, event: CalendarEvent) -> Result<CalendarEvent, DomainError> {
+        self.write(|changes| {
+            let current = visible_by_id(&changes.calendar_events, event.id)?
+                .cloned()
+                .ok_or(DomainError::NotFound)?;
+            let updated = CalendarEvent {
+                connected_account_id: event.connected_account_id,
+                title: event.title,
+                start_time: event.start_time,
+                end_time: event.end_time,
+                description: event.description,
+                updated_at: event.updated_at,
+                ..current
+            };
+            changes.put(|t| &mut t.calendar_events, updated.id, updated.clone());
+            Ok(updated)
+        })
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        self.write(|changes| {
+            ensure_visible(&changes.calendar_events, id)?;
+            changes.remove(|t| &mut t.calendar_events, id);
+            Ok(())
+        })
+    }
+}
+
+/// Participants are scoped through their event
+#[async_trait]
+impl CalendarEventParticipantRepository for InMemoryRepo {
+    async fn find_by_event_id(
+        &self,
+        event_id: Uuid,
+    ) -> Result<Vec<CalendarEventParticipant>, DomainError> {
+        let tables = self.read();
+        ensure_visible(&tables.calendar_events, event_id)?;
+        Ok(tables
+            .calendar_event_participants
+            .values()
+            .filter(|p| p.calendar_event_id == event_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn create(
+        &self,
+        participant: CalendarEventParticipant,
+    ) -> Result<CalendarEventParticipant, DomainError> {
+        self.write(|changes| {
+            ensure_visible(&changes.calendar_events, participant.calendar_event_id)?;
+            changes.put(
+                |t| &mut t.calendar_event_participants,
+                participant.id,
+                participant.clone(),
+            );
+            Ok(participant)
+        })
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        self.write(|changes| {
+            let participant = changes
+                .calendar_event_participants
+                .get(&id)
+                .ok_or(DomainError::NotFound)?;
+            ensure_visible(&changes.calendar_events, participant.calendar_event_id)?;
+            changes.remove(|t| &mut t.calendar_event_participants, id);
+            Ok(())
+        })
+    }
+}
+
+impl InMemoryRepo {
+    /// Live timeline activities in the current workspace that `linked` accepts, newest first
+    fn timeline(
+        &self,
+        linked: impl Fn(&TimelineActivity) -> bool,
+    ) -> Result<Vec<TimelineActivity>, DomainError> {
+        let tables = self.read();
+        let activities = visible(&tables.timeline_activities)?
+            .filter(|link| link.deleted_at.is_none() && linked(&link.row))
+            .map(|link| link.row.clone());
+        Ok(sorted(activities, |a, b| b.created_at.cmp(&a.created_at)))
+    }
+}
+
+#[async_trait]
+impl TimelineActivityRepository for InMemoryRepo {
+    async fn find_all(&self) -> Result<Vec<TimelineActivity>, DomainError> {
+        self.timeline(|_| true)
+    }
+
+    async fn find_by_person_id(
+        &self,
+        person_id: Uuid,
+    ) -> Result<Vec<TimelineActivity>, DomainError> {
+        self.timeline(|a| a.person_id == Some(person_id))
+    }
+
+    async fn find_by_company_id(
+        &self,
+        company_id: Uuid,
+    ) -> Result<Vec<TimelineActivity>, DomainError> {
+        self.timeline(|a| a.company_id == Some(company_id))
+    }
+
+    async fn find_by_opportunity_id(
+        &self,
+        opportunity_id: Uuid,
+    ) -> Result<Vec<TimelineActivity>, DomainError> {
+        self.timeline(|a| a.opportunity_id == Some(opportunity_id))
+    }
+
+    async fn find_by_task_id(&self, task_id: Uuid) -> Result<Vec<TimelineActivity>, DomainError> {
+        self.timeline(|a| a.task_id == Some(task_id))
+    }
+
+    async fn create(&self, activity: TimelineActivity) -> Result<TimelineActivity, DomainError> {
+        check_owner(&activity)?;
+        self.write(|changes| {
+            let link = Link {
+                row: activity.clone(),
+                deleted_at: None,
+            };
+            changes.put(|t| &mut t.timeline_activities, activity.id, link);
+            Ok(activity)
+        })
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        self.write(|changes| {
+            ensure_visible(&changes.timeline_activities, id)?;
+            changes.remove(|t| &mut t.timeline_activities, id);
+            Ok(())
+        })
+    }
+}
+
+impl InMemoryRepo {
+    /// Emails in the current workspace that `matching` accepts, newest first
+    fn emails(&self, matching: impl Fn(&Email) -> bool) -> Result<Vec<Email>, DomainError> {
+        let tables = self.read();
+        let emails = visible(&tables.emails)?.filter(|e| matching(e)).cloned();
+        Ok(sorted(emails, |a, b| b.created_at.cmp(&a.created_at)))
+    }
+}
+
+#[async_trait]
+impl EmailRepository for InMemoryRepo {
+    async fn find_page(&self, query: &ListQuery) -> Result<Page<Email>, DomainError> {
+        let tables = self.read();
+        let rows = visible(&tables.emails)?.cloned();
+        fetch_page(
+            rows,
+            query,
+            &email_fields(),
+            email_value,
+            |e| e.id,
+            Sort::desc("created_at"),
+        )
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Email>, DomainError> {
+        let tables = self.read();
+        Ok(visible_by_id(&tables.emails, id)?.cloned())
+    }
+
+    async fn find_by_person_id(&self, person_id: Uuid) -> Result<Vec<Email>, DomainError> {
+        self.emails(|e| e.person_id == Some(person_id))
+    }
+
+    async fn find_by_company_id(&self, company_id: Uuid) -> Result<Vec<Email>, DomainError> {
+        self.emails(|e| e.company_id == Some(company_id))
+    }
+
+    async fn find_by_opportunity_id(
+        &self,
+        opportunity_id: Uuid,
+    ) -> Result<Vec<Email>, DomainError> {
+        self.emails(|e| e.opportunity_id == Some(opportunity_id))
+    }
+
+    async fn find_pending(&self) -> Result<Vec<Email>, DomainError> {
+        let mut pending = self.emails(|e| e.status == EmailStatus::Pending)?;
+        pending.reverse();
+        Ok(pending)
+    }
+
+    async fn create(&self, email: Email) -> Result<Email, DomainError> {
+        check_owner(&email)?;
+        self.write(|changes| {
+            changes.put(|t| &mut t.emails, email.id, email.clone());
+            Ok(email)
+        })
+    }
+
+    async fn update(&self, email: Email) -> Result<Email, DomainError> {
+        check_owner(&email)?;
+        self.write(|changes| {
+            ensure_visible(&changes.emails, email.id)?;
+            let updated = Email {
+                updated_at: Utc::now(),
+                ..email
+            };
+            changes.put(|t| &mut t.emails, updated.id, updated.clone());
+            Ok(updated)
+        })
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        self.write(|changes| {
+            ensure_visible(&changes.emails, id)?;
+            changes.remove(|t| &mut t.emails, id);
+            Ok(())
+        })
+    }
+}
+
+#[async_trait]
+impl EmailTemplateRepository for InMemoryRepo {
+    async fn find_all(&self) -> Result<Vec<EmailTemplate>, DomainError> {
+        let tables = self.read();
+        let templates = visible(&tables.email_templates)?.cloned();
+        Ok(sorted(templates, |a, b| a.name.cmp(&b.name)))
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<EmailTemplate>, DomainError> {
+        Ok(visible_by_id(&self.read().email_templates, id)?.cloned())
+    }
+
+    async fn find_by_name(&self, name: &str) -> Result<Option<EmailTemplate>, DomainError> {
+        let tables = self.read();
+        let mut templates = visible(&tables.email_templates)?;
+        Ok(templates.find(|t| t.name == name).cloned())
+    }
+
+    async fn create(&self, template: EmailTemplate) -> Result<EmailTemplate, DomainError> {
+        check_owner(&template)?;
+        self.write(|changes| {
+            ensure_unique(&changes.email_templates, template.id, |t| {
+                t.workspace_id == template.workspace_id && t.name == template.name
+            })?;
+            changes.put(|t| &mut t.email_templates, template.id, template.clone());
+            Ok(template)
+        })
+    }
+
+    async fn update(&self, template: EmailTemplate) -> Result<EmailTemplate, DomainError> {
+        check_owner(&template)?;
+        self.write(|changes| {
+            ensure_visible(&changes.email_templates, template.id)?;
+            ensure_unique(&changes.email_templates, template.id, |t| {
+                t.workspace_id == template.workspace_id && t.name == template.name
+            })?;
+            let updated = EmailTemplate {
+                updated_at: Utc::now(),
+                ..template
+            };
+            changes.put(|t| &mut t.email_templates, updated.id, updated.clone());
+            Ok(updated)
+        })
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        self.write(|changes| {
+            ensure_visible(&changes.email_templates, id)?;
+            changes.remove(|t| &mut t.email_templates, id);
+            Ok(())
+        })
+    }
+}
+
+impl InMemoryRepo {
+    /// Live leads in the current workspace that `matching` accepts, highest score first
+    fn leads(&self, matching: impl Fn(&Lead) -> bool) -> Result<Vec<Lead>, DomainError> {
+        let tables = self.read();
+        let leads = visible(&tables.leads)?
+            .filter(|l| l.deleted_at.is_none() && matching(l))
+            .cloned();
+        Ok(sorted(leads, |a, b| b.score.cmp(&a.score)))
+    }
+}
+
+#[async_trait]
+impl LeadRepository for InMemoryRepo {
+    async fn find_page(&self, query: &ListQuery) -> Result<Page<Lead>, DomainError> {
+        let tables = self.read();
+        let rows = visible(&tables.leads)?
+            .filter(|l| l.deleted_at.is_none())
+            .cloned();
+        fetch_page(
+            rows,
+            query,
+            &lead_fields(),
+            lead_value,
+            |l| l.id,
+            Sort::desc("score"),
+        )
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Lead>, DomainError> {
+        let tables = self.read();
+        Ok(visible_by_id(&tables.leads, id)?
+            .filter(|l| l.deleted_at.is_none())
+            .cloned())
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Option<Lead>, DomainError> {
+        Ok(self.leads(|l| l.email == email)?.into_iter().next())
+    }
+
+    async fn find_by_status(&self, status: LeadStatus) -> Result<Vec<Lead>, DomainError> {
+        self.leads(|l| l.status == status)
+    }
+
+    async fn find_unassigned(&self) -> Result<Vec<Lead>, DomainError> {
+        self.leads(|l| l.assigned_to_id.is_none())
+    }
+
+    async fn find_by_assigned_to(&self, assigned_to_id: Uuid) -> Result<Vec<Lead>, DomainError> {
+        self.leads(|l| l.assigned_to_id == Some(assigned_to_id))
+    }
+
+    async fn find_high_score(&self, min_score: i32) -> Result<Vec<Lead>, DomainError> {
+        self.leads(|l| l.score >= min_score)
+    }
+
+    async fn create(&self, lead: Lead) -> Result<Lead, DomainError> {
+        check_owner(&lead)?;
+        let lead = Lead {
+            deleted_at: None,
+            ..lead
+        };
+        self.write(|changes| {
+            ensure_unique(&changes.leads, lead.id, |l| {
+                l.workspace_id == lead.workspace_id
+                    && l.email == lead.email
+                    && l.deleted_at.is_none()
+            })?;
+            changes.put(|t| &mut t.leads, lead.id, lead.clone());
+            Ok(lead)
+        })
+    }
+
+    async fn update(&self, lead: Lead) -> Result<Lead, DomainError> {
+        check_owner(&lead)?;
+        self.write(|changes| {
+            ensure_visible(&changes.leads, lead.id)?;
+            ensure_unique(&changes.leads, lead.id, |l| {
+                l.workspace_id == lead.workspace_id
+                    && l.email == lead.email
+                    && l.deleted_at.is_none()
+            })?;
+            changes.put(|t| &mut t.leads, lead.id, lead.clone());
+            Ok(lead)
+        })
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        self.soft_delete::<Lead>(id)
+    }
+}
+
+#[async_trait]
+impl MetadataRepository for InMemoryRepo {
+    async fn find_object_by_name(
+        &self,
+        name_singular: &str,
+    ) -> Result<Option<ObjectMetadata>, DomainError> {
+        let tables = self.read();
+        let mut rows = visible(&tables.objects)?;
+        Ok(rows.find(|o| o.name_singular == name_singular).cloned())
+    }
+
+    async fn get_schema(&self) -> Result<Vec<(ObjectMetadata, Vec<FieldMetadata>)>, DomainError> {
+        let tables = self.read();
+        let rows = visible(&tables.objects)?;
+        Ok(rows
+            .map(|object| {
+                let fields = tables
+                    .fields
+                    .values()
+                    .filter(|f| f.object_metadata_id == object.id)
+                    .cloned()
+                    .collect();
+                (object.clone(), fields)
+            })
+            .collect())
+    }
+
+    async fn create_object(&self, object: ObjectMetadata) -> Result<ObjectMetadata, DomainError> {
+        check_owner(&object)?;
+        self.write(|changes| {
+            ensure_unique(&changes.objects, object.id, |o| {
+                o.name_singular == object.name_singular
+            })?;
+            changes.put(|t| &mut t.objects, object.id, object.clone());
+            Ok(object)
+        })
+    }
+
+    async fn create_field(&self, field: FieldMetadata) -> Result<FieldMetadata, DomainError> {
+        self.write(|changes| {
+            ensure_visible(&changes.objects, field.object_metadata_id)?;
+            ensure_unique(&changes.fields, field.id, |f| {
+                f.object_metadata_id == field.object_metadata_id && f.name == field.name
+            })?;
+            changes.put(|t| &mut t.fields, field.id, field.clone());
+            Ok(field)
+        })
+    }
+
+    async fn find_object_by_id(&self, id: Uuid) -> Result<Option<ObjectMetadata>, DomainError> {
+        let tables = self.read();
+        Ok(visible_by_id(&tables.objects, id)?.cloned())
+    }
+}
+
+#[async_trait]
+impl CustomObjectDataRepository for InMemoryRepo {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<CustomObjectData>, DomainError> {
+        let tables = self.read();
+        Ok(visible_by_id(&tables.custom_records, id)?.cloned())
+    }
+
+    async fn find_by_object_metadata_id(
+        &self,
+        object_metadata_id: Uuid,
+    ) -> Result<Vec<CustomObjectData>, DomainError> {
+        let tables = self.read();
+        let rows = visible(&tables.custom_records)?;
+        Ok(rows
+            .filter(|r| r.object_metadata_id == object_metadata_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn create(&self, data: CustomObjectData) -> Result<CustomObjectData, DomainError> {
+        check_owner(&data)?;
+        self.write(|changes| {
+            changes.put(|t| &mut t.custom_records, data.id, data.clone());
+            Ok(data)
+        })
+    }
+
+    async fn update(&self, data: CustomObjectData) -> Result<CustomObjectData, DomainError> {
+        check_owner(&data)?;
+        self.write(|changes| {
+            let current =
+                visible_by_id(&changes.custom_records, data.id)?.ok_or(DomainError::NotFound)?;
+            if current.version != data.version {
+                return Err(version_conflict());
+            }
+            let updated = CustomObjectData {
+                version: data.version + 1,
+                ..data
+            };
+            changes.put(|t| &mut t.custom_records, updated.id, updated.clone());
+            Ok(updated)
+        })
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        self.write(|changes| {
+            ensure_visible(&changes.custom_records, id)?;
+            changes.remove(|t| &mut t.custom_records, id);
+            Ok(())
+        })
+    }
+}
+
+#[async_trait]
+impl ViewRepository for InMemoryRepo {
+    async fn find_by_object(&self, object_metadata_id: Uuid) -> Result<Vec<View>, DomainError> {
+        let tables = self.read();
+        let views = visible(&tables.views)?
+            .filter(|v| v.object_metadata_id == object_metadata_id)
+            .cloned();
+        Ok(sorted(views, |a, b| a.position.cmp(&b.position)))
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<View>, DomainError> {
+        let tables = self.read();
+        Ok(visible_by_id(&tables.views, id)?.cloned())
+    }
+
+    async fn create(&self, view: View) -> Result<View, DomainError> {
+        check_owner(&view)?;
+        self.write(|changes| {
+            changes.put(|t| &mut t.views, view.id, view.clone());
+            Ok(view)
+        })
+    }
+
+    async fn update(&self, view: View) -> Result<View, DomainError> {
+        check_owner(&view)?;
+        self.write(|changes| {
+            let current = visible_by_id(&changes.views, view.id)?
+                .cloned()
+                .ok_or(DomainError::NotFound)?;
+            let updated = View {
+                updated_at: Utc::now(),
+                name: view.name,
+                view_type: view.view_type,
+                filters: view.filters,
+                sort: view.sort,
+                position: view.position,
+                workspace_id: view.workspace_id,
+                ..current
+            };
+            changes.put(|t| &mut t.views, updated.id, updated.clone());
+            Ok(updated)
+        })
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        self.write(|changes| {
+            ensure_visible(&changes.views, id)?;
+            changes.remove(|t| &mut t.views, id);
+            Ok(())
+        })
+    }
+}
+
+// Ports without a SeaORM implementation yet; the shared behaviour is covered by the
+// conformance suite
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::context::{run_as, run_as_system, Actor};
+    use crate::application::ports::identity::Identity;
+    use crate::domain::states::{ConnectedAccountStatus, UserState};
+
+    fn member_of(workspace_id: Uuid) -> Actor {
+        let user_id = Uuid::new_v4();
+        Actor::User(Box::new(Identity {
+            user: User {
+                id: user_id,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                email: format!("{}@example.com", user_id),
+                password_hash: String::new(),
+                state: UserState::Active,
+            },
+            member: Some(WorkspaceMember {
+                id: Uuid::new_v4(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                user_id,
+                workspace_id,
+                role: "Admin".to_string(),
+                name: "Member".to_string(),
+                suspended_at: None,
+            }),
+            api_key: None,
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_calendar_participants_are_scoped_through_their_event() {
+        let repo = InMemoryRepo::new();
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let account = run_as_system(ConnectedAccountRepository::create(
+            &repo,
+            ConnectedAccount {
+                id: Uuid::new_v4(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                provider: "google".to_string(),
+                account_owner_id: Uuid::new_v4(),
+                status: ConnectedAccountStatus::Connected,
+            },
+        ))
+        .await
+        .unwrap();
+
+        let participant = run_as(member_of(a), async {
+            let event = CalendarEventRepository::create(
+                &repo,
+                CalendarEvent {
+                    id: Uuid::new_v4(),
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                    connected_account_id: account.id,
+                    title: "Kickoff".to_string(),
+                    start_time: Utc::now(),
+                    end_time: Utc::now(),
+                    description: None,
+                    workspace_id: a,
+                },
+            )
+            .await
+            .unwrap();
+            CalendarEventParticipantRepository::create(
+                &repo,
+                CalendarEventParticipant {
+                    id: Uuid::new_v4(),
+                    created_at: Utc::now(),
+                    calendar_event_id: event.id,
+                    email: "ada@acme.test".to_string(),
+                    person_id: None,
+                },
+            )
+            .await
+            .unwrap()
+        })
+        .await;
+
+        run_as(member_of(b), async {
+            assert!(matches!(
+                repo.find_by_event_id(participant.calendar_event_id).await,
+                Err(DomainError::NotFound)
+            ));
+            assert!(matches!(
+                CalendarEventParticipantRepository::delete(&repo, participant.id).await,
+                Err(DomainError::NotFound)
+            ));
+        })
+        .await;
+
+        run_as(member_of(a), async {
+            CalendarEventParticipantRepository::delete(&repo, participant.id)
+                .await
+                .unwrap();
+            assert!(repo
+                .find_by_event_id(participant.calendar_event_id)
+                .await
+                .unwrap()
+                .is_empty());
+        })
+        .await;
+
+        // Connected accounts belong to a user, not a workspace
+        let failed = ConnectedAccount {
+            status: ConnectedAccountStatus::Failed,
+            ..account.clone()
+        };
+        ConnectedAccountRepository::update(&repo, failed)
+            .await
+            .unwrap();
+        let stored = ConnectedAccountRepository::find_by_id(&repo, account.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.status, ConnectedAccountStatus::Failed);
+        ConnectedAccountRepository::delete(&repo, account.id)
+            .await
+            .unwrap();
+        assert!(ConnectedAccountRepository::find_all(&repo)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,52 @@
+//! Conversion between domain types and the rows they are stored as.
+//!
+//! Each entity module implements `Mapper` for its domain type next to the `to_domain`
+//! conversion of its `Model`, so both directions of a mapping live side by side. The
+//! generic helpers in `crud` are written against this trait.
+
+use crate::domain::DomainError;
+use sea_orm::*;
+use serde::Serialize;
+
+/// A domain type stored as one row of `Entity`
+pub trait Mapper: Sized + Send {
+    type Entity: EntityTrait<Model = Self::Model>;
+    type Model: ModelTrait<Entity = Self::Entity>
+        + FromQueryResult
+        + IntoActiveModel<Self::ActiveModel>
+        + Send
+        + Sync;
+    type ActiveModel: ActiveModelTrait<Entity = Self::Entity> + ActiveModelBehavior + Send;
+
+    fn from_model(model: Self::Model) -> Result<Self, DomainError>;
+
+    /// The row with every column set
+    fn to_active_model(self) -> Self::ActiveModel;
+
+    /// The row with only `columns` and the primary key set, for an update that leaves the
+    /// other columns as they are stored
+    fn to_changes(self, columns: &[<Self::Entity as EntityTrait>::Column]) -> Self::ActiveModel {
+        let mut model = self.to_active_model();
+        let key: Vec<_> = <Self::Entity as EntityTrait>::PrimaryKey::iter()
+            .map(|key| key.into_column())
+            .collect();
+        for column in <Self::Entity as EntityTrait>::Column::iter() {
+            let kept = key
+                .iter()
+                .chain(columns)
+                .any(|c| c.as_str() == column.as_str());
+            if !kept {
+                model.not_set(column);
+            }
+        }
+        model
+    }
+}
+
+/// Stored form of an enum persisted under its serde name, e.g. `UserState::Active`
+pub fn serde_name<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => name,
+        _ => unreachable!("enums stored by name serialize to a string"),
+    }
+}
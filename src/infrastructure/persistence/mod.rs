@@ -1,2 +1,16 @@
+#[cfg(test)]
+mod benchmark;
+#[cfg(test)]
+mod conformance;
+pub mod connection;
+pub mod crud;
 pub mod entities;
+pub mod errors;
+#[cfg(test)]
+pub mod in_memory_repo;
+pub mod mapper;
+pub mod query;
 pub mod sea_orm_repo;
+pub mod transaction;
+pub mod trash;
+pub mod workspace_scope;
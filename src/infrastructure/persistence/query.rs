@@ -0,0 +1,306 @@
+//! Translates the shared `ListQuery` into SeaORM conditions for the SeaORM repositories. The
+//! parsing and cursor helpers are shared with `InMemoryRepo`, which applies queries to its
+//! own rows.
+//!
+//! Pages are keyset based: rows are ordered by the sort column with the primary key as
+//! tie-breaker, and the cursor carries both values of the last row so the next page starts
+//! right after it, whatever was inserted or deleted in between.
+
+use super::errors::map_db_err;
+use crate::application::ports::query::{Filter, FilterOp, ListQuery, Page, Sort, SortDirection};
+use crate::domain::DomainError;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    Text,
+    Integer,
+    /// RFC 3339
+    Timestamp,
+    Uuid,
+    /// Stored snake_case; the PascalCase variant name is accepted as well
+    Enum,
+}
+
+/// A domain field that can be filtered on, and sorted on when `sortable`
+pub struct Field<C> {
+    pub name: &'static str,
+    pub column: C,
+    pub kind: FieldKind,
+    /// Only for non-null columns: keyset comparisons never match NULL
+    pub sortable: bool,
+}
+
+impl<C> Field<C> {
+    pub fn new(name: &'static str, column: C, kind: FieldKind) -> Self {
+        Self {
+            name,
+            column,
+            kind,
+            sortable: false,
+        }
+    }
+
+    pub fn sortable(self) -> Self {
+        Self {
+            sortable: true,
+            ..self
+        }
+    }
+}
+
+/// Position after the last row of a page, serialized as base64url JSON
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Cursor {
+    /// Sort the cursor was issued for, in `Sort::parse` form
+    sort: String,
+    value: String,
+    id: Uuid,
+}
+
+impl Cursor {
+    fn encode(&self) -> String {
+        // Serializing a struct of strings cannot fail
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    fn decode(raw: &str) -> Result<Self, DomainError> {
+        URL_SAFE_NO_PAD
+            .decode(raw)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .ok_or_else(|| DomainError::Validation("Invalid cursor".to_string()))
+    }
+}
+
+fn sort_key(sort: &Sort) -> String {
+    match sort.direction {
+        SortDirection::Asc => sort.field.clone(),
+        SortDirection::Desc => format!("-{}", sort.field),
+    }
+}
+
+fn field<'a, C>(fields: &'a [Field<C>], name: &str) -> Result<&'a Field<C>, DomainError> {
+    fields
+        .iter()
+        .find(|f| f.name == name)
+        .ok_or_else(|| DomainError::Validation(format!("Unknown field '{}'", name)))
+}
+
+/// `WebForm` -> `web_form`; snake_case input is returned unchanged
+pub(super) fn snake_case(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 4);
+    for (i, c) in value.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 && !out.ends_with('_') {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn parse_value<C>(field: &Field<C>, raw: &str) -> Result<Value, DomainError> {
+    let invalid =
+        |expected: &str| DomainError::Validation(format!("'{}' expects {}", field.name, expected));
+    Ok(match field.kind {
+        FieldKind::Text => Value::from(raw.to_string()),
+        FieldKind::Integer => Value::from(raw.parse::<i32>().map_err(|_| invalid("a number"))?),
+        FieldKind::Timestamp => Value::from(
+            DateTime::parse_from_rfc3339(raw)
+                .map_err(|_| invalid("an RFC 3339 timestamp"))?
+                .with_timezone(&Utc),
+        ),
+        FieldKind::Uuid => Value::from(Uuid::parse_str(raw).map_err(|_| invalid("a UUID"))?),
+        FieldKind::Enum => Value::from(snake_case(raw)),
+    })
+}
+
+/// Inverse of `parse_value` for the sort column of the last row of a page
+fn format_value(value: Value) -> Option<String> {
+    match value {
+        Value::String(Some(s)) => Some(*s),
+        Value::Int(Some(i)) => Some(i.to_string()),
+        Value::ChronoDateTimeUtc(Some(d)) => Some(d.to_rfc3339()),
+        Value::ChronoDateTimeWithTimeZone(Some(d)) => Some(d.with_timezone(&Utc).to_rfc3339()),
+        Value::Uuid(Some(u)) => Some(u.to_string()),
+        _ => None,
+    }
+}
+
+/// Field `filter` applies to and its parsed operand; `Contains` keeps the raw text
+pub(super) fn resolve_filter<'a, C>(
+    fields: &'a [Field<C>],
+    filter: &Filter,
+) -> Result<(&'a Field<C>, Value), DomainError> {
+    let field = field(fields, &filter.field)?;
+    if filter.op == FilterOp::Contains {
+        if field.kind != FieldKind::Text {
+            return Err(DomainError::Validation(format!(
+                "'{}' does not support 'contains'",
+                field.name
+            )));
+        }
+        return Ok((field, Value::from(filter.value.clone())));
+    }
+    Ok((field, parse_value(field, &filter.value)?))
+}
+
+fn filter_condition<C: ColumnTrait>(
+    fields: &[Field<C>],
+    filter: &Filter,
+) -> Result<Condition, DomainError> {
+    let (field, value) = resolve_filter(fields, filter)?;
+    let column = field.column;
+    Ok(match filter.op {
+        FilterOp::Eq => Condition::all().add(column.eq(value)),
+        // NULL <> value is not true in SQL, but an absent value is still "not equal"
+        FilterOp::Neq => Condition::any().add(column.ne(value)).add(column.is_null()),
+        FilterOp::Contains => Condition::all().add(column.contains(filter.value.as_str())),
+        FilterOp::Gt => Condition::all().add(column.gt(value)),
+        FilterOp::Gte => Condition::all().add(column.gte(value)),
+        FilterOp::Lt => Condition::all().add(column.lt(value)),
+        FilterOp::Lte => Condition::all().add(column.lte(value)),
+    })
+}
+
+/// Sort of `query` and the field it orders by, which must be sortable
+pub(super) fn resolve_sort<'a, C>(
+    fields: &'a [Field<C>],
+    query: &ListQuery,
+    default_sort: Sort,
+) -> Result<(Sort, &'a Field<C>), DomainError> {
+    let sort = query.sort.clone().unwrap_or(default_sort);
+    let sort_field = field(fields, &sort.field)?;
+    if !sort_field.sortable {
+        return Err(DomainError::Validation(format!(
+            "Cannot sort by '{}'",
+            sort_field.name
+        )));
+    }
+    Ok((sort, sort_field))
+}
+
+/// Sort value and id of the row the page starts after, when `query` continues a previous page
+pub(super) fn resume_after<C>(
+    query: &ListQuery,
+    sort: &Sort,
+    sort_field: &Field<C>,
+) -> Result<Option<(Value, Uuid)>, DomainError> {
+    let Some(raw) = &query.cursor else {
+        return Ok(None);
+    };
+    let cursor = Cursor::decode(raw)?;
+    if cursor.sort != sort_key(sort) {
+        return Err(DomainError::Validation(
+            "Cursor was issued for a different sort".to_string(),
+        ));
+    }
+    Ok(Some((parse_value(sort_field, &cursor.value)?, cursor.id)))
+}
+
+pub(super) fn next_cursor(sort: &Sort, value: Value, id: Uuid) -> Option<String> {
+    let value = format_value(value)?;
+    Some(
+        Cursor {
+            sort: sort_key(sort),
+            value,
+            id,
+        }
+        .encode(),
+    )
+}
+
+/// Applies `query` to `select` and fetches one page. `select` carries the scoping the
+/// repository needs (workspace, soft delete); `default_sort` applies when the query has none.
+pub async fn fetch_page<E>(
+    db: &impl ConnectionTrait,
+    select: Select<E>,
+    query: &ListQuery,
+    fields: &[Field<E::Column>],
+    id_column: E::Column,
+    default_sort: Sort,
+) -> Result<Page<E::Model>, DomainError>
+where
+    E: EntityTrait,
+{
+    let mut select = select;
+    for filter in &query.filters {
+        select = select.filter(filter_condition(fields, filter)?);
+    }
+
+    let (sort, sort_field) = resolve_sort(fields, query, default_sort)?;
+    let column = sort_field.column;
+
+    if let Some((value, id)) = resume_after(query, &sort, sort_field)? {
+        let after = match sort.direction {
+            SortDirection::Asc => Condition::any()
+                .add(column.gt(value.clone()))
+                .add(Condition::all().add(column.eq(value)).add(id_column.gt(id))),
+            SortDirection::Desc => Condition::any()
+                .add(column.lt(value.clone()))
+                .add(Condition::all().add(column.eq(value)).add(id_column.lt(id))),
+        };
+        select = select.filter(after);
+    }
+
+    let order = match sort.direction {
+        SortDirection::Asc => Order::Asc,
+        SortDirection::Desc => Order::Desc,
+    };
+    let limit = query.page_size();
+    // One extra row tells whether there is a next page
+    let mut models = select
+        .order_by(column, order.clone())
+        .order_by(id_column, order)
+        .limit(limit + 1)
+        .all(db)
+        .await
+        .map_err(map_db_err)?;
+
+    let mut next = None;
+    if models.len() as u64 > limit {
+        models.truncate(limit as usize);
+        if let Some(last) = models.last() {
+            if let Value::Uuid(Some(id)) = last.get(id_column) {
+                next = next_cursor(&sort, last.get(column), *id);
+            }
+        }
+    }
+
+    Ok(Page {
+        items: models,
+        next_cursor: next,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_round_trip() {
+        let cursor = Cursor {
+            sort: "-created_at".to_string(),
+            value: "2024-01-30T10:00:00.123456+00:00".to_string(),
+            id: Uuid::new_v4(),
+        };
+        assert_eq!(Cursor::decode(&cursor.encode()).unwrap(), cursor);
+        assert!(Cursor::decode("not a cursor").is_err());
+    }
+
+    #[test]
+    fn enum_values_are_snake_cased() {
+        assert_eq!(snake_case("WebForm"), "web_form");
+        assert_eq!(snake_case("web_form"), "web_form");
+        assert_eq!(snake_case("New"), "new");
+    }
+}
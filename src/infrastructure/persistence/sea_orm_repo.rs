@@ -1,1581 +0,0 @@
-use super::entities::opportunity::{self, Entity as OpportunityEntity};
-use crate::application::ports::output::{
-    CalendarEventRepository, EmailRepository, EmailTemplateRepository, LeadRepository,
-    MetadataRepository, OpportunityRepository, TimelineActivityRepository, UserRepository,
-    ViewRepository, WorkflowRepository, WorkspaceRepository,
-};
-use crate::domain::states::{LeadSource, LeadStatus};
-use crate::domain::{
-    CalendarEvent, DomainError, Email, EmailTemplate, Lead, Opportunity, OpportunityStage, Person,
-    TimelineActivity, User, Workflow, Workspace, WorkspaceMember,
-};
-use crate::infrastructure::persistence::entities::{
-    custom_object_data, person, user, workspace, workspace_member,
-};
-use async_trait::async_trait;
-use sea_orm::*;
-use uuid::Uuid;
-
-pub struct SeaOrmRepo {
-    pub db: DatabaseConnection,
-}
-
-#[async_trait]
-impl crate::application::ports::output::PersonRepository for SeaOrmRepo {
-    async fn find_by_email(&self, email: &str) -> Result<Option<Person>, DomainError> {
-        let model = person::Entity::find()
-            .filter(person::Column::Email.eq(email))
-            .filter(person::Column::DeletedAt.is_null())
-            .one(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(model.map(|m| m.to_domain()))
-    }
-
-    async fn create(&self, person: Person) -> Result<Person, DomainError> {
-        let model = person::ActiveModel {
-            id: Set(person.id),
-            name: Set(person.name),
-            email: Set(person.email),
-            position: Set(person.position),
-            company_id: Set(person.company_id),
-            created_at: Set(person.created_at.into()),
-            updated_at: Set(person.updated_at.into()),
-            deleted_at: Set(None),
-            workspace_id: Set(person.workspace_id),
-        };
-
-        let result = model
-            .insert(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(result.to_domain())
-    }
-
-    async fn find_all(&self) -> Result<Vec<Person>, DomainError> {
-        let models = person::Entity::find()
-            .filter(person::Column::DeletedAt.is_null())
-            .all(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(models.into_iter().map(|m| m.to_domain()).collect())
-    }
-
-    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        // Soft delete
-        let model = person::ActiveModel {
-            id: Set(id),
-            deleted_at: Set(Some(chrono::Utc::now().into())),
-            ..Default::default()
-        };
-        model
-            .update(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(())
-    }
-}
-
-#[async_trait]
-impl OpportunityRepository for SeaOrmRepo {
-    async fn find_all(&self) -> Result<Vec<Opportunity>, DomainError> {
-        let models = OpportunityEntity::find()
-            .filter(opportunity::Column::DeletedAt.is_null())
-            .all(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(models.into_iter().map(|m| m.to_domain()).collect())
-    }
-
-    async fn find_by_id(&self, id: Uuid) -> Result<Option<Opportunity>, DomainError> {
-        let model = OpportunityEntity::find_by_id(id)
-            .one(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(model.map(|m| m.to_domain()))
-    }
-
-    async fn create(&self, opportunity: Opportunity) -> Result<Opportunity, DomainError> {
-        let stage_str = match opportunity.stage {
-            OpportunityStage::Prospecting => "Prospecting",
-            OpportunityStage::Qualification => "Qualification",
-            OpportunityStage::Negotiation => "Negotiation",
-            OpportunityStage::Won => "Won",
-            OpportunityStage::Lost => "Lost",
-        };
-
-        let model = opportunity::ActiveModel {
-            id: Set(opportunity.id),
-            created_at: Set(opportunity.created_at.into()),
-            updated_at: Set(opportunity.updated_at.into()),
-            deleted_at: Set(None),
-            name: Set(opportunity.name),
-            stage: Set(stage_str.to_string()),
-            amount_micros: Set(opportunity.amount_micros),
-            currency_code: Set(opportunity.currency_code),
-            close_date: Set(opportunity.close_date.map(|d| d.into())),
-            company_id: Set(opportunity.company_id),
-            point_of_contact_id: Set(opportunity.point_of_contact_id),
-            owner_id: Set(opportunity.owner_id),
-            workspace_id: Set(opportunity.workspace_id),
-        };
-
-        let result = model
-            .insert(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(result.to_domain())
-    }
-
-    async fn update(&self, opportunity: Opportunity) -> Result<Opportunity, DomainError> {
-        let stage_str = match opportunity.stage {
-            OpportunityStage::Prospecting => "Prospecting",
-            OpportunityStage::Qualification => "Qualification",
-            OpportunityStage::Negotiation => "Negotiation",
-            OpportunityStage::Won => "Won",
-            OpportunityStage::Lost => "Lost",
-        };
-
-        let model = opportunity::ActiveModel {
-            id: Set(opportunity.id),
-            updated_at: Set(chrono::Utc::now().into()),
-            name: Set(opportunity.name),
-            stage: Set(stage_str.to_string()),
-            amount_micros: Set(opportunity.amount_micros),
-            currency_code: Set(opportunity.currency_code),
-            close_date: Set(opportunity.close_date.map(|d| d.into())),
-            company_id: Set(opportunity.company_id),
-            point_of_contact_id: Set(opportunity.point_of_contact_id),
-            owner_id: Set(opportunity.owner_id),
-            ..Default::default()
-        };
-
-        let result = model
-            .update(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(result.to_domain())
-    }
-
-    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        let model = opportunity::ActiveModel {
-            id: Set(id),
-            deleted_at: Set(Some(chrono::Utc::now().into())),
-            ..Default::default()
-        };
-        model
-            .update(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(())
-    }
-}
-
-#[async_trait]
-impl UserRepository for SeaOrmRepo {
-    async fn find_by_email(&self, email: &str) -> Result<Option<User>, DomainError> {
-        let model = user::Entity::find()
-            .filter(user::Column::Email.eq(email))
-            .one(&self.db)
-            .await
-            .map_err(|e| DomainError::Validation(e.to_string()))?;
-        Ok(model.map(|m| m.to_domain()))
-    }
-
-    async fn create(&self, user: User) -> Result<User, DomainError> {
-        let state_str = serde_json::to_value(user.state)
-            .unwrap()
-            .as_str()
-            .unwrap()
-            .to_string();
-
-        let active_model = user::ActiveModel {
-            id: Set(user.id),
-            email: Set(user.email.clone()),
-            password_hash: Set(user.password_hash.clone()),
-            state: Set(state_str),
-            created_at: Set(user.created_at.into()),
-            updated_at: Set(user.updated_at.into()),
-        };
-
-        let result = active_model
-            .insert(&self.db)
-            .await
-            .map_err(|e| DomainError::Validation(e.to_string()))?;
-        Ok(result.to_domain())
-    }
-}
-
-#[async_trait]
-impl WorkspaceRepository for SeaOrmRepo {
-    async fn create(&self, workspace: Workspace) -> Result<Workspace, DomainError> {
-        let state_str = serde_json::to_value(workspace.state)
-            .unwrap()
-            .as_str()
-            .unwrap()
-            .to_string();
-
-        let model = workspace::ActiveModel {
-            id: Set(workspace.id),
-            subdomain: Set(workspace.subdomain),
-            state: Set(state_str),
-            created_at: Set(workspace.created_at.into()),
-            updated_at: Set(workspace.updated_at.into()),
-        };
-
-        let result = model
-            .insert(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-
-        Ok(result.to_domain())
-    }
-
-    async fn find_by_subdomain(&self, subdomain: &str) -> Result<Option<Workspace>, DomainError> {
-        let result = workspace::Entity::find()
-            .filter(workspace::Column::Subdomain.eq(subdomain))
-            .one(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-
-        Ok(result.map(|m| m.to_domain()))
-    }
-
-    async fn add_member(&self, member: WorkspaceMember) -> Result<WorkspaceMember, DomainError> {
-        let model = workspace_member::ActiveModel {
-            id: Set(member.id),
-            user_id: Set(member.user_id),
-            workspace_id: Set(member.workspace_id),
-            role: Set(member.role),
-            name: Set(member.name),
-            created_at: Set(member.created_at.into()),
-            updated_at: Set(member.updated_at.into()),
-        };
-
-        let result = model
-            .insert(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-
-        Ok(result.to_domain())
-    }
-}
-
-#[async_trait]
-impl crate::application::ports::output::CompanyRepository for SeaOrmRepo {
-    async fn find_all(&self) -> Result<Vec<crate::domain::Company>, DomainError> {
-        use crate::infrastructure::persistence::entities::company;
-        let models = company::Entity::find()
-            .filter(company::Column::DeletedAt.is_null())
-            .all(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(models.into_iter().map(|m| m.to_domain()).collect())
-    }
-
-    async fn find_by_id(&self, id: Uuid) -> Result<Option<crate::domain::Company>, DomainError> {
-        use crate::infrastructure::persistence::entities::company;
-        let model = company::Entity::find_by_id(id)
-            .one(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(model.map(|m| m.to_domain()))
-    }
-
-    async fn create(
-        &self,
-        company: crate::domain::Company,
-    ) -> Result<crate::domain::Company, DomainError> {
-        use crate::infrastructure::persistence::entities::company;
-        let model = company::ActiveModel {
-            id: Set(company.id),
-            created_at: Set(company.created_at.into()),
-            updated_at: Set(company.updated_at.into()),
-            deleted_at: Set(None),
-            name: Set(company.name),
-            domain_name: Set(company.domain_name),
-            address: Set(company.address),
-            employees_count: Set(company.employees_count),
-            workspace_id: Set(company.workspace_id),
-        };
-
-        let result = model
-            .insert(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(result.to_domain())
-    }
-
-    async fn update(
-        &self,
-        company: crate::domain::Company,
-    ) -> Result<crate::domain::Company, DomainError> {
-        use crate::infrastructure::persistence::entities::company;
-        let model = company::ActiveModel {
-            id: Set(company.id),
-            updated_at: Set(chrono::Utc::now().into()), // refresh updated_at
-            name: Set(company.name),
-            domain_name: Set(company.domain_name),
-            address: Set(company.address),
-            employees_count: Set(company.employees_count),
-            ..Default::default()
-        };
-
-        let result = model
-            .update(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(result.to_domain())
-    }
-
-    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        use crate::infrastructure::persistence::entities::company;
-        let model = company::ActiveModel {
-            id: Set(id),
-            deleted_at: Set(Some(chrono::Utc::now().into())),
-            ..Default::default()
-        };
-        model
-            .update(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(())
-    }
-}
-#[async_trait]
-impl crate::application::ports::output::TaskRepository for SeaOrmRepo {
-    async fn find_all(&self) -> Result<Vec<crate::domain::Task>, DomainError> {
-        use crate::infrastructure::persistence::entities::task;
-        let models = task::Entity::find()
-            .filter(task::Column::DeletedAt.is_null())
-            .all(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(models.into_iter().map(|m| m.to_domain()).collect())
-    }
-
-    async fn find_by_id(&self, id: Uuid) -> Result<Option<crate::domain::Task>, DomainError> {
-        use crate::infrastructure::persistence::entities::task;
-        let model = task::Entity::find_by_id(id)
-            .one(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(model.map(|m| m.to_domain()))
-    }
-
-    async fn create(&self, task: crate::domain::Task) -> Result<crate::domain::Task, DomainError> {
-        use crate::infrastructure::persistence::entities::task;
-        let status_str = match task.status {
-            crate::domain::states::TaskStatus::Todo => "TODO",
-            crate::domain::states::TaskStatus::InProgress => "IN_PROGRESS",
-            crate::domain::states::TaskStatus::Done => "DONE",
-        };
-
-        let model = task::ActiveModel {
-            id: Set(task.id),
-            created_at: Set(task.created_at.into()),
-            updated_at: Set(task.updated_at.into()),
-            deleted_at: Set(None),
-            title: Set(task.title),
-            body: Set(task.body),
-            status: Set(status_str.to_string()),
-            position: Set(task.position),
-            assignee_id: Set(task.assignee_id),
-            due_at: Set(task.due_at.map(|d| d.into())),
-            workspace_id: Set(task.workspace_id),
-        };
-
-        let result = model
-            .insert(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(result.to_domain())
-    }
-
-    async fn update(&self, task: crate::domain::Task) -> Result<crate::domain::Task, DomainError> {
-        use crate::infrastructure::persistence::entities::task;
-        let status_str = match task.status {
-            crate::domain::states::TaskStatus::Todo => "TODO",
-            crate::domain::states::TaskStatus::InProgress => "IN_PROGRESS",
-            crate::domain::states::TaskStatus::Done => "DONE",
-        };
-
-        let model = task::ActiveModel {
-            id: Set(task.id),
-            updated_at: Set(chrono::Utc::now().into()),
-            title: Set(task.title),
-            body: Set(task.body),
-            status: Set(status_str.to_string()),
-            position: Set(task.position),
-            assignee_id: Set(task.assignee_id),
-            due_at: Set(task.due_at.map(|d| d.into())),
-            ..Default::default()
-        };
-
-        let result = model
-            .update(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(result.to_domain())
-    }
-
-    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        use crate::infrastructure::persistence::entities::task;
-        let model = task::ActiveModel {
-            id: Set(id),
-            deleted_at: Set(Some(chrono::Utc::now().into())),
-            ..Default::default()
-        };
-        model
-            .update(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(())
-    }
-}
-
-#[async_trait]
-impl crate::application::ports::output::NoteRepository for SeaOrmRepo {
-    async fn find_all(&self) -> Result<Vec<crate::domain::Note>, DomainError> {
-        use crate::infrastructure::persistence::entities::note;
-        let models = note::Entity::find()
-            .filter(note::Column::DeletedAt.is_null())
-            .all(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(models.into_iter().map(|m| m.to_domain()).collect())
-    }
-
-    async fn find_by_id(&self, id: Uuid) -> Result<Option<crate::domain::Note>, DomainError> {
-        use crate::infrastructure::persistence::entities::note;
-        let model = note::Entity::find_by_id(id)
-            .one(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(model.map(|m| m.to_domain()))
-    }
-
-    async fn create(&self, note: crate::domain::Note) -> Result<crate::domain::Note, DomainError> {
-        use crate::infrastructure::persistence::entities::note;
-        let model = note::ActiveModel {
-            id: Set(note.id),
-            created_at: Set(note.created_at.into()),
-            updated_at: Set(note.updated_at.into()),
-            deleted_at: Set(None),
-            title: Set(note.title),
-            body_v2: Set(note.body_v2),
-            position: Set(note.position),
-            workspace_id: Set(note.workspace_id),
-        };
-
-        let result = model
-            .insert(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(result.to_domain())
-    }
-
-    async fn update(&self, note: crate::domain::Note) -> Result<crate::domain::Note, DomainError> {
-        use crate::infrastructure::persistence::entities::note;
-        let model = note::ActiveModel {
-            id: Set(note.id),
-            updated_at: Set(chrono::Utc::now().into()),
-            title: Set(note.title),
-            body_v2: Set(note.body_v2),
-            position: Set(note.position),
-            ..Default::default()
-        };
-
-        let result = model
-            .update(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(result.to_domain())
-    }
-
-    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        use crate::infrastructure::persistence::entities::note;
-        let model = note::ActiveModel {
-            id: Set(id),
-            deleted_at: Set(Some(chrono::Utc::now().into())),
-            ..Default::default()
-        };
-        model
-            .update(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(())
-    }
-}
-
-#[async_trait]
-impl crate::application::ports::output::TaskTargetRepository for SeaOrmRepo {
-    async fn find_by_task_id(
-        &self,
-        task_id: Uuid,
-    ) -> Result<Vec<crate::domain::TaskTarget>, DomainError> {
-        use crate::infrastructure::persistence::entities::task_target;
-        let models = task_target::Entity::find()
-            .filter(task_target::Column::TaskId.eq(task_id))
-            .all(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(models.into_iter().map(|m| m.to_domain()).collect())
-    }
-
-    async fn create(
-        &self,
-        task_target: crate::domain::TaskTarget,
-    ) -> Result<crate::domain::TaskTarget, DomainError> {
-        use crate::infrastructure::persistence::entities::task_target;
-        let model = task_target::ActiveModel {
-            id: Set(task_target.id),
-            created_at: Set(task_target.created_at.into()),
-            task_id: Set(task_target.task_id),
-            person_id: Set(task_target.person_id),
-            company_id: Set(task_target.company_id),
-            opportunity_id: Set(task_target.opportunity_id),
-        };
-
-        let result = model
-            .insert(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(result.to_domain())
-    }
-
-    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        use crate::infrastructure::persistence::entities::task_target;
-        task_target::Entity::delete_by_id(id)
-            .exec(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(())
-    }
-}
-
-#[async_trait]
-impl WorkflowRepository for SeaOrmRepo {
-    async fn find_all(&self) -> Result<Vec<Workflow>, DomainError> {
-        use crate::infrastructure::persistence::entities::workflow;
-        let models = workflow::Entity::find()
-            .all(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(models.into_iter().map(|m| m.to_domain()).collect())
-    }
-
-    async fn find_by_id(&self, id: Uuid) -> Result<Option<Workflow>, DomainError> {
-        use crate::infrastructure::persistence::entities::workflow;
-        let model = workflow::Entity::find_by_id(id)
-            .one(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(model.map(|m| m.to_domain()))
-    }
-
-    async fn create(&self, workflow: Workflow) -> Result<Workflow, DomainError> {
-        use crate::infrastructure::persistence::entities::workflow;
-        let model = workflow::ActiveModel {
-            id: Set(workflow.id),
-            name: Set(workflow.name),
-            last_published_version_id: Set(workflow.last_published_version_id),
-            created_at: Set(workflow.created_at.into()),
-            updated_at: Set(workflow.updated_at.into()),
-            workspace_id: Set(workflow.workspace_id),
-        };
-
-        let result = model
-            .insert(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(result.to_domain())
-    }
-
-    async fn update(&self, workflow: Workflow) -> Result<Workflow, DomainError> {
-        use crate::infrastructure::persistence::entities::workflow;
-        let model = workflow::ActiveModel {
-            id: Set(workflow.id),
-            name: Set(workflow.name),
-            last_published_version_id: Set(workflow.last_published_version_id),
-            updated_at: Set(workflow.updated_at.into()),
-            ..Default::default()
-        };
-
-        let result = model
-            .update(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(result.to_domain())
-    }
-
-    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        use crate::infrastructure::persistence::entities::workflow;
-        workflow::Entity::delete_by_id(id)
-            .exec(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(())
-    }
-}
-
-#[async_trait]
-impl CalendarEventRepository for SeaOrmRepo {
-    async fn find_all(&self) -> Result<Vec<CalendarEvent>, DomainError> {
-        use crate::infrastructure::persistence::entities::calendar_event;
-        let models = calendar_event::Entity::find()
-            .all(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(models.into_iter().map(|m| m.to_domain()).collect())
-    }
-
-    async fn find_by_id(&self, id: Uuid) -> Result<Option<CalendarEvent>, DomainError> {
-        use crate::infrastructure::persistence::entities::calendar_event;
-        let model = calendar_event::Entity::find_by_id(id)
-            .one(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(model.map(|m| m.to_domain()))
-    }
-
-    async fn create(&self, event: CalendarEvent) -> Result<CalendarEvent, DomainError> {
-        use crate::infrastructure::persistence::entities::calendar_event;
-        let model = calendar_event::ActiveModel {
-            id: Set(event.id),
-            connected_account_id: Set(event.connected_account_id),
-            title: Set(event.title),
-            start_time: Set(event.start_time.into()),
-            end_time: Set(event.end_time.into()),
-            description: Set(event.description),
-            created_at: Set(event.created_at.into()),
-            updated_at: Set(event.updated_at.into()),
-            workspace_id: Set(event.workspace_id),
-        };
-
-        let result = model
-            .insert(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(result.to_domain())
-    }
-
-    async fn update(&self, event: CalendarEvent) -> Result<CalendarEvent, DomainError> {
-        use crate::infrastructure::persistence::entities::calendar_event;
-        let model = calendar_event::ActiveModel {
-            id: Set(event.id),
-            connected_account_id: Set(event.connected_account_id),
-            title: Set(event.title),
-            start_time: Set(event.start_time.into()),
-            end_time: Set(event.end_time.into()),
-            description: Set(event.description),
-            updated_at: Set(event.updated_at.into()),
-            ..Default::default()
-        };
-
-        let result = model
-            .update(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(result.to_domain())
-    }
-
-    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        use crate::infrastructure::persistence::entities::calendar_event;
-        calendar_event::Entity::delete_by_id(id)
-            .exec(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(())
-    }
-}
-
-#[async_trait]
-impl TimelineActivityRepository for SeaOrmRepo {
-    async fn find_all(&self) -> Result<Vec<TimelineActivity>, DomainError> {
-        use crate::infrastructure::persistence::entities::timeline_activity;
-        let models = timeline_activity::Entity::find()
-            .order_by_desc(timeline_activity::Column::CreatedAt)
-            .all(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(models.into_iter().map(|m| m.to_domain()).collect())
-    }
-
-    async fn find_by_person_id(
-        &self,
-        person_id: Uuid,
-    ) -> Result<Vec<TimelineActivity>, DomainError> {
-        use crate::infrastructure::persistence::entities::timeline_activity;
-        let models = timeline_activity::Entity::find()
-            .filter(timeline_activity::Column::PersonId.eq(person_id))
-            .order_by_desc(timeline_activity::Column::CreatedAt)
-            .all(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(models.into_iter().map(|m| m.to_domain()).collect())
-    }
-
-    async fn find_by_company_id(
-        &self,
-        company_id: Uuid,
-    ) -> Result<Vec<TimelineActivity>, DomainError> {
-        use crate::infrastructure::persistence::entities::timeline_activity;
-        let models = timeline_activity::Entity::find()
-            .filter(timeline_activity::Column::CompanyId.eq(company_id))
-            .order_by_desc(timeline_activity::Column::CreatedAt)
-            .all(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(models.into_iter().map(|m| m.to_domain()).collect())
-    }
-
-    async fn find_by_opportunity_id(
-        &self,
-        opportunity_id: Uuid,
-    ) -> Result<Vec<TimelineActivity>, DomainError> {
-        use crate::infrastructure::persistence::entities::timeline_activity;
-        let models = timeline_activity::Entity::find()
-            .filter(timeline_activity::Column::OpportunityId.eq(opportunity_id))
-            .order_by_desc(timeline_activity::Column::CreatedAt)
-            .all(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(models.into_iter().map(|m| m.to_domain()).collect())
-    }
-
-    async fn find_by_task_id(&self, task_id: Uuid) -> Result<Vec<TimelineActivity>, DomainError> {
-        use crate::infrastructure::persistence::entities::timeline_activity;
-        let models = timeline_activity::Entity::find()
-            .filter(timeline_activity::Column::TaskId.eq(task_id))
-            .order_by_desc(timeline_activity::Column::CreatedAt)
-            .all(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(models.into_iter().map(|m| m.to_domain()).collect())
-    }
-
-    async fn create(&self, activity: TimelineActivity) -> Result<TimelineActivity, DomainError> {
-        use crate::infrastructure::persistence::entities::timeline_activity;
-        let model = timeline_activity::ActiveModel {
-            id: Set(activity.id),
-            created_at: Set(activity.created_at.into()),
-            name: Set(activity.name),
-            workspace_member_id: Set(activity.workspace_member_id),
-            person_id: Set(activity.person_id),
-            company_id: Set(activity.company_id),
-            opportunity_id: Set(activity.opportunity_id),
-            task_id: Set(activity.task_id),
-            note_id: Set(activity.note_id),
-            calendar_event_id: Set(activity.calendar_event_id),
-            workflow_id: Set(activity.workflow_id),
-            workspace_id: Set(activity.workspace_id),
-        };
-
-        let result = model
-            .insert(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(result.to_domain())
-    }
-
-    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        use crate::infrastructure::persistence::entities::timeline_activity;
-        timeline_activity::Entity::delete_by_id(id)
-            .exec(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(())
-    }
-}
-
-#[async_trait]
-impl EmailRepository for SeaOrmRepo {
-    async fn find_all(&self) -> Result<Vec<Email>, DomainError> {
-        use crate::infrastructure::persistence::entities::email;
-        let models = email::Entity::find()
-            .order_by_desc(email::Column::CreatedAt)
-            .all(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(models.into_iter().map(|m| m.to_domain()).collect())
-    }
-
-    async fn find_by_id(&self, id: Uuid) -> Result<Option<Email>, DomainError> {
-        use crate::infrastructure::persistence::entities::email;
-        let model = email::Entity::find_by_id(id)
-            .one(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(model.map(|m| m.to_domain()))
-    }
-
-    async fn find_by_person_id(&self, person_id: Uuid) -> Result<Vec<Email>, DomainError> {
-        use crate::infrastructure::persistence::entities::email;
-        let models = email::Entity::find()
-            .filter(email::Column::PersonId.eq(person_id))
-            .order_by_desc(email::Column::CreatedAt)
-            .all(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(models.into_iter().map(|m| m.to_domain()).collect())
-    }
-
-    async fn find_by_company_id(&self, company_id: Uuid) -> Result<Vec<Email>, DomainError> {
-        use crate::infrastructure::persistence::entities::email;
-        let models = email::Entity::find()
-            .filter(email::Column::CompanyId.eq(company_id))
-            .order_by_desc(email::Column::CreatedAt)
-            .all(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(models.into_iter().map(|m| m.to_domain()).collect())
-    }
-
-    async fn find_by_opportunity_id(
-        &self,
-        opportunity_id: Uuid,
-    ) -> Result<Vec<Email>, DomainError> {
-        use crate::infrastructure::persistence::entities::email;
-        let models = email::Entity::find()
-            .filter(email::Column::OpportunityId.eq(opportunity_id))
-            .order_by_desc(email::Column::CreatedAt)
-            .all(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(models.into_iter().map(|m| m.to_domain()).collect())
-    }
-
-    async fn find_pending(&self) -> Result<Vec<Email>, DomainError> {
-        use crate::infrastructure::persistence::entities::email;
-        let models = email::Entity::find()
-            .filter(email::Column::Status.eq("pending"))
-            .order_by_asc(email::Column::CreatedAt)
-            .all(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(models.into_iter().map(|m| m.to_domain()).collect())
-    }
-
-    async fn create(&self, email: Email) -> Result<Email, DomainError> {
-        use crate::domain::states::{EmailDirection, EmailStatus};
-        use crate::infrastructure::persistence::entities::email;
-
-        let direction_str = match email.direction {
-            EmailDirection::Outbound => "outbound",
-            EmailDirection::Inbound => "inbound",
-        };
-
-        let status_str = match email.status {
-            EmailStatus::Pending => "pending",
-            EmailStatus::Sent => "sent",
-            EmailStatus::Failed => "failed",
-            EmailStatus::Received => "received",
-        };
-
-        let cc_emails_json = email
-            .cc_emails
-            .as_ref()
-            .map(|cc| serde_json::to_value(cc).ok())
-            .flatten();
-
-        let bcc_emails_json = email
-            .bcc_emails
-            .as_ref()
-            .map(|bcc| serde_json::to_value(bcc).ok())
-            .flatten();
-
-        let model = email::ActiveModel {
-            id: Set(email.id),
-            created_at: Set(email.created_at.into()),
-            updated_at: Set(email.updated_at.into()),
-            direction: Set(direction_str.to_string()),
-            status: Set(status_str.to_string()),
-            from_email: Set(email.from_email),
-            to_email: Set(email.to_email),
-            cc_emails: Set(cc_emails_json),
-            bcc_emails: Set(bcc_emails_json),
-            subject: Set(email.subject),
-            body_text: Set(email.body_text),
-            body_html: Set(email.body_html),
-            sent_at: Set(email.sent_at.map(|d| d.into())),
-            failed_at: Set(email.failed_at.map(|d| d.into())),
-            error_message: Set(email.error_message),
-            email_template_id: Set(email.email_template_id),
-            timeline_activity_id: Set(email.timeline_activity_id),
-            person_id: Set(email.person_id),
-            company_id: Set(email.company_id),
-            opportunity_id: Set(email.opportunity_id),
-            task_id: Set(email.task_id),
-            workflow_id: Set(email.workflow_id),
-            workflow_run_id: Set(email.workflow_run_id),
-            metadata: Set(email.metadata),
-            workspace_id: Set(email.workspace_id),
-        };
-
-        let result = model
-            .insert(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(result.to_domain())
-    }
-
-    async fn update(&self, email: Email) -> Result<Email, DomainError> {
-        use crate::domain::states::{EmailDirection, EmailStatus};
-        use crate::infrastructure::persistence::entities::email;
-
-        let direction_str = match email.direction {
-            EmailDirection::Outbound => "outbound",
-            EmailDirection::Inbound => "inbound",
-        };
-
-        let status_str = match email.status {
-            EmailStatus::Pending => "pending",
-            EmailStatus::Sent => "sent",
-            EmailStatus::Failed => "failed",
-            EmailStatus::Received => "received",
-        };
-
-        let cc_emails_json = email
-            .cc_emails
-            .as_ref()
-            .map(|cc| serde_json::to_value(cc).ok())
-            .flatten();
-
-        let bcc_emails_json = email
-            .bcc_emails
-            .as_ref()
-            .map(|bcc| serde_json::to_value(bcc).ok())
-            .flatten();
-
-        let model = email::ActiveModel {
-            id: Set(email.id),
-            created_at: Set(email.created_at.into()),
-            updated_at: Set(chrono::Utc::now().into()),
-            direction: Set(direction_str.to_string()),
-            status: Set(status_str.to_string()),
-            from_email: Set(email.from_email),
-            to_email: Set(email.to_email),
-            cc_emails: Set(cc_emails_json),
-            bcc_emails: Set(bcc_emails_json),
-            subject: Set(email.subject),
-            body_text: Set(email.body_text),
-            body_html: Set(email.body_html),
-            sent_at: Set(email.sent_at.map(|d| d.into())),
-            failed_at: Set(email.failed_at.map(|d| d.into())),
-            error_message: Set(email.error_message),
-            email_template_id: Set(email.email_template_id),
-            timeline_activity_id: Set(email.timeline_activity_id),
-            person_id: Set(email.person_id),
-            company_id: Set(email.company_id),
-            opportunity_id: Set(email.opportunity_id),
-            task_id: Set(email.task_id),
-            workflow_id: Set(email.workflow_id),
-            workflow_run_id: Set(email.workflow_run_id),
-            metadata: Set(email.metadata),
-            workspace_id: Set(email.workspace_id),
-        };
-
-        let result = model
-            .update(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(result.to_domain())
-    }
-
-    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        use crate::infrastructure::persistence::entities::email;
-        email::Entity::delete_by_id(id)
-            .exec(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(())
-    }
-}
-
-#[async_trait]
-impl EmailTemplateRepository for SeaOrmRepo {
-    async fn find_all(&self) -> Result<Vec<EmailTemplate>, DomainError> {
-        use crate::infrastructure::persistence::entities::email_template;
-        let models = email_template::Entity::find()
-            .order_by_asc(email_template::Column::Name)
-            .all(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(models.into_iter().map(|m| m.to_domain()).collect())
-    }
-
-    async fn find_by_id(&self, id: Uuid) -> Result<Option<EmailTemplate>, DomainError> {
-        use crate::infrastructure::persistence::entities::email_template;
-        let model = email_template::Entity::find_by_id(id)
-            .one(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(model.map(|m| m.to_domain()))
-    }
-
-    async fn find_by_name(&self, name: &str) -> Result<Option<EmailTemplate>, DomainError> {
-        use crate::infrastructure::persistence::entities::email_template;
-        let model = email_template::Entity::find()
-            .filter(email_template::Column::Name.eq(name))
-            .one(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(model.map(|m| m.to_domain()))
-    }
-
-    async fn create(&self, template: EmailTemplate) -> Result<EmailTemplate, DomainError> {
-        use crate::infrastructure::persistence::entities::email_template;
-        let model = email_template::ActiveModel {
-            id: Set(template.id),
-            created_at: Set(template.created_at.into()),
-            updated_at: Set(template.updated_at.into()),
-            name: Set(template.name),
-            subject: Set(template.subject),
-            body_text: Set(template.body_text),
-            body_html: Set(template.body_html),
-            category: Set(template.category),
-        };
-
-        let result = model
-            .insert(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(result.to_domain())
-    }
-
-    async fn update(&self, template: EmailTemplate) -> Result<EmailTemplate, DomainError> {
-        use crate::infrastructure::persistence::entities::email_template;
-        let model = email_template::ActiveModel {
-            id: Set(template.id),
-            created_at: Set(template.created_at.into()),
-            updated_at: Set(chrono::Utc::now().into()),
-            name: Set(template.name),
-            subject: Set(template.subject),
-            body_text: Set(template.body_text),
-            body_html: Set(template.body_html),
-            category: Set(template.category),
-        };
-
-        let result = model
-            .update(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(result.to_domain())
-    }
-
-    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        use crate::infrastructure::persistence::entities::email_template;
-        email_template::Entity::delete_by_id(id)
-            .exec(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(())
-    }
-}
-
-#[async_trait]
-impl LeadRepository for SeaOrmRepo {
-    async fn find_all(&self) -> Result<Vec<Lead>, DomainError> {
-        use crate::infrastructure::persistence::entities::lead;
-        let models = lead::Entity::find()
-            .filter(lead::Column::DeletedAt.is_null())
-            .order_by_desc(lead::Column::Score)
-            .order_by_desc(lead::Column::CreatedAt)
-            .all(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(models.into_iter().map(|m| m.to_domain()).collect())
-    }
-
-    async fn find_by_id(&self, id: Uuid) -> Result<Option<Lead>, DomainError> {
-        use crate::infrastructure::persistence::entities::lead;
-        let model = lead::Entity::find_by_id(id)
-            .filter(lead::Column::DeletedAt.is_null())
-            .one(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(model.map(|m| m.to_domain()))
-    }
-
-    async fn find_by_email(&self, email: &str) -> Result<Option<Lead>, DomainError> {
-        use crate::infrastructure::persistence::entities::lead;
-        let model = lead::Entity::find()
-            .filter(lead::Column::Email.eq(email))
-            .filter(lead::Column::DeletedAt.is_null())
-            .one(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(model.map(|m| m.to_domain()))
-    }
-
-    async fn find_by_status(&self, status: LeadStatus) -> Result<Vec<Lead>, DomainError> {
-        use crate::infrastructure::persistence::entities::lead;
-        let status_str = match status {
-            LeadStatus::New => "new",
-            LeadStatus::Contacted => "contacted",
-            LeadStatus::Qualified => "qualified",
-            LeadStatus::Unqualified => "unqualified",
-            LeadStatus::Converted => "converted",
-        };
-        let models = lead::Entity::find()
-            .filter(lead::Column::Status.eq(status_str))
-            .filter(lead::Column::DeletedAt.is_null())
-            .order_by_desc(lead::Column::Score)
-            .all(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(models.into_iter().map(|m| m.to_domain()).collect())
-    }
-
-    async fn find_unassigned(&self) -> Result<Vec<Lead>, DomainError> {
-        use crate::infrastructure::persistence::entities::lead;
-        let models = lead::Entity::find()
-            .filter(lead::Column::AssignedToId.is_null())
-            .filter(lead::Column::DeletedAt.is_null())
-            .order_by_desc(lead::Column::Score)
-            .all(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(models.into_iter().map(|m| m.to_domain()).collect())
-    }
-
-    async fn find_by_assigned_to(&self, assigned_to_id: Uuid) -> Result<Vec<Lead>, DomainError> {
-        use crate::infrastructure::persistence::entities::lead;
-        let models = lead::Entity::find()
-            .filter(lead::Column::AssignedToId.eq(assigned_to_id))
-            .filter(lead::Column::DeletedAt.is_null())
-            .order_by_desc(lead::Column::Score)
-            .all(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(models.into_iter().map(|m| m.to_domain()).collect())
-    }
-
-    async fn find_high_score(&self, min_score: i32) -> Result<Vec<Lead>, DomainError> {
-        use crate::infrastructure::persistence::entities::lead;
-        let models = lead::Entity::find()
-            .filter(lead::Column::Score.gte(min_score))
-            .filter(lead::Column::DeletedAt.is_null())
-            .order_by_desc(lead::Column::Score)
-            .all(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(models.into_iter().map(|m| m.to_domain()).collect())
-    }
-
-    async fn create(&self, lead: Lead) -> Result<Lead, DomainError> {
-        use crate::infrastructure::persistence::entities::lead;
-
-        let source_str = match lead.source {
-            LeadSource::WebForm => "web_form",
-            LeadSource::ManualEntry => "manual_entry",
-            LeadSource::Email => "email",
-            LeadSource::Referral => "referral",
-        };
-
-        let status_str = match lead.status {
-            LeadStatus::New => "new",
-            LeadStatus::Contacted => "contacted",
-            LeadStatus::Qualified => "qualified",
-            LeadStatus::Unqualified => "unqualified",
-            LeadStatus::Converted => "converted",
-        };
-
-        let model = lead::ActiveModel {
-            id: Set(lead.id),
-            created_at: Set(lead.created_at.into()),
-            updated_at: Set(lead.updated_at.into()),
-            deleted_at: Set(None),
-            first_name: Set(lead.first_name),
-            last_name: Set(lead.last_name),
-            email: Set(lead.email),
-            phone: Set(lead.phone),
-            company_name: Set(lead.company_name),
-            job_title: Set(lead.job_title),
-            source: Set(source_str.to_string()),
-            status: Set(status_str.to_string()),
-            score: Set(lead.score),
-            notes: Set(lead.notes),
-            position: Set(lead.position),
-            assigned_to_id: Set(lead.assigned_to_id),
-            converted_person_id: Set(lead.converted_person_id),
-            converted_company_id: Set(lead.converted_company_id),
-            converted_opportunity_id: Set(lead.converted_opportunity_id),
-            converted_at: Set(lead.converted_at.map(|d| d.into())),
-            last_contacted_at: Set(lead.last_contacted_at.map(|d| d.into())),
-            workspace_id: Set(lead.workspace_id),
-        };
-
-        let result = model
-            .insert(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(result.to_domain())
-    }
-
-    async fn update(&self, lead: Lead) -> Result<Lead, DomainError> {
-        use crate::infrastructure::persistence::entities::lead;
-
-        let source_str = match lead.source {
-            LeadSource::WebForm => "web_form",
-            LeadSource::ManualEntry => "manual_entry",
-            LeadSource::Email => "email",
-            LeadSource::Referral => "referral",
-        };
-
-        let status_str = match lead.status {
-            LeadStatus::New => "new",
-            LeadStatus::Contacted => "contacted",
-            LeadStatus::Qualified => "qualified",
-            LeadStatus::Unqualified => "unqualified",
-            LeadStatus::Converted => "converted",
-        };
-
-        let model = lead::ActiveModel {
-            id: Set(lead.id),
-            created_at: Set(lead.created_at.into()),
-            updated_at: Set(lead.updated_at.into()),
-            deleted_at: Set(lead.deleted_at.map(|d| d.into())),
-            first_name: Set(lead.first_name),
-            last_name: Set(lead.last_name),
-            email: Set(lead.email),
-            phone: Set(lead.phone),
-            company_name: Set(lead.company_name),
-            job_title: Set(lead.job_title),
-            source: Set(source_str.to_string()),
-            status: Set(status_str.to_string()),
-            score: Set(lead.score),
-            notes: Set(lead.notes),
-            position: Set(lead.position),
-            assigned_to_id: Set(lead.assigned_to_id),
-            converted_person_id: Set(lead.converted_person_id),
-            converted_company_id: Set(lead.converted_company_id),
-            converted_opportunity_id: Set(lead.converted_opportunity_id),
-            converted_at: Set(lead.converted_at.map(|d| d.into())),
-            last_contacted_at: Set(lead.last_contacted_at.map(|d| d.into())),
-            workspace_id: Set(lead.workspace_id),
-        };
-
-        let result = model
-            .update(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(result.to_domain())
-    }
-
-    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        use crate::infrastructure::persistence::entities::lead;
-        // Soft delete
-        let model = lead::ActiveModel {
-            id: Set(id),
-            deleted_at: Set(Some(chrono::Utc::now().into())),
-            ..Default::default()
-        };
-        model
-            .update(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(())
-    }
-}
-
-#[async_trait]
-impl MetadataRepository for SeaOrmRepo {
-    async fn find_object_by_name(
-        &self,
-        name_singular: &str,
-    ) -> Result<Option<crate::domain::metadata::ObjectMetadata>, DomainError> {
-        use crate::infrastructure::persistence::entities::object_metadata;
-        let model = object_metadata::Entity::find()
-            .filter(object_metadata::Column::NameSingular.eq(name_singular))
-            .one(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(model.map(|m| m.to_domain()))
-    }
-
-    async fn get_schema(
-        &self,
-    ) -> Result<
-        Vec<(
-            crate::domain::metadata::ObjectMetadata,
-            Vec<crate::domain::metadata::FieldMetadata>,
-        )>,
-        DomainError,
-    > {
-        use crate::infrastructure::persistence::entities::{field_metadata, object_metadata};
-
-        let objects = object_metadata::Entity::find()
-            .find_with_related(field_metadata::Entity)
-            .all(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-
-        let schema = objects
-            .into_iter()
-            .map(|(obj, fields)| {
-                (
-                    obj.to_domain(),
-                    fields.into_iter().map(|f| f.to_domain()).collect(),
-                )
-            })
-            .collect();
-
-        Ok(schema)
-    }
-
-    async fn create_object(
-        &self,
-        object: crate::domain::metadata::ObjectMetadata,
-    ) -> Result<crate::domain::metadata::ObjectMetadata, DomainError> {
-        use crate::infrastructure::persistence::entities::object_metadata;
-        let model = object_metadata::ActiveModel {
-            id: Set(object.id),
-            created_at: Set(object.created_at.into()),
-            updated_at: Set(object.updated_at.into()),
-            name_singular: Set(object.name_singular),
-            name_plural: Set(object.name_plural),
-            description: Set(object.description),
-            workspace_id: Set(object.workspace_id),
-        };
-
-        let result = model
-            .insert(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(result.to_domain())
-    }
-
-    async fn create_field(
-        &self,
-        field: crate::domain::metadata::FieldMetadata,
-    ) -> Result<crate::domain::metadata::FieldMetadata, DomainError> {
-        use crate::domain::metadata::FieldType;
-        use crate::infrastructure::persistence::entities::field_metadata;
-
-        let type_str = match field.field_type {
-            FieldType::Text => "Text",
-            FieldType::Number => "Number",
-            FieldType::Date => "Date",
-            FieldType::Boolean => "Boolean",
-            FieldType::Select => "Select",
-            FieldType::Relation => "Relation",
-            FieldType::Json => "Json",
-        };
-
-        let model = field_metadata::ActiveModel {
-            id: Set(field.id),
-            created_at: Set(field.created_at.into()),
-            updated_at: Set(field.updated_at.into()),
-            object_metadata_id: Set(field.object_metadata_id),
-            name: Set(field.name),
-            r#type: Set(type_str.to_string()),
-            is_custom: Set(field.is_custom),
-            settings: Set(field.settings),
-        };
-
-        let result = model
-            .insert(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(result.to_domain())
-    }
-
-    async fn find_object_by_id(
-        &self,
-        id: Uuid,
-    ) -> Result<Option<crate::domain::metadata::ObjectMetadata>, DomainError> {
-        use crate::infrastructure::persistence::entities::object_metadata;
-        let model = object_metadata::Entity::find_by_id(id)
-            .one(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-
-        Ok(model.map(|m| m.to_domain()))
-    }
-}
-
-#[async_trait]
-impl ViewRepository for SeaOrmRepo {
-    async fn find_by_object(
-        &self,
-        object_metadata_id: Uuid,
-    ) -> Result<Vec<crate::domain::metadata::View>, DomainError> {
-        use crate::infrastructure::persistence::entities::view;
-        let models = view::Entity::find()
-            .filter(view::Column::ObjectMetadataId.eq(object_metadata_id))
-            .order_by_asc(view::Column::Position)
-            .all(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(models.into_iter().map(|m| m.to_domain()).collect())
-    }
-
-    async fn find_by_id(
-        &self,
-        id: Uuid,
-    ) -> Result<Option<crate::domain::metadata::View>, DomainError> {
-        use crate::infrastructure::persistence::entities::view;
-        let model = view::Entity::find_by_id(id)
-            .one(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(model.map(|m| m.to_domain()))
-    }
-
-    async fn create(
-        &self,
-        view: crate::domain::metadata::View,
-    ) -> Result<crate::domain::metadata::View, DomainError> {
-        use crate::domain::metadata::ViewType;
-        use crate::infrastructure::persistence::entities::view;
-
-        let type_str = match view.view_type {
-            ViewType::Table => "Table",
-            ViewType::Kanban => "Kanban",
-            ViewType::Calendar => "Calendar",
-            ViewType::List => "List",
-        };
-
-        let model = view::ActiveModel {
-            id: Set(view.id),
-            created_at: Set(view.created_at.into()),
-            updated_at: Set(view.updated_at.into()),
-            object_metadata_id: Set(view.object_metadata_id),
-            name: Set(view.name),
-            r#type: Set(type_str.to_string()),
-            filters: Set(view.filters),
-            sort: Set(view.sort),
-            position: Set(view.position),
-            workspace_id: Set(view.workspace_id),
-        };
-
-        let result = model
-            .insert(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(result.to_domain())
-    }
-
-    async fn update(
-        &self,
-        view: crate::domain::metadata::View,
-    ) -> Result<crate::domain::metadata::View, DomainError> {
-        use crate::domain::metadata::ViewType;
-        use crate::infrastructure::persistence::entities::view;
-
-        let type_str = match view.view_type {
-            ViewType::Table => "Table",
-            ViewType::Kanban => "Kanban",
-            ViewType::Calendar => "Calendar",
-            ViewType::List => "List",
-        };
-
-        let model = view::ActiveModel {
-            id: Set(view.id),
-            updated_at: Set(chrono::Utc::now().into()),
-            name: Set(view.name),
-            r#type: Set(type_str.to_string()),
-            filters: Set(view.filters),
-            sort: Set(view.sort),
-            position: Set(view.position),
-            workspace_id: Set(view.workspace_id),
-            ..Default::default()
-        };
-
-        let result = model
-            .update(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(result.to_domain())
-    }
-
-    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        use crate::infrastructure::persistence::entities::view;
-        view::Entity::delete_by_id(id)
-            .exec(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-        Ok(())
-    }
-}
-
-#[async_trait]
-impl crate::application::ports::output::CustomObjectDataRepository for SeaOrmRepo {
-    async fn find_by_id(
-        &self,
-        id: Uuid,
-    ) -> Result<Option<crate::domain::custom_object_data::CustomObjectData>, DomainError> {
-        let model = custom_object_data::Entity::find_by_id(id)
-            .one(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-
-        Ok(model.map(|m| m.to_domain()))
-    }
-
-    async fn find_by_object_metadata_id(
-        &self,
-        object_metadata_id: Uuid,
-    ) -> Result<Vec<crate::domain::custom_object_data::CustomObjectData>, DomainError> {
-        let models = custom_object_data::Entity::find()
-            .filter(custom_object_data::Column::ObjectMetadataId.eq(object_metadata_id))
-            .all(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-
-        Ok(models.into_iter().map(|m| m.to_domain()).collect())
-    }
-
-    async fn create(
-        &self,
-        data: crate::domain::custom_object_data::CustomObjectData,
-    ) -> Result<crate::domain::custom_object_data::CustomObjectData, DomainError> {
-        let model = custom_object_data::ActiveModel {
-            id: Set(data.id),
-            created_at: Set(data.created_at.into()),
-            updated_at: Set(data.updated_at.into()),
-            object_metadata_id: Set(data.object_metadata_id),
-            properties: Set(data.properties.clone()),
-            workspace_id: Set(data.workspace_id),
-        };
-
-        model
-            .insert(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-
-        Ok(data)
-    }
-
-    async fn update(
-        &self,
-        data: crate::domain::custom_object_data::CustomObjectData,
-    ) -> Result<crate::domain::custom_object_data::CustomObjectData, DomainError> {
-        let model = custom_object_data::ActiveModel {
-            id: Set(data.id),
-            created_at: Set(data.created_at.into()),
-            updated_at: Set(data.updated_at.into()),
-            object_metadata_id: Set(data.object_metadata_id),
-            properties: Set(data.properties.clone()),
-            workspace_id: Set(data.workspace_id),
-        };
-
-        model
-            .update(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-
-        Ok(data)
-    }
-
-    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        custom_object_data::Entity::delete_by_id(id)
-            .exec(&self.db)
-            .await
-            .map_err(|e| DomainError::InfrastructureError(e.to_string()))?;
-
-        Ok(())
-    }
-}
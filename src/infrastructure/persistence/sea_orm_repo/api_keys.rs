@@ -0,0 +1,56 @@
+use crate::application::ports::output::ApiKeyRepository;
+use crate::domain::{ApiKey, DomainError};
+use crate::infrastructure::persistence::crud;
+use crate::infrastructure::persistence::entities::api_key;
+use crate::infrastructure::persistence::errors::map_db_err;
+use crate::infrastructure::persistence::mapper::Mapper;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sea_orm::*;
+use uuid::Uuid;
+
+repository!(SeaOrmApiKeyRepo);
+
+#[async_trait]
+impl ApiKeyRepository for SeaOrmApiKeyRepo {
+    async fn create(&self, api_key: ApiKey) -> Result<ApiKey, DomainError> {
+        crud::insert(&self.conn(), api_key).await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<ApiKey>, DomainError> {
+        crud::find_one(&self.conn(), api_key::Entity::find_by_id(id)).await
+    }
+
+    async fn find_by_token_hash(&self, token_hash: &str) -> Result<Option<ApiKey>, DomainError> {
+        crud::find_one(
+            &self.conn(),
+            api_key::Entity::find().filter(api_key::Column::TokenHash.eq(token_hash)),
+        )
+        .await
+    }
+
+    async fn find_by_member_id(&self, member_id: Uuid) -> Result<Vec<ApiKey>, DomainError> {
+        let select = api_key::Entity::find()
+            .filter(api_key::Column::MemberId.eq(member_id))
+            .filter(api_key::Column::RevokedAt.is_null())
+            .order_by_desc(api_key::Column::CreatedAt);
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn update(&self, api_key: ApiKey) -> Result<ApiKey, DomainError> {
+        use api_key::Column;
+        let changes = api_key.to_changes(&[Column::Name, Column::RevokedAt, Column::UpdatedAt]);
+        crud::update(&self.conn(), changes).await
+    }
+
+    async fn record_use(&self, id: Uuid, at: DateTime<Utc>) -> Result<(), DomainError> {
+        let model = api_key::ActiveModel {
+            id: Set(id),
+            last_used_at: Set(Some(at.into())),
+            ..Default::default()
+        };
+
+        model.update(&self.conn()).await.map_err(map_db_err)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use crate::application::ports::output::CalendarEventRepository;
+use crate::domain::{CalendarEvent, DomainError};
+use crate::infrastructure::persistence::crud;
+use crate::infrastructure::persistence::entities::calendar_event;
+use crate::infrastructure::persistence::errors::map_db_err;
+use crate::infrastructure::persistence::mapper::Mapper;
+use crate::infrastructure::persistence::workspace_scope;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+repository!(SeaOrmCalendarRepo);
+
+#[async_trait]
+impl CalendarEventRepository for SeaOrmCalendarRepo {
+    async fn find_all(&self) -> Result<Vec<CalendarEvent>, DomainError> {
+        crud::find_all(
+            &self.conn(),
+            workspace_scope::find::<calendar_event::Entity>()?,
+        )
+        .await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<CalendarEvent>, DomainError> {
+        crud::find_one(
+            &self.conn(),
+            workspace_scope::find_by_id::<calendar_event::Entity>(id)?,
+        )
+        .await
+    }
+
+    async fn create(&self, event: CalendarEvent) -> Result<CalendarEvent, DomainError> {
+        crud::insert_scoped(&self.conn(), event).await
+    }
+
+    async fn update(&self, event: CalendarEvent) -> Result<CalendarEvent, DomainError> {
+        use calendar_event::Column;
+        let changes = event.to_changes(&[
+            Column::ConnectedAccountId,
+            Column::Title,
+            Column::StartTime,
+            Column::EndTime,
+            Column::Description,
+            Column::UpdatedAt,
+        ]);
+        crud::update_scoped(&self.conn(), changes).await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        let result = workspace_scope::delete_by_id::<calendar_event::Entity>(id)?
+            .exec(&self.conn())
+            .await
+            .map_err(map_db_err)?;
+        workspace_scope::ensure_deleted(result)
+    }
+}
@@ -0,0 +1,85 @@
+use crate::application::ports::output::CompanyRepository;
+use crate::application::ports::query::{ListQuery, Page, Sort};
+use crate::domain::{Company, DomainError};
+use crate::infrastructure::persistence::crud;
+use crate::infrastructure::persistence::entities::company;
+use crate::infrastructure::persistence::mapper::Mapper;
+use crate::infrastructure::persistence::query::{Field, FieldKind};
+use crate::infrastructure::persistence::{trash, workspace_scope};
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::*;
+use uuid::Uuid;
+
+repository!(SeaOrmCompanyRepo);
+
+/// Fields of `Company` that list queries can filter and sort on
+pub(in crate::infrastructure::persistence) fn company_fields() -> Vec<Field<company::Column>> {
+    use company::Column;
+    vec![
+        Field::new("name", Column::Name, FieldKind::Text).sortable(),
+        Field::new("domain_name", Column::DomainName, FieldKind::Text).sortable(),
+        Field::new("address", Column::Address, FieldKind::Text),
+        Field::new(
+            "employees_count",
+            Column::EmployeesCount,
+            FieldKind::Integer,
+        )
+        .sortable(),
+        Field::new("created_at", Column::CreatedAt, FieldKind::Timestamp).sortable(),
+        Field::new("updated_at", Column::UpdatedAt, FieldKind::Timestamp).sortable(),
+    ]
+}
+
+#[async_trait]
+impl CompanyRepository for SeaOrmCompanyRepo {
+    async fn find_all(&self) -> Result<Vec<Company>, DomainError> {
+        let select = workspace_scope::find::<company::Entity>()?
+            .filter(company::Column::DeletedAt.is_null());
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn find_page(&self, query: &ListQuery) -> Result<Page<Company>, DomainError> {
+        let select = workspace_scope::find::<company::Entity>()?
+            .filter(company::Column::DeletedAt.is_null());
+        crud::find_page(
+            &self.conn(),
+            select,
+            query,
+            &company_fields(),
+            company::Column::Id,
+            Sort::asc("created_at"),
+        )
+        .await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Company>, DomainError> {
+        let select = workspace_scope::find_by_id::<company::Entity>(id)?
+            .filter(company::Column::DeletedAt.is_null());
+        crud::find_one(&self.conn(), select).await
+    }
+
+    async fn create(&self, company: Company) -> Result<Company, DomainError> {
+        crud::insert_scoped(&self.conn(), company).await
+    }
+
+    async fn update(&self, mut company: Company) -> Result<Company, DomainError> {
+        use company::Column;
+        let (id, expected) = (company.id, company.version);
+        company.updated_at = Utc::now();
+        company.version += 1;
+        let changes = company.to_changes(&[
+            Column::UpdatedAt,
+            Column::Name,
+            Column::DomainName,
+            Column::Address,
+            Column::EmployeesCount,
+            Column::Version,
+        ]);
+        crud::update_versioned(&self.conn(), id, changes, Column::Version, expected).await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        trash::soft_delete::<company::Entity>(&self.db, id).await
+    }
+}
@@ -0,0 +1,148 @@
+use crate::application::ports::output::{EmailRepository, EmailTemplateRepository};
+use crate::application::ports::query::{ListQuery, Page, Sort};
+use crate::domain::states::EmailStatus;
+use crate::domain::{DomainError, Email, EmailTemplate};
+use crate::infrastructure::persistence::crud;
+use crate::infrastructure::persistence::entities::{email, email_template};
+use crate::infrastructure::persistence::errors::map_db_err;
+use crate::infrastructure::persistence::mapper::Mapper;
+use crate::infrastructure::persistence::query::{Field, FieldKind};
+use crate::infrastructure::persistence::workspace_scope;
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::*;
+use uuid::Uuid;
+
+repository!(
+    /// Emails and the templates they are rendered from
+    SeaOrmEmailRepo
+);
+
+/// Fields of `Email` that list queries can filter and sort on
+pub(in crate::infrastructure::persistence) fn email_fields() -> Vec<Field<email::Column>> {
+    use email::Column;
+    vec![
+        Field::new("direction", Column::Direction, FieldKind::Enum).sortable(),
+        Field::new("status", Column::Status, FieldKind::Enum).sortable(),
+        Field::new("from_email", Column::FromEmail, FieldKind::Text).sortable(),
+        Field::new("to_email", Column::ToEmail, FieldKind::Text).sortable(),
+        Field::new("subject", Column::Subject, FieldKind::Text).sortable(),
+        Field::new("sent_at", Column::SentAt, FieldKind::Timestamp),
+        Field::new("person_id", Column::PersonId, FieldKind::Uuid),
+        Field::new("company_id", Column::CompanyId, FieldKind::Uuid),
+        Field::new("opportunity_id", Column::OpportunityId, FieldKind::Uuid),
+        Field::new("workflow_id", Column::WorkflowId, FieldKind::Uuid),
+        Field::new("created_at", Column::CreatedAt, FieldKind::Timestamp).sortable(),
+    ]
+}
+
+#[async_trait]
+impl EmailRepository for SeaOrmEmailRepo {
+    async fn find_page(&self, query: &ListQuery) -> Result<Page<Email>, DomainError> {
+        crud::find_page(
+            &self.conn(),
+            workspace_scope::find::<email::Entity>()?,
+            query,
+            &email_fields(),
+            email::Column::Id,
+            Sort::desc("created_at"),
+        )
+        .await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Email>, DomainError> {
+        crud::find_one(
+            &self.conn(),
+            workspace_scope::find_by_id::<email::Entity>(id)?,
+        )
+        .await
+    }
+
+    async fn find_by_person_id(&self, person_id: Uuid) -> Result<Vec<Email>, DomainError> {
+        let select = workspace_scope::find::<email::Entity>()?
+            .filter(email::Column::PersonId.eq(person_id))
+            .order_by_desc(email::Column::CreatedAt);
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn find_by_company_id(&self, company_id: Uuid) -> Result<Vec<Email>, DomainError> {
+        let select = workspace_scope::find::<email::Entity>()?
+            .filter(email::Column::CompanyId.eq(company_id))
+            .order_by_desc(email::Column::CreatedAt);
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn find_by_opportunity_id(
+        &self,
+        opportunity_id: Uuid,
+    ) -> Result<Vec<Email>, DomainError> {
+        let select = workspace_scope::find::<email::Entity>()?
+            .filter(email::Column::OpportunityId.eq(opportunity_id))
+            .order_by_desc(email::Column::CreatedAt);
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn find_pending(&self) -> Result<Vec<Email>, DomainError> {
+        let select = workspace_scope::find::<email::Entity>()?
+            .filter(email::Column::Status.eq(email::status_name(EmailStatus::Pending)))
+            .order_by_asc(email::Column::CreatedAt);
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn create(&self, email: Email) -> Result<Email, DomainError> {
+        crud::insert_scoped(&self.conn(), email).await
+    }
+
+    async fn update(&self, mut email: Email) -> Result<Email, DomainError> {
+        email.updated_at = Utc::now();
+        crud::update_scoped(&self.conn(), email.to_active_model()).await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        let result = workspace_scope::delete_by_id::<email::Entity>(id)?
+            .exec(&self.conn())
+            .await
+            .map_err(map_db_err)?;
+        workspace_scope::ensure_deleted(result)
+    }
+}
+
+#[async_trait]
+impl EmailTemplateRepository for SeaOrmEmailRepo {
+    async fn find_all(&self) -> Result<Vec<EmailTemplate>, DomainError> {
+        let select = workspace_scope::find::<email_template::Entity>()?
+            .order_by_asc(email_template::Column::Name);
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<EmailTemplate>, DomainError> {
+        crud::find_one(
+            &self.conn(),
+            workspace_scope::find_by_id::<email_template::Entity>(id)?,
+        )
+        .await
+    }
+
+    async fn find_by_name(&self, name: &str) -> Result<Option<EmailTemplate>, DomainError> {
+        let select = workspace_scope::find::<email_template::Entity>()?
+            .filter(email_template::Column::Name.eq(name));
+        crud::find_one(&self.conn(), select).await
+    }
+
+    async fn create(&self, template: EmailTemplate) -> Result<EmailTemplate, DomainError> {
+        crud::insert_scoped(&self.conn(), template).await
+    }
+
+    async fn update(&self, mut template: EmailTemplate) -> Result<EmailTemplate, DomainError> {
+        template.updated_at = Utc::now();
+        crud::update_scoped(&self.conn(), template.to_active_model()).await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        let result = workspace_scope::delete_by_id::<email_template::Entity>(id)?
+            .exec(&self.conn())
+            .await
+            .map_err(map_db_err)?;
+        workspace_scope::ensure_deleted(result)
+    }
+}
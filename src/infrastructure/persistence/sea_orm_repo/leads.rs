@@ -0,0 +1,114 @@
+use crate::application::ports::output::LeadRepository;
+use crate::application::ports::query::{ListQuery, Page, Sort};
+use crate::domain::states::LeadStatus;
+use crate::domain::{DomainError, Lead};
+use crate::infrastructure::persistence::crud;
+use crate::infrastructure::persistence::entities::lead;
+use crate::infrastructure::persistence::mapper::Mapper;
+use crate::infrastructure::persistence::query::{Field, FieldKind};
+use crate::infrastructure::persistence::{trash, workspace_scope};
+use async_trait::async_trait;
+use sea_orm::*;
+use uuid::Uuid;
+
+repository!(SeaOrmLeadRepo);
+
+/// Fields of `Lead` that list queries can filter and sort on
+pub(in crate::infrastructure::persistence) fn lead_fields() -> Vec<Field<lead::Column>> {
+    use lead::Column;
+    vec![
+        Field::new("first_name", Column::FirstName, FieldKind::Text).sortable(),
+        Field::new("last_name", Column::LastName, FieldKind::Text).sortable(),
+        Field::new("email", Column::Email, FieldKind::Text).sortable(),
+        Field::new("phone", Column::Phone, FieldKind::Text),
+        Field::new("company_name", Column::CompanyName, FieldKind::Text),
+        Field::new("job_title", Column::JobTitle, FieldKind::Text),
+        Field::new("source", Column::Source, FieldKind::Enum).sortable(),
+        Field::new("status", Column::Status, FieldKind::Enum).sortable(),
+        Field::new("score", Column::Score, FieldKind::Integer).sortable(),
+        Field::new("position", Column::Position, FieldKind::Integer).sortable(),
+        Field::new("assigned_to_id", Column::AssignedToId, FieldKind::Uuid),
+        Field::new("converted_at", Column::ConvertedAt, FieldKind::Timestamp),
+        Field::new(
+            "last_contacted_at",
+            Column::LastContactedAt,
+            FieldKind::Timestamp,
+        ),
+        Field::new("created_at", Column::CreatedAt, FieldKind::Timestamp).sortable(),
+        Field::new("updated_at", Column::UpdatedAt, FieldKind::Timestamp).sortable(),
+    ]
+}
+
+#[async_trait]
+impl LeadRepository for SeaOrmLeadRepo {
+    async fn find_page(&self, query: &ListQuery) -> Result<Page<Lead>, DomainError> {
+        let select =
+            workspace_scope::find::<lead::Entity>()?.filter(lead::Column::DeletedAt.is_null());
+        crud::find_page(
+            &self.conn(),
+            select,
+            query,
+            &lead_fields(),
+            lead::Column::Id,
+            Sort::desc("score"),
+        )
+        .await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Lead>, DomainError> {
+        let select = workspace_scope::find_by_id::<lead::Entity>(id)?
+            .filter(lead::Column::DeletedAt.is_null());
+        crud::find_one(&self.conn(), select).await
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Option<Lead>, DomainError> {
+        let select = workspace_scope::find::<lead::Entity>()?
+            .filter(lead::Column::Email.eq(email))
+            .filter(lead::Column::DeletedAt.is_null());
+        crud::find_one(&self.conn(), select).await
+    }
+
+    async fn find_by_status(&self, status: LeadStatus) -> Result<Vec<Lead>, DomainError> {
+        let select = workspace_scope::find::<lead::Entity>()?
+            .filter(lead::Column::Status.eq(lead::status_name(status)))
+            .filter(lead::Column::DeletedAt.is_null())
+            .order_by_desc(lead::Column::Score);
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn find_unassigned(&self) -> Result<Vec<Lead>, DomainError> {
+        let select = workspace_scope::find::<lead::Entity>()?
+            .filter(lead::Column::AssignedToId.is_null())
+            .filter(lead::Column::DeletedAt.is_null())
+            .order_by_desc(lead::Column::Score);
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn find_by_assigned_to(&self, assigned_to_id: Uuid) -> Result<Vec<Lead>, DomainError> {
+        let select = workspace_scope::find::<lead::Entity>()?
+            .filter(lead::Column::AssignedToId.eq(assigned_to_id))
+            .filter(lead::Column::DeletedAt.is_null())
+            .order_by_desc(lead::Column::Score);
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn find_high_score(&self, min_score: i32) -> Result<Vec<Lead>, DomainError> {
+        let select = workspace_scope::find::<lead::Entity>()?
+            .filter(lead::Column::Score.gte(min_score))
+            .filter(lead::Column::DeletedAt.is_null())
+            .order_by_desc(lead::Column::Score);
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn create(&self, lead: Lead) -> Result<Lead, DomainError> {
+        crud::insert_scoped(&self.conn(), lead).await
+    }
+
+    async fn update(&self, lead: Lead) -> Result<Lead, DomainError> {
+        crud::update_scoped(&self.conn(), lead.to_active_model()).await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        trash::soft_delete::<lead::Entity>(&self.db, id).await
+    }
+}
@@ -0,0 +1,166 @@
+use crate::application::ports::output::{
+    CustomObjectDataRepository, MetadataRepository, ViewRepository,
+};
+use crate::domain::custom_object_data::CustomObjectData;
+use crate::domain::metadata::{FieldMetadata, ObjectMetadata, View};
+use crate::domain::DomainError;
+use crate::infrastructure::persistence::crud;
+use crate::infrastructure::persistence::entities::{
+    custom_object_data, field_metadata, object_metadata, view,
+};
+use crate::infrastructure::persistence::errors::map_db_err;
+use crate::infrastructure::persistence::mapper::Mapper;
+use crate::infrastructure::persistence::workspace_scope;
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::*;
+use uuid::Uuid;
+
+repository!(
+    /// Object and field metadata, views, and the records of custom objects
+    SeaOrmMetadataRepo
+);
+
+#[async_trait]
+impl MetadataRepository for SeaOrmMetadataRepo {
+    async fn find_object_by_name(
+        &self,
+        name_singular: &str,
+    ) -> Result<Option<ObjectMetadata>, DomainError> {
+        let select = workspace_scope::find::<object_metadata::Entity>()?
+            .filter(object_metadata::Column::NameSingular.eq(name_singular));
+        crud::find_one(&self.conn(), select).await
+    }
+
+    async fn get_schema(&self) -> Result<Vec<(ObjectMetadata, Vec<FieldMetadata>)>, DomainError> {
+        let objects = workspace_scope::find::<object_metadata::Entity>()?
+            .find_with_related(field_metadata::Entity)
+            .all(&self.conn())
+            .await
+            .map_err(map_db_err)?;
+
+        objects
+            .into_iter()
+            .map(|(object, fields)| {
+                Ok((
+                    ObjectMetadata::from_model(object)?,
+                    fields
+                        .into_iter()
+                        .map(FieldMetadata::from_model)
+                        .collect::<Result<_, _>>()?,
+                ))
+            })
+            .collect()
+    }
+
+    async fn create_object(&self, object: ObjectMetadata) -> Result<ObjectMetadata, DomainError> {
+        crud::insert_scoped(&self.conn(), object).await
+    }
+
+    async fn create_field(&self, field: FieldMetadata) -> Result<FieldMetadata, DomainError> {
+        workspace_scope::ensure_visible::<object_metadata::Entity>(
+            &self.conn(),
+            field.object_metadata_id,
+        )
+        .await?;
+        crud::insert(&self.conn(), field).await
+    }
+
+    async fn find_object_by_id(&self, id: Uuid) -> Result<Option<ObjectMetadata>, DomainError> {
+        crud::find_one(
+            &self.conn(),
+            workspace_scope::find_by_id::<object_metadata::Entity>(id)?,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl ViewRepository for SeaOrmMetadataRepo {
+    async fn find_by_object(&self, object_metadata_id: Uuid) -> Result<Vec<View>, DomainError> {
+        let select = workspace_scope::find::<view::Entity>()?
+            .filter(view::Column::ObjectMetadataId.eq(object_metadata_id))
+            .order_by_asc(view::Column::Position);
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<View>, DomainError> {
+        crud::find_one(
+            &self.conn(),
+            workspace_scope::find_by_id::<view::Entity>(id)?,
+        )
+        .await
+    }
+
+    async fn create(&self, view: View) -> Result<View, DomainError> {
+        crud::insert_scoped(&self.conn(), view).await
+    }
+
+    async fn update(&self, mut view: View) -> Result<View, DomainError> {
+        use view::Column;
+        view.updated_at = Utc::now();
+        let changes = view.to_changes(&[
+            Column::UpdatedAt,
+            Column::Name,
+            Column::Type,
+            Column::Filters,
+            Column::Sort,
+            Column::Position,
+            Column::WorkspaceId,
+        ]);
+        crud::update_scoped(&self.conn(), changes).await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        let result = workspace_scope::delete_by_id::<view::Entity>(id)?
+            .exec(&self.conn())
+            .await
+            .map_err(map_db_err)?;
+        workspace_scope::ensure_deleted(result)
+    }
+}
+
+#[async_trait]
+impl CustomObjectDataRepository for SeaOrmMetadataRepo {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<CustomObjectData>, DomainError> {
+        crud::find_one(
+            &self.conn(),
+            workspace_scope::find_by_id::<custom_object_data::Entity>(id)?,
+        )
+        .await
+    }
+
+    async fn find_by_object_metadata_id(
+        &self,
+        object_metadata_id: Uuid,
+    ) -> Result<Vec<CustomObjectData>, DomainError> {
+        let select = workspace_scope::find::<custom_object_data::Entity>()?
+            .filter(custom_object_data::Column::ObjectMetadataId.eq(object_metadata_id));
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn create(&self, data: CustomObjectData) -> Result<CustomObjectData, DomainError> {
+        crud::insert_scoped(&self.conn(), data).await
+    }
+
+    async fn update(&self, mut data: CustomObjectData) -> Result<CustomObjectData, DomainError> {
+        let (id, expected) = (data.id, data.version);
+        data.version += 1;
+        crud::update_versioned(
+            &self.conn(),
+            id,
+            data.to_active_model(),
+            custom_object_data::Column::Version,
+            expected,
+        )
+        .await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        let result = workspace_scope::delete_by_id::<custom_object_data::Entity>(id)?
+            .exec(&self.conn())
+            .await
+            .map_err(map_db_err)?;
+        workspace_scope::ensure_deleted(result)
+    }
+}
@@ -0,0 +1,127 @@
+//! SeaORM implementations of the repository ports, one struct per aggregate.
+//!
+//! Every repository holds a handle to the same connection pool and runs its queries through
+//! `transaction::connection`, so repositories used together inside a unit of work share its
+//! transaction. Row conversion lives with the entities (`Mapper`), and the generic reads and
+//! writes in `crud` map database errors through `map_db_err`.
+
+use super::transaction;
+use crate::application::ports::unit_of_work::{UnitOfWork, Work};
+use crate::domain::DomainError;
+use async_trait::async_trait;
+use sea_orm::DatabaseConnection;
+use std::sync::Arc;
+
+/// Declares a repository struct over the shared pool
+macro_rules! repository {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        pub struct $name {
+            db: sea_orm::DatabaseConnection,
+        }
+
+        impl $name {
+            pub fn new(db: sea_orm::DatabaseConnection) -> Self {
+                Self { db }
+            }
+
+            /// Where queries run: the ambient unit of work's transaction, or the pool
+            fn conn(&self) -> crate::infrastructure::persistence::transaction::Connection<'_> {
+                crate::infrastructure::persistence::transaction::connection(&self.db)
+            }
+        }
+    };
+}
+
+mod api_keys;
+mod calendar;
+mod companies;
+mod emails;
+mod leads;
+mod metadata;
+mod notes;
+mod opportunities;
+mod people;
+mod tasks;
+mod timeline;
+mod trash;
+mod users;
+mod workflows;
+mod workspaces;
+
+pub use api_keys::SeaOrmApiKeyRepo;
+pub use calendar::SeaOrmCalendarRepo;
+pub use companies::SeaOrmCompanyRepo;
+pub use emails::SeaOrmEmailRepo;
+pub use leads::SeaOrmLeadRepo;
+pub use metadata::SeaOrmMetadataRepo;
+pub use notes::SeaOrmNoteRepo;
+pub use opportunities::SeaOrmOpportunityRepo;
+pub use people::SeaOrmPersonRepo;
+pub use tasks::SeaOrmTaskRepo;
+pub use timeline::SeaOrmTimelineRepo;
+pub use trash::SeaOrmTrashRepo;
+pub use users::SeaOrmUserRepo;
+pub use workflows::SeaOrmWorkflowRepo;
+pub use workspaces::SeaOrmWorkspaceRepo;
+
+// The in-memory repository filters and sorts on the same fields
+#[cfg(test)]
+pub(in crate::infrastructure::persistence) use {
+    companies::company_fields, emails::email_fields, leads::lead_fields, people::person_fields,
+};
+
+/// `UnitOfWork` over the pool the repositories share
+pub struct SeaOrmUnitOfWork {
+    db: DatabaseConnection,
+}
+
+#[async_trait]
+impl UnitOfWork for SeaOrmUnitOfWork {
+    async fn run<'a>(&self, work: Work<'a>) -> Result<(), DomainError> {
+        transaction::run(&self.db, work).await
+    }
+}
+
+/// Every SeaORM repository, built over one connection pool
+pub struct SeaOrmRepositories {
+    pub users: Arc<SeaOrmUserRepo>,
+    pub api_keys: Arc<SeaOrmApiKeyRepo>,
+    pub workspaces: Arc<SeaOrmWorkspaceRepo>,
+    pub people: Arc<SeaOrmPersonRepo>,
+    pub companies: Arc<SeaOrmCompanyRepo>,
+    pub opportunities: Arc<SeaOrmOpportunityRepo>,
+    pub tasks: Arc<SeaOrmTaskRepo>,
+    pub notes: Arc<SeaOrmNoteRepo>,
+    pub leads: Arc<SeaOrmLeadRepo>,
+    pub timeline: Arc<SeaOrmTimelineRepo>,
+    pub workflows: Arc<SeaOrmWorkflowRepo>,
+    pub calendar: Arc<SeaOrmCalendarRepo>,
+    pub emails: Arc<SeaOrmEmailRepo>,
+    pub metadata: Arc<SeaOrmMetadataRepo>,
+    pub trash: Arc<SeaOrmTrashRepo>,
+    pub unit_of_work: Arc<SeaOrmUnitOfWork>,
+}
+
+impl SeaOrmRepositories {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self {
+            users: Arc::new(SeaOrmUserRepo::new(db.clone())),
+            api_keys: Arc::new(SeaOrmApiKeyRepo::new(db.clone())),
+            workspaces: Arc::new(SeaOrmWorkspaceRepo::new(db.clone())),
+            people: Arc::new(SeaOrmPersonRepo::new(db.clone())),
+            companies: Arc::new(SeaOrmCompanyRepo::new(db.clone())),
+            opportunities: Arc::new(SeaOrmOpportunityRepo::new(db.clone())),
+            tasks: Arc::new(SeaOrmTaskRepo::new(db.clone())),
+            notes: Arc::new(SeaOrmNoteRepo::new(db.clone())),
+            leads: Arc::new(SeaOrmLeadRepo::new(db.clone())),
+            timeline: Arc::new(SeaOrmTimelineRepo::new(db.clone())),
+            workflows: Arc::new(SeaOrmWorkflowRepo::new(db.clone())),
+            calendar: Arc::new(SeaOrmCalendarRepo::new(db.clone())),
+            emails: Arc::new(SeaOrmEmailRepo::new(db.clone())),
+            metadata: Arc::new(SeaOrmMetadataRepo::new(db.clone())),
+            trash: Arc::new(SeaOrmTrashRepo::new(db.clone())),
+            unit_of_work: Arc::new(SeaOrmUnitOfWork { db }),
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use crate::application::ports::output::NoteRepository;
+use crate::domain::{DomainError, Note};
+use crate::infrastructure::persistence::crud;
+use crate::infrastructure::persistence::entities::note;
+use crate::infrastructure::persistence::mapper::Mapper;
+use crate::infrastructure::persistence::{trash, workspace_scope};
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::*;
+use uuid::Uuid;
+
+repository!(SeaOrmNoteRepo);
+
+#[async_trait]
+impl NoteRepository for SeaOrmNoteRepo {
+    async fn find_all(&self) -> Result<Vec<Note>, DomainError> {
+        let select =
+            workspace_scope::find::<note::Entity>()?.filter(note::Column::DeletedAt.is_null());
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Note>, DomainError> {
+        let select = workspace_scope::find_by_id::<note::Entity>(id)?
+            .filter(note::Column::DeletedAt.is_null());
+        crud::find_one(&self.conn(), select).await
+    }
+
+    async fn create(&self, note: Note) -> Result<Note, DomainError> {
+        crud::insert_scoped(&self.conn(), note).await
+    }
+
+    async fn update(&self, mut note: Note) -> Result<Note, DomainError> {
+        use note::Column;
+        note.updated_at = Utc::now();
+        let changes = note.to_changes(&[
+            Column::UpdatedAt,
+            Column::Title,
+            Column::BodyV2,
+            Column::Position,
+        ]);
+        crud::update_scoped(&self.conn(), changes).await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        trash::soft_delete::<note::Entity>(&self.db, id).await
+    }
+}
@@ -0,0 +1,55 @@
+use crate::application::ports::output::OpportunityRepository;
+use crate::domain::{DomainError, Opportunity};
+use crate::infrastructure::persistence::crud;
+use crate::infrastructure::persistence::entities::opportunity;
+use crate::infrastructure::persistence::mapper::Mapper;
+use crate::infrastructure::persistence::{trash, workspace_scope};
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::*;
+use uuid::Uuid;
+
+repository!(SeaOrmOpportunityRepo);
+
+#[async_trait]
+impl OpportunityRepository for SeaOrmOpportunityRepo {
+    async fn find_all(&self) -> Result<Vec<Opportunity>, DomainError> {
+        let select = workspace_scope::find::<opportunity::Entity>()?
+            .filter(opportunity::Column::DeletedAt.is_null());
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Opportunity>, DomainError> {
+        let select = workspace_scope::find_by_id::<opportunity::Entity>(id)?
+            .filter(opportunity::Column::DeletedAt.is_null());
+        crud::find_one(&self.conn(), select).await
+    }
+
+    async fn create(&self, opportunity: Opportunity) -> Result<Opportunity, DomainError> {
+        crud::insert_scoped(&self.conn(), opportunity).await
+    }
+
+    async fn update(&self, mut opportunity: Opportunity) -> Result<Opportunity, DomainError> {
+        use opportunity::Column;
+        let (id, expected) = (opportunity.id, opportunity.version);
+        opportunity.updated_at = Utc::now();
+        opportunity.version += 1;
+        let changes = opportunity.to_changes(&[
+            Column::UpdatedAt,
+            Column::Name,
+            Column::Stage,
+            Column::AmountMicros,
+            Column::CurrencyCode,
+            Column::CloseDate,
+            Column::CompanyId,
+            Column::PointOfContactId,
+            Column::OwnerId,
+            Column::Version,
+        ]);
+        crud::update_versioned(&self.conn(), id, changes, Column::Version, expected).await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        trash::soft_delete::<opportunity::Entity>(&self.db, id).await
+    }
+}
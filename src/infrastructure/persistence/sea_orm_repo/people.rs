@@ -0,0 +1,84 @@
+use crate::application::ports::output::PersonRepository;
+use crate::application::ports::query::{ListQuery, Page, Sort};
+use crate::domain::{DomainError, Person};
+use crate::infrastructure::persistence::crud;
+use crate::infrastructure::persistence::entities::person;
+use crate::infrastructure::persistence::mapper::Mapper;
+use crate::infrastructure::persistence::query::{Field, FieldKind};
+use crate::infrastructure::persistence::{trash, workspace_scope};
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::*;
+use uuid::Uuid;
+
+repository!(SeaOrmPersonRepo);
+
+/// Fields of `Person` that list queries can filter and sort on
+pub(in crate::infrastructure::persistence) fn person_fields() -> Vec<Field<person::Column>> {
+    use person::Column;
+    vec![
+        Field::new("name", Column::Name, FieldKind::Text).sortable(),
+        Field::new("email", Column::Email, FieldKind::Text).sortable(),
+        Field::new("position", Column::Position, FieldKind::Integer).sortable(),
+        Field::new("company_id", Column::CompanyId, FieldKind::Uuid),
+        Field::new("created_at", Column::CreatedAt, FieldKind::Timestamp).sortable(),
+        Field::new("updated_at", Column::UpdatedAt, FieldKind::Timestamp).sortable(),
+    ]
+}
+
+#[async_trait]
+impl PersonRepository for SeaOrmPersonRepo {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Person>, DomainError> {
+        let select = workspace_scope::find_by_id::<person::Entity>(id)?
+            .filter(person::Column::DeletedAt.is_null());
+        crud::find_one(&self.conn(), select).await
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Option<Person>, DomainError> {
+        let select = workspace_scope::find::<person::Entity>()?
+            .filter(person::Column::Email.eq(email))
+            .filter(person::Column::DeletedAt.is_null());
+        crud::find_one(&self.conn(), select).await
+    }
+
+    async fn create(&self, person: Person) -> Result<Person, DomainError> {
+        crud::insert_scoped(&self.conn(), person).await
+    }
+
+    async fn update(&self, mut person: Person) -> Result<Person, DomainError> {
+        use person::Column;
+        person.updated_at = Utc::now();
+        let changes = person.to_changes(&[
+            Column::UpdatedAt,
+            Column::Name,
+            Column::Email,
+            Column::Position,
+            Column::CompanyId,
+        ]);
+        crud::update_scoped(&self.conn(), changes).await
+    }
+
+    async fn find_all(&self) -> Result<Vec<Person>, DomainError> {
+        let select =
+            workspace_scope::find::<person::Entity>()?.filter(person::Column::DeletedAt.is_null());
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn find_page(&self, query: &ListQuery) -> Result<Page<Person>, DomainError> {
+        let select =
+            workspace_scope::find::<person::Entity>()?.filter(person::Column::DeletedAt.is_null());
+        crud::find_page(
+            &self.conn(),
+            select,
+            query,
+            &person_fields(),
+            person::Column::Id,
+            Sort::asc("created_at"),
+        )
+        .await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        trash::soft_delete::<person::Entity>(&self.db, id).await
+    }
+}
@@ -0,0 +1,85 @@
+use crate::application::ports::output::{TaskRepository, TaskTargetRepository};
+use crate::domain::{DomainError, Task, TaskTarget};
+use crate::infrastructure::persistence::crud;
+use crate::infrastructure::persistence::entities::{task, task_target};
+use crate::infrastructure::persistence::errors::map_db_err;
+use crate::infrastructure::persistence::mapper::Mapper;
+use crate::infrastructure::persistence::{trash, workspace_scope};
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::*;
+use uuid::Uuid;
+
+repository!(
+    /// Tasks and the records they target
+    SeaOrmTaskRepo
+);
+
+#[async_trait]
+impl TaskRepository for SeaOrmTaskRepo {
+    async fn find_all(&self) -> Result<Vec<Task>, DomainError> {
+        let select =
+            workspace_scope::find::<task::Entity>()?.filter(task::Column::DeletedAt.is_null());
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Task>, DomainError> {
+        let select = workspace_scope::find_by_id::<task::Entity>(id)?
+            .filter(task::Column::DeletedAt.is_null());
+        crud::find_one(&self.conn(), select).await
+    }
+
+    async fn create(&self, task: Task) -> Result<Task, DomainError> {
+        crud::insert_scoped(&self.conn(), task).await
+    }
+
+    async fn update(&self, mut task: Task) -> Result<Task, DomainError> {
+        use task::Column;
+        task.updated_at = Utc::now();
+        let changes = task.to_changes(&[
+            Column::UpdatedAt,
+            Column::Title,
+            Column::Body,
+            Column::Status,
+            Column::Position,
+            Column::AssigneeId,
+            Column::DueAt,
+        ]);
+        crud::update_scoped(&self.conn(), changes).await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        trash::soft_delete::<task::Entity>(&self.db, id).await
+    }
+}
+
+#[async_trait]
+impl TaskTargetRepository for SeaOrmTaskRepo {
+    async fn find_by_task_id(&self, task_id: Uuid) -> Result<Vec<TaskTarget>, DomainError> {
+        workspace_scope::ensure_visible::<task::Entity>(&self.conn(), task_id).await?;
+        let select = task_target::Entity::find()
+            .filter(task_target::Column::TaskId.eq(task_id))
+            .filter(task_target::Column::DeletedAt.is_null());
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn create(&self, task_target: TaskTarget) -> Result<TaskTarget, DomainError> {
+        workspace_scope::ensure_visible::<task::Entity>(&self.conn(), task_target.task_id).await?;
+        crud::insert(&self.conn(), task_target).await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        let model = task_target::Entity::find_by_id(id)
+            .one(&self.conn())
+            .await
+            .map_err(map_db_err)?
+            .ok_or(DomainError::NotFound)?;
+        workspace_scope::ensure_visible::<task::Entity>(&self.conn(), model.task_id).await?;
+
+        task_target::Entity::delete_by_id(id)
+            .exec(&self.conn())
+            .await
+            .map_err(map_db_err)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use crate::application::ports::output::TimelineActivityRepository;
+use crate::domain::{DomainError, TimelineActivity};
+use crate::infrastructure::persistence::crud;
+use crate::infrastructure::persistence::entities::timeline_activity;
+use crate::infrastructure::persistence::errors::map_db_err;
+use crate::infrastructure::persistence::workspace_scope;
+use async_trait::async_trait;
+use sea_orm::*;
+use uuid::Uuid;
+
+repository!(SeaOrmTimelineRepo);
+
+#[async_trait]
+impl TimelineActivityRepository for SeaOrmTimelineRepo {
+    async fn find_all(&self) -> Result<Vec<TimelineActivity>, DomainError> {
+        let select = workspace_scope::find::<timeline_activity::Entity>()?
+            .filter(timeline_activity::Column::DeletedAt.is_null())
+            .order_by_desc(timeline_activity::Column::CreatedAt);
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn find_by_person_id(
+        &self,
+        person_id: Uuid,
+    ) -> Result<Vec<TimelineActivity>, DomainError> {
+        let select = workspace_scope::find::<timeline_activity::Entity>()?
+            .filter(timeline_activity::Column::DeletedAt.is_null())
+            .filter(timeline_activity::Column::PersonId.eq(person_id))
+            .order_by_desc(timeline_activity::Column::CreatedAt);
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn find_by_company_id(
+        &self,
+        company_id: Uuid,
+    ) -> Result<Vec<TimelineActivity>, DomainError> {
+        let select = workspace_scope::find::<timeline_activity::Entity>()?
+            .filter(timeline_activity::Column::DeletedAt.is_null())
+            .filter(timeline_activity::Column::CompanyId.eq(company_id))
+            .order_by_desc(timeline_activity::Column::CreatedAt);
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn find_by_opportunity_id(
+        &self,
+        opportunity_id: Uuid,
+    ) -> Result<Vec<TimelineActivity>, DomainError> {
+        let select = workspace_scope::find::<timeline_activity::Entity>()?
+            .filter(timeline_activity::Column::DeletedAt.is_null())
+            .filter(timeline_activity::Column::OpportunityId.eq(opportunity_id))
+            .order_by_desc(timeline_activity::Column::CreatedAt);
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn find_by_task_id(&self, task_id: Uuid) -> Result<Vec<TimelineActivity>, DomainError> {
+        let select = workspace_scope::find::<timeline_activity::Entity>()?
+            .filter(timeline_activity::Column::DeletedAt.is_null())
+            .filter(timeline_activity::Column::TaskId.eq(task_id))
+            .order_by_desc(timeline_activity::Column::CreatedAt);
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn create(&self, activity: TimelineActivity) -> Result<TimelineActivity, DomainError> {
+        crud::insert_scoped(&self.conn(), activity).await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        let result = workspace_scope::delete_by_id::<timeline_activity::Entity>(id)?
+            .exec(&self.conn())
+            .await
+            .map_err(map_db_err)?;
+        workspace_scope::ensure_deleted(result)
+    }
+}
@@ -0,0 +1,39 @@
+use crate::application::ports::output::TrashRepository;
+use crate::domain::{DomainError, TrashedKind, TrashedRecord};
+use crate::infrastructure::persistence::trash;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+repository!(
+    /// Soft-deleted records of every trashable kind
+    SeaOrmTrashRepo
+);
+
+#[async_trait]
+impl TrashRepository for SeaOrmTrashRepo {
+    async fn find_trashed(&self, kinds: &[TrashedKind]) -> Result<Vec<TrashedRecord>, DomainError> {
+        let mut records = Vec::new();
+        for kind in kinds {
+            records.extend(trash::find_trashed(&self.conn(), *kind).await?);
+        }
+        records.sort_by_key(|record| std::cmp::Reverse(record.deleted_at));
+        Ok(records)
+    }
+
+    async fn restore(&self, kind: TrashedKind, id: Uuid) -> Result<(), DomainError> {
+        trash::restore(&self.db, kind, id).await
+    }
+
+    async fn purge(&self, kind: TrashedKind, id: Uuid) -> Result<(), DomainError> {
+        trash::purge(&self.db, kind, id).await
+    }
+
+    async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<u64, DomainError> {
+        let mut purged = 0;
+        for kind in TrashedKind::ALL {
+            purged += trash::purge_deleted_before(&self.db, kind, cutoff).await?;
+        }
+        Ok(purged)
+    }
+}
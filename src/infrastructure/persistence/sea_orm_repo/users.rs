@@ -0,0 +1,138 @@
+use crate::application::ports::output::{SessionRepository, UserRepository, UserTokenRepository};
+use crate::domain::states::UserTokenPurpose;
+use crate::domain::{DomainError, Session, User, UserToken};
+use crate::infrastructure::persistence::crud;
+use crate::infrastructure::persistence::entities::{session, user, user_token};
+use crate::infrastructure::persistence::errors::map_db_err;
+use crate::infrastructure::persistence::mapper::{serde_name, Mapper};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sea_orm::sea_query::Expr;
+use sea_orm::*;
+use uuid::Uuid;
+
+repository!(
+    /// Users with their one-time tokens and sessions
+    SeaOrmUserRepo
+);
+
+#[async_trait]
+impl UserRepository for SeaOrmUserRepo {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, DomainError> {
+        crud::find_one(&self.conn(), user::Entity::find_by_id(id)).await
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Option<User>, DomainError> {
+        crud::find_one(
+            &self.conn(),
+            user::Entity::find().filter(user::Column::Email.eq(email)),
+        )
+        .await
+    }
+
+    async fn create(&self, user: User) -> Result<User, DomainError> {
+        crud::insert(&self.conn(), user).await
+    }
+
+    async fn update(&self, user: User) -> Result<User, DomainError> {
+        use user::Column;
+        let changes = user.to_changes(&[
+            Column::Email,
+            Column::PasswordHash,
+            Column::State,
+            Column::UpdatedAt,
+        ]);
+        crud::update(&self.conn(), changes).await
+    }
+}
+
+#[async_trait]
+impl UserTokenRepository for SeaOrmUserRepo {
+    async fn create(&self, token: UserToken) -> Result<UserToken, DomainError> {
+        crud::insert(&self.conn(), token).await
+    }
+
+    async fn find_by_token_hash(&self, token_hash: &str) -> Result<Option<UserToken>, DomainError> {
+        crud::find_one(
+            &self.conn(),
+            user_token::Entity::find().filter(user_token::Column::TokenHash.eq(token_hash)),
+        )
+        .await
+    }
+
+    async fn mark_used(&self, id: Uuid, used_at: DateTime<Utc>) -> Result<bool, DomainError> {
+        let result = user_token::Entity::update_many()
+            .col_expr(user_token::Column::UsedAt, Expr::value(used_at))
+            .filter(user_token::Column::Id.eq(id))
+            .filter(user_token::Column::UsedAt.is_null())
+            .exec(&self.conn())
+            .await
+            .map_err(map_db_err)?;
+        Ok(result.rows_affected == 1)
+    }
+
+    async fn mark_all_used(
+        &self,
+        user_id: Uuid,
+        purpose: UserTokenPurpose,
+        used_at: DateTime<Utc>,
+    ) -> Result<(), DomainError> {
+        user_token::Entity::update_many()
+            .col_expr(user_token::Column::UsedAt, Expr::value(used_at))
+            .filter(user_token::Column::UserId.eq(user_id))
+            .filter(user_token::Column::Purpose.eq(serde_name(&purpose)))
+            .filter(user_token::Column::UsedAt.is_null())
+            .exec(&self.conn())
+            .await
+            .map_err(map_db_err)?;
+        Ok(())
+    }
+
+    async fn count_since(
+        &self,
+        user_id: Uuid,
+        purpose: UserTokenPurpose,
+        since: DateTime<Utc>,
+    ) -> Result<u64, DomainError> {
+        user_token::Entity::find()
+            .filter(user_token::Column::UserId.eq(user_id))
+            .filter(user_token::Column::Purpose.eq(serde_name(&purpose)))
+            .filter(user_token::Column::CreatedAt.gte(since))
+            .count(&self.conn())
+            .await
+            .map_err(map_db_err)
+    }
+}
+
+#[async_trait]
+impl SessionRepository for SeaOrmUserRepo {
+    async fn create(&self, session: Session) -> Result<Session, DomainError> {
+        crud::insert(&self.conn(), session).await
+    }
+
+    async fn find_by_token_hash(&self, token_hash: &str) -> Result<Option<Session>, DomainError> {
+        crud::find_one(
+            &self.conn(),
+            session::Entity::find().filter(session::Column::TokenHash.eq(token_hash)),
+        )
+        .await
+    }
+
+    async fn delete_by_token_hash(&self, token_hash: &str) -> Result<(), DomainError> {
+        session::Entity::delete_many()
+            .filter(session::Column::TokenHash.eq(token_hash))
+            .exec(&self.conn())
+            .await
+            .map_err(map_db_err)?;
+        Ok(())
+    }
+
+    async fn delete_by_user_id(&self, user_id: Uuid) -> Result<(), DomainError> {
+        session::Entity::delete_many()
+            .filter(session::Column::UserId.eq(user_id))
+            .exec(&self.conn())
+            .await
+            .map_err(map_db_err)?;
+        Ok(())
+    }
+}
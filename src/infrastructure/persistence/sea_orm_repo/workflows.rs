@@ -0,0 +1,276 @@
+use crate::application::ports::output::{
+    WorkflowRepository, WorkflowRunRepository, WorkflowVersionRepository,
+    WorkflowVersionStepRepository,
+};
+use crate::domain::states::{WorkflowRunStatus, WorkflowStepType};
+use crate::domain::{DomainError, Workflow, WorkflowRun, WorkflowVersion, WorkflowVersionStep};
+use crate::infrastructure::persistence::crud;
+use crate::infrastructure::persistence::entities::{
+    workflow, workflow_run, workflow_version, workflow_version_step,
+};
+use crate::infrastructure::persistence::errors::map_db_err;
+use crate::infrastructure::persistence::mapper::Mapper;
+use crate::infrastructure::persistence::workspace_scope;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sea_orm::*;
+use uuid::Uuid;
+
+repository!(
+    /// Workflows with their versions, steps and runs
+    SeaOrmWorkflowRepo
+);
+
+#[async_trait]
+impl WorkflowRepository for SeaOrmWorkflowRepo {
+    async fn find_all(&self) -> Result<Vec<Workflow>, DomainError> {
+        crud::find_all(&self.conn(), workspace_scope::find::<workflow::Entity>()?).await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Workflow>, DomainError> {
+        crud::find_one(
+            &self.conn(),
+            workspace_scope::find_by_id::<workflow::Entity>(id)?,
+        )
+        .await
+    }
+
+    async fn create(&self, workflow: Workflow) -> Result<Workflow, DomainError> {
+        crud::insert_scoped(&self.conn(), workflow).await
+    }
+
+    async fn update(&self, workflow: Workflow) -> Result<Workflow, DomainError> {
+        use workflow::Column;
+        let changes = workflow.to_changes(&[
+            Column::Name,
+            Column::LastPublishedVersionId,
+            Column::UpdatedAt,
+        ]);
+        crud::update_scoped(&self.conn(), changes).await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        let result = workspace_scope::delete_by_id::<workflow::Entity>(id)?
+            .exec(&self.conn())
+            .await
+            .map_err(map_db_err)?;
+        workspace_scope::ensure_deleted(result)
+    }
+
+    async fn find_published_triggers(
+        &self,
+        workspace_id: Option<Uuid>,
+    ) -> Result<Vec<(Workflow, WorkflowVersionStep)>, DomainError> {
+        let mut select = workspace_scope::find::<workflow::Entity>()?
+            .filter(workflow::Column::LastPublishedVersionId.is_not_null());
+        if let Some(workspace_id) = workspace_id {
+            select = select.filter(workflow::Column::WorkspaceId.eq(workspace_id));
+        }
+        let workflows: Vec<Workflow> = crud::find_all(&self.conn(), select).await?;
+        let version_ids: Vec<Uuid> = workflows
+            .iter()
+            .filter_map(|w| w.last_published_version_id)
+            .collect();
+        if version_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let steps = workflow_version_step::Entity::find()
+            .filter(workflow_version_step::Column::WorkflowVersionId.is_in(version_ids))
+            .filter(
+                workflow_version_step::Column::Type
+                    .eq(workflow_version_step::type_name(WorkflowStepType::Trigger)),
+            )
+            .order_by_asc(workflow_version_step::Column::Position);
+        let steps: Vec<WorkflowVersionStep> = crud::find_all(&self.conn(), steps).await?;
+        Ok(steps
+            .into_iter()
+            .filter_map(|step| {
+                let workflow = workflows
+                    .iter()
+                    .find(|w| w.last_published_version_id == Some(step.workflow_version_id))?;
+                Some((workflow.clone(), step))
+            })
+            .collect())
+    }
+}
+
+/// Fails with `NotFound` unless the workflow version exists in a workflow of the current
+/// workspace. Versions, steps and runs are scoped through their workflow this way.
+async fn ensure_workflow_version_visible(
+    db: &impl ConnectionTrait,
+    version_id: Uuid,
+) -> Result<(), DomainError> {
+    let version = workflow_version::Entity::find_by_id(version_id)
+        .one(db)
+        .await
+        .map_err(map_db_err)?
+        .ok_or(DomainError::NotFound)?;
+    workspace_scope::ensure_visible::<workflow::Entity>(db, version.workflow_id).await
+}
+
+#[async_trait]
+impl WorkflowVersionRepository for SeaOrmWorkflowRepo {
+    async fn find_all(&self) -> Result<Vec<WorkflowVersion>, DomainError> {
+        let mut query = workflow_version::Entity::find().inner_join(workflow::Entity);
+        if let Some(workspace_id) = workspace_scope::current_scope()? {
+            query = query.filter(workflow::Column::WorkspaceId.eq(workspace_id));
+        }
+        crud::find_all(&self.conn(), query).await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<WorkflowVersion>, DomainError> {
+        let version: Option<WorkflowVersion> =
+            crud::find_one(&self.conn(), workflow_version::Entity::find_by_id(id)).await?;
+        match version {
+            Some(version) => {
+                match workspace_scope::ensure_visible::<workflow::Entity>(
+                    &self.conn(),
+                    version.workflow_id,
+                )
+                .await
+                {
+                    Ok(()) => Ok(Some(version)),
+                    Err(DomainError::NotFound) => Ok(None),
+                    Err(e) => Err(e),
+                }
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn find_by_workflow_id(
+        &self,
+        workflow_id: Uuid,
+    ) -> Result<Vec<WorkflowVersion>, DomainError> {
+        workspace_scope::ensure_visible::<workflow::Entity>(&self.conn(), workflow_id).await?;
+        let select = workflow_version::Entity::find()
+            .filter(workflow_version::Column::WorkflowId.eq(workflow_id))
+            .order_by_asc(workflow_version::Column::CreatedAt);
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn create(&self, version: WorkflowVersion) -> Result<WorkflowVersion, DomainError> {
+        workspace_scope::ensure_visible::<workflow::Entity>(&self.conn(), version.workflow_id)
+            .await?;
+        crud::insert(&self.conn(), version).await
+    }
+
+    async fn update(&self, version: WorkflowVersion) -> Result<WorkflowVersion, DomainError> {
+        use workflow_version::Column;
+        ensure_workflow_version_visible(&self.conn(), version.id).await?;
+        let changes = version.to_changes(&[Column::UpdatedAt, Column::Status]);
+        crud::update(&self.conn(), changes).await
+    }
+}
+
+#[async_trait]
+impl WorkflowVersionStepRepository for SeaOrmWorkflowRepo {
+    async fn find_by_version_id(
+        &self,
+        version_id: Uuid,
+    ) -> Result<Vec<WorkflowVersionStep>, DomainError> {
+        ensure_workflow_version_visible(&self.conn(), version_id).await?;
+        let select = workflow_version_step::Entity::find()
+            .filter(workflow_version_step::Column::WorkflowVersionId.eq(version_id))
+            .order_by_asc(workflow_version_step::Column::Position);
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn create(&self, step: WorkflowVersionStep) -> Result<WorkflowVersionStep, DomainError> {
+        ensure_workflow_version_visible(&self.conn(), step.workflow_version_id).await?;
+        crud::insert(&self.conn(), step).await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        let model = workflow_version_step::Entity::find_by_id(id)
+            .one(&self.conn())
+            .await
+            .map_err(map_db_err)?
+            .ok_or(DomainError::NotFound)?;
+        ensure_workflow_version_visible(&self.conn(), model.workflow_version_id).await?;
+
+        workflow_version_step::Entity::delete_by_id(id)
+            .exec(&self.conn())
+            .await
+            .map_err(map_db_err)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl WorkflowRunRepository for SeaOrmWorkflowRepo {
+    async fn find_all(&self) -> Result<Vec<WorkflowRun>, DomainError> {
+        let query = visible_runs()?.order_by_desc(workflow_run::Column::CreatedAt);
+        crud::find_all(&self.conn(), query).await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<WorkflowRun>, DomainError> {
+        let run: Option<WorkflowRun> =
+            crud::find_one(&self.conn(), workflow_run::Entity::find_by_id(id)).await?;
+        match run {
+            Some(run) => {
+                match ensure_workflow_version_visible(&self.conn(), run.workflow_version_id).await {
+                    Ok(()) => Ok(Some(run)),
+                    Err(DomainError::NotFound) => Ok(None),
+                    Err(e) => Err(e),
+                }
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn create(&self, run: WorkflowRun) -> Result<WorkflowRun, DomainError> {
+        ensure_workflow_version_visible(&self.conn(), run.workflow_version_id).await?;
+        crud::insert(&self.conn(), run).await
+    }
+
+    async fn update(&self, run: WorkflowRun) -> Result<WorkflowRun, DomainError> {
+        use workflow_run::Column;
+        ensure_workflow_version_visible(&self.conn(), run.workflow_version_id).await?;
+        let changes = run.to_changes(&[
+            Column::UpdatedAt,
+            Column::Status,
+            Column::Output,
+            Column::Error,
+            Column::Cursor,
+            Column::WakeAt,
+        ]);
+        crud::update(&self.conn(), changes).await
+    }
+
+    async fn find_due(&self, now: DateTime<Utc>) -> Result<Vec<WorkflowRun>, DomainError> {
+        use workflow_run::Column;
+        let query = visible_runs()?
+            .filter(Column::Status.eq(workflow_run::status_name(WorkflowRunStatus::Pending)))
+            .filter(Column::WakeAt.lte(now))
+            .order_by_asc(Column::WakeAt);
+        crud::find_all(&self.conn(), query).await
+    }
+
+    async fn find_running_before(
+        &self,
+        before: DateTime<Utc>,
+    ) -> Result<Vec<WorkflowRun>, DomainError> {
+        use workflow_run::Column;
+        let query = visible_runs()?
+            .filter(Column::Status.eq(workflow_run::status_name(WorkflowRunStatus::Running)))
+            .filter(Column::UpdatedAt.lt(before))
+            .order_by_asc(Column::CreatedAt);
+        crud::find_all(&self.conn(), query).await
+    }
+}
+
+/// Runs of the workflows in the current scope
+fn visible_runs() -> Result<Select<workflow_run::Entity>, DomainError> {
+    let mut query = workflow_run::Entity::find()
+        .inner_join(workflow_version::Entity)
+        .join(
+            JoinType::InnerJoin,
+            workflow_version::Relation::Workflow.def(),
+        );
+    if let Some(workspace_id) = workspace_scope::current_scope()? {
+        query = query.filter(workflow::Column::WorkspaceId.eq(workspace_id));
+    }
+    Ok(query)
+}
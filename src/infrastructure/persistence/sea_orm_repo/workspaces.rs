@@ -0,0 +1,182 @@
+use crate::application::ports::output::{
+    InvitationRepository, RoleRepository, SsoConfigRepository, WorkspaceRepository,
+};
+use crate::domain::permissions::RoleDefinition;
+use crate::domain::{DomainError, Invitation, SsoConfig, Workspace, WorkspaceMember};
+use crate::infrastructure::persistence::crud;
+use crate::infrastructure::persistence::entities::{
+    workspace, workspace_invitation, workspace_member, workspace_role, workspace_sso_config,
+};
+use crate::infrastructure::persistence::errors::map_db_err;
+use crate::infrastructure::persistence::mapper::Mapper;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sea_orm::sea_query::Expr;
+use sea_orm::*;
+use uuid::Uuid;
+
+repository!(
+    /// Workspaces with their members, invitations, roles and SSO configuration
+    SeaOrmWorkspaceRepo
+);
+
+#[async_trait]
+impl WorkspaceRepository for SeaOrmWorkspaceRepo {
+    async fn create(&self, workspace: Workspace) -> Result<Workspace, DomainError> {
+        crud::insert(&self.conn(), workspace).await
+    }
+
+    async fn find_by_subdomain(&self, subdomain: &str) -> Result<Option<Workspace>, DomainError> {
+        crud::find_one(
+            &self.conn(),
+            workspace::Entity::find().filter(workspace::Column::Subdomain.eq(subdomain)),
+        )
+        .await
+    }
+
+    async fn add_member(&self, member: WorkspaceMember) -> Result<WorkspaceMember, DomainError> {
+        crud::insert(&self.conn(), member).await
+    }
+
+    async fn find_members_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<WorkspaceMember>, DomainError> {
+        let select = workspace_member::Entity::find()
+            .filter(workspace_member::Column::UserId.eq(user_id))
+            .order_by_asc(workspace_member::Column::CreatedAt);
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn find_member_by_id(&self, id: Uuid) -> Result<Option<WorkspaceMember>, DomainError> {
+        crud::find_one(&self.conn(), workspace_member::Entity::find_by_id(id)).await
+    }
+
+    async fn update_member(&self, member: WorkspaceMember) -> Result<WorkspaceMember, DomainError> {
+        crud::update(&self.conn(), member.to_active_model()).await
+    }
+
+    async fn find_members(&self, workspace_id: Uuid) -> Result<Vec<WorkspaceMember>, DomainError> {
+        let select = workspace_member::Entity::find()
+            .filter(workspace_member::Column::WorkspaceId.eq(workspace_id))
+            .order_by_asc(workspace_member::Column::CreatedAt);
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn remove_member(&self, id: Uuid) -> Result<(), DomainError> {
+        workspace_member::Entity::delete_by_id(id)
+            .exec(&self.conn())
+            .await
+            .map_err(map_db_err)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl InvitationRepository for SeaOrmWorkspaceRepo {
+    async fn create(&self, invitation: Invitation) -> Result<Invitation, DomainError> {
+        crud::insert(&self.conn(), invitation).await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Invitation>, DomainError> {
+        crud::find_one(&self.conn(), workspace_invitation::Entity::find_by_id(id)).await
+    }
+
+    async fn find_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<Invitation>, DomainError> {
+        let select = workspace_invitation::Entity::find()
+            .filter(workspace_invitation::Column::TokenHash.eq(token_hash));
+        crud::find_one(&self.conn(), select).await
+    }
+
+    async fn find_open(&self, workspace_id: Uuid) -> Result<Vec<Invitation>, DomainError> {
+        let select = workspace_invitation::Entity::find()
+            .filter(workspace_invitation::Column::WorkspaceId.eq(workspace_id))
+            .filter(workspace_invitation::Column::AcceptedAt.is_null())
+            .filter(workspace_invitation::Column::RevokedAt.is_null())
+            .order_by_desc(workspace_invitation::Column::CreatedAt);
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn update(&self, invitation: Invitation) -> Result<Invitation, DomainError> {
+        use workspace_invitation::Column;
+        let changes = invitation.to_changes(&[
+            Column::Role,
+            Column::ExpiresAt,
+            Column::AcceptedAt,
+            Column::RevokedAt,
+            Column::UpdatedAt,
+        ]);
+        crud::update(&self.conn(), changes).await
+    }
+
+    async fn mark_accepted(
+        &self,
+        id: Uuid,
+        accepted_at: DateTime<Utc>,
+    ) -> Result<bool, DomainError> {
+        use workspace_invitation::Column;
+        let result = workspace_invitation::Entity::update_many()
+            .col_expr(Column::AcceptedAt, Expr::value(accepted_at))
+            .col_expr(Column::UpdatedAt, Expr::value(accepted_at))
+            .filter(Column::Id.eq(id))
+            .filter(Column::AcceptedAt.is_null())
+            .filter(Column::RevokedAt.is_null())
+            .exec(&self.conn())
+            .await
+            .map_err(map_db_err)?;
+        Ok(result.rows_affected == 1)
+    }
+}
+
+#[async_trait]
+impl RoleRepository for SeaOrmWorkspaceRepo {
+    async fn find_all(&self, workspace_id: Uuid) -> Result<Vec<RoleDefinition>, DomainError> {
+        let select = workspace_role::Entity::find()
+            .filter(workspace_role::Column::WorkspaceId.eq(workspace_id))
+            .order_by_asc(workspace_role::Column::Name);
+        crud::find_all(&self.conn(), select).await
+    }
+
+    async fn find_by_name(
+        &self,
+        workspace_id: Uuid,
+        name: &str,
+    ) -> Result<Option<RoleDefinition>, DomainError> {
+        let select = workspace_role::Entity::find()
+            .filter(workspace_role::Column::WorkspaceId.eq(workspace_id))
+            .filter(workspace_role::Column::Name.eq(name));
+        crud::find_one(&self.conn(), select).await
+    }
+
+    async fn create(&self, role: RoleDefinition) -> Result<RoleDefinition, DomainError> {
+        crud::insert(&self.conn(), role).await
+    }
+}
+
+#[async_trait]
+impl SsoConfigRepository for SeaOrmWorkspaceRepo {
+    async fn find_by_workspace_id(
+        &self,
+        workspace_id: Uuid,
+    ) -> Result<Option<SsoConfig>, DomainError> {
+        let select = workspace_sso_config::Entity::find()
+            .filter(workspace_sso_config::Column::WorkspaceId.eq(workspace_id));
+        crud::find_one(&self.conn(), select).await
+    }
+
+    async fn save(&self, config: SsoConfig) -> Result<SsoConfig, DomainError> {
+        let existing = workspace_sso_config::Entity::find_by_id(config.id)
+            .one(&self.conn())
+            .await
+            .map_err(map_db_err)?;
+
+        if existing.is_some() {
+            crud::update(&self.conn(), config.to_active_model()).await
+        } else {
+            crud::insert(&self.conn(), config).await
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! Ambient transactions behind `UnitOfWork`.
+//!
+//! A unit of work opens a transaction and runs its work with it as a task-local; every query
+//! of the SeaORM repositories goes through `connection`, which picks that transaction up and
+//! falls back to the pool outside of any unit of work.
+
+use super::errors::map_db_err;
+use crate::domain::DomainError;
+use async_trait::async_trait;
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, DatabaseTransaction, DbBackend, DbErr, ExecResult,
+    QueryResult, Statement, TransactionTrait,
+};
+use std::future::Future;
+use std::sync::Arc;
+
+tokio::task_local! {
+    static TRANSACTION: Arc<DatabaseTransaction>;
+}
+
+/// Connection a query runs on: the ambient transaction, or the pool
+pub enum Connection<'a> {
+    Pool(&'a DatabaseConnection),
+    Transaction(Arc<DatabaseTransaction>),
+}
+
+pub fn connection(db: &DatabaseConnection) -> Connection<'_> {
+    TRANSACTION
+        .try_with(|txn| Connection::Transaction(txn.clone()))
+        .unwrap_or(Connection::Pool(db))
+}
+
+/// Runs `work` in a new transaction, or in a savepoint of the ambient one, committing
+/// when it succeeds and rolling back when it fails
+pub async fn run<F>(db: &DatabaseConnection, work: F) -> Result<(), DomainError>
+where
+    F: Future<Output = Result<(), DomainError>>,
+{
+    let begun = match TRANSACTION.try_with(|txn| txn.clone()) {
+        Ok(outer) => outer.begin().await,
+        Err(_) => db.begin().await,
+    };
+    let txn = Arc::new(begun.map_err(map_db_err)?);
+
+    let result = TRANSACTION.scope(txn.clone(), work).await;
+
+    // Queries hold their clone only while they run, so this is the last one
+    let txn = Arc::try_unwrap(txn).map_err(|_| {
+        DomainError::InfrastructureError("Transaction still in use after its work".to_string())
+    })?;
+    match result {
+        Ok(()) => txn.commit().await.map_err(map_db_err),
+        Err(e) => {
+            if let Err(rollback) = txn.rollback().await {
+                tracing::error!("Failed to roll back transaction: {}", rollback);
+            }
+            Err(e)
+        }
+    }
+}
+
+#[async_trait]
+impl ConnectionTrait for Connection<'_> {
+    fn get_database_backend(&self) -> DbBackend {
+        match self {
+            Connection::Pool(db) => db.get_database_backend(),
+            Connection::Transaction(txn) => txn.get_database_backend(),
+        }
+    }
+
+    async fn execute(&self, stmt: Statement) -> Result<ExecResult, DbErr> {
+        match self {
+            Connection::Pool(db) => db.execute(stmt).await,
+            Connection::Transaction(txn) => txn.execute(stmt).await,
+        }
+    }
+
+    async fn execute_unprepared(&self, sql: &str) -> Result<ExecResult, DbErr> {
+        match self {
+            Connection::Pool(db) => db.execute_unprepared(sql).await,
+            Connection::Transaction(txn) => txn.execute_unprepared(sql).await,
+        }
+    }
+
+    async fn query_one(&self, stmt: Statement) -> Result<Option<QueryResult>, DbErr> {
+        match self {
+            Connection::Pool(db) => db.query_one(stmt).await,
+            Connection::Transaction(txn) => txn.query_one(stmt).await,
+        }
+    }
+
+    async fn query_all(&self, stmt: Statement) -> Result<Vec<QueryResult>, DbErr> {
+        match self {
+            Connection::Pool(db) => db.query_all(stmt).await,
+            Connection::Transaction(txn) => txn.query_all(stmt).await,
+        }
+    }
+}
@@ -0,0 +1,362 @@
+//! Soft delete for the records that have a `deleted_at` column.
+//!
+//! Deleting a record stamps it, and the task targets and timeline activities linked to it,
+//! with the same `deleted_at`. Restoring clears the stamp on the record and on the links
+//! carrying that exact stamp, so links trashed separately stay in the trash. Purging removes
+//! the record for good along with its task targets and trashed timeline activities; live
+//! timeline activities only lose their link to it.
+
+use super::entities::{
+    company, lead, note, opportunity, person, task, task_target, timeline_activity,
+};
+use super::errors::map_db_err;
+use super::transaction;
+use super::workspace_scope::{self, WorkspaceScoped};
+use crate::domain::{DomainError, TrashedKind, TrashedRecord};
+use chrono::{DateTime, Utc};
+use sea_orm::sea_query::{Expr, SimpleExpr};
+use sea_orm::*;
+use uuid::Uuid;
+
+/// Records purged per round trip, which keeps `IN (...)` lists within database limits
+const PURGE_BATCH: u64 = 500;
+
+/// An entity that is moved to the trash instead of being deleted
+pub trait Trashable: WorkspaceScoped {
+    fn id_column() -> Self::Column;
+    fn deleted_at_column() -> Self::Column;
+    /// Column of `task_target` pointing at the record, if tasks can target it
+    fn task_target_link() -> Option<task_target::Column>;
+    /// Column of `timeline_activity` pointing at the record, if activities can link to it
+    fn timeline_link() -> Option<timeline_activity::Column>;
+    fn to_trashed(model: Self::Model) -> TrashedRecord;
+}
+
+macro_rules! trashable {
+    ($entity:ident, $kind:ident, $task_target:expr, $timeline:expr, |$model:ident| $name:expr) => {
+        impl Trashable for $entity::Entity {
+            fn id_column() -> Self::Column {
+                $entity::Column::Id
+            }
+
+            fn deleted_at_column() -> Self::Column {
+                $entity::Column::DeletedAt
+            }
+
+            fn task_target_link() -> Option<task_target::Column> {
+                $task_target
+            }
+
+            fn timeline_link() -> Option<timeline_activity::Column> {
+                $timeline
+            }
+
+            fn to_trashed($model: $entity::Model) -> TrashedRecord {
+                TrashedRecord {
+                    kind: TrashedKind::$kind,
+                    id: $model.id,
+                    name: $name,
+                    deleted_at: $model.deleted_at.map(|d| d.into()).unwrap_or_default(),
+                }
+            }
+        }
+    };
+}
+
+trashable!(
+    person,
+    Person,
+    Some(task_target::Column::PersonId),
+    Some(timeline_activity::Column::PersonId),
+    |m| m.name
+);
+trashable!(
+    company,
+    Company,
+    Some(task_target::Column::CompanyId),
+    Some(timeline_activity::Column::CompanyId),
+    |m| m.name
+);
+trashable!(
+    opportunity,
+    Opportunity,
+    Some(task_target::Column::OpportunityId),
+    Some(timeline_activity::Column::OpportunityId),
+    |m| m.name
+);
+trashable!(
+    task,
+    Task,
+    Some(task_target::Column::TaskId),
+    Some(timeline_activity::Column::TaskId),
+    |m| m.title
+);
+trashable!(
+    note,
+    Note,
+    None,
+    Some(timeline_activity::Column::NoteId),
+    |m| m.title
+);
+trashable!(lead, Lead, None, None, |m| format!(
+    "{} {}",
+    m.first_name, m.last_name
+));
+
+/// Runs `$body` with `$entity` bound to the entity of `$kind`
+macro_rules! with_entity {
+    ($kind:expr, $entity:ident => $body:expr) => {
+        match $kind {
+            TrashedKind::Person => {
+                type $entity = person::Entity;
+                $body
+            }
+            TrashedKind::Company => {
+                type $entity = company::Entity;
+                $body
+            }
+            TrashedKind::Opportunity => {
+                type $entity = opportunity::Entity;
+                $body
+            }
+            TrashedKind::Task => {
+                type $entity = task::Entity;
+                $body
+            }
+            TrashedKind::Note => {
+                type $entity = note::Entity;
+                $body
+            }
+            TrashedKind::Lead => {
+                type $entity = lead::Entity;
+                $body
+            }
+        }
+    };
+}
+
+/// Moves the live record `id` to the trash together with its links
+pub async fn soft_delete<E>(db: &DatabaseConnection, id: Uuid) -> Result<(), DomainError>
+where
+    E: Trashable,
+{
+    transaction::run(db, async {
+        let conn = transaction::connection(db);
+        let deleted_at = Value::from(Utc::now());
+        let result = workspace_scope::update_many::<E>()?
+            .col_expr(E::deleted_at_column(), Expr::value(deleted_at.clone()))
+            .filter(E::id_column().eq(id))
+            .filter(E::deleted_at_column().is_null())
+            .exec(&conn)
+            .await
+            .map_err(map_db_err)?;
+        if result.rows_affected == 0 {
+            return Err(DomainError::NotFound);
+        }
+
+        // The record is visible, so its links are too
+        if let Some(link) = E::task_target_link() {
+            task_target::Entity::update_many()
+                .col_expr(
+                    task_target::Column::DeletedAt,
+                    Expr::value(deleted_at.clone()),
+                )
+                .filter(link.eq(id))
+                .filter(task_target::Column::DeletedAt.is_null())
+                .exec(&conn)
+                .await
+                .map_err(map_db_err)?;
+        }
+        if let Some(link) = E::timeline_link() {
+            timeline_activity::Entity::update_many()
+                .col_expr(
+                    timeline_activity::Column::DeletedAt,
+                    Expr::value(deleted_at),
+                )
+                .filter(link.eq(id))
+                .filter(timeline_activity::Column::DeletedAt.is_null())
+                .exec(&conn)
+                .await
+                .map_err(map_db_err)?;
+        }
+        Ok(())
+    })
+    .await
+}
+
+/// Trashed records of `kind` in the current workspace
+pub async fn find_trashed(
+    db: &impl ConnectionTrait,
+    kind: TrashedKind,
+) -> Result<Vec<TrashedRecord>, DomainError> {
+    with_entity!(kind, E => trashed_records::<E>(db).await)
+}
+
+/// Takes the trashed record `id` of `kind` out of the trash with the links trashed along with it
+pub async fn restore(
+    db: &DatabaseConnection,
+    kind: TrashedKind,
+    id: Uuid,
+) -> Result<(), DomainError> {
+    with_entity!(kind, E => restore_record::<E>(db, id).await)
+}
+
+/// Permanently deletes the trashed record `id` of `kind`
+pub async fn purge(
+    db: &DatabaseConnection,
+    kind: TrashedKind,
+    id: Uuid,
+) -> Result<(), DomainError> {
+    with_entity!(kind, E => purge_record::<E>(db, id).await)
+}
+
+/// Permanently deletes the records of `kind` trashed before `cutoff`; returns how many
+pub async fn purge_deleted_before(
+    db: &DatabaseConnection,
+    kind: TrashedKind,
+    cutoff: DateTime<Utc>,
+) -> Result<u64, DomainError> {
+    with_entity!(kind, E => purge_expired::<E>(db, cutoff).await)
+}
+
+async fn trashed_records<E>(db: &impl ConnectionTrait) -> Result<Vec<TrashedRecord>, DomainError>
+where
+    E: Trashable,
+{
+    let models = workspace_scope::find::<E>()?
+        .filter(E::deleted_at_column().is_not_null())
+        .all(db)
+        .await
+        .map_err(map_db_err)?;
+    Ok(models.into_iter().map(E::to_trashed).collect())
+}
+
+async fn restore_record<E>(db: &DatabaseConnection, id: Uuid) -> Result<(), DomainError>
+where
+    E: Trashable,
+    Uuid: Into<<E::PrimaryKey as PrimaryKeyTrait>::ValueType>,
+{
+    transaction::run(db, async {
+        let conn = transaction::connection(db);
+        let model = workspace_scope::find_by_id::<E>(id)?
+            .filter(E::deleted_at_column().is_not_null())
+            .one(&conn)
+            .await
+            .map_err(map_db_err)?
+            .ok_or(DomainError::NotFound)?;
+        let deleted_at = model.get(E::deleted_at_column());
+        let live = Expr::value(Option::<DateTime<Utc>>::None);
+
+        workspace_scope::update_many::<E>()?
+            .col_expr(E::deleted_at_column(), live.clone())
+            .filter(E::id_column().eq(id))
+            .exec(&conn)
+            .await
+            .map_err(map_db_err)?;
+        if let Some(link) = E::task_target_link() {
+            task_target::Entity::update_many()
+                .col_expr(task_target::Column::DeletedAt, live.clone())
+                .filter(link.eq(id))
+                .filter(task_target::Column::DeletedAt.eq(deleted_at.clone()))
+                .exec(&conn)
+                .await
+                .map_err(map_db_err)?;
+        }
+        if let Some(link) = E::timeline_link() {
+            timeline_activity::Entity::update_many()
+                .col_expr(timeline_activity::Column::DeletedAt, live)
+                .filter(link.eq(id))
+                .filter(timeline_activity::Column::DeletedAt.eq(deleted_at))
+                .exec(&conn)
+                .await
+                .map_err(map_db_err)?;
+        }
+        Ok(())
+    })
+    .await
+}
+
+async fn purge_record<E>(db: &DatabaseConnection, id: Uuid) -> Result<(), DomainError>
+where
+    E: Trashable,
+{
+    let purged = purge_where::<E>(db, E::id_column().eq(id)).await?;
+    if purged == 0 {
+        return Err(DomainError::NotFound);
+    }
+    Ok(())
+}
+
+async fn purge_expired<E>(
+    db: &DatabaseConnection,
+    cutoff: DateTime<Utc>,
+) -> Result<u64, DomainError>
+where
+    E: Trashable,
+{
+    let mut purged = 0;
+    loop {
+        let batch = purge_where::<E>(db, E::deleted_at_column().lt(cutoff)).await?;
+        purged += batch;
+        if batch < PURGE_BATCH {
+            return Ok(purged);
+        }
+    }
+}
+
+/// Purges up to `PURGE_BATCH` trashed records of `E` matching `condition`
+async fn purge_where<E>(db: &DatabaseConnection, condition: SimpleExpr) -> Result<u64, DomainError>
+where
+    E: Trashable,
+{
+    let mut purged = 0;
+    transaction::run(db, async {
+        let conn = transaction::connection(db);
+        let ids: Vec<Uuid> = workspace_scope::find::<E>()?
+            .select_only()
+            .column(E::id_column())
+            .filter(E::deleted_at_column().is_not_null())
+            .filter(condition)
+            .limit(PURGE_BATCH)
+            .into_tuple()
+            .all(&conn)
+            .await
+            .map_err(map_db_err)?;
+        if ids.is_empty() {
+            return Ok(());
+        }
+
+        // A task target means nothing without either end
+        if let Some(link) = E::task_target_link() {
+            task_target::Entity::delete_many()
+                .filter(link.is_in(ids.clone()))
+                .exec(&conn)
+                .await
+                .map_err(map_db_err)?;
+        }
+        // Activities may also belong to other records, which keep them
+        if let Some(link) = E::timeline_link() {
+            timeline_activity::Entity::delete_many()
+                .filter(link.is_in(ids.clone()))
+                .filter(timeline_activity::Column::DeletedAt.is_not_null())
+                .exec(&conn)
+                .await
+                .map_err(map_db_err)?;
+            timeline_activity::Entity::update_many()
+                .col_expr(link, Expr::value(Option::<Uuid>::None))
+                .filter(link.is_in(ids.clone()))
+                .exec(&conn)
+                .await
+                .map_err(map_db_err)?;
+        }
+        purged = E::delete_many()
+            .filter(E::id_column().is_in(ids))
+            .exec(&conn)
+            .await
+            .map_err(map_db_err)?
+            .rows_affected;
+        Ok(())
+    })
+    .await?;
+    Ok(purged)
+}
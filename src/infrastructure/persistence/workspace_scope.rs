@@ -0,0 +1,197 @@
+//! Tenant isolation for the SeaORM repositories.
+//!
+//! Every query on a workspace-owned table is built through the helpers below, which
+//! restrict it to the workspace of the ambient actor (see `application::context`).
+//! Users and workspace-bound background work only ever see their own workspace, system
+//! work sees every workspace and anonymous callers see nothing.
+
+use super::errors::map_db_err;
+use crate::application::context::{current_actor, Actor};
+use crate::domain::DomainError;
+use crate::infrastructure::persistence::entities;
+use sea_orm::*;
+use uuid::Uuid;
+
+/// An entity whose rows belong to a single workspace
+pub trait WorkspaceScoped: EntityTrait {
+    fn workspace_column() -> Self::Column;
+}
+
+macro_rules! workspace_scoped {
+    ($($entity:ident),* $(,)?) => {
+        $(
+            impl WorkspaceScoped for entities::$entity::Entity {
+                fn workspace_column() -> Self::Column {
+                    entities::$entity::Column::WorkspaceId
+                }
+            }
+        )*
+    };
+}
+
+workspace_scoped!(
+    calendar_event,
+    company,
+    custom_object_data,
+    email,
+    email_template,
+    lead,
+    note,
+    object_metadata,
+    opportunity,
+    person,
+    task,
+    timeline_activity,
+    view,
+    workflow,
+);
+
+/// Workspace the current query is restricted to; `None` for system work, which spans
+/// every workspace
+pub fn current_scope() -> Result<Option<Uuid>, DomainError> {
+    match current_actor() {
+        Some(Actor::System) => Ok(None),
+        Some(Actor::Workspace(workspace_id)) => Ok(Some(workspace_id)),
+        Some(Actor::User(identity)) => match identity.member {
+            Some(member) => Ok(Some(member.workspace_id)),
+            None => Err(DomainError::Permission(
+                "Not a member of any workspace".to_string(),
+            )),
+        },
+        None => Err(DomainError::Permission(
+            "Authentication required".to_string(),
+        )),
+    }
+}
+
+/// `E::find()` restricted to the current workspace
+pub fn find<E: WorkspaceScoped>() -> Result<Select<E>, DomainError> {
+    let query = E::find();
+    Ok(match current_scope()? {
+        Some(workspace_id) => query.filter(E::workspace_column().eq(workspace_id)),
+        None => query,
+    })
+}
+
+/// `E::find_by_id(id)` restricted to the current workspace
+pub fn find_by_id<E>(id: Uuid) -> Result<Select<E>, DomainError>
+where
+    E: WorkspaceScoped,
+    Uuid: Into<<E::PrimaryKey as PrimaryKeyTrait>::ValueType>,
+{
+    let query = E::find_by_id(id);
+    Ok(match current_scope()? {
+        Some(workspace_id) => query.filter(E::workspace_column().eq(workspace_id)),
+        None => query,
+    })
+}
+
+/// Rejects a model that would be written to another workspace
+pub fn check_owner<A>(model: &A) -> Result<(), DomainError>
+where
+    A: ActiveModelTrait,
+    A::Entity: WorkspaceScoped,
+{
+    let Some(workspace_id) = current_scope()? else {
+        return Ok(());
+    };
+    match model.get(A::Entity::workspace_column()).into_value() {
+        Some(value) if value != Value::from(workspace_id) => Err(DomainError::Permission(
+            "Record belongs to another workspace".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// `E::update(model)` restricted to the current workspace; executing it fails with
+/// `DbErr::RecordNotUpdated` when the row is not visible, which `map_db_err` reports as
+/// `NotFound`
+pub fn update<A>(model: A) -> Result<UpdateOne<A>, DomainError>
+where
+    A: ActiveModelTrait,
+    A::Entity: WorkspaceScoped,
+{
+    check_owner(&model)?;
+    let query = A::Entity::update(model);
+    Ok(match current_scope()? {
+        Some(workspace_id) => query.filter(A::Entity::workspace_column().eq(workspace_id)),
+        None => query,
+    })
+}
+
+/// `E::update_many()` restricted to the current workspace
+pub fn update_many<E: WorkspaceScoped>() -> Result<UpdateMany<E>, DomainError> {
+    let query = E::update_many();
+    Ok(match current_scope()? {
+        Some(workspace_id) => query.filter(E::workspace_column().eq(workspace_id)),
+        None => query,
+    })
+}
+
+/// `E::delete_by_id(id)` restricted to the current workspace
+pub fn delete_by_id<E>(id: Uuid) -> Result<DeleteMany<E>, DomainError>
+where
+    E: WorkspaceScoped,
+    Uuid: Into<<E::PrimaryKey as PrimaryKeyTrait>::ValueType>,
+{
+    let query = E::delete_by_id(id);
+    Ok(match current_scope()? {
+        Some(workspace_id) => query.filter(E::workspace_column().eq(workspace_id)),
+        None => query,
+    })
+}
+
+/// Maps the error of a compare-and-swap update, i.e. `update(model)` filtered on the version
+/// the caller read: a row that is still visible but was not updated has changed since
+pub async fn map_versioned_write_err<E>(
+    db: &impl ConnectionTrait,
+    id: Uuid,
+    e: DbErr,
+) -> DomainError
+where
+    E: WorkspaceScoped,
+    Uuid: Into<<E::PrimaryKey as PrimaryKeyTrait>::ValueType>,
+{
+    if !matches!(e, DbErr::RecordNotUpdated) {
+        return map_db_err(e);
+    }
+    let current = match find_by_id::<E>(id) {
+        Ok(query) => query.one(db).await,
+        Err(e) => return e,
+    };
+    match current {
+        Ok(Some(_)) => version_conflict(),
+        Ok(None) => DomainError::NotFound,
+        Err(e) => map_db_err(e),
+    }
+}
+
+/// Error of an update based on a version of the record that is no longer current
+pub fn version_conflict() -> DomainError {
+    DomainError::Conflict(
+        "The record was changed by someone else; reload it and try again".to_string(),
+    )
+}
+
+/// Fails with `NotFound` when a scoped delete matched no row
+pub fn ensure_deleted(result: DeleteResult) -> Result<(), DomainError> {
+    if result.rows_affected == 0 {
+        return Err(DomainError::NotFound);
+    }
+    Ok(())
+}
+
+/// Fails with `NotFound` unless the row `id` of `E` is in the current workspace. Child
+/// tables without their own `workspace_id` are scoped through their parent this way.
+pub async fn ensure_visible<E>(db: &impl ConnectionTrait, id: Uuid) -> Result<(), DomainError>
+where
+    E: WorkspaceScoped,
+    Uuid: Into<<E::PrimaryKey as PrimaryKeyTrait>::ValueType>,
+{
+    find_by_id::<E>(id)?
+        .one(db)
+        .await
+        .map_err(map_db_err)?
+        .map(|_| ())
+        .ok_or(DomainError::NotFound)
+}
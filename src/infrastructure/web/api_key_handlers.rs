@@ -0,0 +1,82 @@
+use crate::application::use_cases::manage_api_keys::ManageApiKeys;
+use crate::domain::entities::ApiKey;
+use crate::domain::permissions::ApiKeyScope;
+use crate::infrastructure::web::errors::error_status;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct ApiKeyAppState {
+    pub manage_api_keys: Arc<ManageApiKeys>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateApiKeyPayload {
+    pub name: String,
+    pub scope: ApiKeyScope,
+    /// Object keys (e.g. "lead" or "custom_object:<id>") the key is limited to
+    pub objects: Option<Vec<String>>,
+}
+
+/// A new key with its token; the token cannot be retrieved again
+#[derive(Serialize)]
+pub struct CreatedApiKey {
+    #[serde(flatten)]
+    pub api_key: ApiKey,
+    pub token: String,
+}
+
+// GET /api/api-keys - List the current member's keys
+pub async fn list_api_keys_handler(State(state): State<ApiKeyAppState>) -> impl IntoResponse {
+    match state.manage_api_keys.list().await {
+        Ok(keys) => Json(keys).into_response(),
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+// POST /api/api-keys - Create a key
+pub async fn create_api_key_handler(
+    State(state): State<ApiKeyAppState>,
+    Json(payload): Json<CreateApiKeyPayload>,
+) -> impl IntoResponse {
+    match state
+        .manage_api_keys
+        .create(payload.name, payload.scope, payload.objects)
+        .await
+    {
+        Ok((api_key, token)) => {
+            (StatusCode::CREATED, Json(CreatedApiKey { api_key, token })).into_response()
+        }
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+// DELETE /api/api-keys/:id - Revoke a key
+pub async fn revoke_api_key_handler(
+    State(state): State<ApiKeyAppState>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.manage_api_keys.revoke(id).await {
+        Ok(api_key) => Json(api_key).into_response(),
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
@@ -0,0 +1,141 @@
+use crate::application::context::{self, Actor};
+use crate::application::ports::identity::{Credential, Identity, IdentityProvider};
+use crate::domain::entities::{ApiKey, User, Workspace, WorkspaceMember};
+use crate::infrastructure::web::handlers::SESSION_COOKIE;
+use async_trait::async_trait;
+use axum::{
+    extract::{FromRequestParts, Request, State},
+    http::{header, request::Parts, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Redirect, Response},
+    Json,
+};
+use axum_extra::extract::CookieJar;
+use std::sync::Arc;
+
+/// Resolves the caller from the `Authorization: Bearer` header (API key or session token)
+/// or the session cookie, stores the `Identity` in the request extensions and runs the
+/// rest of the request with it as the ambient actor. The membership is the one in the workspace resolved
+/// by `workspace_middleware`, if any. Anonymous requests pass through untouched; the
+/// `CurrentUser`/`CurrentMember` extractors and the use cases reject them.
+pub async fn identity_middleware(
+    State(identity_provider): State<Arc<dyn IdentityProvider>>,
+    jar: CookieJar,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let bearer = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(|t| Credential::Bearer(t.trim().to_string()));
+    // API keys are for the JSON API only, never for browsing the app
+    let bearer = bearer.filter(|c| match c {
+        Credential::Bearer(t) => {
+            !t.starts_with(ApiKey::TOKEN_PREFIX) || is_api_path(request.uri().path())
+        }
+        Credential::Session(_) => true,
+    });
+    let credential = bearer.or_else(|| {
+        jar.get(SESSION_COOKIE)
+            .map(|c| Credential::Session(c.value().to_string()))
+    });
+
+    let workspace_id = request.extensions().get::<Workspace>().map(|w| w.id);
+
+    if let Some(credential) = credential {
+        match identity_provider
+            .authenticate(&credential, workspace_id)
+            .await
+        {
+            Ok(Some(identity)) => {
+                request.extensions_mut().insert(identity.clone());
+                return context::run_as(Actor::User(Box::new(identity)), next.run(request)).await;
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("Error resolving identity: {}", e),
+        }
+    }
+
+    next.run(request).await
+}
+
+/// The authenticated user, regardless of workspace membership
+pub struct CurrentUser(pub User);
+
+/// The authenticated user's membership in the current workspace
+pub struct CurrentMember(pub WorkspaceMember);
+
+/// JSON endpoints answer errors with JSON instead of redirects or pages
+pub(crate) fn is_api_path(path: &str) -> bool {
+    path.starts_with("/api/") || path.starts_with("/webhooks/")
+}
+
+fn is_api_request(parts: &Parts) -> bool {
+    is_api_path(parts.uri.path())
+}
+
+fn unauthenticated(parts: &Parts) -> Response {
+    if is_api_request(parts) {
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({ "error": "Authentication required" })),
+        )
+            .into_response();
+    }
+    if parts.headers.contains_key("HX-Request") {
+        return (StatusCode::UNAUTHORIZED, [("HX-Redirect", "/login")], "").into_response();
+    }
+    Redirect::to("/login").into_response()
+}
+
+fn no_workspace(parts: &Parts) -> Response {
+    // Routed to a workspace the user does not belong to
+    if parts.extensions.get::<Workspace>().is_some() {
+        if is_api_request(parts) {
+            return (
+                StatusCode::FORBIDDEN,
+                Json(serde_json::json!({ "error": "Not a member of this workspace" })),
+            )
+                .into_response();
+        }
+        return (StatusCode::FORBIDDEN, "Not a member of this workspace").into_response();
+    }
+    if is_api_request(parts) {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(serde_json::json!({ "error": "Not a member of any workspace" })),
+        )
+            .into_response();
+    }
+    Redirect::to("/workspaces").into_response()
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for CurrentUser {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        match parts.extensions.get::<Identity>() {
+            Some(identity) => Ok(CurrentUser(identity.user.clone())),
+            None => Err(unauthenticated(parts)),
+        }
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for CurrentMember {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        match parts.extensions.get::<Identity>() {
+            Some(Identity {
+                member: Some(member),
+                ..
+            }) => Ok(CurrentMember(member.clone())),
+            Some(_) => Err(no_workspace(parts)),
+            None => Err(unauthenticated(parts)),
+        }
+    }
+}
@@ -1,5 +1,8 @@
 use crate::application::use_cases::manage_custom_object_data::ManageCustomObjectData;
 use crate::application::use_cases::manage_metadata::ManageMetadata;
+use crate::domain::DomainError;
+use crate::infrastructure::web::auth::CurrentMember;
+use crate::infrastructure::web::errors::{conflict_response, error_status, required_version};
 use axum::{
     extract::{Path, State},
     http::StatusCode,
@@ -24,6 +27,8 @@ pub struct CreateRecordPayload {
 #[derive(Deserialize)]
 pub struct UpdateRecordPayload {
     pub properties: serde_json::Value,
+    /// Version of the record the change is based on, required, see `required_version`
+    pub version: Option<i32>,
 }
 
 pub async fn list_records_handler(
@@ -37,7 +42,7 @@ pub async fn list_records_handler(
     {
         Ok(records) => Json(records).into_response(),
         Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
+            error_status(&e),
             Json(serde_json::json!({ "error": e.to_string() })),
         )
             .into_response(),
@@ -46,17 +51,18 @@ pub async fn list_records_handler(
 
 pub async fn create_record_handler(
     State(state): State<CustomObjectAppState>,
+    CurrentMember(member): CurrentMember,
     Path(object_id): Path<Uuid>,
     Json(payload): Json<CreateRecordPayload>,
 ) -> impl IntoResponse {
     match state
         .manage_custom_object_data
-        .create_record(object_id, payload.properties, Uuid::default())
+        .create_record(object_id, payload.properties, member.workspace_id)
         .await
     {
         Ok(record) => (StatusCode::CREATED, Json(record)).into_response(),
         Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
+            error_status(&e),
             Json(serde_json::json!({ "error": e.to_string() })),
         )
             .into_response(),
@@ -75,7 +81,7 @@ pub async fn get_record_handler(
         )
             .into_response(),
         Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
+            error_status(&e),
             Json(serde_json::json!({ "error": e.to_string() })),
         )
             .into_response(),
@@ -87,14 +93,33 @@ pub async fn update_record_handler(
     Path(id): Path<Uuid>,
     Json(payload): Json<UpdateRecordPayload>,
 ) -> impl IntoResponse {
+    let version = match required_version(payload.version) {
+        Ok(version) => version,
+        Err(e) => {
+            return (
+                error_status(&e),
+                Json(serde_json::json!({ "error": e.to_string() })),
+            )
+                .into_response()
+        }
+    };
     match state
         .manage_custom_object_data
-        .update_record(id, payload.properties)
+        .update_record(id, payload.properties, Some(version))
         .await
     {
         Ok(record) => Json(record).into_response(),
+        Err(e @ DomainError::Conflict(_)) => {
+            let current = state
+                .manage_custom_object_data
+                .get_record(id)
+                .await
+                .ok()
+                .flatten();
+            conflict_response(&e, current)
+        }
         Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
+            error_status(&e),
             Json(serde_json::json!({ "error": e.to_string() })),
         )
             .into_response(),
@@ -108,7 +133,7 @@ pub async fn delete_record_handler(
     match state.manage_custom_object_data.delete_record(id).await {
         Ok(_) => StatusCode::NO_CONTENT.into_response(),
         Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
+            error_status(&e),
             Json(serde_json::json!({ "error": e.to_string() })),
         )
             .into_response(),
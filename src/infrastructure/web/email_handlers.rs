@@ -1,9 +1,17 @@
+use crate::application::context;
+use crate::application::ports::identity::IdentityProvider;
 use crate::application::ports::output::{EmailRepository, EmailTemplateRepository};
 use crate::application::use_cases::manage_email_template::{
     CreateEmailTemplateInput, ManageEmailTemplate, UpdateEmailTemplateInput,
 };
 use crate::application::use_cases::receive_email::{ReceiveEmail, ReceiveEmailInput};
 use crate::application::use_cases::send_email::{SendEmail, SendEmailInput};
+use crate::domain::permissions::{objects, Permission};
+use crate::domain::DomainError;
+use crate::infrastructure::web::auth::CurrentMember;
+use crate::infrastructure::web::errors::error_status;
+use crate::infrastructure::web::list_query::ListParams;
+use crate::infrastructure::web::tenant::CurrentWorkspace;
 use axum::{
     extract::{Path, State},
     http::StatusCode,
@@ -22,6 +30,7 @@ pub struct EmailAppState {
     pub manage_email_template: Arc<ManageEmailTemplate>,
     pub email_repo: Arc<dyn EmailRepository>,
     pub email_template_repo: Arc<dyn EmailTemplateRepository>,
+    pub identity_provider: Arc<dyn IdentityProvider>,
 }
 
 #[derive(Deserialize)]
@@ -71,6 +80,7 @@ pub struct EmailTemplateResponse {
 // POST /api/emails - Send an email
 pub async fn send_email_handler(
     State(state): State<EmailAppState>,
+    CurrentMember(member): CurrentMember,
     Json(payload): Json<SendEmailPayload>,
 ) -> impl IntoResponse {
     let input = SendEmailInput {
@@ -89,7 +99,7 @@ pub async fn send_email_handler(
         task_id: payload.task_id,
         workflow_id: payload.workflow_id,
         workflow_run_id: payload.workflow_run_id,
-        workspace_id: Uuid::default(), // TODO: Get from auth context
+        workspace_id: member.workspace_id,
     };
 
     match state.send_email.execute(input).await {
@@ -125,6 +135,7 @@ pub async fn send_email_handler(
 // POST /webhooks/inbound-email - Receive inbound email webhook
 pub async fn inbound_email_webhook_handler(
     State(state): State<EmailAppState>,
+    CurrentWorkspace(workspace): CurrentWorkspace,
     Json(payload): Json<InboundEmailWebhookPayload>,
 ) -> impl IntoResponse {
     let input = ReceiveEmailInput {
@@ -134,9 +145,11 @@ pub async fn inbound_email_webhook_handler(
         body_text: payload.body_text,
         body_html: payload.body_html,
         received_at: payload.received_at.unwrap_or_else(Utc::now),
+        workspace_id: workspace.id,
     };
 
-    match state.receive_email.execute(input).await {
+    // Inbound mail is delivered by the email provider, not by a workspace member
+    match context::run_as_system(state.receive_email.execute(input)).await {
         Ok(email) => {
             let response = EmailResponse {
                 id: email.id,
@@ -191,9 +204,14 @@ pub async fn list_email_templates_handler(State(state): State<EmailAppState>) ->
 // POST /api/email-templates - Create email template
 pub async fn create_email_template_handler(
     State(state): State<EmailAppState>,
+    CurrentMember(member): CurrentMember,
     Json(input): Json<CreateEmailTemplateInput>,
 ) -> impl IntoResponse {
-    match state.manage_email_template.create(input).await {
+    match state
+        .manage_email_template
+        .create(input, member.workspace_id)
+        .await
+    {
         Ok(template) => {
             let response = EmailTemplateResponse {
                 id: template.id,
@@ -275,10 +293,29 @@ pub async fn delete_email_template_handler(
     }
 }
 
-// GET /api/emails - List all emails
-pub async fn list_emails_handler(State(state): State<EmailAppState>) -> impl IntoResponse {
-    match state.email_repo.find_all().await {
-        Ok(emails) => Json(emails).into_response(),
+// GET /api/emails - List emails a page at a time (see `list_query`)
+pub async fn list_emails_handler(
+    State(state): State<EmailAppState>,
+    ListParams(query): ListParams,
+) -> impl IntoResponse {
+    if let Err(e) = state
+        .identity_provider
+        .authorize(&Permission::read(objects::EMAIL))
+        .await
+    {
+        return (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response();
+    }
+    match state.email_repo.find_page(&query).await {
+        Ok(page) => Json(page).into_response(),
+        Err(e @ DomainError::Validation(_)) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
         Err(e) => {
             tracing::error!("Failed to list emails: {}", e);
             (
@@ -297,6 +334,17 @@ pub async fn get_email_handler(
     State(state): State<EmailAppState>,
     Path(id): Path<Uuid>,
 ) -> impl IntoResponse {
+    if let Err(e) = state
+        .identity_provider
+        .authorize(&Permission::read(objects::EMAIL))
+        .await
+    {
+        return (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response();
+    }
     match state.email_repo.find_by_id(id).await {
         Ok(Some(email)) => Json(email).into_response(),
         Ok(None) => (
@@ -0,0 +1,34 @@
+use crate::domain::DomainError;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::Serialize;
+
+/// HTTP status for a use case error in the JSON API
+pub fn error_status(error: &DomainError) -> StatusCode {
+    match error {
+        DomainError::Validation(_) | DomainError::InvalidState(_) => StatusCode::BAD_REQUEST,
+        DomainError::Permission(_) => StatusCode::FORBIDDEN,
+        DomainError::NotFound => StatusCode::NOT_FOUND,
+        DomainError::InfrastructureError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        DomainError::Conflict(_) => StatusCode::CONFLICT,
+    }
+}
+
+/// The version an update sent to the API is based on. It is required, so that a client
+/// working from a stale copy gets a conflict instead of overwriting newer changes.
+pub fn required_version(version: Option<i32>) -> Result<i32, DomainError> {
+    version.ok_or_else(|| {
+        DomainError::Validation("Missing version, the version the change is based on".to_string())
+    })
+}
+
+/// 409 for an update based on a stale version, carrying the record as it is now so the
+/// client can reapply its change on top of it
+pub fn conflict_response<T: Serialize>(error: &DomainError, current: Option<T>) -> Response {
+    (
+        StatusCode::CONFLICT,
+        Json(serde_json::json!({ "error": error.to_string(), "current": current })),
+    )
+        .into_response()
+}
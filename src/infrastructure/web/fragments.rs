@@ -1,4 +1,6 @@
-use crate::domain::{Opportunity, OpportunityStage, Person, TimelineActivity};
+use crate::domain::{
+    Invitation, Opportunity, OpportunityStage, Person, TimelineActivity, TrashedRecord,
+};
 use maud::{html, Markup, DOCTYPE};
 
 pub fn layout(content: Markup) -> Markup {
@@ -34,6 +36,7 @@ pub fn layout(content: Markup) -> Markup {
 
                              div class="border-t border-gray-700 my-4" {}
 
+                             a href="/trash" class="block py-2.5 px-4 rounded transition duration-200 hover:bg-gray-700 hover:text-white" { "Trash" }
                              a href="/settings/objects" class="block py-2.5 px-4 rounded transition duration-200 hover:bg-gray-700 hover:text-white" { "Settings" }
                          }
                     }
@@ -60,6 +63,7 @@ pub fn board_card(opportunity: &Opportunity) -> Markup {
     html! {
         div
             id=(format!("card-{}", opportunity.id))
+            data-version=(opportunity.version)
             class=(format!("p-4 mb-2 rounded shadow cursor-move bg-white {}", stage_class))
             x-data=(format!("{{ dragging: false, id: '{}' }}", opportunity.id))
             draggable="true"
@@ -98,11 +102,14 @@ pub fn kanban_board(opportunities: &[Opportunity]) -> Markup {
                         "ondragover"="event.preventDefault()"
                         "ondrop"=(format!("
                             let id = event.dataTransfer.getData('text/plain');
-                            htmx.ajax('POST', '/cards/' + id + '/move', {{ 
-                                target: '#board-container', 
-                                swap: 'outerHTML', 
-                                values: {{ new_stage: '{:?}' }} 
-                            }}).then(() => window.location.reload())", stage)) 
+                            let card = document.getElementById('card-' + id);
+                            fetch('/cards/' + id + '/move', {{
+                                method: 'POST',
+                                headers: {{ 'Content-Type': 'application/json' }},
+                                body: JSON.stringify({{ new_stage: '{:?}', version: Number(card.dataset.version) }})
+                            }}).then(response => response.ok
+                                ? response.text().then(board => {{ document.getElementById('board-container').outerHTML = board; }})
+                                : window.location.reload())", stage))
                     {
                         @for opp in opportunities.iter().filter(|o| o.stage == stage) {
                             (board_card(opp))
@@ -133,6 +140,105 @@ pub fn register_form() -> Markup {
     }
 }
 
+pub fn login_form() -> Markup {
+    html! {
+        div class="max-w-md mx-auto mt-10" {
+            form hx-post="/login" hx-target="#result" {
+                h2 class="text-2xl font-bold mb-4" { "Log in" }
+
+                label class="block mb-2" { "Email" }
+                input type="email" name="email" class="border p-2 w-full mb-4" required;
+
+                label class="block mb-2" { "Password" }
+                input type="password" name="password" class="border p-2 w-full mb-4" required;
+
+                button type="submit" class="bg-blue-500 text-white p-2 rounded" { "Log in" }
+                a href="/forgot-password" class="ml-4 text-blue-500" { "Forgot password?" }
+            }
+            a href="/sso/login" class="block mt-4 text-blue-500" { "Sign in with single sign-on" }
+            div id="result" class="mt-4" {}
+        }
+    }
+}
+
+pub fn forgot_password_form() -> Markup {
+    html! {
+        div class="max-w-md mx-auto mt-10" {
+            form hx-post="/forgot-password" hx-target="#result" {
+                h2 class="text-2xl font-bold mb-4" { "Reset Password" }
+
+                label class="block mb-2" { "Email" }
+                input type="email" name="email" class="border p-2 w-full mb-4" required;
+
+                button type="submit" class="bg-blue-500 text-white p-2 rounded" { "Send reset link" }
+            }
+            div id="result" class="mt-4" {}
+        }
+    }
+}
+
+pub fn reset_password_form(token: &str) -> Markup {
+    html! {
+        div class="max-w-md mx-auto mt-10" {
+            form hx-post=(format!("/reset-password/{}", token)) hx-target="#result" {
+                h2 class="text-2xl font-bold mb-4" { "Choose a New Password" }
+
+                label class="block mb-2" { "New password" }
+                input type="password" name="password" class="border p-2 w-full mb-4" required;
+
+                button type="submit" class="bg-blue-500 text-white p-2 rounded" { "Reset password" }
+            }
+            div id="result" class="mt-4" {}
+        }
+    }
+}
+
+pub fn accept_invitation_form(token: &str, invitation: &Invitation) -> Markup {
+    html! {
+        div class="max-w-md mx-auto mt-10" {
+            form hx-post=(format!("/invitations/{}/accept", token)) hx-target="#result" {
+                h2 class="text-2xl font-bold mb-4" { "Join Workspace" }
+
+                p class="mb-4" {
+                    "You have been invited as " strong { (invitation.role) }
+                    " with " strong { (invitation.email) } "."
+                }
+
+                label class="block mb-2" { "Password" }
+                input type="password" name="password" class="border p-2 w-full mb-1";
+                p class="text-sm text-gray-500 mb-4" {
+                    "Only needed if you don't have an account yet. Otherwise log in first."
+                }
+
+                button type="submit" class="bg-blue-500 text-white p-2 rounded" { "Accept" }
+            }
+            div id="result" class="mt-4" {}
+        }
+    }
+}
+
+pub fn email_verification_result(message: &str, verified: bool) -> Markup {
+    html! {
+        div class="max-w-md mx-auto mt-10" {
+            h2 class="text-2xl font-bold mb-4" { "Email Verification" }
+            p class="mb-4" { (message) }
+            @if verified {
+                a href="/login" class="bg-blue-500 text-white p-2 rounded" { "Log in" }
+            }
+        }
+    }
+}
+
+pub fn sso_error(message: &str) -> Markup {
+    html! {
+        div class="max-w-md mx-auto mt-10" {
+            h2 class="text-2xl font-bold mb-4" { "Single Sign-On" }
+            p class="mb-4" { (message) }
+            a href="/login" class="bg-blue-500 text-white p-2 rounded" { "Back to log in" }
+        }
+    }
+}
+
 pub fn create_workspace_form() -> Markup {
     html! {
         div class="max-w-md mx-auto mt-10" {
@@ -149,7 +255,8 @@ pub fn create_workspace_form() -> Markup {
     }
 }
 
-pub fn person_list(people: &[Person]) -> Markup {
+/// `next_page` links to the following page when there is one
+pub fn person_list(people: &[Person], next_page: Option<&str>) -> Markup {
     html! {
         div class="max-w-4xl mx-auto mt-10" {
             div class="flex justify-between items-center mb-6" {
@@ -184,6 +291,11 @@ pub fn person_list(people: &[Person]) -> Markup {
                     }
                 }
             }
+            @if let Some(href) = next_page {
+                div class="flex justify-end mt-4" {
+                    a href=(href) class="text-blue-500 hover:text-blue-700" { "Next →" }
+                }
+            }
         }
     }
 }
@@ -212,7 +324,8 @@ pub fn person_form() -> Markup {
     }
 }
 
-pub fn company_list(companies: &[crate::domain::Company]) -> Markup {
+/// `next_page` links to the following page when there is one
+pub fn company_list(companies: &[crate::domain::Company], next_page: Option<&str>) -> Markup {
     html! {
         div class="max-w-4xl mx-auto mt-10" {
             div class="flex justify-between items-center mb-6" {
@@ -247,6 +360,11 @@ pub fn company_list(companies: &[crate::domain::Company]) -> Markup {
                     }
                 }
             }
+            @if let Some(href) = next_page {
+                div class="flex justify-end mt-4" {
+                    a href=(href) class="text-blue-500 hover:text-blue-700" { "Next →" }
+                }
+            }
         }
     }
 }
@@ -752,3 +870,49 @@ pub fn timeline_activity_form() -> Markup {
         }
     }
 }
+
+pub fn trash_list(records: &[TrashedRecord], retention_days: i64) -> Markup {
+    html! {
+        div class="max-w-4xl mx-auto mt-10" {
+            div class="flex justify-between items-center mb-6" {
+                 h2 class="text-2xl font-bold" { "Trash" }
+                 span class="text-gray-500" { (format!("Deleted records are removed for good after {} days", retention_days)) }
+            }
+
+            table class="min-w-full bg-white border" {
+                thead {
+                    tr {
+                        th class="p-4 border-b text-left" { "Name" }
+                        th class="p-4 border-b text-left" { "Type" }
+                        th class="p-4 border-b text-left" { "Deleted" }
+                        th class="p-4 border-b text-left" { "Actions" }
+                    }
+                }
+                tbody {
+                    @for record in records {
+                        tr class="hover:bg-gray-50" {
+                            td class="p-4 border-b" { (record.name) }
+                            td class="p-4 border-b" { (record.kind.object()) }
+                            td class="p-4 border-b" { (record.deleted_at.format("%Y-%m-%d %H:%M").to_string()) }
+                            td class="p-4 border-b" {
+                                button
+                                    hx-post=(format!("/trash/{}/{}/restore", record.kind.object(), record.id))
+                                    hx-target="closest tr"
+                                    hx-swap="outerHTML"
+                                    class="text-blue-500 hover:text-blue-700 mr-4"
+                                { "Restore" }
+                                button
+                                    hx-delete=(format!("/trash/{}/{}", record.kind.object(), record.id))
+                                    hx-confirm="Delete this record permanently?"
+                                    hx-target="closest tr"
+                                    hx-swap="outerHTML"
+                                    class="text-red-500 hover:text-red-700"
+                                { "Delete forever" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,8 +1,6 @@
 use crate::application::ports::input::{CreateWorkspaceUseCase, RecordUseCase};
-use crate::application::ports::output::{
-    CalendarEventRepository, CompanyRepository, NoteRepository, OpportunityRepository,
-    PersonRepository, TaskRepository, TimelineActivityRepository, WorkflowRepository,
-};
+use crate::application::ports::output::OpportunityRepository;
+use crate::application::ports::query::{ListQuery, Page};
 use crate::application::use_cases::create_calendar_event::CreateCalendarEvent;
 use crate::application::use_cases::create_company::CreateCompany;
 use crate::application::use_cases::create_note::CreateNote;
@@ -17,16 +15,22 @@ use crate::application::use_cases::manage_company::ManageCompany;
 use crate::application::use_cases::manage_note::ManageNote;
 use crate::application::use_cases::manage_opportunity::ManageOpportunity;
 use crate::application::use_cases::manage_person::ManagePerson;
+use crate::application::use_cases::manage_session::{ManageSession, SESSION_TTL_DAYS};
 use crate::application::use_cases::manage_task::ManageTask;
 use crate::application::use_cases::manage_timeline_activity::ManageTimelineActivity;
 use crate::application::use_cases::manage_workflow::ManageWorkflow;
 use crate::application::use_cases::register_user::RegisterUser;
-use crate::domain::OpportunityStage;
+use crate::domain::{DomainError, OpportunityStage};
+use crate::infrastructure::web::auth::{CurrentMember, CurrentUser};
+use crate::infrastructure::web::errors::{conflict_response, error_status, required_version};
+use crate::infrastructure::web::list_query::{next_page_href, ListParams};
 use axum::{
     extract::{Path, State},
-    response::IntoResponse,
+    http::{StatusCode, Uri},
+    response::{IntoResponse, Response},
     Json,
 };
+use axum_extra::extract::cookie::{Cookie, CookieJar, SameSite};
 use serde::Deserialize;
 use std::sync::Arc;
 use uuid::Uuid;
@@ -35,36 +39,34 @@ use uuid::Uuid;
 pub struct AppState {
     pub record_use_case: Arc<dyn RecordUseCase>,
     pub register_user: Arc<RegisterUser>,
+    pub manage_session: Arc<ManageSession>,
     pub create_workspace: Arc<CreateWorkspace>,
     pub create_person: Arc<CreatePerson>,
     pub manage_person: Arc<ManagePerson>,
-    pub person_repo: Arc<dyn PersonRepository>,
     pub create_company: Arc<CreateCompany>,
     pub manage_company: Arc<ManageCompany>,
-    pub company_repo: Arc<dyn CompanyRepository>,
     pub create_opportunity: Arc<CreateOpportunity>,
     pub manage_opportunity: Arc<ManageOpportunity>,
     pub opportunity_repo: Arc<dyn OpportunityRepository>,
     pub create_task: Arc<CreateTask>,
     pub manage_task: Arc<ManageTask>,
-    pub task_repo: Arc<dyn TaskRepository>,
     pub create_note: Arc<CreateNote>,
     pub manage_note: Arc<ManageNote>,
-    pub note_repo: Arc<dyn NoteRepository>,
     pub create_workflow: Arc<CreateWorkflow>,
     pub manage_workflow: Arc<ManageWorkflow>,
-    pub workflow_repo: Arc<dyn WorkflowRepository>,
     pub create_calendar_event: Arc<CreateCalendarEvent>,
     pub manage_calendar_event: Arc<ManageCalendarEvent>,
-    pub calendar_event_repo: Arc<dyn CalendarEventRepository>,
     pub create_timeline_activity: Arc<CreateTimelineActivity>,
     pub manage_timeline_activity: Arc<ManageTimelineActivity>,
-    pub timeline_activity_repo: Arc<dyn TimelineActivityRepository>,
+    /// Served over https, so the session cookie is only sent back over it
+    pub secure_cookies: bool,
 }
 
 #[derive(Deserialize)]
 pub struct MoveCardPayload {
     pub new_stage: OpportunityStage,
+    /// Version of the card as shown on the board, required, see `required_version`
+    pub version: Option<i32>,
 }
 
 #[derive(Deserialize)]
@@ -73,29 +75,79 @@ pub struct RegisterUserPayload {
     pub password: String,
 }
 
+#[derive(Deserialize)]
+pub struct LoginPayload {
+    pub email: String,
+    pub password: String,
+}
+
+pub const SESSION_COOKIE: &str = "oxicrm_session";
+
+/// Cookie carrying the raw session `token`; `secure` keeps it off plain http
+pub fn session_cookie(token: String, secure: bool) -> Cookie<'static> {
+    Cookie::build((SESSION_COOKIE, token))
+        .http_only(true)
+        .secure(secure)
+        .same_site(SameSite::Lax)
+        .path("/")
+        .max_age(time::Duration::days(SESSION_TTL_DAYS))
+        .build()
+}
+
 #[derive(Deserialize)]
 pub struct CreateWorkspacePayload {
     pub subdomain: String,
 }
 
+/// Moves a card dropped on another column and answers with the board as it is now, so
+/// the cards carry their new versions. Moves from a stale board get a 409 with the card.
 pub async fn move_card_handler(
     State(state): State<AppState>,
     Path(card_id): Path<Uuid>,
     Json(payload): Json<MoveCardPayload>,
 ) -> impl IntoResponse {
+    let version = match required_version(payload.version) {
+        Ok(version) => version,
+        Err(e) => return (error_status(&e), e.to_string()).into_response(),
+    };
     match state
         .record_use_case
-        .move_board_card(card_id, payload.new_stage)
+        .move_board_card(card_id, payload.new_stage, version)
         .await
     {
-        Ok(_) => "Card moved successfully",
+        Ok(_) => match state.record_use_case.list_opportunities().await {
+            Ok(opportunities) => {
+                crate::infrastructure::web::fragments::kanban_board(&opportunities).into_response()
+            }
+            Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+        },
+        Err(e @ DomainError::Conflict(_)) => {
+            let current = state
+                .opportunity_repo
+                .find_by_id(card_id)
+                .await
+                .ok()
+                .flatten();
+            conflict_response(&e, current)
+        }
         Err(e) => {
             eprintln!("Error moving card: {}", e);
-            "Error moving card"
+            (error_status(&e), "Error moving card").into_response()
         }
     }
 }
 
+/// The error page for a failed request, with the status the JSON API gives the error
+fn error_page(error: &DomainError) -> Response {
+    (
+        error_status(error),
+        crate::infrastructure::web::fragments::layout(maud::html! {
+            (format!("Error: {}", error))
+        }),
+    )
+        .into_response()
+}
+
 pub async fn get_board_handler(State(state): State<AppState>) -> impl IntoResponse {
     let opps = state
         .record_use_case
@@ -130,6 +182,52 @@ pub async fn post_register_handler(
     }
 }
 
+pub async fn get_login_handler() -> impl IntoResponse {
+    crate::infrastructure::web::fragments::layout(
+        crate::infrastructure::web::fragments::login_form(),
+    )
+}
+
+pub async fn post_login_handler(
+    State(state): State<AppState>,
+    jar: CookieJar,
+    axum::Form(payload): axum::Form<LoginPayload>,
+) -> axum::response::Response {
+    match state
+        .manage_session
+        .login(&payload.email, &payload.password)
+        .await
+    {
+        Ok((_, token)) => {
+            let cookie = session_cookie(token, state.secure_cookies);
+            (jar.add(cookie), [("HX-Redirect", "/")], "Logged in").into_response()
+        }
+        Err(DomainError::Validation(msg)) | Err(DomainError::Permission(msg)) => {
+            msg.into_response()
+        }
+        Err(e) => {
+            eprintln!("Error logging in: {:?}", e);
+            "Error logging in".into_response()
+        }
+    }
+}
+
+pub async fn post_logout_handler(
+    State(state): State<AppState>,
+    jar: CookieJar,
+) -> impl IntoResponse {
+    if let Some(cookie) = jar.get(SESSION_COOKIE) {
+        if let Err(e) = state.manage_session.logout(cookie.value()).await {
+            eprintln!("Error logging out: {:?}", e);
+        }
+    }
+    (
+        jar.remove(Cookie::build(SESSION_COOKIE).path("/")),
+        [("HX-Redirect", "/login")],
+        "Logged out",
+    )
+}
+
 pub async fn get_create_workspace_handler() -> impl IntoResponse {
     crate::infrastructure::web::fragments::layout(
         crate::infrastructure::web::fragments::create_workspace_form(),
@@ -138,19 +236,9 @@ pub async fn get_create_workspace_handler() -> impl IntoResponse {
 
 pub async fn post_create_workspace_handler(
     State(state): State<AppState>,
+    CurrentUser(user): CurrentUser,
     axum::Form(payload): axum::Form<CreateWorkspacePayload>,
 ) -> impl IntoResponse {
-    // TEMPORARY: Create a dummy user ID to satisfy FK constraints
-    let email = format!("user-{}@example.com", Uuid::new_v4());
-    let pwd = "password";
-    let user = match state.register_user.execute(email, pwd.to_string()).await {
-        Ok(u) => u,
-        Err(e) => {
-            eprintln!("Error creating dummy user: {:?}", e);
-            return "Error creating dummy user".to_string();
-        }
-    };
-
     match state
         .create_workspace
         .execute(user.id, payload.subdomain)
@@ -171,15 +259,27 @@ pub struct CreatePersonPayload {
     pub position: i32,
 }
 
-pub async fn get_people_handler(State(state): State<AppState>) -> impl IntoResponse {
-    let people = state.person_repo.find_all().await.unwrap_or(vec![]);
-    crate::infrastructure::web::fragments::layout(
-        crate::infrastructure::web::fragments::person_list(&people),
-    )
+pub async fn get_people_handler(
+    State(state): State<AppState>,
+    uri: Uri,
+    ListParams(query): ListParams,
+) -> impl IntoResponse {
+    match state.manage_person.list(&query).await {
+        Ok(page) => {
+            let next = page.next_cursor.map(|cursor| next_page_href(&uri, &cursor));
+            crate::infrastructure::web::fragments::layout(
+                crate::infrastructure::web::fragments::person_list(&page.items, next.as_deref()),
+            )
+            .into_response()
+        }
+        Err(e) => error_page(&e),
+    }
 }
 
 pub async fn post_create_person_handler(
     State(state): State<AppState>,
+    CurrentMember(member): CurrentMember,
+    uri: Uri,
     axum::Form(payload): axum::Form<CreatePersonPayload>,
 ) -> impl IntoResponse {
     match state
@@ -188,15 +288,23 @@ pub async fn post_create_person_handler(
             payload.name,
             payload.email,
             payload.position,
-            Uuid::default(),
-        ) // TODO: Get from auth context
+            member.workspace_id,
+        )
         .await
     {
         Ok(_) => {
             // Return list to update table via HTMX or redirect
             // For now, redirect to list
-            let people = state.person_repo.find_all().await.unwrap_or(vec![]);
-            crate::infrastructure::web::fragments::person_list(&people)
+            let page = state
+                .manage_person
+                .list(&ListQuery::default())
+                .await
+                .unwrap_or(Page {
+                    items: vec![],
+                    next_cursor: None,
+                });
+            let next = page.next_cursor.map(|cursor| next_page_href(&uri, &cursor));
+            crate::infrastructure::web::fragments::person_list(&page.items, next.as_deref())
         }
         Err(e) => {
             eprintln!("Error creating person: {:?}", e);
@@ -226,11 +334,21 @@ pub struct CreateCompanyPayload {
     pub employees_count: Option<i32>,
 }
 
-pub async fn get_companies_handler(State(state): State<AppState>) -> impl IntoResponse {
-    let companies = state.company_repo.find_all().await.unwrap_or(vec![]);
-    crate::infrastructure::web::fragments::layout(
-        crate::infrastructure::web::fragments::company_list(&companies),
-    )
+pub async fn get_companies_handler(
+    State(state): State<AppState>,
+    uri: Uri,
+    ListParams(query): ListParams,
+) -> impl IntoResponse {
+    match state.manage_company.list(&query).await {
+        Ok(page) => {
+            let next = page.next_cursor.map(|cursor| next_page_href(&uri, &cursor));
+            crate::infrastructure::web::fragments::layout(
+                crate::infrastructure::web::fragments::company_list(&page.items, next.as_deref()),
+            )
+            .into_response()
+        }
+        Err(e) => error_page(&e),
+    }
 }
 
 pub async fn get_create_company_handler() -> impl IntoResponse {
@@ -241,6 +359,8 @@ pub async fn get_create_company_handler() -> impl IntoResponse {
 
 pub async fn post_create_company_handler(
     State(state): State<AppState>,
+    CurrentMember(member): CurrentMember,
+    uri: Uri,
     axum::Form(payload): axum::Form<CreateCompanyPayload>,
 ) -> impl IntoResponse {
     match state
@@ -251,14 +371,22 @@ pub async fn post_create_company_handler(
                 domain_name: payload.domain_name,
                 address: payload.address,
                 employees_count: payload.employees_count,
-                workspace_id: Uuid::default(), // TODO: Get from auth context
+                workspace_id: member.workspace_id,
             },
         )
         .await
     {
         Ok(_) => {
-            let companies = state.company_repo.find_all().await.unwrap_or(vec![]);
-            crate::infrastructure::web::fragments::company_list(&companies)
+            let page = state
+                .manage_company
+                .list(&ListQuery::default())
+                .await
+                .unwrap_or(Page {
+                    items: vec![],
+                    next_cursor: None,
+                });
+            let next = page.next_cursor.map(|cursor| next_page_href(&uri, &cursor));
+            crate::infrastructure::web::fragments::company_list(&page.items, next.as_deref())
         }
         Err(e) => {
             eprintln!("Error creating company: {:?}", e);
@@ -293,22 +421,30 @@ pub struct CreateOpportunityPayload {
 }
 
 pub async fn get_opportunities_handler(State(state): State<AppState>) -> impl IntoResponse {
-    let opportunities = state.opportunity_repo.find_all().await.unwrap_or(vec![]);
-    crate::infrastructure::web::fragments::layout(
-        crate::infrastructure::web::fragments::opportunity_list(&opportunities),
-    )
+    match state.manage_opportunity.get_all().await {
+        Ok(opportunities) => crate::infrastructure::web::fragments::layout(
+            crate::infrastructure::web::fragments::opportunity_list(&opportunities),
+        )
+        .into_response(),
+        Err(e) => error_page(&e),
+    }
 }
 
 pub async fn get_create_opportunity_handler(State(state): State<AppState>) -> impl IntoResponse {
-    let companies = state.company_repo.find_all().await.unwrap_or(vec![]);
-    let people = state.person_repo.find_all().await.unwrap_or(vec![]);
-    crate::infrastructure::web::fragments::layout(
-        crate::infrastructure::web::fragments::opportunity_form(&companies, &people),
-    )
+    let companies = state.manage_company.get_all().await;
+    let people = state.manage_person.get_all().await;
+    match (companies, people) {
+        (Ok(companies), Ok(people)) => crate::infrastructure::web::fragments::layout(
+            crate::infrastructure::web::fragments::opportunity_form(&companies, &people),
+        )
+        .into_response(),
+        (Err(e), _) | (_, Err(e)) => error_page(&e),
+    }
 }
 
 pub async fn post_create_opportunity_handler(
     State(state): State<AppState>,
+    CurrentMember(member): CurrentMember,
     axum::Form(payload): axum::Form<CreateOpportunityPayload>,
 ) -> impl IntoResponse {
     // Parse close_date from string if provided
@@ -333,13 +469,13 @@ pub async fn post_create_opportunity_handler(
                 company_id: payload.company_id,
                 point_of_contact_id: payload.point_of_contact_id,
                 owner_id: payload.owner_id,
-                workspace_id: Uuid::default(), // TODO: Get from auth context
+                workspace_id: member.workspace_id,
             },
         )
         .await
     {
         Ok(_) => {
-            let opportunities = state.opportunity_repo.find_all().await.unwrap_or(vec![]);
+            let opportunities = state.manage_opportunity.get_all().await.unwrap_or(vec![]);
             crate::infrastructure::web::fragments::layout(
                 crate::infrastructure::web::fragments::opportunity_list(&opportunities),
             )
@@ -375,10 +511,13 @@ pub struct CreateTaskPayload {
 }
 
 pub async fn get_tasks_handler(State(state): State<AppState>) -> impl IntoResponse {
-    let tasks = state.task_repo.find_all().await.unwrap_or(vec![]);
-    crate::infrastructure::web::fragments::layout(crate::infrastructure::web::fragments::task_list(
-        &tasks,
-    ))
+    match state.manage_task.get_all().await {
+        Ok(tasks) => crate::infrastructure::web::fragments::layout(
+            crate::infrastructure::web::fragments::task_list(&tasks),
+        )
+        .into_response(),
+        Err(e) => error_page(&e),
+    }
 }
 
 pub async fn get_create_task_handler() -> impl IntoResponse {
@@ -387,6 +526,7 @@ pub async fn get_create_task_handler() -> impl IntoResponse {
 
 pub async fn post_create_task_handler(
     State(state): State<AppState>,
+    CurrentMember(member): CurrentMember,
     axum::Form(payload): axum::Form<CreateTaskPayload>,
 ) -> impl IntoResponse {
     let due_at = payload.due_at.and_then(|d| {
@@ -407,13 +547,13 @@ pub async fn post_create_task_handler(
                 status: payload.status,
                 assignee_id: payload.assignee_id,
                 due_at,
-                workspace_id: Uuid::default(), // TODO: Get from auth context
+                workspace_id: member.workspace_id,
             },
         )
         .await
     {
         Ok(_) => {
-            let tasks = state.task_repo.find_all().await.unwrap_or(vec![]);
+            let tasks = state.manage_task.get_all().await.unwrap_or(vec![]);
             crate::infrastructure::web::fragments::layout(
                 crate::infrastructure::web::fragments::task_list(&tasks),
             )
@@ -446,10 +586,13 @@ pub struct CreateNotePayload {
 }
 
 pub async fn get_notes_handler(State(state): State<AppState>) -> impl IntoResponse {
-    let notes = state.note_repo.find_all().await.unwrap_or(vec![]);
-    crate::infrastructure::web::fragments::layout(crate::infrastructure::web::fragments::note_list(
-        &notes,
-    ))
+    match state.manage_note.get_all().await {
+        Ok(notes) => crate::infrastructure::web::fragments::layout(
+            crate::infrastructure::web::fragments::note_list(&notes),
+        )
+        .into_response(),
+        Err(e) => error_page(&e),
+    }
 }
 
 pub async fn get_create_note_handler() -> impl IntoResponse {
@@ -458,6 +601,7 @@ pub async fn get_create_note_handler() -> impl IntoResponse {
 
 pub async fn post_create_note_handler(
     State(state): State<AppState>,
+    CurrentMember(member): CurrentMember,
     axum::Form(payload): axum::Form<CreateNotePayload>,
 ) -> impl IntoResponse {
     match state
@@ -466,13 +610,13 @@ pub async fn post_create_note_handler(
             crate::application::use_cases::create_note::CreateNoteInput {
                 title: payload.title,
                 body_v2: payload.body_v2,
-                workspace_id: Uuid::default(), // TODO: Get from auth context
+                workspace_id: member.workspace_id,
             },
         )
         .await
     {
         Ok(_) => {
-            let notes = state.note_repo.find_all().await.unwrap_or(vec![]);
+            let notes = state.manage_note.get_all().await.unwrap_or(vec![]);
             crate::infrastructure::web::fragments::layout(
                 crate::infrastructure::web::fragments::note_list(&notes),
             )
@@ -504,10 +648,13 @@ pub struct CreateWorkflowPayload {
 }
 
 pub async fn get_workflows_handler(State(state): State<AppState>) -> impl IntoResponse {
-    let workflows = state.workflow_repo.find_all().await.unwrap_or(vec![]);
-    crate::infrastructure::web::fragments::layout(
-        crate::infrastructure::web::fragments::workflow_list(&workflows),
-    )
+    match state.manage_workflow.get_all().await {
+        Ok(workflows) => crate::infrastructure::web::fragments::layout(
+            crate::infrastructure::web::fragments::workflow_list(&workflows),
+        )
+        .into_response(),
+        Err(e) => error_page(&e),
+    }
 }
 
 pub async fn get_create_workflow_handler() -> impl IntoResponse {
@@ -518,6 +665,7 @@ pub async fn get_create_workflow_handler() -> impl IntoResponse {
 
 pub async fn post_create_workflow_handler(
     State(state): State<AppState>,
+    CurrentMember(member): CurrentMember,
     axum::Form(payload): axum::Form<CreateWorkflowPayload>,
 ) -> impl IntoResponse {
     match state
@@ -525,13 +673,13 @@ pub async fn post_create_workflow_handler(
         .execute(
             crate::application::use_cases::create_workflow::CreateWorkflowInput {
                 name: payload.name,
-                workspace_id: Uuid::default(), // TODO: Get from auth context
+                workspace_id: member.workspace_id,
             },
         )
         .await
     {
         Ok(_) => {
-            let workflows = state.workflow_repo.find_all().await.unwrap_or(vec![]);
+            let workflows = state.manage_workflow.get_all().await.unwrap_or(vec![]);
             crate::infrastructure::web::fragments::layout(
                 crate::infrastructure::web::fragments::workflow_list(&workflows),
             )
@@ -566,10 +714,13 @@ pub struct CreateCalendarEventPayload {
 }
 
 pub async fn get_calendar_events_handler(State(state): State<AppState>) -> impl IntoResponse {
-    let events = state.calendar_event_repo.find_all().await.unwrap_or(vec![]);
-    crate::infrastructure::web::fragments::layout(
-        crate::infrastructure::web::fragments::calendar_event_list(&events),
-    )
+    match state.manage_calendar_event.get_all().await {
+        Ok(events) => crate::infrastructure::web::fragments::layout(
+            crate::infrastructure::web::fragments::calendar_event_list(&events),
+        )
+        .into_response(),
+        Err(e) => error_page(&e),
+    }
 }
 
 pub async fn get_create_calendar_event_handler() -> impl IntoResponse {
@@ -580,6 +731,7 @@ pub async fn get_create_calendar_event_handler() -> impl IntoResponse {
 
 pub async fn post_create_calendar_event_handler(
     State(state): State<AppState>,
+    CurrentMember(member): CurrentMember,
     axum::Form(payload): axum::Form<CreateCalendarEventPayload>,
 ) -> impl IntoResponse {
     // Parse datetime strings from datetime-local format (YYYY-MM-DDTHH:MM)
@@ -611,13 +763,17 @@ pub async fn post_create_calendar_event_handler(
                 start_time,
                 end_time,
                 description: payload.description,
-                workspace_id: Uuid::default(), // TODO: Get from auth context
+                workspace_id: member.workspace_id,
             },
         )
         .await
     {
         Ok(_) => {
-            let events = state.calendar_event_repo.find_all().await.unwrap_or(vec![]);
+            let events = state
+                .manage_calendar_event
+                .get_all()
+                .await
+                .unwrap_or(vec![]);
             crate::infrastructure::web::fragments::layout(
                 crate::infrastructure::web::fragments::calendar_event_list(&events),
             )
@@ -657,14 +813,13 @@ pub struct CreateTimelineActivityPayload {
 }
 
 pub async fn get_timeline_activities_handler(State(state): State<AppState>) -> impl IntoResponse {
-    let activities = state
-        .timeline_activity_repo
-        .find_all()
-        .await
-        .unwrap_or(vec![]);
-    crate::infrastructure::web::fragments::layout(
-        crate::infrastructure::web::fragments::timeline_activity_list(&activities),
-    )
+    match state.manage_timeline_activity.get_all().await {
+        Ok(activities) => crate::infrastructure::web::fragments::layout(
+            crate::infrastructure::web::fragments::timeline_activity_list(&activities),
+        )
+        .into_response(),
+        Err(e) => error_page(&e),
+    }
 }
 
 pub async fn get_create_timeline_activity_handler() -> impl IntoResponse {
@@ -675,6 +830,7 @@ pub async fn get_create_timeline_activity_handler() -> impl IntoResponse {
 
 pub async fn post_create_timeline_activity_handler(
     State(state): State<AppState>,
+    CurrentMember(member): CurrentMember,
     axum::Form(payload): axum::Form<CreateTimelineActivityPayload>,
 ) -> impl IntoResponse {
     match state
@@ -690,15 +846,15 @@ pub async fn post_create_timeline_activity_handler(
                 note_id: payload.note_id,
                 calendar_event_id: payload.calendar_event_id,
                 workflow_id: payload.workflow_id,
-                workspace_id: Uuid::default(), // TODO: Get from auth context
+                workspace_id: member.workspace_id,
             },
         )
         .await
     {
         Ok(_) => {
             let activities = state
-                .timeline_activity_repo
-                .find_all()
+                .manage_timeline_activity
+                .get_all()
                 .await
                 .unwrap_or(vec![]);
             crate::infrastructure::web::fragments::layout(
@@ -724,3 +880,281 @@ pub async fn delete_timeline_activity_handler(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::context::run_as;
+    use crate::application::events::record_events::RecordEvents;
+    use crate::application::ports::identity::PasswordHasher;
+    use crate::application::ports::output::UserRepository;
+    use crate::application::use_cases::testing::{
+        acting_as, custom_role, identity_provider, member, user, workspace,
+    };
+    use crate::application::use_cases::verify_email::VerifyEmail;
+    use crate::application::use_cases::RecordBoardCard;
+    use crate::domain::permissions::{objects, Action};
+    use crate::infrastructure::email::MockEmailProvider;
+    use crate::infrastructure::identity::Argon2PasswordHasher;
+    use crate::infrastructure::messaging::InMemoryEventBus;
+    use crate::infrastructure::persistence::in_memory_repo::InMemoryRepo;
+
+    /// The state the HTML handlers are served with, over `repo`
+    fn app_state(repo: &Arc<InMemoryRepo>) -> AppState {
+        let identity_provider = identity_provider(repo);
+        let record_events = Arc::new(RecordEvents::new(Arc::new(InMemoryEventBus::new())));
+        let password_hasher = Arc::new(Argon2PasswordHasher::new());
+        AppState {
+            record_use_case: Arc::new(RecordBoardCard {
+                opportunity_repo: repo.clone(),
+                identity_provider: identity_provider.clone(),
+                record_events: record_events.clone(),
+            }),
+            register_user: Arc::new(RegisterUser {
+                user_repo: repo.clone(),
+                password_hasher: password_hasher.clone(),
+                verify_email: Arc::new(VerifyEmail::new(
+                    repo.clone(),
+                    repo.clone(),
+                    Arc::new(MockEmailProvider::new()),
+                    "https://oxicrm.test".to_string(),
+                )),
+            }),
+            manage_session: Arc::new(ManageSession::new(
+                repo.clone(),
+                repo.clone(),
+                password_hasher,
+            )),
+            create_workspace: Arc::new(CreateWorkspace::new(repo.clone())),
+            create_person: Arc::new(CreatePerson::new(
+                repo.clone(),
+                identity_provider.clone(),
+                record_events.clone(),
+            )),
+            manage_person: Arc::new(ManagePerson::new(
+                repo.clone(),
+                identity_provider.clone(),
+                record_events.clone(),
+            )),
+            create_company: Arc::new(CreateCompany::new(
+                repo.clone(),
+                identity_provider.clone(),
+                record_events.clone(),
+            )),
+            manage_company: Arc::new(ManageCompany::new(
+                repo.clone(),
+                identity_provider.clone(),
+                record_events.clone(),
+            )),
+            create_opportunity: Arc::new(CreateOpportunity::new(
+                repo.clone(),
+                identity_provider.clone(),
+                record_events.clone(),
+            )),
+            manage_opportunity: Arc::new(ManageOpportunity::new(
+                repo.clone(),
+                identity_provider.clone(),
+                record_events.clone(),
+            )),
+            opportunity_repo: repo.clone(),
+            create_task: Arc::new(CreateTask::new(
+                repo.clone(),
+                identity_provider.clone(),
+                record_events.clone(),
+            )),
+            manage_task: Arc::new(ManageTask::new(
+                repo.clone(),
+                identity_provider.clone(),
+                record_events.clone(),
+            )),
+            create_note: Arc::new(CreateNote::new(
+                repo.clone(),
+                identity_provider.clone(),
+                record_events.clone(),
+            )),
+            manage_note: Arc::new(ManageNote::new(
+                repo.clone(),
+                identity_provider.clone(),
+                record_events,
+            )),
+            create_workflow: Arc::new(CreateWorkflow::new(repo.clone(), identity_provider.clone())),
+            manage_workflow: Arc::new(ManageWorkflow::new(repo.clone(), identity_provider.clone())),
+            create_calendar_event: Arc::new(CreateCalendarEvent::new(
+                repo.clone(),
+                identity_provider.clone(),
+            )),
+            manage_calendar_event: Arc::new(ManageCalendarEvent::new(
+                repo.clone(),
+                identity_provider.clone(),
+            )),
+            create_timeline_activity: Arc::new(CreateTimelineActivity::new(
+                repo.clone(),
+                identity_provider.clone(),
+            )),
+            manage_timeline_activity: Arc::new(ManageTimelineActivity::new(
+                repo.clone(),
+                identity_provider,
+            )),
+            secure_cookies: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_session_cookies_are_secure_over_https() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let mut ada = user(&repo, "ada@example.com").await;
+        ada.password_hash = Argon2PasswordHasher::new().hash("correct horse").unwrap();
+        UserRepository::update(repo.as_ref(), ada).await.unwrap();
+
+        for secure_cookies in [false, true] {
+            let state = AppState {
+                secure_cookies,
+                ..app_state(&repo)
+            };
+            let response = post_login_handler(
+                State(state),
+                CookieJar::new(),
+                axum::Form(LoginPayload {
+                    email: "ada@example.com".to_string(),
+                    password: "correct horse".to_string(),
+                }),
+            )
+            .await;
+            let cookie = response.headers()[axum::http::header::SET_COOKIE]
+                .to_str()
+                .unwrap();
+            assert!(cookie.starts_with(SESSION_COOKIE));
+            assert!(cookie.contains("HttpOnly"));
+            assert_eq!(cookie.contains("Secure"), secure_cookies);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lists_are_forbidden_without_read_permission() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let state = app_state(&repo);
+        let a = workspace(&repo).await;
+        let ada = user(&repo, "ada@example.com").await;
+        let admin = member(&repo, a, &ada, "Admin").await;
+        custom_role(&repo, a, "Note takers", &[(objects::NOTE, &[Action::Read])]).await;
+        let grace = user(&repo, "grace@example.com").await;
+        let note_taker = member(&repo, a, &grace, "Note takers").await;
+        let uri = Uri::from_static("/people");
+
+        run_as(acting_as(&grace, &note_taker), async {
+            let people = get_people_handler(
+                State(state.clone()),
+                uri.clone(),
+                ListParams(ListQuery::default()),
+            )
+            .await
+            .into_response();
+            assert_eq!(people.status(), StatusCode::FORBIDDEN);
+            for response in [
+                get_companies_handler(
+                    State(state.clone()),
+                    uri.clone(),
+                    ListParams(ListQuery::default()),
+                )
+                .await
+                .into_response(),
+                get_opportunities_handler(State(state.clone()))
+                    .await
+                    .into_response(),
+                get_create_opportunity_handler(State(state.clone()))
+                    .await
+                    .into_response(),
+                get_tasks_handler(State(state.clone()))
+                    .await
+                    .into_response(),
+                get_workflows_handler(State(state.clone()))
+                    .await
+                    .into_response(),
+                get_calendar_events_handler(State(state.clone()))
+                    .await
+                    .into_response(),
+                get_timeline_activities_handler(State(state.clone()))
+                    .await
+                    .into_response(),
+            ] {
+                assert_eq!(response.status(), StatusCode::FORBIDDEN);
+            }
+            let notes = get_notes_handler(State(state.clone()))
+                .await
+                .into_response();
+            assert_eq!(notes.status(), StatusCode::OK);
+        })
+        .await;
+
+        run_as(acting_as(&ada, &admin), async {
+            let people = get_people_handler(
+                State(state.clone()),
+                uri.clone(),
+                ListParams(ListQuery::default()),
+            )
+            .await
+            .into_response();
+            assert_eq!(people.status(), StatusCode::OK);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_cards_move_again_with_the_version_from_the_board() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let state = app_state(&repo);
+        let a = workspace(&repo).await;
+        let ada = user(&repo, "ada@example.com").await;
+        let admin = member(&repo, a, &ada, "Admin").await;
+
+        run_as(acting_as(&ada, &admin), async {
+            let card = state
+                .create_opportunity
+                .execute(
+                    crate::application::use_cases::create_opportunity::CreateOpportunityInput {
+                        name: "Acme".to_string(),
+                        stage: None,
+                        amount_micros: None,
+                        currency_code: None,
+                        close_date: None,
+                        company_id: None,
+                        point_of_contact_id: None,
+                        owner_id: None,
+                        workspace_id: a,
+                    },
+                )
+                .await
+                .unwrap();
+            let move_card = |stage: OpportunityStage, version: i32| {
+                let state = state.clone();
+                async move {
+                    move_card_handler(
+                        State(state),
+                        Path(card.id),
+                        Json(MoveCardPayload {
+                            new_stage: stage,
+                            version: Some(version),
+                        }),
+                    )
+                    .await
+                    .into_response()
+                }
+            };
+
+            let moved = move_card(OpportunityStage::Qualification, card.version).await;
+            assert_eq!(moved.status(), StatusCode::OK);
+            let board = axum::body::to_bytes(moved.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let board = String::from_utf8(board.to_vec()).unwrap();
+            let shown = format!("data-version=\"{}\"", card.version + 1);
+            assert!(board.contains(&shown));
+
+            let moved = move_card(OpportunityStage::Negotiation, card.version + 1).await;
+            assert_eq!(moved.status(), StatusCode::OK);
+            let stale = move_card(OpportunityStage::Won, card.version + 1).await;
+            assert_eq!(stale.status(), StatusCode::CONFLICT);
+        })
+        .await;
+    }
+}
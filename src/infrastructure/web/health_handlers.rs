@@ -0,0 +1,39 @@
+use crate::application::use_cases::check_readiness::CheckReadiness;
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct HealthAppState {
+    pub check_readiness: Arc<CheckReadiness>,
+}
+
+// GET /healthz - The process is up and serving HTTP
+pub async fn healthz_handler() -> impl IntoResponse {
+    Json(serde_json::json!({ "status": "ok" }))
+}
+
+// GET /readyz - Every dependency is healthy; 503 with the failing checks otherwise
+pub async fn readyz_handler(State(state): State<HealthAppState>) -> impl IntoResponse {
+    let results = state.check_readiness.execute().await;
+    let ready = results.iter().all(|result| result.error.is_none());
+    let checks: BTreeMap<_, _> = results
+        .into_iter()
+        .map(|result| {
+            (
+                result.name,
+                result.error.unwrap_or_else(|| "ok".to_string()),
+            )
+        })
+        .collect();
+
+    let (status, label) = if ready {
+        (StatusCode::OK, "ready")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "unavailable")
+    };
+    (
+        status,
+        Json(serde_json::json!({ "status": label, "checks": checks })),
+    )
+}
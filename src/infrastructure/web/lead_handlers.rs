@@ -3,6 +3,9 @@ use crate::application::use_cases::convert_lead::{ConvertLead, ConvertLeadInput}
 use crate::application::use_cases::create_lead::{CreateLead, CreateLeadInput};
 use crate::application::use_cases::manage_lead::ManageLead;
 use crate::domain::states::{LeadSource, LeadStatus};
+use crate::infrastructure::web::auth::CurrentMember;
+use crate::infrastructure::web::errors::error_status;
+use crate::infrastructure::web::list_query::ListParams;
 use axum::{
     extract::{Path, State},
     http::StatusCode,
@@ -50,6 +53,7 @@ pub struct UpdateLeadStatusPayload {
 // POST /api/leads - Create lead
 pub async fn create_lead_handler(
     State(state): State<LeadAppState>,
+    CurrentMember(member): CurrentMember,
     Json(payload): Json<CreateLeadPayload>,
 ) -> impl IntoResponse {
     let source = match payload.source.as_str() {
@@ -68,25 +72,28 @@ pub async fn create_lead_handler(
         job_title: payload.job_title,
         source,
         notes: payload.notes,
-        workspace_id: Uuid::default(), // TODO: Get from auth context
+        workspace_id: member.workspace_id,
     };
 
     match state.create_lead.execute(input).await {
         Ok(lead) => (StatusCode::CREATED, Json(lead)).into_response(),
         Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
+            error_status(&e),
             Json(serde_json::json!({ "error": e.to_string() })),
         )
             .into_response(),
     }
 }
 
-// GET /api/leads - List all leads
-pub async fn list_leads_handler(State(state): State<LeadAppState>) -> impl IntoResponse {
-    match state.manage_lead.list().await {
-        Ok(leads) => Json(leads).into_response(),
+// GET /api/leads - List leads a page at a time (see `list_query`)
+pub async fn list_leads_handler(
+    State(state): State<LeadAppState>,
+    ListParams(query): ListParams,
+) -> impl IntoResponse {
+    match state.manage_lead.list(&query).await {
+        Ok(page) => Json(page).into_response(),
         Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
+            error_status(&e),
             Json(serde_json::json!({ "error": e.to_string() })),
         )
             .into_response(),
@@ -126,7 +133,7 @@ pub async fn convert_lead_handler(
     match state.convert_lead.execute(input).await {
         Ok(result) => Json(result).into_response(),
         Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
+            error_status(&e),
             Json(serde_json::json!({ "error": e.to_string() })),
         )
             .into_response(),
@@ -150,7 +157,7 @@ pub async fn update_lead_status_handler(
     match state.manage_lead.update_status(id, status).await {
         Ok(lead) => Json(lead).into_response(),
         Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
+            error_status(&e),
             Json(serde_json::json!({ "error": e.to_string() })),
         )
             .into_response(),
@@ -165,7 +172,7 @@ pub async fn delete_lead_handler(
     match state.manage_lead.delete(id).await {
         Ok(_) => StatusCode::NO_CONTENT.into_response(),
         Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
+            error_status(&e),
             Json(serde_json::json!({ "error": e.to_string() })),
         )
             .into_response(),
@@ -175,6 +182,7 @@ pub async fn delete_lead_handler(
 // POST /webhooks/lead-capture - Web form webhook
 pub async fn lead_capture_webhook_handler(
     State(state): State<LeadAppState>,
+    CurrentMember(member): CurrentMember,
     Json(payload): Json<CreateLeadPayload>,
 ) -> impl IntoResponse {
     // Same as create_lead_handler but always uses WebForm source
@@ -187,13 +195,13 @@ pub async fn lead_capture_webhook_handler(
         job_title: payload.job_title,
         source: LeadSource::WebForm,
         notes: payload.notes,
-        workspace_id: Uuid::default(), // TODO: Get from auth context
+        workspace_id: member.workspace_id,
     };
 
     match state.create_lead.execute(input).await {
         Ok(lead) => (StatusCode::CREATED, Json(lead)).into_response(),
         Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
+            error_status(&e),
             Json(serde_json::json!({ "error": e.to_string() })),
         )
             .into_response(),
@@ -0,0 +1,146 @@
+//! `?limit=&cursor=&sort=&filter[...]` parameters of list endpoints.
+//!
+//! `sort=name` sorts ascending and `sort=-name` descending; `filter[status]=new` matches
+//! exactly and `filter[score][gte]=50` applies one of the `FilterOp` operators.
+
+use crate::application::ports::query::{Filter, FilterOp, ListQuery, Sort};
+use crate::infrastructure::web::auth::is_api_path;
+use async_trait::async_trait;
+use axum::{
+    extract::{FromRequestParts, Query},
+    http::{request::Parts, StatusCode, Uri},
+    response::{IntoResponse, Response},
+    Json,
+};
+
+/// The list query of the request
+pub struct ListParams(pub ListQuery);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for ListParams {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let pairs = Query::<Vec<(String, String)>>::try_from_uri(&parts.uri)
+            .map(|Query(pairs)| pairs)
+            .map_err(|_| "Invalid query string".to_string())
+            .and_then(|pairs| parse(&pairs));
+        match pairs {
+            Ok(query) => Ok(ListParams(query)),
+            Err(message) if is_api_path(parts.uri.path()) => Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": message })),
+            )
+                .into_response()),
+            Err(message) => Err((StatusCode::BAD_REQUEST, message).into_response()),
+        }
+    }
+}
+
+fn parse(pairs: &[(String, String)]) -> Result<ListQuery, String> {
+    let mut query = ListQuery::default();
+    for (key, value) in pairs {
+        match key.as_str() {
+            "limit" => {
+                query.limit = Some(
+                    value
+                        .parse()
+                        .map_err(|_| "'limit' must be a positive number".to_string())?,
+                )
+            }
+            "cursor" if !value.is_empty() => query.cursor = Some(value.clone()),
+            "sort" if !value.is_empty() => {
+                query.sort =
+                    Some(Sort::parse(value).ok_or_else(|| format!("Invalid sort '{}'", value))?)
+            }
+            _ => {
+                if let Some(filter) = key.strip_prefix("filter[") {
+                    query.filters.push(parse_filter(filter, value)?);
+                }
+            }
+        }
+    }
+    Ok(query)
+}
+
+/// `field]` or `field][op]`, the remainder of a `filter[...]` key
+fn parse_filter(key: &str, value: &str) -> Result<Filter, String> {
+    let invalid = || format!("Invalid filter 'filter[{}'", key);
+    let (field, rest) = key.split_once(']').ok_or_else(invalid)?;
+    let op = match rest {
+        "" => FilterOp::Eq,
+        _ => rest
+            .strip_prefix('[')
+            .and_then(|op| op.strip_suffix(']'))
+            .and_then(FilterOp::parse)
+            .ok_or_else(invalid)?,
+    };
+    if field.is_empty() {
+        return Err(invalid());
+    }
+    Ok(Filter {
+        field: field.to_string(),
+        op,
+        value: value.to_string(),
+    })
+}
+
+/// Link to the page after the current one: the request's own query with `cursor` replaced
+pub fn next_page_href(uri: &Uri, next_cursor: &str) -> String {
+    let mut pairs: Vec<(String, String)> = Query::try_from_uri(uri)
+        .map(|Query(pairs)| pairs)
+        .unwrap_or_default();
+    pairs.retain(|(key, _)| key != "cursor");
+    pairs.push(("cursor".to_string(), next_cursor.to_string()));
+    format!(
+        "{}?{}",
+        uri.path(),
+        serde_urlencoded::to_string(&pairs).unwrap_or_default()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(query: &str) -> Vec<(String, String)> {
+        serde_urlencoded::from_str(query).unwrap()
+    }
+
+    #[test]
+    fn test_parse_list_query() {
+        let query = parse(&pairs(
+            "limit=20&cursor=abc&sort=-score&filter%5Bstatus%5D=New&filter[score][gte]=50",
+        ))
+        .unwrap();
+        assert_eq!(query.limit, Some(20));
+        assert_eq!(query.cursor.as_deref(), Some("abc"));
+        assert_eq!(query.sort, Some(Sort::desc("score")));
+        assert_eq!(
+            query.filters,
+            vec![
+                Filter {
+                    field: "status".to_string(),
+                    op: FilterOp::Eq,
+                    value: "New".to_string(),
+                },
+                Filter {
+                    field: "score".to_string(),
+                    op: FilterOp::Gte,
+                    value: "50".to_string(),
+                },
+            ]
+        );
+
+        assert!(parse(&pairs("limit=-1")).is_err());
+        assert!(parse(&pairs("filter[score][between]=1")).is_err());
+        assert!(parse(&pairs("filter[]=1")).is_err());
+        assert_eq!(parse(&pairs("other=1")).unwrap(), ListQuery::default());
+    }
+
+    #[test]
+    fn test_next_page_href() {
+        let uri: Uri = "/people?sort=name&cursor=old".parse().unwrap();
+        assert_eq!(next_page_href(&uri, "new"), "/people?sort=name&cursor=new");
+    }
+}
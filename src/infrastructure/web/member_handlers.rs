@@ -0,0 +1,183 @@
+use crate::application::context::{current_actor, Actor};
+use crate::application::use_cases::manage_invitations::ManageInvitations;
+use crate::application::use_cases::manage_members::ManageMembers;
+use crate::domain::DomainError;
+use crate::infrastructure::web::errors::error_status;
+use crate::infrastructure::web::fragments;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct MemberAppState {
+    pub manage_members: Arc<ManageMembers>,
+    pub manage_invitations: Arc<ManageInvitations>,
+}
+
+#[derive(Deserialize)]
+pub struct InvitePayload {
+    pub email: String,
+    pub role: String,
+}
+
+#[derive(Deserialize)]
+pub struct AcceptInvitationPayload {
+    pub password: Option<String>,
+}
+
+// GET /api/members - List members of the current workspace
+pub async fn list_members_handler(State(state): State<MemberAppState>) -> impl IntoResponse {
+    match state.manage_members.list().await {
+        Ok(members) => Json(members).into_response(),
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+// DELETE /api/members/:id - Remove a member from the current workspace
+pub async fn remove_member_handler(
+    State(state): State<MemberAppState>,
+    Path(member_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.manage_members.remove(member_id).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+// POST /api/members/:id/suspend - Block a member from the current workspace
+pub async fn suspend_member_handler(
+    State(state): State<MemberAppState>,
+    Path(member_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.manage_members.suspend(member_id).await {
+        Ok(member) => Json(member).into_response(),
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+// POST /api/members/:id/reactivate - Lift a suspension
+pub async fn reactivate_member_handler(
+    State(state): State<MemberAppState>,
+    Path(member_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.manage_members.reactivate(member_id).await {
+        Ok(member) => Json(member).into_response(),
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+// GET /api/invitations - List pending invitations
+pub async fn list_invitations_handler(State(state): State<MemberAppState>) -> impl IntoResponse {
+    match state.manage_invitations.list().await {
+        Ok(invitations) => Json(invitations).into_response(),
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+// POST /api/invitations - Invite an email address to the current workspace
+pub async fn create_invitation_handler(
+    State(state): State<MemberAppState>,
+    Json(payload): Json<InvitePayload>,
+) -> impl IntoResponse {
+    match state
+        .manage_invitations
+        .invite(payload.email, payload.role)
+        .await
+    {
+        Ok(invitation) => (StatusCode::CREATED, Json(invitation)).into_response(),
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+// DELETE /api/invitations/:id - Revoke a pending invitation
+pub async fn revoke_invitation_handler(
+    State(state): State<MemberAppState>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.manage_invitations.revoke(id).await {
+        Ok(invitation) => Json(invitation).into_response(),
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+// GET /invitations/:token - Page to accept an emailed invitation
+pub async fn get_invitation_handler(
+    State(state): State<MemberAppState>,
+    Path(token): Path<String>,
+) -> impl IntoResponse {
+    match state.manage_invitations.find_pending(&token).await {
+        Ok(invitation) => fragments::layout(fragments::accept_invitation_form(&token, &invitation))
+            .into_response(),
+        Err(e) => (error_status(&e), invitation_error(e)).into_response(),
+    }
+}
+
+// POST /invitations/:token/accept - Join the workspace, registering if needed
+pub async fn accept_invitation_handler(
+    State(state): State<MemberAppState>,
+    Path(token): Path<String>,
+    axum::Form(payload): axum::Form<AcceptInvitationPayload>,
+) -> impl IntoResponse {
+    match state
+        .manage_invitations
+        .accept(&token, payload.password)
+        .await
+    {
+        Ok(_) => {
+            // Accounts created by the invitation still have to log in
+            let redirect = match current_actor() {
+                Some(Actor::User(_)) => "/",
+                _ => "/login",
+            };
+            ([("HX-Redirect", redirect)], "Invitation accepted").into_response()
+        }
+        Err(e) => invitation_error(e).into_response(),
+    }
+}
+
+fn invitation_error(error: DomainError) -> String {
+    match error {
+        DomainError::Validation(msg)
+        | DomainError::Permission(msg)
+        | DomainError::InvalidState(msg) => msg,
+        DomainError::NotFound => "Invitation not found or no longer valid".to_string(),
+        e => {
+            eprintln!("Error accepting invitation: {:?}", e);
+            "Error accepting invitation".to_string()
+        }
+    }
+}
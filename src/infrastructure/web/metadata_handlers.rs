@@ -1,6 +1,8 @@
 use crate::application::use_cases::manage_metadata::ManageMetadata;
 use crate::application::use_cases::manage_view::ManageView;
 use crate::domain::metadata::{FieldType, ObjectMetadata, ViewType};
+use crate::infrastructure::web::auth::CurrentMember;
+use crate::infrastructure::web::errors::error_status;
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
@@ -60,7 +62,7 @@ pub async fn get_schema_handler(State(state): State<MetadataAppState>) -> impl I
     match state.manage_metadata.get_schema().await {
         Ok(schema) => Json(schema).into_response(),
         Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
+            error_status(&e),
             Json(serde_json::json!({ "error": e.to_string() })),
         )
             .into_response(),
@@ -69,6 +71,7 @@ pub async fn get_schema_handler(State(state): State<MetadataAppState>) -> impl I
 
 pub async fn create_object_handler(
     State(state): State<MetadataAppState>,
+    CurrentMember(member): CurrentMember,
     Json(payload): Json<CreateObjectPayload>,
 ) -> impl IntoResponse {
     match state
@@ -77,13 +80,13 @@ pub async fn create_object_handler(
             payload.name_singular,
             payload.name_plural,
             payload.description,
-            Uuid::default(), // TODO: Get from auth
+            member.workspace_id,
         )
         .await
     {
         Ok(object) => (StatusCode::CREATED, Json(object)).into_response(),
         Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
+            error_status(&e),
             Json(serde_json::json!({ "error": e.to_string() })),
         )
             .into_response(),
@@ -112,7 +115,7 @@ pub async fn create_field_handler(
     {
         Ok(field) => (StatusCode::CREATED, Json(field)).into_response(),
         Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
+            error_status(&e),
             Json(serde_json::json!({ "error": e.to_string() })),
         )
             .into_response(),
@@ -130,7 +133,7 @@ pub async fn list_views_handler(
     {
         Ok(views) => Json(views).into_response(),
         Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
+            error_status(&e),
             Json(serde_json::json!({ "error": e.to_string() })),
         )
             .into_response(),
@@ -139,6 +142,7 @@ pub async fn list_views_handler(
 
 pub async fn create_view_handler(
     State(state): State<MetadataAppState>,
+    CurrentMember(member): CurrentMember,
     Json(payload): Json<CreateViewPayload>,
 ) -> impl IntoResponse {
     let view_type = match payload.view_type.as_str() {
@@ -156,13 +160,13 @@ pub async fn create_view_handler(
             view_type,
             payload.filters,
             payload.sort,
-            Uuid::default(), // TODO: Get from auth
+            member.workspace_id,
         )
         .await
     {
         Ok(view) => (StatusCode::CREATED, Json(view)).into_response(),
         Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
+            error_status(&e),
             Json(serde_json::json!({ "error": e.to_string() })),
         )
             .into_response(),
@@ -181,7 +185,7 @@ pub async fn update_view_handler(
     {
         Ok(view) => Json(view).into_response(),
         Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
+            error_status(&e),
             Json(serde_json::json!({ "error": e.to_string() })),
         )
             .into_response(),
@@ -195,7 +199,7 @@ pub async fn delete_view_handler(
     match state.manage_view.delete(id).await {
         Ok(_) => StatusCode::NO_CONTENT.into_response(),
         Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
+            error_status(&e),
             Json(serde_json::json!({ "error": e.to_string() })),
         )
             .into_response(),
@@ -1,9 +1,21 @@
+pub mod api_key_handlers;
+pub mod auth;
 pub mod custom_object_handlers;
 pub mod dynamic_ui_handlers;
 pub mod email_handlers;
+pub mod errors;
 pub mod fragments;
 pub mod handlers;
+pub mod health_handlers;
 pub mod lead_handlers;
+pub mod list_query;
+pub mod member_handlers;
 pub mod metadata_handlers;
 pub mod metadata_ui_handlers;
 pub mod oob;
+pub mod role_handlers;
+pub mod sso_handlers;
+pub mod tenant;
+pub mod trash_handlers;
+pub mod user_handlers;
+pub mod workflow_handlers;
@@ -0,0 +1,77 @@
+use crate::application::use_cases::manage_roles::ManageRoles;
+use crate::domain::permissions::Action;
+use crate::infrastructure::web::errors::error_status;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct RoleAppState {
+    pub manage_roles: Arc<ManageRoles>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateRolePayload {
+    pub name: String,
+    /// Object key (e.g. "lead", "custom_object:<id>" or "*") to granted actions
+    pub permissions: HashMap<String, Vec<Action>>,
+}
+
+#[derive(Deserialize)]
+pub struct AssignRolePayload {
+    pub role: String,
+}
+
+// GET /api/roles - List custom roles
+pub async fn list_roles_handler(State(state): State<RoleAppState>) -> impl IntoResponse {
+    match state.manage_roles.list().await {
+        Ok(roles) => Json(roles).into_response(),
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+// POST /api/roles - Create custom role
+pub async fn create_role_handler(
+    State(state): State<RoleAppState>,
+    Json(payload): Json<CreateRolePayload>,
+) -> impl IntoResponse {
+    match state
+        .manage_roles
+        .create(payload.name, payload.permissions)
+        .await
+    {
+        Ok(role) => (StatusCode::CREATED, Json(role)).into_response(),
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+// PUT /api/members/:id/role - Assign a built-in or custom role
+pub async fn assign_role_handler(
+    State(state): State<RoleAppState>,
+    Path(member_id): Path<Uuid>,
+    Json(payload): Json<AssignRolePayload>,
+) -> impl IntoResponse {
+    match state.manage_roles.assign(member_id, payload.role).await {
+        Ok(member) => Json(member).into_response(),
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
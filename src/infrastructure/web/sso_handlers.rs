@@ -0,0 +1,187 @@
+use crate::application::use_cases::manage_sso::{ManageSso, SsoConfigInput};
+use crate::domain::DomainError;
+use crate::infrastructure::web::errors::error_status;
+use crate::infrastructure::web::fragments;
+use crate::infrastructure::web::handlers::session_cookie;
+use crate::infrastructure::web::tenant::CurrentWorkspace;
+use axum::{
+    extract::{Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Redirect, Response},
+    Json,
+};
+use axum_extra::extract::cookie::{Cookie, CookieJar, SameSite};
+use serde::Deserialize;
+use std::sync::Arc;
+
+/// Cookie carrying state, nonce and PKCE verifier from `/sso/login` to `/sso/callback`
+pub const SSO_FLOW_COOKIE: &str = "oxicrm_sso";
+const SSO_FLOW_TTL_MINUTES: i64 = 10;
+
+#[derive(Clone)]
+pub struct SsoAppState {
+    pub manage_sso: Arc<ManageSso>,
+    /// Scheme of the public URLs the provider redirects back to (`https` behind TLS)
+    pub scheme: String,
+}
+
+#[derive(Deserialize)]
+pub struct CallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct SsoConfigPayload {
+    pub issuer_url: String,
+    pub client_id: String,
+    /// Omit to keep the stored secret
+    pub client_secret: Option<String>,
+    pub default_role: String,
+    #[serde(default)]
+    pub enforced: bool,
+}
+
+/// Callback URL on the workspace's own host, which must be registered with the provider
+fn redirect_uri(state: &SsoAppState, headers: &HeaderMap) -> Option<String> {
+    let host = headers.get(header::HOST)?.to_str().ok()?;
+    Some(format!("{}://{}/sso/callback", state.scheme, host))
+}
+
+fn sso_error(status: StatusCode, message: &str) -> Response {
+    (status, fragments::layout(fragments::sso_error(message))).into_response()
+}
+
+// GET /sso/login - Redirect to the workspace's identity provider
+pub async fn sso_login_handler(
+    State(state): State<SsoAppState>,
+    CurrentWorkspace(workspace): CurrentWorkspace,
+    headers: HeaderMap,
+    jar: CookieJar,
+) -> Response {
+    let Some(redirect_uri) = redirect_uri(&state, &headers) else {
+        return sso_error(StatusCode::BAD_REQUEST, "Missing Host header");
+    };
+
+    match state.manage_sso.start(workspace.id, &redirect_uri).await {
+        Ok(authorization) => {
+            // All three parts are hex, so '.' separates them unambiguously
+            let flow = format!(
+                "{}.{}.{}",
+                authorization.state, authorization.nonce, authorization.pkce_verifier
+            );
+            let cookie = Cookie::build((SSO_FLOW_COOKIE, flow))
+                .http_only(true)
+                .same_site(SameSite::Lax)
+                .path("/sso")
+                .max_age(time::Duration::minutes(SSO_FLOW_TTL_MINUTES))
+                .build();
+            (jar.add(cookie), Redirect::to(&authorization.url)).into_response()
+        }
+        Err(DomainError::InvalidState(msg)) => sso_error(StatusCode::BAD_REQUEST, &msg),
+        Err(e) => {
+            eprintln!("Error starting single sign-on: {:?}", e);
+            sso_error(
+                error_status(&e),
+                "The identity provider could not be reached",
+            )
+        }
+    }
+}
+
+// GET /sso/callback - Return from the identity provider
+pub async fn sso_callback_handler(
+    State(state): State<SsoAppState>,
+    CurrentWorkspace(workspace): CurrentWorkspace,
+    headers: HeaderMap,
+    jar: CookieJar,
+    Query(query): Query<CallbackQuery>,
+) -> Response {
+    if let Some(error) = query.error {
+        return sso_error(
+            StatusCode::FORBIDDEN,
+            &format!("Sign-on was not completed: {}", error),
+        );
+    }
+
+    let flow = jar
+        .get(SSO_FLOW_COOKIE)
+        .map(|c| c.value().to_string())
+        .unwrap_or_default();
+    let jar = jar.remove(Cookie::build(SSO_FLOW_COOKIE).path("/sso"));
+    let mut parts = flow.splitn(3, '.');
+    let (Some(expected_state), Some(nonce), Some(pkce_verifier)) =
+        (parts.next(), parts.next(), parts.next())
+    else {
+        return sso_error(
+            StatusCode::BAD_REQUEST,
+            "Sign-on session expired, please try again",
+        );
+    };
+    let (Some(code), Some(returned_state)) = (query.code, query.state) else {
+        return sso_error(StatusCode::BAD_REQUEST, "Invalid sign-on response");
+    };
+    if returned_state != expected_state {
+        return sso_error(StatusCode::BAD_REQUEST, "Invalid sign-on response");
+    }
+    let Some(redirect_uri) = redirect_uri(&state, &headers) else {
+        return sso_error(StatusCode::BAD_REQUEST, "Missing Host header");
+    };
+
+    match state
+        .manage_sso
+        .complete(workspace.id, &code, &redirect_uri, pkce_verifier, nonce)
+        .await
+    {
+        Ok((_, token)) => {
+            let cookie = session_cookie(token, state.scheme == "https");
+            (jar.add(cookie), Redirect::to("/")).into_response()
+        }
+        Err(DomainError::Permission(msg)) | Err(DomainError::InvalidState(msg)) => {
+            (jar, sso_error(StatusCode::FORBIDDEN, &msg)).into_response()
+        }
+        Err(e) => {
+            eprintln!("Error completing single sign-on: {:?}", e);
+            (
+                jar,
+                sso_error(error_status(&e), "Error completing single sign-on"),
+            )
+                .into_response()
+        }
+    }
+}
+
+// GET /api/sso - Single sign-on settings of the workspace
+pub async fn get_sso_config_handler(State(state): State<SsoAppState>) -> impl IntoResponse {
+    match state.manage_sso.get_config().await {
+        Ok(config) => Json(config).into_response(),
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+// PUT /api/sso - Configure single sign-on
+pub async fn put_sso_config_handler(
+    State(state): State<SsoAppState>,
+    Json(payload): Json<SsoConfigPayload>,
+) -> impl IntoResponse {
+    let input = SsoConfigInput {
+        issuer_url: payload.issuer_url,
+        client_id: payload.client_id,
+        client_secret: payload.client_secret,
+        default_role: payload.default_role,
+        enforced: payload.enforced,
+    };
+    match state.manage_sso.configure(input).await {
+        Ok(config) => Json(config).into_response(),
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
@@ -0,0 +1,147 @@
+use crate::application::ports::output::WorkspaceRepository;
+use crate::domain::entities::Workspace;
+use crate::domain::states::WorkspaceState;
+use crate::infrastructure::web::auth::is_api_path;
+use async_trait::async_trait;
+use axum::{
+    extract::{FromRequestParts, Request, State},
+    http::{header, request::Parts, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use std::sync::Arc;
+
+/// Header naming the workspace subdomain when the host carries none (local development,
+/// scripts talking to `localhost:3001`)
+pub const WORKSPACE_HEADER: &str = "X-Workspace";
+
+/// State of `workspace_middleware`
+#[derive(Clone)]
+pub struct WorkspaceRouting {
+    pub workspace_repo: Arc<dyn WorkspaceRepository>,
+    /// Domain the workspace subdomains live under, e.g. `oxicrm.app` for `acme.oxicrm.app`
+    pub base_domain: String,
+}
+
+/// Resolves the workspace a request is addressed to from the `Host` header
+/// (`<subdomain>.<base domain>`), falling back to the `X-Workspace` header, and stores it
+/// in the request extensions. Must run before `identity_middleware`, which picks the
+/// caller's membership in that workspace; use cases then see it as the ambient
+/// workspace. Requests without a subdomain pass through untouched.
+pub async fn workspace_middleware(
+    State(routing): State<WorkspaceRouting>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let Some(subdomain) = requested_subdomain(request.headers(), &routing.base_domain) else {
+        return next.run(request).await;
+    };
+
+    let workspace = match routing.workspace_repo.find_by_subdomain(&subdomain).await {
+        Ok(Some(workspace)) => workspace,
+        Ok(None) => {
+            return reject(&request, StatusCode::NOT_FOUND, "Unknown workspace");
+        }
+        Err(e) => {
+            eprintln!("Error resolving workspace: {}", e);
+            return reject(
+                &request,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Could not resolve workspace",
+            );
+        }
+    };
+
+    match workspace.state {
+        WorkspaceState::Active => {}
+        WorkspaceState::Pending => {
+            return reject(
+                &request,
+                StatusCode::FORBIDDEN,
+                "Workspace is not active yet",
+            );
+        }
+        WorkspaceState::Suspended => {
+            return reject(&request, StatusCode::FORBIDDEN, "Workspace is suspended");
+        }
+    }
+
+    request.extensions_mut().insert(workspace);
+    next.run(request).await
+}
+
+fn requested_subdomain(headers: &HeaderMap, base_domain: &str) -> Option<String> {
+    headers
+        .get(header::HOST)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|host| subdomain_of(host, base_domain))
+        .or_else(|| {
+            headers
+                .get(WORKSPACE_HEADER)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.trim().to_ascii_lowercase())
+                .filter(|v| !v.is_empty())
+        })
+}
+
+/// `acme` for `acme.oxicrm.app:3001` under `oxicrm.app`; `None` for the bare domain,
+/// other domains and nested subdomains
+fn subdomain_of(host: &str, base_domain: &str) -> Option<String> {
+    let host = host.split(':').next()?.to_ascii_lowercase();
+    let subdomain = host
+        .strip_suffix(&base_domain.to_ascii_lowercase())?
+        .strip_suffix('.')?;
+    if subdomain.is_empty() || subdomain.contains('.') {
+        return None;
+    }
+    Some(subdomain.to_string())
+}
+
+fn reject(request: &Request, status: StatusCode, message: &str) -> Response {
+    if is_api_path(request.uri().path()) {
+        return (status, Json(serde_json::json!({ "error": message }))).into_response();
+    }
+    (status, message.to_string()).into_response()
+}
+
+/// The workspace the request is routed to (see `workspace_middleware`)
+pub struct CurrentWorkspace(pub Workspace);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for CurrentWorkspace {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        match parts.extensions.get::<Workspace>() {
+            Some(workspace) => Ok(CurrentWorkspace(workspace.clone())),
+            None if is_api_path(parts.uri.path()) => Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": "No workspace in request" })),
+            )
+                .into_response()),
+            None => Err((StatusCode::BAD_REQUEST, "No workspace in request").into_response()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subdomain_of_host() {
+        assert_eq!(
+            subdomain_of("acme.oxicrm.app", "oxicrm.app"),
+            Some("acme".to_string())
+        );
+        assert_eq!(
+            subdomain_of("Acme.localhost:3001", "localhost"),
+            Some("acme".to_string())
+        );
+        assert_eq!(subdomain_of("oxicrm.app", "oxicrm.app"), None);
+        assert_eq!(subdomain_of("a.b.oxicrm.app", "oxicrm.app"), None);
+        assert_eq!(subdomain_of("acme.example.com", "oxicrm.app"), None);
+        assert_eq!(subdomain_of("acmeoxicrm.app", "oxicrm.app"), None);
+    }
+}
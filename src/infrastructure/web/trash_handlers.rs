@@ -0,0 +1,113 @@
+use crate::application::use_cases::manage_trash::ManageTrash;
+use crate::domain::{DomainError, TrashedKind};
+use crate::infrastructure::web::errors::error_status;
+use crate::infrastructure::web::fragments;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{Html, IntoResponse},
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct TrashAppState {
+    pub manage_trash: Arc<ManageTrash>,
+    /// Days a record stays in the trash before it is purged
+    pub retention_days: i64,
+}
+
+/// `person`, `company`, ... as in `TrashedKind::object`; unknown kinds have no records
+fn parse_kind(kind: &str) -> Result<TrashedKind, DomainError> {
+    TrashedKind::parse(kind).ok_or(DomainError::NotFound)
+}
+
+// GET /api/trash - List trashed records
+pub async fn list_trash_handler(State(state): State<TrashAppState>) -> impl IntoResponse {
+    match state.manage_trash.list().await {
+        Ok(records) => Json(records).into_response(),
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+// POST /api/trash/:kind/:id/restore - Restore a trashed record
+pub async fn restore_trashed_handler(
+    State(state): State<TrashAppState>,
+    Path((kind, id)): Path<(String, Uuid)>,
+) -> impl IntoResponse {
+    let result = match parse_kind(&kind) {
+        Ok(kind) => state.manage_trash.restore(kind, id).await,
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+// DELETE /api/trash/:kind/:id - Permanently delete a trashed record
+pub async fn purge_trashed_handler(
+    State(state): State<TrashAppState>,
+    Path((kind, id)): Path<(String, Uuid)>,
+) -> impl IntoResponse {
+    let result = match parse_kind(&kind) {
+        Ok(kind) => state.manage_trash.purge(kind, id).await,
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+pub async fn trash_page_handler(State(state): State<TrashAppState>) -> impl IntoResponse {
+    let records = state.manage_trash.list().await.unwrap_or_default();
+    Html(fragments::layout(fragments::trash_list(&records, state.retention_days)).into_string())
+}
+
+pub async fn restore_trashed_row_handler(
+    State(state): State<TrashAppState>,
+    Path((kind, id)): Path<(String, Uuid)>,
+) -> impl IntoResponse {
+    let result = match parse_kind(&kind) {
+        Ok(kind) => state.manage_trash.restore(kind, id).await,
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(()) => "", // Removes the row
+        Err(e) => {
+            eprintln!("Error restoring record: {:?}", e);
+            "Error restoring record"
+        }
+    }
+}
+
+pub async fn purge_trashed_row_handler(
+    State(state): State<TrashAppState>,
+    Path((kind, id)): Path<(String, Uuid)>,
+) -> impl IntoResponse {
+    let result = match parse_kind(&kind) {
+        Ok(kind) => state.manage_trash.purge(kind, id).await,
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(()) => "", // Removes the row
+        Err(e) => {
+            eprintln!("Error deleting record: {:?}", e);
+            "Error deleting record"
+        }
+    }
+}
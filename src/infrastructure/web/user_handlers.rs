@@ -0,0 +1,141 @@
+use crate::application::use_cases::manage_users::ManageUsers;
+use crate::application::use_cases::reset_password::ResetPassword;
+use crate::application::use_cases::verify_email::VerifyEmail;
+use crate::domain::DomainError;
+use crate::infrastructure::web::errors::error_status;
+use crate::infrastructure::web::fragments;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct UserAppState {
+    pub verify_email: Arc<VerifyEmail>,
+    pub manage_users: Arc<ManageUsers>,
+    pub reset_password: Arc<ResetPassword>,
+}
+
+#[derive(Deserialize)]
+pub struct ForgotPasswordPayload {
+    pub email: String,
+}
+
+#[derive(Deserialize)]
+pub struct ResetPasswordPayload {
+    pub password: String,
+}
+
+#[derive(Deserialize)]
+pub struct DeleteAccountPayload {
+    pub password: String,
+}
+
+// GET /verify-email/:token - Link from the verification email
+pub async fn verify_email_handler(
+    State(state): State<UserAppState>,
+    Path(token): Path<String>,
+) -> impl IntoResponse {
+    let (status, message, verified) = match state.verify_email.verify(&token).await {
+        Ok(_) => (
+            StatusCode::OK,
+            "Your email address is verified.".to_string(),
+            true,
+        ),
+        Err(DomainError::NotFound) => (
+            StatusCode::NOT_FOUND,
+            "Verification link not found".to_string(),
+            false,
+        ),
+        Err(DomainError::InvalidState(msg)) => (StatusCode::BAD_REQUEST, msg, false),
+        Err(e) => {
+            eprintln!("Error verifying email: {:?}", e);
+            (error_status(&e), "Error verifying email".to_string(), false)
+        }
+    };
+    (
+        status,
+        fragments::layout(fragments::email_verification_result(&message, verified)),
+    )
+}
+
+pub async fn get_forgot_password_handler() -> impl IntoResponse {
+    fragments::layout(fragments::forgot_password_form())
+}
+
+// POST /forgot-password - Email a reset link; the answer never reveals whether the account exists
+pub async fn post_forgot_password_handler(
+    State(state): State<UserAppState>,
+    axum::Form(payload): axum::Form<ForgotPasswordPayload>,
+) -> impl IntoResponse {
+    if let Err(e) = state.reset_password.request(&payload.email).await {
+        eprintln!("Error requesting password reset: {:?}", e);
+    }
+    "If an account exists for that email, a reset link is on its way."
+}
+
+// GET /reset-password/:token - Link from the reset email
+pub async fn get_reset_password_handler(
+    State(state): State<UserAppState>,
+    Path(token): Path<String>,
+) -> impl IntoResponse {
+    match state.reset_password.find_valid(&token).await {
+        Ok(_) => fragments::layout(fragments::reset_password_form(&token)).into_response(),
+        Err(e) => (error_status(&e), reset_error(e)).into_response(),
+    }
+}
+
+pub async fn post_reset_password_handler(
+    State(state): State<UserAppState>,
+    Path(token): Path<String>,
+    axum::Form(payload): axum::Form<ResetPasswordPayload>,
+) -> impl IntoResponse {
+    match state.reset_password.reset(&token, &payload.password).await {
+        Ok(_) => ([("HX-Redirect", "/login")], "Password changed").into_response(),
+        Err(e) => reset_error(e).into_response(),
+    }
+}
+
+fn reset_error(error: DomainError) -> String {
+    match error {
+        DomainError::Validation(msg)
+        | DomainError::Permission(msg)
+        | DomainError::InvalidState(msg) => msg,
+        DomainError::NotFound => "Reset link not found".to_string(),
+        e => {
+            eprintln!("Error resetting password: {:?}", e);
+            "Error resetting password".to_string()
+        }
+    }
+}
+
+// POST /api/account/verification - Resend the verification email
+pub async fn resend_verification_handler(State(state): State<UserAppState>) -> impl IntoResponse {
+    match state.verify_email.resend().await {
+        Ok(()) => StatusCode::ACCEPTED.into_response(),
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+// DELETE /api/account - Delete and anonymize the signed-in user's account
+pub async fn delete_account_handler(
+    State(state): State<UserAppState>,
+    Json(payload): Json<DeleteAccountPayload>,
+) -> impl IntoResponse {
+    match state.manage_users.delete_account(&payload.password).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
@@ -0,0 +1,200 @@
+use crate::application::use_cases::manage_workflow_versions::{
+    AddStepInput, ManageWorkflowVersions,
+};
+use crate::application::workflow::launcher::WorkflowLauncher;
+use crate::domain::states::WorkflowStepType;
+use crate::infrastructure::web::errors::error_status;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct WorkflowAppState {
+    pub manage_workflow_versions: Arc<ManageWorkflowVersions>,
+}
+
+#[derive(Clone)]
+pub struct WorkflowWebhookAppState {
+    pub launcher: Arc<WorkflowLauncher>,
+}
+
+#[derive(Deserialize)]
+pub struct AddStepPayload {
+    pub name: Option<String>,
+    pub step_type: WorkflowStepType,
+    #[serde(default = "empty_settings")]
+    pub settings: serde_json::Value,
+    pub position: Option<i32>,
+}
+
+fn empty_settings() -> serde_json::Value {
+    serde_json::json!({})
+}
+
+// GET /api/workflows/:id/versions - List versions of a workflow
+pub async fn list_versions_handler(
+    State(state): State<WorkflowAppState>,
+    Path(workflow_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state
+        .manage_workflow_versions
+        .list_versions(workflow_id)
+        .await
+    {
+        Ok(versions) => Json(versions).into_response(),
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+// POST /api/workflows/:id/versions - Create a draft version
+pub async fn create_version_handler(
+    State(state): State<WorkflowAppState>,
+    Path(workflow_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state
+        .manage_workflow_versions
+        .create_version(workflow_id)
+        .await
+    {
+        Ok(version) => (StatusCode::CREATED, Json(version)).into_response(),
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+// GET /api/workflow-versions/:id/steps - List steps of a version
+pub async fn list_steps_handler(
+    State(state): State<WorkflowAppState>,
+    Path(version_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.manage_workflow_versions.list_steps(version_id).await {
+        Ok(steps) => Json(steps).into_response(),
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+// POST /api/workflow-versions/:id/steps - Add a step to a draft version
+pub async fn add_step_handler(
+    State(state): State<WorkflowAppState>,
+    Path(version_id): Path<Uuid>,
+    Json(payload): Json<AddStepPayload>,
+) -> impl IntoResponse {
+    let input = AddStepInput {
+        name: payload.name,
+        step_type: payload.step_type,
+        settings: payload.settings,
+        position: payload.position,
+    };
+    match state
+        .manage_workflow_versions
+        .add_step(version_id, input)
+        .await
+    {
+        Ok(step) => (StatusCode::CREATED, Json(step)).into_response(),
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+// POST /api/workflow-versions/:id/publish - Publish a draft version
+pub async fn publish_version_handler(
+    State(state): State<WorkflowAppState>,
+    Path(version_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.manage_workflow_versions.publish(version_id).await {
+        Ok(version) => Json(version).into_response(),
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct WebhookQuery {
+    pub token: String,
+}
+
+// POST /api/workflows/:id/runs - Run the published version, the body being its input
+pub async fn run_workflow_handler(
+    State(state): State<WorkflowAppState>,
+    Path(workflow_id): Path<Uuid>,
+    payload: Option<Json<serde_json::Value>>,
+) -> impl IntoResponse {
+    let payload = payload.map(|Json(payload)| payload);
+    match state
+        .manage_workflow_versions
+        .run(workflow_id, payload)
+        .await
+    {
+        Ok(run) => (StatusCode::CREATED, Json(run)).into_response(),
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+// GET /api/workflow-runs/:id - Get a run
+pub async fn get_run_handler(
+    State(state): State<WorkflowAppState>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.manage_workflow_versions.get_run(id).await {
+        Ok(run) => Json(run).into_response(),
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+// POST /webhooks/workflows/:id?token= - Run a workflow from its webhook trigger
+pub async fn workflow_webhook_handler(
+    State(state): State<WorkflowWebhookAppState>,
+    Path(workflow_id): Path<Uuid>,
+    Query(query): Query<WebhookQuery>,
+    payload: Option<Json<serde_json::Value>>,
+) -> impl IntoResponse {
+    let payload = payload.map(|Json(payload)| payload);
+    match state
+        .launcher
+        .run_webhook(workflow_id, &query.token, payload)
+        .await
+    {
+        // The caller learns which run it started, not what the workflow did
+        Ok(run) => (
+            StatusCode::ACCEPTED,
+            Json(serde_json::json!({ "run_id": run.id })),
+        )
+            .into_response(),
+        Err(e) => (
+            error_status(&e),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
@@ -1,6 +1,5 @@
 use axum::{routing::get, Router};
 use migration::{Migrator, MigratorTrait};
-use sea_orm::Database;
 use std::sync::Arc;
 use tokio::net::TcpListener;
 use tokio::sync::mpsc;
@@ -10,28 +9,36 @@ mod domain;
 mod infrastructure;
 mod shared;
 
+use application::events::record_events::RecordEvents;
 use application::ports::{identity::IdentityProvider, time::Clock};
 use application::use_cases::RecordBoardCard;
-use infrastructure::persistence::sea_orm_repo::SeaOrmRepo;
+use infrastructure::config::Config;
+use infrastructure::persistence::sea_orm_repo::SeaOrmRepositories;
 use infrastructure::web::handlers::{get_board_handler, move_card_handler, AppState};
 // New Adapters
 use infrastructure::billing::MockBillingProvider;
 use infrastructure::external::MockWebhookSender;
-use infrastructure::identity::MockIdentityProvider;
+use infrastructure::identity::RepositoryIdentityProvider;
 use infrastructure::messaging::InMemoryEventBus;
 use infrastructure::scheduling::InMemoryJobQueue;
 use infrastructure::search::MockSearchIndex;
 use infrastructure::storage::FileSystemStorage;
 use infrastructure::time::SystemClock;
+use infrastructure::web::tenant::WorkspaceRouting;
+
+#[cfg(not(any(feature = "sqlite", feature = "postgres")))]
+compile_error!("Enable a database driver: the `sqlite` or the `postgres` feature");
 
 #[tokio::main]
 async fn main() {
     // Initialize tracing
     tracing_subscriber::fmt::init();
+    // Workflow runs saved before this are the previous process's, see `WorkflowRunWorker`
+    let started_at = chrono::Utc::now();
 
-    // 1. Database Connection
-    let db_url = std::env::var("DATABASE_URL").unwrap_or_else(|_| "sqlite::memory:".to_owned());
-    let db = Database::connect(&db_url)
+    // 1. Configuration and Database Connection
+    let config = Config::from_env().unwrap_or_else(|e| panic!("Invalid configuration: {}", e));
+    let db = infrastructure::persistence::connection::connect(&config.database)
         .await
         .expect("Failed to connect to database");
 
@@ -41,15 +48,23 @@ async fn main() {
         .expect("Failed to run migrations");
 
     // 3. Initialize Adapters
-    let repo = Arc::new(SeaOrmRepo { db });
+    let repos = SeaOrmRepositories::new(db.clone());
     let event_bus = Arc::new(InMemoryEventBus::new());
+    let record_events = Arc::new(RecordEvents::new(event_bus.clone()));
 
     // Logic for Job Queue Receiver would go here in real app, spawning a worker
     let (job_sender, _job_receiver) = mpsc::channel(100);
     let job_queue = Arc::new(InMemoryJobQueue::new(job_sender));
 
     let clock = Arc::new(SystemClock);
-    let identity_provider = Arc::new(MockIdentityProvider);
+    let identity_provider: Arc<dyn IdentityProvider> = Arc::new(RepositoryIdentityProvider::new(
+        repos.users.clone(),
+        repos.users.clone(),
+        repos.api_keys.clone(),
+        repos.workspaces.clone(),
+        repos.workspaces.clone(),
+        repos.workspaces.clone(),
+    ));
     let search_index = Arc::new(MockSearchIndex);
     let webhook_sender = Arc::new(MockWebhookSender);
     let billing_provider = Arc::new(MockBillingProvider);
@@ -73,36 +88,114 @@ async fn main() {
     use application::use_cases::manage_note::ManageNote;
     use application::use_cases::manage_opportunity::ManageOpportunity;
     use application::use_cases::manage_person::ManagePerson;
+    use application::use_cases::manage_session::ManageSession;
     use application::use_cases::manage_task::ManageTask;
     use application::use_cases::manage_timeline_activity::ManageTimelineActivity;
     use application::use_cases::manage_workflow::ManageWorkflow;
     use application::use_cases::register_user::RegisterUser;
+    use application::use_cases::verify_email::VerifyEmail;
+    use infrastructure::identity::Argon2PasswordHasher;
     // ... imports ...
 
     // 4. Initialize Use Cases
     let record_use_case = Arc::new(RecordBoardCard {
-        opportunity_repo: repo.clone(),
+        opportunity_repo: repos.opportunities.clone(),
+        identity_provider: identity_provider.clone(),
+        record_events: record_events.clone(),
     });
+    let password_hasher = Arc::new(Argon2PasswordHasher::new());
+    let email_provider = Arc::new(MockEmailProvider::new());
+    let verify_email_use_case = Arc::new(VerifyEmail::new(
+        repos.users.clone(),
+        repos.users.clone(),
+        email_provider.clone(),
+        config.app_url(),
+    ));
     let register_user_use_case = Arc::new(RegisterUser {
-        user_repo: repo.clone(),
+        user_repo: repos.users.clone(),
+        password_hasher: password_hasher.clone(),
+        verify_email: verify_email_use_case.clone(),
     });
-    let create_workspace_use_case = Arc::new(CreateWorkspace::new(repo.clone()));
-    let create_person_use_case = Arc::new(CreatePerson::new(repo.clone()));
-    let manage_person_use_case = Arc::new(ManagePerson::new(repo.clone()));
-    let create_company_use_case = Arc::new(CreateCompany::new(repo.clone()));
-    let manage_company_use_case = Arc::new(ManageCompany::new(repo.clone()));
-    let create_opportunity_use_case = Arc::new(CreateOpportunity::new(repo.clone()));
-    let manage_opportunity_use_case = Arc::new(ManageOpportunity::new(repo.clone()));
-    let create_task_use_case = Arc::new(CreateTask::new(repo.clone()));
-    let manage_task_use_case = Arc::new(ManageTask::new(repo.clone()));
-    let create_note_use_case = Arc::new(CreateNote::new(repo.clone()));
-    let manage_note_use_case = Arc::new(ManageNote::new(repo.clone()));
-    let create_workflow_use_case = Arc::new(CreateWorkflow::new(repo.clone()));
-    let manage_workflow_use_case = Arc::new(ManageWorkflow::new(repo.clone()));
-    let create_calendar_event_use_case = Arc::new(CreateCalendarEvent::new(repo.clone()));
-    let manage_calendar_event_use_case = Arc::new(ManageCalendarEvent::new(repo.clone()));
-    let create_timeline_activity_use_case = Arc::new(CreateTimelineActivity::new(repo.clone()));
-    let manage_timeline_activity_use_case = Arc::new(ManageTimelineActivity::new(repo.clone()));
+    let manage_session_use_case = Arc::new(ManageSession::new(
+        repos.users.clone(),
+        repos.users.clone(),
+        password_hasher.clone(),
+    ));
+    let create_workspace_use_case = Arc::new(CreateWorkspace::new(repos.workspaces.clone()));
+    let create_person_use_case = Arc::new(CreatePerson::new(
+        repos.people.clone(),
+        identity_provider.clone(),
+        record_events.clone(),
+    ));
+    let manage_person_use_case = Arc::new(ManagePerson::new(
+        repos.people.clone(),
+        identity_provider.clone(),
+        record_events.clone(),
+    ));
+    let create_company_use_case = Arc::new(CreateCompany::new(
+        repos.companies.clone(),
+        identity_provider.clone(),
+        record_events.clone(),
+    ));
+    let manage_company_use_case = Arc::new(ManageCompany::new(
+        repos.companies.clone(),
+        identity_provider.clone(),
+        record_events.clone(),
+    ));
+    let create_opportunity_use_case = Arc::new(CreateOpportunity::new(
+        repos.opportunities.clone(),
+        identity_provider.clone(),
+        record_events.clone(),
+    ));
+    let manage_opportunity_use_case = Arc::new(ManageOpportunity::new(
+        repos.opportunities.clone(),
+        identity_provider.clone(),
+        record_events.clone(),
+    ));
+    let create_task_use_case = Arc::new(CreateTask::new(
+        repos.tasks.clone(),
+        identity_provider.clone(),
+        record_events.clone(),
+    ));
+    let manage_task_use_case = Arc::new(ManageTask::new(
+        repos.tasks.clone(),
+        identity_provider.clone(),
+        record_events.clone(),
+    ));
+    let create_note_use_case = Arc::new(CreateNote::new(
+        repos.notes.clone(),
+        identity_provider.clone(),
+        record_events.clone(),
+    ));
+    let manage_note_use_case = Arc::new(ManageNote::new(
+        repos.notes.clone(),
+        identity_provider.clone(),
+        record_events.clone(),
+    ));
+    let create_workflow_use_case = Arc::new(CreateWorkflow::new(
+        repos.workflows.clone(),
+        identity_provider.clone(),
+    ));
+    let manage_workflow_use_case = Arc::new(ManageWorkflow::new(
+        repos.workflows.clone(),
+        identity_provider.clone(),
+    ));
+    let create_calendar_event_use_case = Arc::new(CreateCalendarEvent::new(
+        repos.calendar.clone(),
+        identity_provider.clone(),
+    ));
+    let manage_calendar_event_use_case = Arc::new(ManageCalendarEvent::new(
+        repos.calendar.clone(),
+        identity_provider.clone(),
+    ));
+    let create_timeline_activity_use_case = Arc::new(CreateTimelineActivity::new(
+        repos.timeline.clone(),
+        identity_provider.clone(),
+    ));
+    let manage_timeline_activity_use_case = Arc::new(ManageTimelineActivity::new(
+        repos.timeline.clone(),
+        identity_provider.clone(),
+    ));
 
     // Email System Initialization
     use application::events::email_subscriber::EmailEventSubscriber;
@@ -122,74 +215,196 @@ async fn main() {
     use application::use_cases::manage_metadata::ManageMetadata;
     use application::use_cases::manage_view::ManageView;
 
-    let email_provider = Arc::new(MockEmailProvider::new());
     let template_engine = Arc::new(SimpleTemplateEngine::new());
 
     let send_email_use_case = Arc::new(SendEmail::new(
-        repo.clone(),
-        repo.clone(),
-        repo.clone(),
+        repos.emails.clone(),
+        repos.emails.clone(),
+        repos.timeline.clone(),
         email_provider.clone(),
         template_engine.clone(),
+        identity_provider.clone(),
+        repos.unit_of_work.clone(),
     ));
 
-    let receive_email_use_case = Arc::new(ReceiveEmail::new(repo.clone(), repo.clone()));
-
-    let manage_email_template_use_case = Arc::new(ManageEmailTemplate::new(repo.clone()));
+    let receive_email_use_case = Arc::new(ReceiveEmail::new(
+        repos.emails.clone(),
+        repos.timeline.clone(),
+        identity_provider.clone(),
+        repos.unit_of_work.clone(),
+    ));
 
-    // Initialize workflow executor (commented out until workflow repositories are implemented)
-    // let workflow_executor = Arc::new(WorkflowExecutor::new(
-    //     repo.clone(),
-    //     repo.clone(),
-    //     repo.clone(),
-    //     send_email_use_case.clone(),
-    // ));
+    let manage_email_template_use_case = Arc::new(ManageEmailTemplate::new(
+        repos.emails.clone(),
+        identity_provider.clone(),
+    ));
 
     // Start event subscriber
     let email_subscriber = Arc::new(EmailEventSubscriber::new(
         event_bus.clone(),
         send_email_use_case.clone(),
     ));
-    email_subscriber
+    let email_subscriber_task = email_subscriber
         .start()
         .await
         .expect("Failed to start email event subscriber");
 
     // Initialize lead use cases
-    let create_lead_use_case = Arc::new(CreateLead::new(repo.clone(), event_bus.clone()));
+    let create_lead_use_case = Arc::new(CreateLead::new(
+        repos.leads.clone(),
+        event_bus.clone(),
+        identity_provider.clone(),
+        record_events.clone(),
+    ));
 
-    let manage_lead_use_case = Arc::new(ManageLead::new(repo.clone(), repo.clone()));
+    let manage_lead_use_case = Arc::new(ManageLead::new(
+        repos.leads.clone(),
+        repos.timeline.clone(),
+        identity_provider.clone(),
+        record_events.clone(),
+    ));
 
     let convert_lead_use_case = Arc::new(ConvertLead::new(
-        repo.clone(),
-        repo.clone(),
-        repo.clone(),
-        repo.clone(),
-        repo.clone(),
+        repos.leads.clone(),
+        repos.people.clone(),
+        repos.companies.clone(),
+        repos.opportunities.clone(),
+        repos.timeline.clone(),
+        identity_provider.clone(),
+        repos.unit_of_work.clone(),
+        record_events.clone(),
     ));
 
-    let manage_metadata_use_case = Arc::new(ManageMetadata::new(repo.clone()));
-    let manage_view_use_case = Arc::new(ManageView::new(repo.clone()));
-    let manage_custom_object_data_use_case =
-        Arc::new(ManageCustomObjectData::new(repo.clone(), repo.clone()));
+    let manage_metadata_use_case = Arc::new(ManageMetadata::new(
+        repos.metadata.clone(),
+        identity_provider.clone(),
+    ));
+    let manage_view_use_case = Arc::new(ManageView::new(
+        repos.metadata.clone(),
+        identity_provider.clone(),
+    ));
+    let manage_custom_object_data_use_case = Arc::new(ManageCustomObjectData::new(
+        repos.metadata.clone(),
+        repos.metadata.clone(),
+        identity_provider.clone(),
+        record_events.clone(),
+    ));
 
     // Start lead event subscriber
     let lead_subscriber = Arc::new(LeadEventSubscriber::new(
         event_bus.clone(),
         send_email_use_case.clone(),
-        repo.clone(),
+        repos.timeline.clone(),
     ));
-    lead_subscriber
+    let lead_subscriber_task = lead_subscriber
         .start()
         .await
         .expect("Failed to start lead event subscriber");
 
+    // Workflow steps write records through the same use cases as the routes
+    use application::workflow::records::RecordActions;
+    let record_actions = Arc::new(RecordActions {
+        create_person: create_person_use_case.clone(),
+        manage_person: manage_person_use_case.clone(),
+        person_repo: repos.people.clone(),
+        create_company: create_company_use_case.clone(),
+        manage_company: manage_company_use_case.clone(),
+        company_repo: repos.companies.clone(),
+        create_opportunity: create_opportunity_use_case.clone(),
+        manage_opportunity: manage_opportunity_use_case.clone(),
+        create_task: create_task_use_case.clone(),
+        manage_task: manage_task_use_case.clone(),
+        create_note: create_note_use_case.clone(),
+        manage_note: manage_note_use_case.clone(),
+        create_lead: create_lead_use_case.clone(),
+        manage_lead: manage_lead_use_case.clone(),
+        manage_custom_object_data: manage_custom_object_data_use_case.clone(),
+        identity_provider: identity_provider.clone(),
+    });
+    let workflow_executor = Arc::new(WorkflowExecutor::new(
+        repos.workflows.clone(),
+        repos.workflows.clone(),
+        repos.workflows.clone(),
+        repos.workflows.clone(),
+        send_email_use_case.clone(),
+        record_actions,
+    ));
+
+    // Start workflows from their triggers: record events, and cron schedules checked every
+    // minute. Webhook and manual triggers go through the routes below.
+    use application::events::workflow_trigger_subscriber::WorkflowTriggerSubscriber;
+    use application::workflow::launcher::WorkflowLauncher;
+    let workflow_launcher = Arc::new(WorkflowLauncher::new(
+        repos.workflows.clone(),
+        workflow_executor.clone(),
+    ));
+    let workflow_trigger_subscriber = Arc::new(WorkflowTriggerSubscriber::new(
+        event_bus.clone(),
+        workflow_launcher.clone(),
+    ));
+    let workflow_subscriber_task = workflow_trigger_subscriber
+        .start()
+        .await
+        .expect("Failed to start workflow trigger subscriber");
+    let schedule_launcher = workflow_launcher.clone();
+    let workflow_scheduler_task = tokio::spawn(async move {
+        use std::time::Duration;
+        let mut interval = tokio::time::interval(Duration::from_secs(60));
+        let mut since = chrono::Utc::now();
+        loop {
+            interval.tick().await;
+            let until = chrono::Utc::now();
+            let due = schedule_launcher.run_due_schedules(since, until);
+            if let Err(e) = application::context::run_as_system(due).await {
+                tracing::error!("Failed to run scheduled workflows: {}", e);
+            }
+            since = until;
+        }
+    });
+
+    // Resume the runs a restart interrupted, then every 15 seconds those whose delay is over
+    use application::jobs::workflow_worker::WorkflowRunWorker;
+    let (workflow_job_sender, workflow_job_receiver) = mpsc::channel(10);
+    let workflow_run_worker = WorkflowRunWorker::new(
+        repos.workflows.clone(),
+        workflow_executor.clone(),
+        workflow_job_receiver,
+        started_at,
+    );
+    let workflow_run_worker_task = tokio::spawn(async move {
+        application::context::run_as_system(workflow_run_worker.start()).await;
+    });
+    tokio::spawn(async move {
+        use application::ports::scheduling::Job;
+        use std::time::Duration;
+        let _ = workflow_job_sender
+            .send(Job {
+                name: "resume_interrupted_workflow_runs".to_string(),
+                payload: "{}".to_string(),
+            })
+            .await;
+        let mut interval = tokio::time::interval(Duration::from_secs(15));
+        loop {
+            interval.tick().await;
+            let _ = workflow_job_sender
+                .send(Job {
+                    name: "resume_due_workflow_runs".to_string(),
+                    payload: "{}".to_string(),
+                })
+                .await;
+        }
+    });
+
     // Start job worker
     let (email_job_sender, email_job_receiver) = mpsc::channel(100);
-    let email_worker =
-        EmailJobWorker::new(repo.clone(), email_provider.clone(), email_job_receiver);
-    tokio::spawn(async move {
-        email_worker.start().await;
+    let email_worker = EmailJobWorker::new(
+        repos.emails.clone(),
+        email_provider.clone(),
+        email_job_receiver,
+    );
+    // The worker serves every workspace, so it runs as the system actor
+    let email_worker_task = tokio::spawn(async move {
+        application::context::run_as_system(email_worker.start()).await;
     });
 
     // Schedule periodic job to process pending emails (every 60 seconds)
@@ -209,35 +424,57 @@ async fn main() {
         }
     });
 
+    // Purge the trash hourly; records stay restorable for TRASH_RETENTION_DAYS (30 by default)
+    use application::jobs::trash_worker::TrashPurgeWorker;
+    let trash_retention_days = i64::from(config.trash_retention_days);
+    let (trash_job_sender, trash_job_receiver) = mpsc::channel(10);
+    let trash_worker = TrashPurgeWorker::new(
+        repos.trash.clone(),
+        chrono::Duration::days(trash_retention_days),
+        trash_job_receiver,
+    );
+    let trash_worker_task = tokio::spawn(async move {
+        application::context::run_as_system(trash_worker.start()).await;
+    });
+    tokio::spawn(async move {
+        use std::time::Duration;
+        let mut interval = tokio::time::interval(Duration::from_secs(60 * 60));
+        loop {
+            interval.tick().await;
+            use application::ports::scheduling::Job;
+            let _ = trash_job_sender
+                .send(Job {
+                    name: "purge_trash".to_string(),
+                    payload: "{}".to_string(),
+                })
+                .await;
+        }
+    });
+
     // 5. Initialize App State
     let app_state = AppState {
         record_use_case: record_use_case.clone(),
         register_user: register_user_use_case.clone(),
+        manage_session: manage_session_use_case.clone(),
         create_workspace: create_workspace_use_case.clone(),
         create_person: create_person_use_case.clone(),
         manage_person: manage_person_use_case.clone(),
-        person_repo: repo.clone(),
         create_company: create_company_use_case.clone(),
         manage_company: manage_company_use_case.clone(),
-        company_repo: repo.clone(),
         create_opportunity: create_opportunity_use_case.clone(),
         manage_opportunity: manage_opportunity_use_case.clone(),
-        opportunity_repo: repo.clone(),
+        opportunity_repo: repos.opportunities.clone(),
         create_task: create_task_use_case.clone(),
         manage_task: manage_task_use_case.clone(),
-        task_repo: repo.clone(),
         create_note: create_note_use_case.clone(),
         manage_note: manage_note_use_case.clone(),
-        note_repo: repo.clone(),
         create_workflow: create_workflow_use_case.clone(),
         manage_workflow: manage_workflow_use_case.clone(),
-        workflow_repo: repo.clone(),
         create_calendar_event: create_calendar_event_use_case.clone(),
         manage_calendar_event: manage_calendar_event_use_case.clone(),
-        calendar_event_repo: repo.clone(),
         create_timeline_activity: create_timeline_activity_use_case.clone(),
         manage_timeline_activity: manage_timeline_activity_use_case.clone(),
-        timeline_activity_repo: repo.clone(),
+        secure_cookies: config.app_scheme == "https",
     };
 
     // ... seeding ...
@@ -250,6 +487,15 @@ async fn main() {
             get(infrastructure::web::handlers::get_register_handler)
                 .post(infrastructure::web::handlers::post_register_handler),
         )
+        .route(
+            "/login",
+            get(infrastructure::web::handlers::get_login_handler)
+                .post(infrastructure::web::handlers::post_login_handler),
+        )
+        .route(
+            "/logout",
+            axum::routing::post(infrastructure::web::handlers::post_logout_handler),
+        )
         .route(
             "/workspaces",
             get(infrastructure::web::handlers::get_create_workspace_handler)
@@ -364,8 +610,9 @@ async fn main() {
         send_email: send_email_use_case.clone(),
         receive_email: receive_email_use_case.clone(),
         manage_email_template: manage_email_template_use_case.clone(),
-        email_repo: repo.clone(),
-        email_template_repo: repo.clone(),
+        email_repo: repos.emails.clone(),
+        email_template_repo: repos.emails.clone(),
+        identity_provider: identity_provider.clone(),
     };
 
     let email_router = Router::new()
@@ -398,7 +645,7 @@ async fn main() {
         create_lead: create_lead_use_case.clone(),
         manage_lead: manage_lead_use_case.clone(),
         convert_lead: convert_lead_use_case.clone(),
-        lead_repo: repo.clone(),
+        lead_repo: repos.leads.clone(),
     };
 
     let lead_router = Router::new()
@@ -472,6 +719,267 @@ async fn main() {
         )
         .with_state(custom_object_app_state.clone());
 
+    // Role Management Routes
+    use application::use_cases::manage_roles::ManageRoles;
+    use infrastructure::web::role_handlers::{
+        assign_role_handler, create_role_handler, list_roles_handler, RoleAppState,
+    };
+
+    let role_app_state = RoleAppState {
+        manage_roles: Arc::new(ManageRoles::new(
+            repos.workspaces.clone(),
+            repos.workspaces.clone(),
+            identity_provider.clone(),
+        )),
+    };
+
+    let role_router = Router::new()
+        .route(
+            "/api/roles",
+            axum::routing::get(list_roles_handler).post(create_role_handler),
+        )
+        .route(
+            "/api/members/:id/role",
+            axum::routing::put(assign_role_handler),
+        )
+        .with_state(role_app_state);
+
+    // Member & Invitation Routes
+    use application::use_cases::manage_invitations::ManageInvitations;
+    use application::use_cases::manage_members::ManageMembers;
+    use infrastructure::web::member_handlers::{
+        accept_invitation_handler, create_invitation_handler, get_invitation_handler,
+        list_invitations_handler, list_members_handler, reactivate_member_handler,
+        remove_member_handler, revoke_invitation_handler, suspend_member_handler, MemberAppState,
+    };
+
+    let member_app_state = MemberAppState {
+        manage_members: Arc::new(ManageMembers::new(
+            repos.workspaces.clone(),
+            identity_provider.clone(),
+        )),
+        manage_invitations: Arc::new(ManageInvitations::new(
+            repos.workspaces.clone(),
+            repos.workspaces.clone(),
+            repos.workspaces.clone(),
+            repos.users.clone(),
+            register_user_use_case.clone(),
+            send_email_use_case.clone(),
+            identity_provider.clone(),
+            repos.unit_of_work.clone(),
+            config.app_url(),
+        )),
+    };
+
+    let member_router = Router::new()
+        .route("/api/members", axum::routing::get(list_members_handler))
+        .route(
+            "/api/members/:id",
+            axum::routing::delete(remove_member_handler),
+        )
+        .route(
+            "/api/members/:id/suspend",
+            axum::routing::post(suspend_member_handler),
+        )
+        .route(
+            "/api/members/:id/reactivate",
+            axum::routing::post(reactivate_member_handler),
+        )
+        .route(
+            "/api/invitations",
+            axum::routing::get(list_invitations_handler).post(create_invitation_handler),
+        )
+        .route(
+            "/api/invitations/:id",
+            axum::routing::delete(revoke_invitation_handler),
+        )
+        .route(
+            "/invitations/:token",
+            axum::routing::get(get_invitation_handler),
+        )
+        .route(
+            "/invitations/:token/accept",
+            axum::routing::post(accept_invitation_handler),
+        )
+        .with_state(member_app_state);
+
+    // API Key Routes
+    use application::use_cases::manage_api_keys::ManageApiKeys;
+    use infrastructure::web::api_key_handlers::{
+        create_api_key_handler, list_api_keys_handler, revoke_api_key_handler, ApiKeyAppState,
+    };
+
+    let api_key_app_state = ApiKeyAppState {
+        manage_api_keys: Arc::new(ManageApiKeys::new(repos.api_keys.clone())),
+    };
+
+    let api_key_router = Router::new()
+        .route(
+            "/api/api-keys",
+            axum::routing::get(list_api_keys_handler).post(create_api_key_handler),
+        )
+        .route(
+            "/api/api-keys/:id",
+            axum::routing::delete(revoke_api_key_handler),
+        )
+        .with_state(api_key_app_state);
+
+    // Trash Routes
+    use application::use_cases::manage_trash::ManageTrash;
+    use infrastructure::web::trash_handlers::{
+        list_trash_handler, purge_trashed_handler, purge_trashed_row_handler,
+        restore_trashed_handler, restore_trashed_row_handler, trash_page_handler, TrashAppState,
+    };
+
+    let trash_app_state = TrashAppState {
+        manage_trash: Arc::new(ManageTrash::new(
+            repos.trash.clone(),
+            identity_provider.clone(),
+        )),
+        retention_days: trash_retention_days,
+    };
+
+    let trash_router = Router::new()
+        .route("/api/trash", get(list_trash_handler))
+        .route(
+            "/api/trash/:kind/:id",
+            axum::routing::delete(purge_trashed_handler),
+        )
+        .route(
+            "/api/trash/:kind/:id/restore",
+            axum::routing::post(restore_trashed_handler),
+        )
+        .route("/trash", get(trash_page_handler))
+        .route(
+            "/trash/:kind/:id",
+            axum::routing::delete(purge_trashed_row_handler),
+        )
+        .route(
+            "/trash/:kind/:id/restore",
+            axum::routing::post(restore_trashed_row_handler),
+        )
+        .with_state(trash_app_state);
+
+    // User Lifecycle Routes
+    use application::use_cases::manage_users::ManageUsers;
+    use application::use_cases::reset_password::ResetPassword;
+    use infrastructure::web::user_handlers::{
+        delete_account_handler, get_forgot_password_handler, get_reset_password_handler,
+        post_forgot_password_handler, post_reset_password_handler, resend_verification_handler,
+        verify_email_handler, UserAppState,
+    };
+
+    let user_app_state = UserAppState {
+        verify_email: verify_email_use_case.clone(),
+        manage_users: Arc::new(ManageUsers::new(
+            repos.users.clone(),
+            repos.users.clone(),
+            repos.workspaces.clone(),
+            password_hasher.clone(),
+            repos.unit_of_work.clone(),
+        )),
+        reset_password: Arc::new(ResetPassword::new(
+            repos.users.clone(),
+            repos.users.clone(),
+            repos.users.clone(),
+            password_hasher.clone(),
+            email_provider.clone(),
+            config.app_url(),
+        )),
+    };
+
+    let user_router = Router::new()
+        .route(
+            "/forgot-password",
+            get(get_forgot_password_handler).post(post_forgot_password_handler),
+        )
+        .route(
+            "/reset-password/:token",
+            get(get_reset_password_handler).post(post_reset_password_handler),
+        )
+        .route(
+            "/verify-email/:token",
+            axum::routing::get(verify_email_handler),
+        )
+        .route(
+            "/api/account/verification",
+            axum::routing::post(resend_verification_handler),
+        )
+        .route(
+            "/api/account",
+            axum::routing::delete(delete_account_handler),
+        )
+        .with_state(user_app_state);
+
+    // Workflow Version Routes
+    use application::use_cases::manage_workflow_versions::ManageWorkflowVersions;
+    use infrastructure::web::workflow_handlers::{
+        add_step_handler, create_version_handler, get_run_handler, list_steps_handler,
+        list_versions_handler, publish_version_handler, run_workflow_handler,
+        workflow_webhook_handler, WorkflowAppState, WorkflowWebhookAppState,
+    };
+
+    let workflow_app_state = WorkflowAppState {
+        manage_workflow_versions: Arc::new(ManageWorkflowVersions::new(
+            repos.workflows.clone(),
+            repos.workflows.clone(),
+            repos.workflows.clone(),
+            repos.workflows.clone(),
+            workflow_executor.clone(),
+            identity_provider.clone(),
+        )),
+    };
+
+    let workflow_router = Router::new()
+        .route(
+            "/api/workflows/:id/versions",
+            get(list_versions_handler).post(create_version_handler),
+        )
+        .route(
+            "/api/workflows/:id/runs",
+            axum::routing::post(run_workflow_handler),
+        )
+        .route(
+            "/api/workflow-versions/:id/steps",
+            get(list_steps_handler).post(add_step_handler),
+        )
+        .route(
+            "/api/workflow-versions/:id/publish",
+            axum::routing::post(publish_version_handler),
+        )
+        .route("/api/workflow-runs/:id", get(get_run_handler))
+        .with_state(workflow_app_state);
+
+    // Single Sign-On Routes
+    use application::use_cases::manage_sso::ManageSso;
+    use infrastructure::identity::oidc::OidcSsoProvider;
+    use infrastructure::web::sso_handlers::{
+        get_sso_config_handler, put_sso_config_handler, sso_callback_handler, sso_login_handler,
+        SsoAppState,
+    };
+
+    let sso_app_state = SsoAppState {
+        manage_sso: Arc::new(ManageSso::new(
+            repos.workspaces.clone(),
+            repos.users.clone(),
+            repos.workspaces.clone(),
+            repos.workspaces.clone(),
+            manage_session_use_case.clone(),
+            Arc::new(OidcSsoProvider::new()),
+            identity_provider.clone(),
+        )),
+        scheme: config.app_scheme.clone(),
+    };
+
+    let sso_router = Router::new()
+        .route("/sso/login", get(sso_login_handler))
+        .route("/sso/callback", get(sso_callback_handler))
+        .route(
+            "/api/sso",
+            get(get_sso_config_handler).put(put_sso_config_handler),
+        )
+        .with_state(sso_app_state);
+
     // UI Routes for Metadata and Dynamic Objects
     use infrastructure::web::dynamic_ui_handlers::{
         dynamic_record_create_form_handler, dynamic_record_list_handler, nav_custom_objects_handler,
@@ -516,14 +1024,79 @@ async fn main() {
         .merge(lead_router)
         .merge(metadata_router)
         .merge(custom_object_router)
-        .merge(ui_router);
+        .merge(role_router)
+        .merge(member_router)
+        .merge(user_router)
+        .merge(api_key_router)
+        .merge(trash_router)
+        .merge(sso_router)
+        .merge(workflow_router)
+        .merge(ui_router)
+        .layer(axum::middleware::from_fn_with_state(
+            identity_provider.clone(),
+            infrastructure::web::auth::identity_middleware,
+        ))
+        // Outermost, so the identity is resolved within the routed workspace
+        .layer(axum::middleware::from_fn_with_state(
+            WorkspaceRouting {
+                workspace_repo: repos.workspaces.clone(),
+                base_domain: config.app_domain.clone(),
+            },
+            infrastructure::web::tenant::workspace_middleware,
+        ));
+
+    // Health Routes, outside the workspace and identity middleware so that probes need
+    // neither a workspace host nor credentials
+    use application::use_cases::check_readiness::CheckReadiness;
+    use infrastructure::health::{DatabaseHealth, TaskHealth};
+    use infrastructure::web::health_handlers::{healthz_handler, readyz_handler, HealthAppState};
+
+    let health_app_state = HealthAppState {
+        check_readiness: Arc::new(CheckReadiness::new(vec![
+            Arc::new(DatabaseHealth::new(db)),
+            Arc::new(TaskHealth::new("email_worker", email_worker_task)),
+            Arc::new(TaskHealth::new("trash_worker", trash_worker_task)),
+            Arc::new(TaskHealth::new("email_subscriber", email_subscriber_task)),
+            Arc::new(TaskHealth::new("lead_subscriber", lead_subscriber_task)),
+            Arc::new(TaskHealth::new(
+                "workflow_subscriber",
+                workflow_subscriber_task,
+            )),
+            Arc::new(TaskHealth::new(
+                "workflow_scheduler",
+                workflow_scheduler_task,
+            )),
+            Arc::new(TaskHealth::new(
+                "workflow_run_worker",
+                workflow_run_worker_task,
+            )),
+        ])),
+    };
+
+    let health_router = Router::new()
+        .route("/healthz", get(healthz_handler))
+        .route("/readyz", get(readyz_handler))
+        .with_state(health_app_state);
+
+    // Workflow webhooks authenticate with the token of their trigger rather than a session
+    // or an API key, so they are outside the identity middleware as well
+    let workflow_webhook_router = Router::new()
+        .route(
+            "/webhooks/workflows/:id",
+            axum::routing::post(workflow_webhook_handler),
+        )
+        .with_state(WorkflowWebhookAppState {
+            launcher: workflow_launcher.clone(),
+        });
+
+    let app = app.merge(health_router).merge(workflow_webhook_router);
 
-    let listener = TcpListener::bind("0.0.0.0:3001").await.unwrap();
+    let listener = TcpListener::bind(&config.listen_addr).await.unwrap();
     println!("Listening on {}", listener.local_addr().unwrap());
 
     // Prevent unused warnings for new adapters by "using" them in a print (scaffolding hack)
-    println!("Services initialized: Time={:?}, Auth={:?}, Search={:?}, Webhook={:?}, Billing={:?}, Storage={:?}, EventBus={:?}, JobQueue={:?}",
-             clock.now(), identity_provider.get_current_user_id().await, search_index, webhook_sender, billing_provider, storage_provider, event_bus, job_queue);
+    println!("Services initialized: Time={:?}, Search={:?}, Webhook={:?}, Billing={:?}, Storage={:?}, EventBus={:?}, JobQueue={:?}",
+             clock.now(), search_index, webhook_sender, billing_provider, storage_provider, event_bus, job_queue);
 
     axum::serve(listener, app).await.unwrap();
 }
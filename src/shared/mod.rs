@@ -1,3 +1,5 @@
+pub mod token;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Identifier(pub uuid::Uuid);
 
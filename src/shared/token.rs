@@ -0,0 +1,14 @@
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+/// Generates an opaque, URL-safe secret (256 bits, hex encoded)
+pub fn generate() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// Digest stored at rest in place of the secret itself
+pub fn hash(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}